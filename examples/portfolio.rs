@@ -0,0 +1,63 @@
+use anyhow::Error;
+use ndarray::{array, Array1, Array2};
+use pso::builder::SwarmBuilder;
+use pso::constraints::ConstraintHandling;
+use pso::objective::ObjectiveBuilder;
+use pso::pso::Options;
+
+/// Weights below this are considered out of the portfolio.
+const THRESHOLD: f64 = 1e-3;
+
+fn variance(covariance: &Array2<f64>, w: &Array1<f64>) -> f64 {
+    w.dot(&covariance.dot(w))
+}
+
+fn assets(w: &Array1<f64>) -> usize {
+    w.iter().filter(|&&v| v > THRESHOLD).count()
+}
+
+fn main() -> Result<(), Error> {
+    // Expected returns and covariance of five assets
+    let returns = array![0.08, 0.12, 0.10, 0.15, 0.06];
+    let covariance = array![
+        [0.040, 0.006, 0.004, 0.010, 0.002],
+        [0.006, 0.090, 0.012, 0.030, 0.003],
+        [0.004, 0.012, 0.060, 0.015, 0.002],
+        [0.010, 0.030, 0.015, 0.160, 0.004],
+        [0.002, 0.003, 0.002, 0.004, 0.010],
+    ];
+    let risk_aversion = 3.0;
+    let max_assets = 3;
+
+    let fitness = {
+        let returns = returns.clone();
+        let covariance = covariance.clone();
+
+        ObjectiveBuilder::new(true)
+            .term("risk", risk_aversion, move |w| variance(&covariance, w))
+            .term("return", -1.0, move |w| w.dot(&returns))
+            .term("cardinality", 1.0, move |w| {
+                assets(w).saturating_sub(max_assets) as f64
+            })
+            .build()
+    };
+
+    let mut particles = SwarmBuilder::new(fitness)
+        .size(30)
+        .dim(returns.len())
+        .value_range(0.0, 1.0)
+        .velocity_range(-0.1, 0.1)
+        .seed(0)
+        .build()?;
+    particles.set_verbose(false);
+    particles.set_constraint_handling(Some(ConstraintHandling::Simplex));
+    particles.run(Options::canonical(), 200)?;
+
+    let weights = particles.best();
+    println!("Weights: {:.3}", weights);
+    println!("Assets: {} (max {})", assets(weights), max_assets);
+    println!("Expected return: {:.4}", weights.dot(&returns));
+    println!("Volatility: {:.4}", variance(&covariance, weights).sqrt());
+
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use crate::error::PsoError;
+use crate::manifest::Manifest;
+use crate::pso::{Options, Particles};
+use crate::robust::Candidate;
+use crate::sensitivity::Sensitivity;
+use crate::termination::Deadline;
+use ndarray::Array1;
+use std::time::{Duration, SystemTime};
+
+/// Best-so-far of a time-budgeted run.
+#[derive(Debug, Clone)]
+pub struct OptimizationResult {
+    /// In the original coordinates, also for normalized swarms.
+    pub best: Array1<f64>,
+    pub best_fitness: f64,
+    /// Steps that fit in the budget.
+    pub iters: usize,
+    pub elapsed: Duration,
+    /// Options of the run, to reuse them when warm starting, see
+    /// [`SwarmBuilder::warm_start`](crate::builder::SwarmBuilder::warm_start).
+    pub options: Options,
+    /// How the fitness varies around the best, only with a
+    /// [sensitivity analysis](Particles::set_sensitivity_analysis).
+    pub sensitivity: Option<Sensitivity>,
+    /// Best by mean fitness after re-evaluating the top candidates, for noisy
+    /// objectives, only with a [robust selection](Particles::set_robust).
+    pub robust_best: Option<Candidate>,
+    /// Where the result came from, to reproduce it.
+    pub manifest: Manifest,
+    /// How much the best violates every constraint, for fitnesses with
+    /// constraints, see [`Fitness::with_constraints`](crate::fitness::Fitness::with_constraints).
+    pub violations: Option<Vec<f64>>,
+}
+
+impl<'a> Particles<'a> {
+    /// Runs as many steps as fit in `budget` of wall-clock time, the next
+    /// step is skipped if it would likely exceed it.
+    pub fn optimize_for(
+        &mut self,
+        options: Options,
+        budget: Duration,
+    ) -> Result<OptimizationResult, PsoError> {
+        let started = SystemTime::now();
+        let mut deadline = Deadline::new(budget);
+        let iters = self.run_with(options, usize::MAX, &mut [&mut deadline])?;
+        let sensitivity = match self.sensitivity_analysis() {
+            Some(analysis) => Some(self.sensitivity(analysis.radius, analysis.samples)?),
+            None => None,
+        };
+        let robust_best = match self.robust() {
+            Some(robust) => self
+                .robust_best(robust.candidates, robust.repeats)?
+                .into_iter()
+                .next(),
+            None => None,
+        };
+
+        Ok(OptimizationResult {
+            best: self.denormalize(self.best()),
+            best_fitness: self.best_fitness(),
+            iters,
+            elapsed: deadline.elapsed(),
+            options,
+            sensitivity,
+            robust_best,
+            manifest: self.manifest(options, started),
+            violations: self.best_violations(),
+        })
+    }
+}
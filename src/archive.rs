@@ -0,0 +1,116 @@
+use crate::metric::{Distance, Metric};
+use ndarray::Array1;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Solution {
+    pub value: Array1<f64>,
+    #[serde(with = "crate::checkpoint::nonfinite")]
+    pub fitness: f64,
+}
+
+/// Best distinct solutions found so far, no two of them closer than
+/// `min_distance`, sorted from best to worst.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Archive {
+    capacity: usize,
+    min_distance: f64,
+    #[serde(default)]
+    metric: Metric,
+    solutions: Vec<Solution>,
+}
+
+impl Archive {
+    pub fn new(capacity: usize, min_distance: f64) -> Self {
+        assert!(capacity > 0, "The archive must hold at least one solution");
+        assert!(min_distance >= 0.0, "Minimum distance can't be negative");
+
+        Self {
+            capacity,
+            min_distance,
+            metric: Metric::default(),
+            solutions: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Measures the minimum distance with `metric`, Euclidean by default.
+    /// Swarms use their own metric instead, custom ones included.
+    pub fn with_metric(mut self, metric: Metric) -> Self {
+        self.metric = metric;
+        self
+    }
+
+    pub fn metric(&self) -> Metric {
+        self.metric
+    }
+
+    pub fn solutions(&self) -> &[Solution] {
+        &self.solutions
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn min_distance(&self) -> f64 {
+        self.min_distance
+    }
+
+    /// Adds the solution if it's better than every archived one closer than
+    /// the minimum distance (which are dropped) and than the worst one when
+    /// full. Returns whether it was added.
+    pub fn offer(&mut self, value: &Array1<f64>, fitness: f64, minimization: bool) -> bool {
+        let metric = self.metric;
+        self.offer_with(&metric, value, fitness, minimization)
+    }
+
+    /// Same as [`offer`](Self::offer), measuring the distances with `metric`.
+    pub fn offer_with(
+        &mut self,
+        metric: &impl Distance,
+        value: &Array1<f64>,
+        fitness: f64,
+        minimization: bool,
+    ) -> bool {
+        let better = |a: f64, b: f64| if minimization { a < b } else { a > b };
+        let distance = |other: &Array1<f64>| metric.distance(value, other);
+
+        let close: Vec<_> = (0..self.solutions.len())
+            .filter(|&i| distance(&self.solutions[i].value) < self.min_distance)
+            .collect();
+
+        if close
+            .iter()
+            .any(|&i| !better(fitness, self.solutions[i].fitness))
+        {
+            return false;
+        }
+
+        if close.is_empty() && self.solutions.len() == self.capacity {
+            let worst = self.solutions.last().expect("Archive is empty");
+            if !better(fitness, worst.fitness) {
+                return false;
+            }
+
+            self.solutions.pop();
+        }
+
+        for i in close.into_iter().rev() {
+            self.solutions.remove(i);
+        }
+
+        let at = self
+            .solutions
+            .iter()
+            .position(|solution| better(fitness, solution.fitness))
+            .unwrap_or(self.solutions.len());
+
+        let solution = Solution {
+            value: value.clone(),
+            fitness,
+        };
+        self.solutions.insert(at, solution);
+
+        true
+    }
+}
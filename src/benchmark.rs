@@ -0,0 +1,500 @@
+use crate::velocity::orthonormalize;
+use ndarray::{s, Array1, Array2};
+use ndarray_rand::rand_distr::{StandardNormal, Uniform};
+use ndarray_rand::RandomExt;
+use rand::Rng;
+use std::f64::consts::PI;
+
+/// `Σ x_i²`, minimum 0 at the origin.
+pub fn sphere(x: &Array1<f64>) -> f64 {
+    x.mapv(|v| v * v).sum()
+}
+
+/// High conditioned elliptic `Σ (10⁶)^(i / (n - 1)) x_i²`, minimum 0 at the
+/// origin.
+pub fn elliptic(x: &Array1<f64>) -> f64 {
+    let n = x.len().max(2) as f64;
+    x.iter()
+        .enumerate()
+        .map(|(i, v)| 1e6f64.powf(i as f64 / (n - 1.0)) * v * v)
+        .sum()
+}
+
+/// `Σ x_i² - 10 cos(2π x_i) + 10`, minimum 0 at the origin.
+pub fn rastrigin(x: &Array1<f64>) -> f64 {
+    x.iter()
+        .map(|v| v * v - 10.0 * (2.0 * PI * v).cos() + 10.0)
+        .sum()
+}
+
+/// `Σ 100 (x_{i+1} - x_i²)² + (x_i - 1)²`, minimum 0 at `(1, ..., 1)`.
+pub fn rosenbrock(x: &Array1<f64>) -> f64 {
+    x.iter()
+        .zip(x.iter().skip(1))
+        .map(|(a, b)| 100.0 * (b - a * a).powi(2) + (a - 1.0).powi(2))
+        .sum()
+}
+
+/// CEC-style instance of a benchmark, `f(M (x - o)) + bias`.
+#[derive(Debug, Clone, Default)]
+pub struct Transform {
+    shift: Option<Array1<f64>>,
+    rotation: Option<Array2<f64>>,
+    bias: f64,
+}
+
+impl Transform {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Moves the optimum from `0` to `o` (for benchmarks with the optimum at
+    /// the origin).
+    pub fn shift(mut self, o: Array1<f64>) -> Self {
+        self.shift = Some(o);
+        self
+    }
+
+    /// Random shift with every component in `(min, max)`.
+    pub fn random_shift(self, dim: usize, range: (f64, f64), rng: &mut impl Rng) -> Self {
+        self.shift(Array1::random_using(
+            (dim,),
+            Uniform::new(range.0, range.1),
+            rng,
+        ))
+    }
+
+    /// `m` should be orthogonal to keep the benchmark's shape.
+    pub fn rotation(mut self, m: Array2<f64>) -> Self {
+        assert!(m.is_square(), "Rotation matrix must be square");
+
+        self.rotation = Some(m);
+        self
+    }
+
+    /// Uniformly distributed random rotation.
+    pub fn random_rotation(self, dim: usize, rng: &mut impl Rng) -> Self {
+        let gaussian = Array2::random_using((dim, dim), StandardNormal, rng);
+        self.rotation(orthonormalize(gaussian))
+    }
+
+    /// Added to every value, so the optimum isn't 0.
+    pub fn bias(mut self, bias: f64) -> Self {
+        self.bias = bias;
+        self
+    }
+
+    /// Point where `f` is evaluated instead of `x`, `M (x - o)`.
+    pub fn apply(&self, x: &Array1<f64>) -> Array1<f64> {
+        let shifted = match &self.shift {
+            Some(o) => x - o,
+            None => x.clone(),
+        };
+
+        match &self.rotation {
+            Some(m) => m.dot(&shifted),
+            None => shifted,
+        }
+    }
+
+    /// Transformed benchmark, pass it to [`Fitness::new`](crate::fitness::Fitness::new)
+    /// (or `shared` if `f` is `Send + Sync`).
+    pub fn wrap<F>(self, f: F) -> impl Fn(&Array1<f64>) -> f64
+    where
+        F: Fn(&Array1<f64>) -> f64,
+    {
+        move |x| f(&self.apply(x)) + self.bias
+    }
+}
+
+/// Multi-objective benchmark, every objective minimized, with a known
+/// Pareto front to compute quality indicators against. Reduce it to a single
+/// objective with a [`Scalarization`](crate::objective::Scalarization).
+pub trait MultiObjective {
+    fn name(&self) -> &str;
+
+    fn objectives(&self) -> usize;
+
+    /// Lower and upper bound of every dimension.
+    fn bounds(&self) -> (Array1<f64>, Array1<f64>);
+
+    /// Value of every objective.
+    fn evaluate(&self, x: &Array1<f64>) -> Vec<f64>;
+
+    /// About `points` points of the Pareto front, evenly spread, never more.
+    fn front(&self, points: usize) -> Vec<Vec<f64>>;
+
+    fn dim(&self) -> usize {
+        self.bounds().0.len()
+    }
+}
+
+/// Zitzler-Deb-Thiele two-objective problems. ZDT5 is left out, it's
+/// defined over bit strings.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ZdtKind {
+    /// Convex front.
+    Zdt1,
+    /// Concave front.
+    Zdt2,
+    /// Front of five disconnected pieces.
+    Zdt3,
+    /// Convex front with `21⁹` local fronts.
+    Zdt4,
+    /// Concave front, non-uniformly distributed solutions.
+    Zdt6,
+}
+
+impl ZdtKind {
+    /// Dimension of the original definition, 30 or 10.
+    pub fn standard_dim(self) -> usize {
+        match self {
+            ZdtKind::Zdt4 | ZdtKind::Zdt6 => 10,
+            _ => 30,
+        }
+    }
+}
+
+/// Disconnected pieces of the ZDT3 front, as ranges of `f_1`.
+const ZDT3_PIECES: [(f64, f64); 5] = [
+    (0.0, 0.083_001_534_9),
+    (0.182_228_728_0, 0.257_762_363_4),
+    (0.409_313_674_8, 0.453_882_104_1),
+    (0.618_396_794_4, 0.652_511_703_8),
+    (0.823_331_798_3, 0.851_832_865_4),
+];
+
+/// Smallest `f_1` of the ZDT6 front.
+const ZDT6_MIN: f64 = 0.280_775_319_1;
+
+/// ZDT problem, `f_1(x_1)` and `f_2 = g(x_2..x_n) h(f_1, g)`, the front is at
+/// `g = 1`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Zdt {
+    kind: ZdtKind,
+    dim: usize,
+}
+
+impl Zdt {
+    pub fn new(kind: ZdtKind, dim: usize) -> Self {
+        assert!(dim >= 2, "ZDT problems need at least 2 dimensions");
+
+        Self { kind, dim }
+    }
+
+    /// With the dimension of the original definition.
+    pub fn standard(kind: ZdtKind) -> Self {
+        Self::new(kind, kind.standard_dim())
+    }
+
+    pub fn kind(&self) -> ZdtKind {
+        self.kind
+    }
+
+    fn f1(&self, x1: f64) -> f64 {
+        match self.kind {
+            ZdtKind::Zdt6 => 1.0 - (-4.0 * x1).exp() * (6.0 * PI * x1).sin().powi(6),
+            _ => x1,
+        }
+    }
+
+    fn g(&self, rest: &[f64]) -> f64 {
+        let n = rest.len() as f64;
+        match self.kind {
+            ZdtKind::Zdt4 => {
+                let sum: f64 = rest
+                    .iter()
+                    .map(|v| v * v - 10.0 * (4.0 * PI * v).cos())
+                    .sum();
+                1.0 + 10.0 * n + sum
+            }
+            ZdtKind::Zdt6 => 1.0 + 9.0 * (rest.iter().sum::<f64>() / n).powf(0.25),
+            _ => 1.0 + 9.0 * rest.iter().sum::<f64>() / n,
+        }
+    }
+
+    fn h(&self, f1: f64, g: f64) -> f64 {
+        let ratio = f1 / g;
+        match self.kind {
+            ZdtKind::Zdt1 | ZdtKind::Zdt4 => 1.0 - ratio.sqrt(),
+            ZdtKind::Zdt2 | ZdtKind::Zdt6 => 1.0 - ratio * ratio,
+            ZdtKind::Zdt3 => 1.0 - ratio.sqrt() - ratio * (10.0 * PI * f1).sin(),
+        }
+    }
+}
+
+impl MultiObjective for Zdt {
+    fn name(&self) -> &str {
+        match self.kind {
+            ZdtKind::Zdt1 => "ZDT1",
+            ZdtKind::Zdt2 => "ZDT2",
+            ZdtKind::Zdt3 => "ZDT3",
+            ZdtKind::Zdt4 => "ZDT4",
+            ZdtKind::Zdt6 => "ZDT6",
+        }
+    }
+
+    fn objectives(&self) -> usize {
+        2
+    }
+
+    /// `[0, 1]`, but `[-5, 5]` for all but the first dimension of ZDT4.
+    fn bounds(&self) -> (Array1<f64>, Array1<f64>) {
+        let mut min = Array1::zeros(self.dim);
+        let mut max = Array1::ones(self.dim);
+        if self.kind == ZdtKind::Zdt4 {
+            min.slice_mut(s![1..]).fill(-5.0);
+            max.slice_mut(s![1..]).fill(5.0);
+        }
+
+        (min, max)
+    }
+
+    fn evaluate(&self, x: &Array1<f64>) -> Vec<f64> {
+        assert_eq!(x.len(), self.dim, "Expected {} dimensions", self.dim);
+
+        let f1 = self.f1(x[0]);
+        let rest: Vec<_> = x.iter().skip(1).copied().collect();
+        let g = self.g(&rest);
+
+        vec![f1, g * self.h(f1, g)]
+    }
+
+    fn front(&self, points: usize) -> Vec<Vec<f64>> {
+        let pieces: &[(f64, f64)] = match self.kind {
+            ZdtKind::Zdt3 => &ZDT3_PIECES,
+            ZdtKind::Zdt6 => &[(ZDT6_MIN, 1.0)],
+            _ => &[(0.0, 1.0)],
+        };
+
+        // Points spread over the pieces by their length
+        let total: f64 = pieces.iter().map(|(a, b)| b - a).sum();
+        let mut front = Vec::with_capacity(points);
+        for &(a, b) in pieces {
+            let count = (points as f64 * (b - a) / total).floor() as usize;
+            for i in 0..count {
+                let f1 = if count == 1 {
+                    a
+                } else {
+                    a + (b - a) * i as f64 / (count - 1) as f64
+                };
+                front.push(vec![f1, self.h(f1, 1.0)]);
+            }
+        }
+
+        front
+    }
+}
+
+/// Deb-Thiele-Laumanns-Zitzler problems, scalable in objectives.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DtlzKind {
+    /// Linear front `Σ f_i = 0.5`, with `11^k - 1` local fronts.
+    Dtlz1,
+    /// Spherical front `Σ f_i² = 1`.
+    Dtlz2,
+    /// Spherical front with the local fronts of DTLZ1.
+    Dtlz3,
+    /// Spherical front, solutions biased towards its edges.
+    Dtlz4,
+}
+
+impl DtlzKind {
+    /// Variables `k` of the distance function in the original definition.
+    pub fn standard_k(self) -> usize {
+        match self {
+            DtlzKind::Dtlz1 => 5,
+            _ => 10,
+        }
+    }
+}
+
+/// DTLZ problem of `objectives` objectives and `dim` variables in `[0, 1]`,
+/// the last `dim - objectives + 1` set the distance to the front, which is
+/// at 0.5.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Dtlz {
+    kind: DtlzKind,
+    objectives: usize,
+    dim: usize,
+}
+
+impl Dtlz {
+    pub fn new(kind: DtlzKind, objectives: usize, dim: usize) -> Self {
+        assert!(objectives >= 2, "DTLZ problems need at least 2 objectives");
+        assert!(
+            dim >= objectives,
+            "DTLZ problems need at least as many dimensions as objectives"
+        );
+
+        Self {
+            kind,
+            objectives,
+            dim,
+        }
+    }
+
+    /// With the `k` of the original definition, `objectives + k - 1`
+    /// dimensions.
+    pub fn standard(kind: DtlzKind, objectives: usize) -> Self {
+        Self::new(kind, objectives, objectives + kind.standard_k() - 1)
+    }
+
+    pub fn kind(&self) -> DtlzKind {
+        self.kind
+    }
+
+    fn g(&self, distance: &[f64]) -> f64 {
+        match self.kind {
+            DtlzKind::Dtlz1 | DtlzKind::Dtlz3 => {
+                let sum: f64 = distance
+                    .iter()
+                    .map(|v| (v - 0.5).powi(2) - (20.0 * PI * (v - 0.5)).cos())
+                    .sum();
+                100.0 * (distance.len() as f64 + sum)
+            }
+            DtlzKind::Dtlz2 | DtlzKind::Dtlz4 => distance.iter().map(|v| (v - 0.5).powi(2)).sum(),
+        }
+    }
+}
+
+impl MultiObjective for Dtlz {
+    fn name(&self) -> &str {
+        match self.kind {
+            DtlzKind::Dtlz1 => "DTLZ1",
+            DtlzKind::Dtlz2 => "DTLZ2",
+            DtlzKind::Dtlz3 => "DTLZ3",
+            DtlzKind::Dtlz4 => "DTLZ4",
+        }
+    }
+
+    fn objectives(&self) -> usize {
+        self.objectives
+    }
+
+    fn bounds(&self) -> (Array1<f64>, Array1<f64>) {
+        (Array1::zeros(self.dim), Array1::ones(self.dim))
+    }
+
+    fn evaluate(&self, x: &Array1<f64>) -> Vec<f64> {
+        assert_eq!(x.len(), self.dim, "Expected {} dimensions", self.dim);
+
+        let m = self.objectives;
+        let x = x.as_slice().expect("Contiguous array");
+        let (position, distance) = x.split_at(m - 1);
+        let g = self.g(distance);
+
+        (0..m)
+            .map(|i| {
+                // The i-th objective takes the first m - 1 - i positions
+                // whole, and the next one complemented
+                let taken = &position[..m - 1 - i];
+                let last = position.get(m - 1 - i);
+
+                match self.kind {
+                    DtlzKind::Dtlz1 => {
+                        let product: f64 = taken.iter().product();
+                        0.5 * (1.0 + g) * product * last.map_or(1.0, |v| 1.0 - v)
+                    }
+                    _ => {
+                        let alpha = if self.kind == DtlzKind::Dtlz4 { 100 } else { 1 };
+                        let angle = |v: f64| v.powi(alpha) * PI / 2.0;
+                        let product: f64 = taken.iter().map(|&v| angle(v).cos()).product();
+                        (1.0 + g) * product * last.map_or(1.0, |&v| angle(v).sin())
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// Das-Dennis points of the simplex, projected onto the sphere for all
+    /// but DTLZ1.
+    fn front(&self, points: usize) -> Vec<Vec<f64>> {
+        if points == 0 {
+            return Vec::new();
+        }
+
+        let m = self.objectives;
+        let mut divisions = 1;
+        while simplex_points(m, divisions + 1) <= points {
+            divisions += 1;
+        }
+        if simplex_points(m, divisions) > points {
+            // Not even the corners fit
+            return Vec::new();
+        }
+
+        let mut front = Vec::new();
+        let mut point = vec![0; m];
+        das_dennis(divisions, 0, &mut point, &mut front);
+
+        front
+            .into_iter()
+            .map(|point| {
+                let point: Vec<_> = point
+                    .into_iter()
+                    .map(|v| v as f64 / divisions as f64)
+                    .collect();
+                match self.kind {
+                    DtlzKind::Dtlz1 => point.into_iter().map(|v| v / 2.0).collect(),
+                    _ => {
+                        let norm = point.iter().map(|v| v * v).sum::<f64>().sqrt();
+                        point.into_iter().map(|v| v / norm).collect()
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+/// Points of the simplex of `m` objectives with `divisions` divisions,
+/// `C(divisions + m - 1, m - 1)`.
+fn simplex_points(m: usize, divisions: usize) -> usize {
+    (1..m).fold(1, |count, i| count * (divisions + i) / i)
+}
+
+/// Every way to split `left` units among the coordinates from `i` on.
+fn das_dennis(left: usize, i: usize, point: &mut Vec<usize>, points: &mut Vec<Vec<usize>>) {
+    if i == point.len() - 1 {
+        point[i] = left;
+        points.push(point.clone());
+        return;
+    }
+
+    for units in 0..=left {
+        point[i] = units;
+        das_dennis(left - units, i + 1, point, points);
+    }
+}
+
+/// Euclidean distance from `point` to the closest point of `set`, infinite
+/// if it's empty.
+fn closest(point: &[f64], set: &[Vec<f64>]) -> f64 {
+    set.iter()
+        .map(|other| {
+            point
+                .iter()
+                .zip(other)
+                .map(|(a, b)| (a - b).powi(2))
+                .sum::<f64>()
+                .sqrt()
+        })
+        .fold(f64::INFINITY, f64::min)
+}
+
+/// Generational distance, mean distance of the points found to the closest
+/// point of the true `front`. Measures convergence, 0 when every point is
+/// on the front.
+pub fn generational_distance(found: &[Vec<f64>], front: &[Vec<f64>]) -> f64 {
+    if found.is_empty() {
+        return f64::INFINITY;
+    }
+
+    found.iter().map(|p| closest(p, front)).sum::<f64>() / found.len() as f64
+}
+
+/// Inverted generational distance, mean distance of the points of the true
+/// `front` to the closest point found. Measures both convergence and spread.
+pub fn inverted_generational_distance(found: &[Vec<f64>], front: &[Vec<f64>]) -> f64 {
+    generational_distance(front, found)
+}
@@ -0,0 +1,53 @@
+use crate::pso::Particles;
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+
+/// Order in which particles move when the evaluation budget can't afford a
+/// whole step, see [`Particles::set_evaluation_budget`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Priority {
+    /// By index.
+    #[default]
+    InOrder,
+    /// Best current fitness first.
+    BestFirst,
+    /// Highest improvement rate first, the particles expected to improve.
+    MostImproving,
+    /// At random.
+    Random,
+}
+
+impl<'a> Particles<'a> {
+    /// Particles that can move on the next step given the budget left,
+    /// `None` when all of them can.
+    pub(crate) fn schedule(&mut self) -> Option<Vec<bool>> {
+        let left = self.evaluations_left()?;
+        let size = self.particles().len();
+        if left >= size {
+            return None;
+        }
+
+        let mut order: Vec<_> = (0..size).collect();
+        match self.priority() {
+            Priority::InOrder => {}
+            Priority::BestFirst => order.sort_by(|&a, &b| {
+                let particles = self.particles();
+                self.compare(particles[b].fitness(), particles[a].fitness())
+            }),
+            Priority::MostImproving => order.sort_by(|&a, &b| {
+                let ra = self.particles()[a].stats().improvement_rate();
+                let rb = self.particles()[b].stats().improvement_rate();
+
+                rb.partial_cmp(&ra).expect("Received a NaN")
+            }),
+            Priority::Random => order.shuffle(self.rng_mut()),
+        }
+
+        let mut scheduled = vec![false; size];
+        for &i in order.iter().take(left) {
+            scheduled[i] = true;
+        }
+
+        Some(scheduled)
+    }
+}
@@ -0,0 +1,472 @@
+use crate::anytime::OptimizationResult;
+use crate::archive::Archive;
+use crate::budget::Priority;
+use crate::clustering::ClusterRestart;
+use crate::coordinates::Coordinates;
+use crate::differential::Differential;
+use crate::error::PsoError;
+use crate::fitness::{Comparison, Fitness};
+use crate::manifold::Manifold;
+use crate::metric::Metric;
+use crate::operator::Operator;
+use crate::pso::{self, Algorithm, Confinement, Options, Parallelism, Particles, TieBreak};
+use crate::report::{Language, NumberFormat};
+use crate::robust::RobustSelection;
+use crate::screening::Screening;
+use crate::sensitivity::SensitivityAnalysis;
+use crate::surrogate::Surrogate;
+use crate::topology::Topology;
+use crate::transcript::Transcript;
+use crate::velocity::{Coefficients, VelocityPolicy};
+use crate::warmup::Warmup;
+use ndarray::Array1;
+
+/// Validated construction of a swarm.
+pub struct SwarmBuilder<'a> {
+    size: usize,
+    dim: usize,
+    value_range: (f64, f64),
+    velocity_range: (f64, f64),
+    fitness: Fitness<'a, Array1<f64>>,
+    seed: Option<u64>,
+    tie_break: TieBreak,
+    parallelism: Parallelism,
+    topology: Topology,
+    algorithm: Algorithm,
+    velocity_policy: VelocityPolicy,
+    coefficients: Option<Coefficients>,
+    confinement: Confinement,
+    manifold: Manifold,
+    operators: Vec<Operator>,
+    evaluation_budget: Option<usize>,
+    priority: Priority,
+    metric: Metric,
+    comparison: Comparison,
+    bounds: Option<(Array1<f64>, Array1<f64>)>,
+    relative_velocities: bool,
+    warm_start: Option<(Array1<f64>, Options)>,
+    spread: f64,
+    archive: Option<(usize, f64)>,
+    warmup: Option<Warmup>,
+    screening: Option<Screening>,
+    sensitivity_analysis: Option<SensitivityAnalysis>,
+    robust: Option<RobustSelection>,
+    transcript: Option<Transcript>,
+    language: Language,
+    number_format: NumberFormat,
+}
+
+impl<'a> SwarmBuilder<'a> {
+    pub fn new(fitness: Fitness<'a, Array1<f64>>) -> Self {
+        Self {
+            size: 10,
+            dim: 2,
+            value_range: (-10.0, 10.0),
+            velocity_range: (-1.0, 1.0),
+            fitness,
+            seed: None,
+            tie_break: TieBreak::default(),
+            parallelism: Parallelism::default(),
+            topology: Topology::default(),
+            algorithm: Algorithm::default(),
+            velocity_policy: VelocityPolicy::default(),
+            coefficients: None,
+            confinement: Confinement::default(),
+            manifold: Manifold::default(),
+            operators: Vec::new(),
+            evaluation_budget: None,
+            priority: Priority::default(),
+            metric: Metric::default(),
+            comparison: Comparison::default(),
+            bounds: None,
+            relative_velocities: false,
+            warm_start: None,
+            spread: 0.1,
+            archive: None,
+            warmup: None,
+            screening: None,
+            sensitivity_analysis: None,
+            robust: None,
+            transcript: None,
+            language: Language::default(),
+            number_format: NumberFormat::default(),
+        }
+    }
+
+    /// Standard PSO 2011: 40 particles, random topology with 3 informants,
+    /// hypersphere velocity update, bounded confinement and velocities
+    /// sampled relative to the positions. Should be run with
+    /// [`Options::spso_2011`](crate::pso::Options::spso_2011).
+    pub fn spso_2011(fitness: Fitness<'a, Array1<f64>>) -> Self {
+        let mut builder = Self::new(fitness)
+            .size(40)
+            .topology(Topology::spso_2011())
+            .velocity_policy(VelocityPolicy::Hypersphere)
+            .confinement(Confinement::Bounded);
+
+        builder.relative_velocities = true;
+        builder
+    }
+
+    pub fn size(mut self, size: usize) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn dim(mut self, dim: usize) -> Self {
+        self.dim = dim;
+        self
+    }
+
+    pub fn value_range(mut self, min: f64, max: f64) -> Self {
+        self.value_range = (min, max);
+        self
+    }
+
+    pub fn velocity_range(mut self, min: f64, max: f64) -> Self {
+        self.velocity_range = (min, max);
+        self
+    }
+
+    /// Seeds the swarm's RNG, making runs reproducible.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn tie_break(mut self, tie_break: TieBreak) -> Self {
+        self.tie_break = tie_break;
+        self
+    }
+
+    /// Parallel modes need a [shared](Fitness::shared) fitness and the
+    /// `parallel` feature, checked when building.
+    pub fn parallelism(mut self, parallelism: Parallelism) -> Self {
+        self.parallelism = parallelism;
+        self
+    }
+
+    pub fn topology(mut self, topology: Topology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    pub fn algorithm(mut self, algorithm: Algorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    pub fn velocity_policy(mut self, policy: VelocityPolicy) -> Self {
+        self.velocity_policy = policy;
+        self
+    }
+
+    /// Per-dimension coefficients, see [`Particles::set_coefficients`].
+    pub fn coefficients(mut self, coefficients: Coefficients) -> Self {
+        self.coefficients = Some(coefficients);
+        self
+    }
+
+    pub fn confinement(mut self, confinement: Confinement) -> Self {
+        self.confinement = confinement;
+        self
+    }
+
+    pub fn manifold(mut self, manifold: Manifold) -> Self {
+        self.manifold = manifold;
+        self
+    }
+
+    /// Maximum amount of fitness evaluations, see
+    /// [`Particles::set_evaluation_budget`].
+    pub fn evaluation_budget(mut self, budget: usize) -> Self {
+        self.evaluation_budget = Some(budget);
+        self
+    }
+
+    pub fn priority(mut self, priority: Priority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Appends an operator to the pipeline applied after every step, see
+    /// [`Particles::set_operators`].
+    pub fn operator(mut self, operator: Operator) -> Self {
+        self.operators.push(operator);
+        self
+    }
+
+    /// Applies a differential evolution operator after every step.
+    pub fn differential(self, differential: Differential) -> Self {
+        self.operator(Operator::Differential(differential))
+    }
+
+    /// Sends a scout to the most promising position predicted by a
+    /// surrogate model every few steps.
+    pub fn surrogate(self, surrogate: Surrogate) -> Self {
+        self.operator(Operator::Surrogate(surrogate))
+    }
+
+    /// Restarts the clusters of particles that explore the surroundings of
+    /// a better one every few steps.
+    pub fn cluster_restart(self, restart: ClusterRestart) -> Self {
+        self.operator(Operator::ClusterRestart(restart))
+    }
+
+    /// Re-disperses particles that collapse within `tolerance` of a better
+    /// one after every step.
+    pub fn deduplicate(self, tolerance: f64) -> Self {
+        self.operator(Operator::Deduplication { tolerance })
+    }
+
+    /// Metric used for duplicate detection, diversity and tie breaks.
+    pub fn metric(mut self, metric: Metric) -> Self {
+        self.metric = metric;
+        self
+    }
+
+    /// How fitness values are ranked, see [`Particles::set_comparison`].
+    pub fn comparison(mut self, comparison: Comparison) -> Self {
+        self.comparison = comparison;
+        self
+    }
+
+    /// Moves the swarm in the unit hypercube, mapping it to `[min, max]`
+    /// before every fitness call, so the velocity coefficients behave the
+    /// same on every dimension. The value range becomes `(0, 1)` and the
+    /// velocity range is taken relative to it, positions reported by the
+    /// swarm are normalized, see [`Particles::denormalize`], but for the
+    /// best of an [`OptimizationResult`]. Warm starts take it in the
+    /// original coordinates too.
+    pub fn normalize(mut self, min: Array1<f64>, max: Array1<f64>) -> Self {
+        self.bounds = Some((min, max));
+        self
+    }
+
+    /// Keeps up to `capacity` distinct good solutions, at least
+    /// `min_distance` apart, see [`Particles::archive`].
+    pub fn archive(mut self, capacity: usize, min_distance: f64) -> Self {
+        self.archive = Some((capacity, min_distance));
+        self
+    }
+
+    /// Starts with an exploratory phase, see [`Particles::set_warmup`].
+    pub fn warmup(mut self, warmup: Warmup) -> Self {
+        self.warmup = Some(warmup);
+        self
+    }
+
+    /// Only evaluates the most promising particles on every step, see
+    /// [`Particles::set_screening`].
+    pub fn screening(mut self, screening: Screening) -> Self {
+        self.screening = Some(screening);
+        self
+    }
+
+    /// Analyzes the sensitivity of the best at the end of a run, sampling
+    /// `samples` points within `radius` times the width of the value range,
+    /// see [`Particles::set_sensitivity_analysis`].
+    pub fn sensitivity(mut self, radius: f64, samples: usize) -> Self {
+        self.sensitivity_analysis = Some(SensitivityAnalysis { radius, samples });
+        self
+    }
+
+    /// Reports the best of the `candidates` best distinct solutions by their
+    /// mean over `repeats` evaluations, for noisy objectives, see
+    /// [`Particles::set_robust`].
+    pub fn robust(mut self, candidates: usize, repeats: usize) -> Self {
+        self.robust = Some(RobustSelection {
+            candidates,
+            repeats,
+        });
+        self
+    }
+
+    /// Seeds the swarm around the best of a previous run, for problems that
+    /// change slightly between runs. Its options are reused as uniform
+    /// [coefficients](Self::coefficients) unless those are set, so they
+    /// take over the ones given to the run, but for a drawn omega.
+    pub fn warm_start(mut self, result: &OptimizationResult) -> Self {
+        self.warm_start = Some((result.best.clone(), result.options));
+        self
+    }
+
+    /// Half width of the box particles are seeded in when warm starting,
+    /// relative to the width of the value range, 0.1 by default.
+    pub fn spread(mut self, spread: f64) -> Self {
+        self.spread = spread;
+        self
+    }
+
+    /// Logs every evaluation, the initial swarm included, see
+    /// [`Particles::set_transcript`].
+    pub fn transcript(mut self, transcript: Transcript) -> Self {
+        self.transcript = Some(transcript);
+        self
+    }
+
+    pub fn language(mut self, language: Language) -> Self {
+        self.language = language;
+        self
+    }
+
+    pub fn number_format(mut self, format: NumberFormat) -> Self {
+        self.number_format = format;
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), PsoError> {
+        pso::validate_shape(self.size, self.dim, self.value_range, self.velocity_range)?;
+
+        if let Some((best, _)) = &self.warm_start {
+            if best.len() != self.dim {
+                return Err(PsoError::InvalidOptions(format!(
+                    "warm start has {} dimensions, expected {}",
+                    best.len(),
+                    self.dim
+                )));
+            }
+
+            if !(self.spread >= 0.0 && self.spread.is_finite()) {
+                return Err(PsoError::InvalidOptions(format!(
+                    "spread must be non-negative, got {}",
+                    self.spread
+                )));
+            }
+        }
+
+        if let Some((min, max)) = &self.bounds {
+            if min.len() != self.dim || max.len() != self.dim {
+                return Err(PsoError::InvalidOptions(format!(
+                    "normalization bounds must have {} dimensions",
+                    self.dim
+                )));
+            }
+
+            let valid = min
+                .iter()
+                .zip(max)
+                .all(|(min, max)| min.is_finite() && max.is_finite() && min < max);
+            if !valid {
+                return Err(PsoError::InvalidOptions(
+                    "normalization bounds must satisfy min < max".to_string(),
+                ));
+            }
+
+            if self.fitness.terms().is_some() {
+                return Err(PsoError::InvalidOptions(
+                    "composite objectives can't be normalized".to_string(),
+                ));
+            }
+        }
+
+        if let Some(coefficients) = &self.coefficients {
+            coefficients.validate(self.dim)?;
+        }
+
+        if let Some((capacity, min_distance)) = self.archive {
+            if capacity == 0 {
+                return Err(PsoError::InvalidOptions(
+                    "the archive must hold at least one solution".to_string(),
+                ));
+            }
+
+            if min_distance.is_nan() || min_distance < 0.0 {
+                return Err(PsoError::InvalidOptions(format!(
+                    "minimum distance can't be negative, got {}",
+                    min_distance
+                )));
+            }
+        }
+
+        self.algorithm.validate()?;
+        for operator in &self.operators {
+            operator.validate()?;
+        }
+
+        self.topology.validate(self.size)
+    }
+
+    pub fn build(self) -> Result<Particles<'a>, PsoError> {
+        self.validate()?;
+
+        let normalization = self
+            .bounds
+            .as_ref()
+            .map(|(min, max)| Coordinates::normalized(min, max));
+        let (value_range, fitness) = match normalization.clone() {
+            Some(coordinates) => (
+                (0.0, 1.0),
+                self.fitness.map_input(move |x| coordinates.decode(x)),
+            ),
+            None => (self.value_range, self.fitness),
+        };
+
+        let mut particles = match self.seed {
+            Some(seed) => Particles::try_with_seed(
+                self.size,
+                self.dim,
+                value_range,
+                self.velocity_range,
+                fitness,
+                seed,
+            )?,
+            None => Particles::try_new(
+                self.size,
+                self.dim,
+                value_range,
+                self.velocity_range,
+                fitness,
+            )?,
+        };
+
+        if self.transcript.is_some() {
+            particles.set_transcript(self.transcript);
+            particles.transcribe_initial();
+        }
+
+        particles.set_comparison(self.comparison);
+        particles.set_tie_break(self.tie_break);
+        particles.set_parallelism(self.parallelism)?;
+        particles.set_topology(self.topology)?;
+        particles.set_algorithm(self.algorithm)?;
+        particles.set_velocity_policy(self.velocity_policy);
+        let dim = self.dim;
+        let warm_coefficients = self
+            .warm_start
+            .as_ref()
+            .map(|(_, options)| Coefficients::uniform(dim, options));
+        particles.set_coefficients(self.coefficients.or(warm_coefficients))?;
+        particles.set_confinement(self.confinement)?;
+        if let Some((best, _)) = &self.warm_start {
+            let center = match &normalization {
+                Some(coordinates) => coordinates.encode(best),
+                None => best.clone(),
+            };
+            particles.seed_around(&center, self.spread)?;
+        }
+        if self.relative_velocities {
+            particles.resample_velocities();
+        }
+        if self.manifold != Manifold::Euclidean {
+            particles.set_manifold(self.manifold);
+        }
+
+        particles.set_normalization(normalization);
+        particles.set_operators(self.operators)?;
+        particles.set_evaluation_budget(self.evaluation_budget);
+        particles.set_priority(self.priority);
+        particles.set_metric(self.metric);
+        particles.set_archive(
+            self.archive
+                .map(|(capacity, min_distance)| Archive::new(capacity, min_distance)),
+        );
+        particles.set_warmup(self.warmup)?;
+        particles.set_screening(self.screening)?;
+        particles.set_sensitivity_analysis(self.sensitivity_analysis)?;
+        particles.set_robust(self.robust)?;
+        particles.set_language(self.language);
+        particles.set_number_format(self.number_format);
+        Ok(particles)
+    }
+}
@@ -0,0 +1,374 @@
+use crate::error::PsoError;
+use crate::fitness::Fitness;
+use crate::history::{History, Retention};
+use crate::observer::{Control, Observer};
+use crate::pso::{Options, Particles, SwarmState};
+use crate::reconfigure::Reconfiguration;
+use ndarray::Array1;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// When a [`Checkpointer`] saves and how many files it keeps.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Autosave {
+    pub every: Option<usize>,
+    pub interval: Option<Duration>,
+    pub keep: usize,
+}
+
+impl Default for Autosave {
+    /// Every 100 iterations, keeping 3 files.
+    fn default() -> Self {
+        Self {
+            every: Some(100),
+            interval: None,
+            keep: 3,
+        }
+    }
+}
+
+/// Everything needed to resume a run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Checkpoint {
+    /// Iterations done so far.
+    pub iter: usize,
+    pub options: Options,
+    pub state: SwarmState,
+    pub history: History,
+    /// Settings of the checkpointer that saved it, restored on [`resume`].
+    #[serde(default)]
+    pub autosave: Autosave,
+}
+
+/// Same layout as [`Checkpoint`], borrowing the history instead of cloning
+/// it on every save.
+#[derive(Serialize)]
+struct CheckpointRef<'a> {
+    iter: usize,
+    options: Options,
+    state: SwarmState,
+    history: &'a History,
+    autosave: Autosave,
+}
+
+fn write_json<T: Serialize, P: AsRef<Path>>(value: &T, path: P) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    serde_json::to_writer(&mut writer, value)?;
+    writer.flush()
+}
+
+impl Checkpoint {
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        write_json(self, path)
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let reader = BufReader::new(File::open(path)?);
+        Ok(serde_json::from_reader(reader)?)
+    }
+}
+
+/// Serde adapters for fitness values, which may be NaN or infinite. JSON has
+/// no literal for them, so those are written as strings to round-trip.
+pub(crate) mod nonfinite {
+    use ndarray::Array1;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::collections::VecDeque;
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(untagged)]
+    enum Float {
+        Number(f64),
+        Text(String),
+    }
+
+    impl Float {
+        fn new(value: f64) -> Self {
+            if value.is_finite() {
+                Float::Number(value)
+            } else {
+                Float::Text(value.to_string())
+            }
+        }
+
+        fn value<E: serde::de::Error>(self) -> Result<f64, E> {
+            match self {
+                Float::Number(value) => Ok(value),
+                Float::Text(text) => text
+                    .parse()
+                    .map_err(|_| E::custom(format!("invalid float {:?}", text))),
+            }
+        }
+    }
+
+    pub fn serialize<S: Serializer>(value: &f64, serializer: S) -> Result<S::Ok, S::Error> {
+        Float::new(*value).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+        Float::deserialize(deserializer)?.value()
+    }
+
+    /// For sequences of fitness values.
+    pub mod seq {
+        use super::*;
+
+        pub fn serialize<S>(values: &[f64], serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            serializer.collect_seq(values.iter().map(|&v| Float::new(v)))
+        }
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<f64>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            Vec::<Float>::deserialize(deserializer)?
+                .into_iter()
+                .map(Float::value)
+                .collect()
+        }
+    }
+
+    /// For remembered evaluations, positions with their fitness.
+    pub mod evaluations {
+        use super::*;
+
+        pub fn serialize<S>(
+            values: &VecDeque<(Array1<f64>, f64)>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            serializer.collect_seq(values.iter().map(|(x, f)| (x, Float::new(*f))))
+        }
+
+        pub fn deserialize<'de, D>(
+            deserializer: D,
+        ) -> Result<VecDeque<(Array1<f64>, f64)>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            Vec::<(Array1<f64>, Float)>::deserialize(deserializer)?
+                .into_iter()
+                .map(|(x, f)| Ok((x, f.value()?)))
+                .collect()
+        }
+    }
+}
+
+/// Autosaves the swarm, its RNG and the history while observing a run.
+///
+/// The newest checkpoint is always at `path`, older ones are rotated to
+/// `path.1`, `path.2`, ... up to the amount of kept files. A checkpoint is
+/// also saved when the run ends.
+pub struct Checkpointer {
+    path: PathBuf,
+    options: Options,
+    autosave: Autosave,
+    offset: usize,
+    iter: usize,
+    last_save: Instant,
+    history: History,
+    error: Option<io::Error>,
+}
+
+impl Checkpointer {
+    /// Saves every 100 iterations by default, `options` must be the ones
+    /// the run is using.
+    pub fn new<P: Into<PathBuf>>(path: P, options: Options) -> Self {
+        Self {
+            path: path.into(),
+            options,
+            autosave: Autosave::default(),
+            offset: 0,
+            iter: 0,
+            last_save: Instant::now(),
+            history: History::new(),
+            error: None,
+        }
+    }
+
+    /// Saves every `iters` iterations, `None` disables it.
+    pub fn every(mut self, iters: Option<usize>) -> Self {
+        self.autosave.every = iters.filter(|&iters| iters > 0);
+        self
+    }
+
+    /// Saves once this much time has passed since the last save, `None`
+    /// disables it.
+    pub fn interval(mut self, interval: Option<Duration>) -> Self {
+        self.autosave.interval = interval;
+        self
+    }
+
+    /// Amount of checkpoint files to keep, at least one.
+    pub fn keep(mut self, keep: usize) -> Self {
+        self.autosave.keep = keep.max(1);
+        self
+    }
+
+    /// Which records the saved history keeps, all of them by default. Long
+    /// runs should keep fewer, as the history is written on every save.
+    pub fn retention(mut self, retention: Retention) -> Result<Self, PsoError> {
+        self.history = History::with_retention(retention)?;
+        Ok(self)
+    }
+
+    pub fn autosave(&self) -> Autosave {
+        self.autosave
+    }
+
+    pub fn history(&self) -> &History {
+        &self.history
+    }
+
+    /// Iterations done, counting the ones before resuming.
+    pub fn iter(&self) -> usize {
+        self.iter
+    }
+
+    /// Returns the recorded history, or the first error found while saving.
+    pub fn finish(mut self) -> io::Result<History> {
+        match self.error.take() {
+            Some(err) => Err(err),
+            None => Ok(self.history),
+        }
+    }
+
+    pub fn save(&mut self, particles: &Particles) -> io::Result<()> {
+        let checkpoint = CheckpointRef {
+            iter: self.iter,
+            options: self.options,
+            state: particles.state(),
+            history: &self.history,
+            autosave: self.autosave,
+        };
+
+        let tmp = self.rotated(0).with_extension("tmp");
+        write_json(&checkpoint, &tmp)?;
+
+        for i in (1..self.autosave.keep).rev() {
+            let older = self.rotated(i - 1);
+            if older.exists() {
+                fs::rename(older, self.rotated(i))?;
+            }
+        }
+
+        fs::rename(tmp, &self.path)?;
+        self.last_save = Instant::now();
+
+        Ok(())
+    }
+
+    fn rotated(&self, i: usize) -> PathBuf {
+        if i == 0 {
+            self.path.clone()
+        } else {
+            let mut path = self.path.clone().into_os_string();
+            path.push(format!(".{}", i));
+            path.into()
+        }
+    }
+
+    fn is_due(&self) -> bool {
+        let Autosave {
+            every, interval, ..
+        } = self.autosave;
+        let by_iters = matches!(every, Some(every) if self.iter.is_multiple_of(every));
+        let by_time = matches!(interval, Some(interval) if self.last_save.elapsed() >= interval);
+
+        by_iters || by_time
+    }
+}
+
+impl Observer for Checkpointer {
+    fn on_start(&mut self, particles: &Particles) -> Control {
+        // Runs keep counting from the last one, e.g. after resuming
+        self.offset = self.iter;
+        if self.history.records().is_empty() {
+            self.history.record(0, particles);
+        }
+
+        Control::Continue
+    }
+
+    fn on_step(&mut self, iter: usize, particles: &Particles) -> Control {
+        self.iter = self.offset + iter;
+        self.history.record(self.iter, particles);
+
+        if self.is_due() {
+            if let Err(err) = self.save(particles) {
+                self.error = Some(err);
+                return Control::Stop;
+            }
+        }
+
+        Control::Continue
+    }
+
+    fn on_finish(&mut self, particles: &Particles) {
+        if self.error.is_none() {
+            if let Err(err) = self.save(particles) {
+                self.error = Some(err);
+            }
+        }
+    }
+}
+
+/// A run restored from a checkpoint.
+pub struct Resumed<'a> {
+    pub particles: Particles<'a>,
+    pub options: Options,
+    /// Keeps saving to the same path as often as before, with the previous
+    /// history and iteration count.
+    pub checkpointer: Checkpointer,
+}
+
+impl<'a> Resumed<'a> {
+    /// Iterations done so far, counting the ones before the checkpoint.
+    pub fn iter(&self) -> usize {
+        self.checkpointer.iter
+    }
+
+    /// Steers the resumed run, new options are also saved on the following
+    /// checkpoints.
+    pub fn reconfigure(&mut self, reconfiguration: &Reconfiguration) -> Result<(), PsoError> {
+        reconfiguration.apply(&mut self.particles, &mut self.options)?;
+        self.checkpointer.options = self.options;
+        Ok(())
+    }
+
+    /// Runs the remaining iterations to reach `total`.
+    pub fn run(&mut self, total: usize) -> Result<usize, PsoError> {
+        let remaining = total.saturating_sub(self.iter());
+        self.particles
+            .run_with(self.options, remaining, &mut [&mut self.checkpointer])
+    }
+}
+
+/// Restores the newest checkpoint at `path`, `fitness` must be the same one
+/// of the original run.
+pub fn resume<'a, P: Into<PathBuf>>(
+    path: P,
+    fitness: Fitness<'a, Array1<f64>>,
+) -> io::Result<Resumed<'a>> {
+    let path = path.into();
+    let checkpoint = Checkpoint::load(&path)?;
+
+    let mut checkpointer = Checkpointer::new(path, checkpoint.options);
+    checkpointer.autosave = checkpoint.autosave;
+    checkpointer.iter = checkpoint.iter;
+    checkpointer.history = checkpoint.history;
+
+    Ok(Resumed {
+        particles: Particles::from_state(checkpoint.state, fitness),
+        options: checkpoint.options,
+        checkpointer,
+    })
+}
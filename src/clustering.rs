@@ -0,0 +1,215 @@
+use crate::error::PsoError;
+use crate::pso::Particles;
+use ndarray::Array1;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Restarts of redundant regions for multimodal problems. Every `every`
+/// steps the positions are split in `clusters` with k-means, and a cluster
+/// whose centroid is within `radius` times the width of the value range
+/// of a better one, by the best personal best of its particles, is
+/// considered already explored: it keeps its best particle and the rest are
+/// [restarted](Particles::restart_particle). Distances use the swarm's
+/// metric, and restarts stop when the evaluation budget runs out.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClusterRestart {
+    pub every: usize,
+    pub clusters: usize,
+    pub radius: f64,
+}
+
+impl Default for ClusterRestart {
+    /// 4 clusters every 20 steps, redundant within a tenth of the range.
+    fn default() -> Self {
+        Self {
+            every: 20,
+            clusters: 4,
+            radius: 0.1,
+        }
+    }
+}
+
+impl ClusterRestart {
+    pub fn validate(&self) -> Result<(), PsoError> {
+        if self.every == 0 {
+            return Err(PsoError::InvalidOptions(
+                "cluster restarts need every to be at least 1".to_string(),
+            ));
+        }
+
+        if self.clusters < 2 {
+            return Err(PsoError::InvalidOptions(format!(
+                "cluster restarts need at least 2 clusters, got {}",
+                self.clusters
+            )));
+        }
+
+        if !(self.radius.is_finite() && self.radius >= 0.0) {
+            return Err(PsoError::InvalidOptions(format!(
+                "cluster radius must be finite and non-negative, got {}",
+                self.radius
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// Partition of a set of points found by [`kmeans`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Clusters {
+    pub centroids: Vec<Array1<f64>>,
+    /// Cluster of every point.
+    pub labels: Vec<usize>,
+}
+
+impl Clusters {
+    /// Indices of the points in the k-th cluster.
+    pub fn members(&self, k: usize) -> Vec<usize> {
+        (0..self.labels.len())
+            .filter(|&i| self.labels[i] == k)
+            .collect()
+    }
+}
+
+/// Most iterations of Lloyd's algorithm, it usually settles much earlier.
+const MAX_ITERS: usize = 100;
+
+/// Splits `points` in `k` clusters (at most one per point) minimizing the
+/// squared Euclidean distances to their centroids, with k-means++ seeding
+/// and Lloyd's iterations. A cluster left empty keeps its centroid.
+pub fn kmeans(points: &[Array1<f64>], k: usize, rng: &mut impl Rng) -> Clusters {
+    let k = k.min(points.len());
+    let squared = |a: &Array1<f64>, b: &Array1<f64>| (a - b).mapv(|v| v * v).sum();
+    let nearest = |x: &Array1<f64>, centroids: &[Array1<f64>]| {
+        (0..centroids.len())
+            .map(|c| (c, squared(x, &centroids[c])))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .expect("No centroids")
+    };
+
+    // k-means++, every seed is drawn proportionally to its squared distance
+    // to the closest one already chosen
+    let mut centroids: Vec<Array1<f64>> = Vec::with_capacity(k);
+    if k > 0 {
+        centroids.push(points[rng.gen_range(0, points.len())].clone());
+    }
+    while centroids.len() < k {
+        let weights: Vec<_> = points
+            .iter()
+            .map(|x| nearest(x, &centroids).1)
+            .map(|w| if w.is_finite() { w } else { 0.0 })
+            .collect();
+        let total: f64 = weights.iter().sum();
+        if !(total.is_finite() && total > 0.0) {
+            // Fewer distinct points than clusters
+            break;
+        }
+
+        let mut target = rng.gen_range(0.0, total);
+        let mut chosen = points.len() - 1;
+        for (i, weight) in weights.iter().enumerate() {
+            if target < *weight {
+                chosen = i;
+                break;
+            }
+            target -= weight;
+        }
+        centroids.push(points[chosen].clone());
+    }
+
+    let mut labels = vec![0; points.len()];
+    for iter in 0..MAX_ITERS {
+        let mut changed = false;
+        for (i, x) in points.iter().enumerate() {
+            let (c, _) = nearest(x, &centroids);
+            changed |= labels[i] != c;
+            labels[i] = c;
+        }
+
+        if iter > 0 && !changed {
+            break;
+        }
+
+        for (c, centroid) in centroids.iter_mut().enumerate() {
+            let members: Vec<_> = (0..points.len()).filter(|&i| labels[i] == c).collect();
+            if members.is_empty() {
+                continue;
+            }
+
+            centroid.fill(0.0);
+            for &i in &members {
+                *centroid += &points[i];
+            }
+            *centroid /= members.len() as f64;
+        }
+    }
+
+    Clusters { centroids, labels }
+}
+
+impl<'a> Particles<'a> {
+    /// Clusters the current positions with k-means.
+    pub fn clusters(&mut self, k: usize) -> Clusters {
+        let points: Vec<_> = self.particles().iter().map(|p| p.value().clone()).collect();
+        kmeans(&points, k, self.rng_mut())
+    }
+
+    /// Applies `restart` once, see [`ClusterRestart`], returns how many
+    /// particles were re-initialized. Does nothing between restarts, and
+    /// never restarts more particles than evaluations are left.
+    pub fn restart_clusters(&mut self, restart: &ClusterRestart) -> usize {
+        if !self.steps().is_multiple_of(restart.every) {
+            return 0;
+        }
+
+        let clusters = self.clusters(restart.clusters);
+        let (min, max) = self.value_range();
+        let radius = restart.radius * (max - min);
+
+        // Best member of every non-empty cluster, best clusters first
+        let mut leaders: Vec<_> = (0..clusters.centroids.len())
+            .filter_map(|k| {
+                clusters
+                    .members(k)
+                    .into_iter()
+                    .max_by(|&a, &b| {
+                        let particles = self.particles();
+                        self.compare(particles[a].best_fitness(), particles[b].best_fitness())
+                    })
+                    .map(|leader| (k, leader))
+            })
+            .collect();
+        leaders.sort_by(|a, b| {
+            let particles = self.particles();
+            self.compare(particles[b.1].best_fitness(), particles[a.1].best_fitness())
+        });
+
+        let mut kept: Vec<usize> = Vec::new();
+        let mut restarted = 0;
+        for (k, leader) in leaders {
+            let centroid = &clusters.centroids[k];
+            let redundant = kept
+                .iter()
+                .any(|&other| self.distance(centroid, &clusters.centroids[other]) <= radius);
+
+            if !redundant {
+                kept.push(k);
+                continue;
+            }
+
+            for i in clusters.members(k) {
+                if self.evaluations_left() == Some(0) {
+                    return restarted;
+                }
+
+                if i != leader {
+                    self.restart_particle(i);
+                    restarted += 1;
+                }
+            }
+        }
+
+        restarted
+    }
+}
@@ -0,0 +1,51 @@
+use crate::pso::Particles;
+use ndarray::Array1;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+impl<'a> Particles<'a> {
+    /// One round of the competitive swarm optimizer, see
+    /// [`Algorithm::Competitive`](crate::pso::Algorithm::Competitive).
+    pub(crate) fn compete(&mut self, phi: f64) {
+        let size = self.particles().len();
+        let dim = self.best().len();
+
+        let mut mean = Array1::zeros(dim);
+        for particle in self.particles() {
+            mean += particle.value();
+        }
+        mean /= size as f64;
+
+        let mut order: Vec<_> = (0..size).collect();
+        order.shuffle(self.rng_mut());
+
+        // With an odd size the last particle sits the round out
+        for pair in order.chunks_exact(2) {
+            if self.budget_exhausted() {
+                break;
+            }
+
+            let fitness = |i: usize| self.particles()[i].fitness();
+            let (winner, loser) = if !self.improves(fitness(pair[1]), fitness(pair[0])) {
+                (pair[0], pair[1])
+            } else {
+                (pair[1], pair[0])
+            };
+
+            let rng = self.rng_mut();
+            let rands: Vec<(f64, f64, f64)> = (0..dim)
+                .map(|_| (rng.gen(), rng.gen(), rng.gen()))
+                .collect();
+
+            let particles = self.particles();
+            let (w, l) = (particles[winner].value(), particles[loser].value());
+            let v = particles[loser].velocity();
+            let velocity = Array1::from_shape_fn(dim, |d| {
+                let (r_1, r_2, r_3) = rands[d];
+                r_1 * v[d] + r_2 * (w[d] - l[d]) + phi * r_3 * (mean[d] - l[d])
+            });
+
+            self.relocate(loser, velocity);
+        }
+    }
+}
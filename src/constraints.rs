@@ -0,0 +1,548 @@
+use crate::error::PsoError;
+use crate::fitness::Fitness;
+use crate::problems::Problem;
+use crate::pso::Particles;
+use ndarray::{Array1, Array2};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::f64::consts::PI;
+use std::sync::{Arc, RwLock};
+
+/// Linear inequality constraints `A x <= b`, optionally with bounds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinearConstraints {
+    a: Array2<f64>,
+    b: Array1<f64>,
+    bounds: Option<(Array1<f64>, Array1<f64>)>,
+}
+
+impl LinearConstraints {
+    /// One constraint per row of `a`.
+    pub fn new(a: Array2<f64>, b: Array1<f64>) -> Self {
+        assert_eq!(a.nrows(), b.len(), "A and b have different number of rows");
+
+        Self { a, b, bounds: None }
+    }
+
+    /// Box constraints only, `min <= x <= max`.
+    pub fn bounds(min: Array1<f64>, max: Array1<f64>) -> Self {
+        Self::new(Array2::zeros((0, min.len())), Array1::zeros(0)).with_bounds(min, max)
+    }
+
+    pub fn with_bounds(mut self, min: Array1<f64>, max: Array1<f64>) -> Self {
+        assert_eq!(min.len(), max.len(), "Bounds have different dimensions");
+
+        self.bounds = Some((min, max));
+        self
+    }
+
+    /// How much every constraint is violated (0 when satisfied), bounds come
+    /// after the rows of `A`, lower bound first.
+    pub fn violation(&self, x: &Array1<f64>) -> Array1<f64> {
+        let rows = (self.a.dot(x) - &self.b).mapv(|v| v.max(0.0));
+
+        match &self.bounds {
+            Some((min, max)) => {
+                let lower = (min - x).mapv(|v| v.max(0.0));
+                let upper = (x - max).mapv(|v| v.max(0.0));
+
+                let mut violation = rows.to_vec();
+                for (l, u) in lower.iter().zip(upper.iter()) {
+                    violation.push(*l);
+                    violation.push(*u);
+                }
+
+                Array1::from(violation)
+            }
+            None => rows,
+        }
+    }
+
+    pub fn is_feasible(&self, x: &Array1<f64>, tolerance: f64) -> bool {
+        self.violation(x).iter().all(|&v| v <= tolerance)
+    }
+
+    /// Euclidean projection onto the feasible set using Dykstra's alternating
+    /// projections, the set must be non-empty.
+    pub fn project(&self, x: &Array1<f64>) -> Array1<f64> {
+        const MAX_ITERS: usize = 1000;
+        const TOLERANCE: f64 = 1e-10;
+
+        if self.is_feasible(x, 0.0) {
+            return x.clone();
+        }
+
+        let sets = self.a.nrows() + self.bounds.is_some() as usize;
+        let mut increments = vec![Array1::zeros(x.len()); sets];
+        let mut x = x.clone();
+
+        for _ in 0..MAX_ITERS {
+            let prev = x.clone();
+
+            for (i, increment) in increments.iter_mut().enumerate() {
+                let y = &x + &*increment;
+                let projected = self.project_onto(i, &y);
+
+                *increment = &y - &projected;
+                x = projected;
+            }
+
+            let change = (&x - &prev).mapv(|v| v * v).sum().sqrt();
+            if change < TOLERANCE {
+                break;
+            }
+        }
+
+        x
+    }
+
+    /// Projection onto a single halfspace, or the box if `i` is past them.
+    fn project_onto(&self, i: usize, y: &Array1<f64>) -> Array1<f64> {
+        if i < self.a.nrows() {
+            let row = self.a.row(i);
+            let excess = row.dot(y) - self.b[i];
+            let norm = row.dot(&row);
+
+            if excess > 0.0 && norm > 0.0 {
+                y - &(&row * (excess / norm))
+            } else {
+                y.clone()
+            }
+        } else {
+            let (min, max) = self.bounds.as_ref().expect("No bounds set");
+
+            let mut y = y.clone();
+            for ((v, lo), hi) in y.iter_mut().zip(min.iter()).zip(max.iter()) {
+                *v = v.max(*lo).min(*hi);
+            }
+
+            y
+        }
+    }
+}
+
+/// Euclidean projection onto the probability simplex (non-negative
+/// components that add up to one).
+pub fn project_simplex(x: &Array1<f64>) -> Array1<f64> {
+    let mut sorted = x.to_vec();
+    sorted.sort_by(|a, b| b.partial_cmp(a).expect("Received a NaN"));
+
+    let mut sum = 0.0;
+    let mut theta = 0.0;
+    for (i, v) in sorted.iter().enumerate() {
+        sum += v;
+        let t = (sum - 1.0) / (i + 1) as f64;
+        if v - t > 0.0 {
+            theta = t;
+        }
+    }
+
+    x.mapv(|v| (v - theta).max(0.0))
+}
+
+/// Maps any point onto the interior of the probability simplex.
+pub fn softmax(x: &Array1<f64>) -> Array1<f64> {
+    let max = x.fold(f64::NEG_INFINITY, |max, &v| max.max(v));
+    let exp = x.mapv(|v| (v - max).exp());
+    let sum = exp.sum();
+
+    exp / sum
+}
+
+/// Softmax re-parameterization of an objective over the simplex, the swarm
+/// moves freely and `f` is evaluated on `softmax(x)`. Apply [`softmax`] to
+/// the resulting best to get the weights.
+pub fn on_softmax<F>(f: F) -> impl Fn(&Array1<f64>) -> f64
+where
+    F: Fn(&Array1<f64>) -> f64,
+{
+    move |x| f(&softmax(x))
+}
+
+/// Domain rule enforced on every new position, after the constraints are
+/// repaired and before it's evaluated (e.g. normalizing a weight vector so it
+/// adds up to one), see [`Particles::set_repair_hook`](crate::pso::Particles::set_repair_hook).
+pub type RepairHook<'a> = Box<dyn Fn(&mut Array1<f64>) + Send + Sync + 'a>;
+
+/// How infeasible positions are dealt with.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[allow(clippy::large_enum_variant)]
+pub enum ConstraintHandling {
+    /// Positions are projected onto the feasible set right after moving,
+    /// so the fitness is only ever evaluated on feasible points.
+    Projection(LinearConstraints),
+    /// Positions are projected onto the probability simplex, e.g. portfolio
+    /// or mixture weights.
+    Simplex,
+}
+
+impl ConstraintHandling {
+    /// Position that should be evaluated in place of `x`.
+    pub fn repair(&self, x: &Array1<f64>) -> Array1<f64> {
+        match self {
+            ConstraintHandling::Projection(constraints) => constraints.project(x),
+            ConstraintHandling::Simplex => project_simplex(x),
+        }
+    }
+}
+
+/// Objective and constraint violation of a solution of a [`Problem`].
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Evaluation {
+    pub objective: f64,
+    /// `Σ max(0, g_i(x))²`, 0 when feasible.
+    pub violation: f64,
+}
+
+impl Evaluation {
+    pub fn of(problem: &dyn Problem, x: &Array1<f64>) -> Self {
+        Self {
+            objective: problem.objective(x),
+            violation: problem
+                .constraints(x)
+                .iter()
+                .map(|&g| g.max(0.0).powi(2))
+                .sum(),
+        }
+    }
+
+    pub fn is_feasible(&self) -> bool {
+        self.violation <= 0.0
+    }
+}
+
+/// How solutions of a constrained [`Problem`] are compared, the objective
+/// being minimized.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum ConstraintComparison {
+    /// Static penalty, by `f(x) + weight * violation`, see
+    /// [`Problem::penalized`].
+    Penalty(f64),
+    /// Deb's rules: feasible solutions beat infeasible ones, feasible ones
+    /// are compared by objective and infeasible ones by violation.
+    #[default]
+    FeasibilityRules,
+    /// Runarsson and Yao's stochastic ranking: by objective when both are
+    /// feasible or with probability `pf`, by violation otherwise. Balances
+    /// objective and violation without tuning penalties, `pf` is usually
+    /// a bit below 0.5.
+    StochasticRanking { pf: f64 },
+}
+
+impl ConstraintComparison {
+    /// Stochastic ranking with the usual `pf = 0.45`.
+    pub fn stochastic_ranking() -> Self {
+        ConstraintComparison::StochasticRanking { pf: 0.45 }
+    }
+
+    pub fn validate(&self) -> Result<(), PsoError> {
+        match *self {
+            ConstraintComparison::Penalty(weight) if !(weight.is_finite() && weight >= 0.0) => {
+                Err(PsoError::InvalidOptions(format!(
+                    "penalty weight must be finite and non-negative, got {}",
+                    weight
+                )))
+            }
+            ConstraintComparison::StochasticRanking { pf } if !(0.0..=1.0).contains(&pf) => Err(
+                PsoError::InvalidOptions(format!("pf must be between 0 and 1, got {}", pf)),
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    /// `Greater` if `a` is better than `b`. Stochastic ranking draws from
+    /// `rng` when a solution is infeasible.
+    pub fn compare(&self, a: &Evaluation, b: &Evaluation, rng: &mut impl Rng) -> Ordering {
+        let by_objective = || b.objective.total_cmp(&a.objective);
+        let by_violation = || b.violation.total_cmp(&a.violation);
+
+        match *self {
+            ConstraintComparison::Penalty(weight) => {
+                let penalized = |e: &Evaluation| e.objective + weight * e.violation;
+                penalized(b).total_cmp(&penalized(a))
+            }
+            ConstraintComparison::FeasibilityRules => match (a.is_feasible(), b.is_feasible()) {
+                (true, true) => by_objective(),
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                (false, false) => by_violation(),
+            },
+            ConstraintComparison::StochasticRanking { pf } => {
+                let both = a.is_feasible() && b.is_feasible();
+                if both || rng.gen_range(0.0, 1.0) < pf {
+                    by_objective()
+                } else {
+                    by_violation()
+                }
+            }
+        }
+    }
+
+    /// Indices of `evaluations` from the best to the worst. Stochastic
+    /// ranking does up to `n` bubble-sort sweeps of random comparisons,
+    /// stopping early when a sweep swaps nothing.
+    pub fn rank(&self, evaluations: &[Evaluation], rng: &mut impl Rng) -> Vec<usize> {
+        let mut order: Vec<_> = (0..evaluations.len()).collect();
+
+        match self {
+            ConstraintComparison::StochasticRanking { .. } => {
+                for _ in 0..evaluations.len() {
+                    let mut swapped = false;
+                    for j in 1..order.len() {
+                        let (a, b) = (&evaluations[order[j - 1]], &evaluations[order[j]]);
+                        if self.compare(a, b, rng) == Ordering::Less {
+                            order.swap(j - 1, j);
+                            swapped = true;
+                        }
+                    }
+
+                    if !swapped {
+                        break;
+                    }
+                }
+            }
+            _ => order.sort_by(|&a, &b| self.compare(&evaluations[b], &evaluations[a], rng)),
+        }
+
+        order
+    }
+
+    /// Fitness to minimize that orders solutions as [`compare`](Self::compare)
+    /// does, `None` for stochastic ranking which only ranks populations.
+    /// Under feasibility rules feasible objectives are squashed into
+    /// `(-1, 0)`, losing resolution when they're large.
+    pub fn fitness<'p, P>(&self, problem: &'p P) -> Option<Fitness<'p, Array1<f64>>>
+    where
+        P: Problem + Sync,
+    {
+        match *self {
+            ConstraintComparison::Penalty(weight) => Some(
+                Fitness::shared(move |x: &Array1<f64>| problem.penalized(x, weight), true)
+                    .with_constraints(move |x: &Array1<f64>| problem.constraints(x)),
+            ),
+            ConstraintComparison::FeasibilityRules => Some(
+                Fitness::shared(
+                    move |x: &Array1<f64>| {
+                        let evaluation = Evaluation::of(problem, x);
+                        if evaluation.is_feasible() {
+                            evaluation.objective.atan() / PI - 0.5
+                        } else {
+                            evaluation.violation
+                        }
+                    },
+                    true,
+                )
+                .with_constraints(move |x: &Array1<f64>| problem.constraints(x)),
+            ),
+            ConstraintComparison::StochasticRanking { .. } => None,
+        }
+    }
+}
+
+impl<'a> Particles<'a> {
+    /// How much the global best violates every constraint of the fitness,
+    /// `None` if it has none, see [`Fitness::with_constraints`].
+    pub fn best_violations(&self) -> Option<Vec<f64>> {
+        self.fitness().violations(self.best())
+    }
+
+    /// Amount of particles at a position that satisfies every constraint
+    /// of the fitness, `None` if it has none.
+    pub fn feasible_particles(&self) -> Option<usize> {
+        let fitness = self.fitness();
+        if !fitness.has_constraints() {
+            return None;
+        }
+
+        let feasible = self.particles().iter().filter(|particle| {
+            let violations = fitness.violations(particle.value()).unwrap_or_default();
+            violations.iter().all(|&v| v <= 0.0)
+        });
+
+        Some(feasible.count())
+    }
+
+    /// Indices of the particles by their personal best on `problem`, from
+    /// the best to the worst, drawing from the swarm's RNG. Bests of
+    /// normalized swarms are denormalized first.
+    pub fn rank_bests(
+        &mut self,
+        problem: &dyn Problem,
+        comparison: ConstraintComparison,
+    ) -> Vec<usize> {
+        let evaluations: Vec<_> = self
+            .particles()
+            .iter()
+            .map(|particle| Evaluation::of(problem, &self.denormalize(particle.best())))
+            .collect();
+
+        comparison.rank(&evaluations, self.rng_mut())
+    }
+
+    /// Makes the first personal best of [`rank_bests`](Self::rank_bests)
+    /// the global best and returns its index. Call it after every step to
+    /// lead the swarm by stochastic ranking while it moves with a penalty.
+    pub fn promote_ranked_best(
+        &mut self,
+        problem: &dyn Problem,
+        comparison: ConstraintComparison,
+    ) -> usize {
+        let best = self.rank_bests(problem, comparison)[0];
+        self.promote_best(best);
+
+        best
+    }
+}
+
+/// Penalty weights that adapt to the fraction of particles satisfying each
+/// constraint, so they don't have to be tuned by hand. The fitness is
+/// `f(x) + Σ w_i max(0, g_i(x))²`, see [`Particles::adapt_penalty`].
+/// Clones share the same weights.
+#[derive(Debug, Clone)]
+pub struct AdaptivePenalty {
+    weights: Arc<RwLock<Vec<f64>>>,
+    target: f64,
+    factor: f64,
+    range: (f64, f64),
+}
+
+impl AdaptivePenalty {
+    /// Weights of `constraints` constraints, all starting at `initial`.
+    pub fn new(constraints: usize, initial: f64) -> Self {
+        Self {
+            weights: Arc::new(RwLock::new(vec![initial; constraints])),
+            target: 0.75,
+            factor: 2.0,
+            range: (1e-6, 1e12),
+        }
+    }
+
+    /// Fraction of particles that should satisfy each constraint, 0.75 by
+    /// default. Weights of constraints satisfied by fewer grow, the rest
+    /// shrink. Lower targets let the swarm explore more infeasible space,
+    /// but its best may end up slightly infeasible.
+    pub fn target(mut self, target: f64) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// How much weights grow or shrink on every adaptation, 2 by default.
+    pub fn factor(mut self, factor: f64) -> Self {
+        self.factor = factor;
+        self
+    }
+
+    /// Weights stay within `[min, max]`, `[1e-6, 1e12]` by default.
+    pub fn range(mut self, min: f64, max: f64) -> Self {
+        self.range = (min, max);
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), PsoError> {
+        if !(0.0..=1.0).contains(&self.target) {
+            return Err(PsoError::InvalidOptions(format!(
+                "target feasible fraction must be between 0 and 1, got {}",
+                self.target
+            )));
+        }
+
+        if !(self.factor.is_finite() && self.factor > 1.0) {
+            return Err(PsoError::InvalidOptions(format!(
+                "adaptation factor must be finite and greater than 1, got {}",
+                self.factor
+            )));
+        }
+
+        let (min, max) = self.range;
+        if !(min > 0.0 && min <= max && max.is_finite()) {
+            return Err(PsoError::InvalidOptions(format!(
+                "invalid weight range ({}, {})",
+                min, max
+            )));
+        }
+
+        Ok(())
+    }
+
+    pub fn weights(&self) -> Vec<f64> {
+        self.weights.read().expect("Poisoned weights").clone()
+    }
+
+    pub fn penalized(&self, problem: &dyn Problem, x: &Array1<f64>) -> f64 {
+        let weights = self.weights.read().expect("Poisoned weights");
+        let constraints = problem.constraints(x);
+        assert_eq!(
+            constraints.len(),
+            weights.len(),
+            "Expected {} constraints",
+            weights.len()
+        );
+
+        let violation: f64 = weights
+            .iter()
+            .zip(&constraints)
+            .map(|(w, g)| w * g.max(0.0).powi(2))
+            .sum();
+
+        problem.objective(x) + violation
+    }
+
+    /// Fitness to minimize with the current weights, whatever they adapt to.
+    pub fn fitness<'p, P>(&self, problem: &'p P) -> Fitness<'p, Array1<f64>>
+    where
+        P: Problem + Sync,
+    {
+        let penalty = self.clone();
+        Fitness::shared(move |x: &Array1<f64>| penalty.penalized(problem, x), true)
+            .with_constraints(move |x: &Array1<f64>| problem.constraints(x))
+    }
+
+    /// Adapts every weight to the fraction of `positions` satisfying its
+    /// constraint, returns the fraction satisfying all of them.
+    pub fn adapt(&self, problem: &dyn Problem, positions: &[Array1<f64>]) -> f64 {
+        let mut weights = self.weights.write().expect("Poisoned weights");
+        let mut satisfied = vec![0; weights.len()];
+        let mut feasible = 0;
+        for x in positions {
+            let constraints = problem.constraints(x);
+            for (count, g) in satisfied.iter_mut().zip(&constraints) {
+                *count += (*g <= 0.0) as usize;
+            }
+            feasible += constraints.iter().all(|&g| g <= 0.0) as usize;
+        }
+
+        let n = positions.len().max(1) as f64;
+        let (min, max) = self.range;
+        for (weight, &count) in weights.iter_mut().zip(&satisfied) {
+            let fraction = count as f64 / n;
+            if fraction < self.target {
+                *weight *= self.factor;
+            } else if fraction > self.target {
+                *weight /= self.factor;
+            }
+            *weight = weight.max(min).min(max);
+        }
+
+        feasible as f64 / n
+    }
+}
+
+impl<'a> Particles<'a> {
+    /// Adapts `penalty` to the current positions of the particles and
+    /// re-evaluates the bests with the new weights, call it after every
+    /// step of a swarm using [`AdaptivePenalty::fitness`]. Returns the
+    /// fraction of feasible particles.
+    pub fn adapt_penalty(&mut self, penalty: &AdaptivePenalty, problem: &dyn Problem) -> f64 {
+        let positions: Vec<_> = self
+            .particles()
+            .iter()
+            .map(|particle| self.denormalize(particle.value()))
+            .collect();
+
+        let feasible = penalty.adapt(problem, &positions);
+        self.reevaluate_bests();
+
+        feasible
+    }
+}
@@ -0,0 +1,106 @@
+use ndarray::Array1;
+use serde::{Deserialize, Serialize};
+
+/// How a dimension of the search space maps to the value the fitness
+/// receives.
+#[derive(Debug, Copy, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub enum Coordinate {
+    #[default]
+    Linear,
+    /// Searched as `log10(x)`, for positive values spanning orders of
+    /// magnitude (e.g. learning rates).
+    Log10,
+    /// Searched as `ln(x / (1 - x))`, for values in `(0, 1)`.
+    Logit,
+    /// Searched as `(x - offset) / scale`.
+    Affine { scale: f64, offset: f64 },
+}
+
+impl Coordinate {
+    /// Original value of the search coordinate `y`.
+    pub fn decode(self, y: f64) -> f64 {
+        match self {
+            Coordinate::Linear => y,
+            Coordinate::Log10 => 10f64.powf(y),
+            Coordinate::Logit => 1.0 / (1.0 + (-y).exp()),
+            Coordinate::Affine { scale, offset } => scale * y + offset,
+        }
+    }
+
+    /// Search coordinate of the original value `x`.
+    pub fn encode(self, x: f64) -> f64 {
+        match self {
+            Coordinate::Linear => x,
+            Coordinate::Log10 => x.log10(),
+            Coordinate::Logit => (x / (1.0 - x)).ln(),
+            Coordinate::Affine { scale, offset } => (x - offset) / scale,
+        }
+    }
+}
+
+/// Per-dimension coordinates of the search space, the swarm moves in the
+/// transformed space while the fitness receives the original values.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Coordinates {
+    coordinates: Vec<Coordinate>,
+}
+
+impl Coordinates {
+    pub fn new(coordinates: Vec<Coordinate>) -> Self {
+        Self { coordinates }
+    }
+
+    /// Same coordinate on every dimension.
+    pub fn uniform(dim: usize, coordinate: Coordinate) -> Self {
+        Self::new(vec![coordinate; dim])
+    }
+
+    /// Maps the box `[min, max]` to the unit hypercube, so every dimension
+    /// moves at the same relative speed whatever its scale.
+    pub fn normalized(min: &Array1<f64>, max: &Array1<f64>) -> Self {
+        assert_eq!(min.len(), max.len(), "Bounds have different dimensions");
+
+        let coordinates = min
+            .iter()
+            .zip(max)
+            .map(|(&min, &max)| Coordinate::Affine {
+                scale: max - min,
+                offset: min,
+            })
+            .collect();
+
+        Self::new(coordinates)
+    }
+
+    pub fn dim(&self) -> usize {
+        self.coordinates.len()
+    }
+
+    pub fn decode(&self, y: &Array1<f64>) -> Array1<f64> {
+        assert_eq!(y.len(), self.dim(), "Wrong number of dimensions");
+
+        y.iter()
+            .zip(&self.coordinates)
+            .map(|(&y, c)| c.decode(y))
+            .collect()
+    }
+
+    pub fn encode(&self, x: &Array1<f64>) -> Array1<f64> {
+        assert_eq!(x.len(), self.dim(), "Wrong number of dimensions");
+
+        x.iter()
+            .zip(&self.coordinates)
+            .map(|(&x, c)| c.encode(x))
+            .collect()
+    }
+
+    /// Fitness in the transformed space, pass it to
+    /// [`Fitness::new`](crate::fitness::Fitness::new) and decode the
+    /// resulting best to get the original values.
+    pub fn wrap<F>(self, f: F) -> impl Fn(&Array1<f64>) -> f64
+    where
+        F: Fn(&Array1<f64>) -> f64,
+    {
+        move |y| f(&self.decode(y))
+    }
+}
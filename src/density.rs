@@ -0,0 +1,143 @@
+use crate::error::PsoError;
+use crate::observer::{Control, Observer};
+use crate::pso::{Particles, PLOT_SIZE};
+use ndarray::Array2;
+use plotters::coord::Shift;
+use plotters::drawing::backend::DrawingBackend;
+use plotters::prelude::*;
+use std::path::Path;
+
+/// 2D histogram of the positions visited on the first two dimensions over a
+/// whole run, works as an observer of [`Particles::run_with`]. Empty cells
+/// are regions the swarm never explored.
+#[derive(Debug, Clone)]
+pub struct VisitDensity {
+    x_range: (f64, f64),
+    y_range: (f64, f64),
+    counts: Array2<usize>,
+    outside: usize,
+}
+
+impl VisitDensity {
+    /// `bins` cells per axis over the given `(min, max)` ranges.
+    pub fn new(x_range: (f64, f64), y_range: (f64, f64), bins: usize) -> Self {
+        assert!(bins > 0, "At least one bin is needed");
+        assert!(
+            x_range.0 < x_range.1 && y_range.0 < y_range.1,
+            "Ranges must satisfy min < max"
+        );
+
+        Self {
+            x_range,
+            y_range,
+            counts: Array2::zeros((bins, bins)),
+            outside: 0,
+        }
+    }
+
+    /// Visits of every cell, indexed by `[x bin, y bin]`.
+    pub fn counts(&self) -> &Array2<usize> {
+        &self.counts
+    }
+
+    /// Visits that fell outside of the ranges.
+    pub fn outside(&self) -> usize {
+        self.outside
+    }
+
+    pub fn total(&self) -> usize {
+        self.counts.sum() + self.outside
+    }
+
+    /// Fraction of the cells visited at least once.
+    pub fn coverage(&self) -> f64 {
+        let visited = self.counts.iter().filter(|&&c| c > 0).count();
+        visited as f64 / self.counts.len() as f64
+    }
+
+    /// Adds the current position of every particle.
+    pub fn add(&mut self, particles: &Particles) {
+        let bins = self.counts.nrows();
+        let bin = |v: f64, (min, max): (f64, f64)| {
+            if (min..=max).contains(&v) {
+                let i = ((v - min) / (max - min) * bins as f64) as usize;
+                Some(i.min(bins - 1))
+            } else {
+                None
+            }
+        };
+
+        for particle in particles.particles() {
+            let value = particle.value();
+            match (bin(value[0], self.x_range), bin(value[1], self.y_range)) {
+                (Some(i), Some(j)) => self.counts[[i, j]] += 1,
+                _ => self.outside += 1,
+            }
+        }
+    }
+
+    /// Heatmap of the visits on a log scale, unvisited cells are left
+    /// white.
+    pub fn plot<D>(&self, root: &DrawingArea<D, Shift>) -> Result<(), PsoError>
+    where
+        D: DrawingBackend,
+        <D as DrawingBackend>::ErrorType: 'static,
+    {
+        root.fill(&WHITE)?;
+
+        let (x_min, x_max) = self.x_range;
+        let (y_min, y_max) = self.y_range;
+        let mut chart = ChartBuilder::on(root)
+            .set_label_area_size(LabelAreaPosition::Left, 50)
+            .set_label_area_size(LabelAreaPosition::Bottom, 50)
+            .caption(
+                format!("Visits (coverage = {:.1}%)", 100.0 * self.coverage()),
+                ("sans-serif", 40),
+            )
+            .build_ranged(x_min..x_max, y_min..y_max)?;
+
+        chart.configure_mesh().disable_mesh().draw()?;
+
+        let bins = self.counts.nrows() as f64;
+        let (width, height) = ((x_max - x_min) / bins, (y_max - y_min) / bins);
+        let max = (*self.counts.iter().max().unwrap_or(&0) as f64).ln_1p();
+
+        chart.draw_series(
+            self.counts
+                .indexed_iter()
+                .filter(|(_, &count)| count > 0)
+                .map(|((i, j), &count)| {
+                    // From pale yellow to dark red as visits grow
+                    let t = if max > 0.0 {
+                        (count as f64).ln_1p() / max
+                    } else {
+                        1.0
+                    };
+                    let color = HSLColor(0.15 * (1.0 - t), 1.0, 0.85 - 0.5 * t);
+
+                    let x = x_min + i as f64 * width;
+                    let y = y_min + j as f64 * height;
+                    Rectangle::new([(x, y), (x + width, y + height)], color.filled())
+                }),
+        )?;
+
+        Ok(root.present()?)
+    }
+
+    pub fn plot_to_svg<P: AsRef<Path>>(&self, path: P) -> Result<(), PsoError> {
+        let root = SVGBackend::new(path.as_ref(), PLOT_SIZE).into_drawing_area();
+        self.plot(&root)
+    }
+}
+
+impl Observer for VisitDensity {
+    fn on_start(&mut self, particles: &Particles) -> Control {
+        self.add(particles);
+        Control::Continue
+    }
+
+    fn on_step(&mut self, _iter: usize, particles: &Particles) -> Control {
+        self.add(particles);
+        Control::Continue
+    }
+}
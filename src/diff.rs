@@ -0,0 +1,224 @@
+use crate::pso::{Particle, Particles, SwarmState};
+use ndarray::Array1;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Changes of a particle between two snapshots, matched by ID. Distances
+/// are Euclidean.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParticleDiff {
+    pub id: usize,
+    /// Indices in the swarm before and after, they differ when particles
+    /// were swapped or removed.
+    pub index: (usize, usize),
+    pub displacement: f64,
+    /// Norm of the change of velocity.
+    pub acceleration: f64,
+    pub fitness: (f64, f64),
+    /// Whether its personal best changed, and how far it moved.
+    pub best_changed: bool,
+    pub best_displacement: f64,
+}
+
+impl ParticleDiff {
+    pub fn moved(&self) -> bool {
+        self.displacement > 0.0
+    }
+
+    fn changed(&self) -> bool {
+        self.moved() || self.acceleration > 0.0 || self.best_changed || self.index.0 != self.index.1
+    }
+}
+
+/// What changed between two snapshots of a swarm, see
+/// [`Particles::diff`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SwarmDiff {
+    /// Steps done at each snapshot.
+    pub steps: (usize, usize),
+    /// Particles in both snapshots that changed in any way, by ID.
+    pub changed: Vec<ParticleDiff>,
+    /// IDs only in the later snapshot.
+    pub added: Vec<usize>,
+    /// IDs only in the earlier snapshot.
+    pub removed: Vec<usize>,
+    pub best_fitness: (f64, f64),
+    /// How far the global best moved.
+    pub best_displacement: f64,
+}
+
+impl SwarmDiff {
+    fn between(before: View, after: View) -> Self {
+        let mut changed = Vec::new();
+        let mut removed = Vec::new();
+        for (i, old) in before.particles.iter().enumerate() {
+            let j = match after.particles.iter().position(|p| p.id() == old.id()) {
+                Some(j) => j,
+                None => {
+                    removed.push(old.id());
+                    continue;
+                }
+            };
+
+            let new = &after.particles[j];
+            let diff = ParticleDiff {
+                id: old.id(),
+                index: (i, j),
+                displacement: distance(old.value(), new.value()),
+                acceleration: distance(old.velocity(), new.velocity()),
+                fitness: (old.fitness(), new.fitness()),
+                best_changed: old.best() != new.best()
+                    || old.best_fitness().to_bits() != new.best_fitness().to_bits(),
+                best_displacement: distance(old.best(), new.best()),
+            };
+
+            if diff.changed() {
+                changed.push(diff);
+            }
+        }
+
+        changed.sort_by_key(|diff| diff.id);
+        removed.sort_unstable();
+
+        let mut added: Vec<_> = after
+            .particles
+            .iter()
+            .map(Particle::id)
+            .filter(|&id| before.particles.iter().all(|p| p.id() != id))
+            .collect();
+        added.sort_unstable();
+
+        Self {
+            steps: (before.steps, after.steps),
+            changed,
+            added,
+            removed,
+            best_fitness: (before.best_fitness, after.best_fitness),
+            best_displacement: distance(before.best, after.best),
+        }
+    }
+
+    /// Diff between two snapshots, e.g. two checkpoints of the same run.
+    pub fn of_states(before: &SwarmState, after: &SwarmState) -> Self {
+        Self::between(View::from(before), View::from(after))
+    }
+
+    /// Whether nothing changed, the step counts aside.
+    pub fn is_empty(&self) -> bool {
+        self.changed.is_empty()
+            && self.added.is_empty()
+            && self.removed.is_empty()
+            && self.best_displacement == 0.0
+            && self.best_fitness.0.to_bits() == self.best_fitness.1.to_bits()
+    }
+
+    /// Particles in both snapshots at a different position.
+    pub fn moved(&self) -> impl Iterator<Item = &ParticleDiff> {
+        self.changed.iter().filter(|diff| diff.moved())
+    }
+
+    /// Particles in both snapshots whose personal best changed.
+    pub fn improved(&self) -> impl Iterator<Item = &ParticleDiff> {
+        self.changed.iter().filter(|diff| diff.best_changed)
+    }
+
+    /// Largest displacement of a particle, 0 if none moved.
+    pub fn max_displacement(&self) -> f64 {
+        self.changed
+            .iter()
+            .map(|diff| diff.displacement)
+            .fold(0.0, f64::max)
+    }
+}
+
+impl fmt::Display for SwarmDiff {
+    /// One line per changed particle, after a header with the global best.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "steps {} -> {}, best {} -> {} (moved {})",
+            self.steps.0,
+            self.steps.1,
+            self.best_fitness.0,
+            self.best_fitness.1,
+            self.best_displacement
+        )?;
+
+        for diff in &self.changed {
+            write!(
+                f,
+                "#{}: moved {}, fitness {} -> {}",
+                diff.id, diff.displacement, diff.fitness.0, diff.fitness.1
+            )?;
+            if diff.best_changed {
+                write!(f, ", best moved {}", diff.best_displacement)?;
+            }
+            if diff.index.0 != diff.index.1 {
+                write!(f, ", index {} -> {}", diff.index.0, diff.index.1)?;
+            }
+            writeln!(f)?;
+        }
+
+        for id in &self.added {
+            writeln!(f, "#{}: added", id)?;
+        }
+        for id in &self.removed {
+            writeln!(f, "#{}: removed", id)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The parts of a swarm or a snapshot that are compared.
+struct View<'s> {
+    particles: &'s [Particle],
+    best: &'s Array1<f64>,
+    best_fitness: f64,
+    steps: usize,
+}
+
+impl<'s> From<&'s SwarmState> for View<'s> {
+    fn from(state: &'s SwarmState) -> Self {
+        Self {
+            particles: state.particles(),
+            best: state.best(),
+            best_fitness: state.best_fitness(),
+            steps: state.steps(),
+        }
+    }
+}
+
+impl<'s, 'a> From<&'s Particles<'a>> for View<'s> {
+    fn from(particles: &'s Particles<'a>) -> Self {
+        Self {
+            particles: particles.particles(),
+            best: particles.best(),
+            best_fitness: particles.best_fitness(),
+            steps: particles.steps(),
+        }
+    }
+}
+
+/// Euclidean distance, infinite between different dimensions.
+fn distance(a: &Array1<f64>, b: &Array1<f64>) -> f64 {
+    if a.len() != b.len() {
+        return f64::INFINITY;
+    }
+
+    (a - b).mapv(|v| v * v).sum().sqrt()
+}
+
+impl<'a> Particles<'a> {
+    /// What changed from this swarm to `other`, usually a later version of
+    /// it (e.g. restored from a checkpoint) to debug custom policies or
+    /// check that resuming is exact.
+    pub fn diff(&self, other: &Particles) -> SwarmDiff {
+        SwarmDiff::between(View::from(self), View::from(other))
+    }
+
+    /// Same as [`diff`](Self::diff), against a snapshot.
+    pub fn diff_state(&self, other: &SwarmState) -> SwarmDiff {
+        SwarmDiff::between(View::from(self), View::from(other))
+    }
+}
@@ -0,0 +1,102 @@
+use crate::error::PsoError;
+use crate::lineage::LineageEvent;
+use crate::pso::Particles;
+use ndarray::Array1;
+use rand::seq::index;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// DE/rand/1/bin operator on the personal bests (PSO-DE hybrid). On every
+/// application a `fraction` of the particles, picked at random, get a trial
+/// position mixing their best with `b_1 + scale (b_2 - b_3)`, the bests of
+/// three other random particles, each dimension taken from the mutant with
+/// probability `crossover`. Particles only move to their trial when it
+/// improves their personal best.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Differential {
+    pub fraction: f64,
+    pub scale: f64,
+    pub crossover: f64,
+}
+
+impl Default for Differential {
+    /// A fifth of the swarm, `scale = 0.5` and `crossover = 0.9`.
+    fn default() -> Self {
+        Self {
+            fraction: 0.2,
+            scale: 0.5,
+            crossover: 0.9,
+        }
+    }
+}
+
+impl Differential {
+    pub fn validate(&self) -> Result<(), PsoError> {
+        let rules = [
+            ("fraction", self.fraction, 0.0..=1.0),
+            ("scale", self.scale, 0.0..=2.0),
+            ("crossover", self.crossover, 0.0..=1.0),
+        ];
+
+        for (name, value, range) in rules.iter() {
+            if !range.contains(value) {
+                return Err(PsoError::InvalidOptions(format!(
+                    "{} must be in [{}, {}], got {}",
+                    name,
+                    range.start(),
+                    range.end(),
+                    value
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> Particles<'a> {
+    /// Applies `differential` once, returns how many particles moved to
+    /// their trial. Swarms with less than 4 particles are left untouched.
+    pub fn apply_differential(&mut self, differential: &Differential) -> usize {
+        let size = self.particles().len();
+        let dim = self.best().len();
+        if size < 4 {
+            return 0;
+        }
+
+        let count = (differential.fraction * size as f64).round() as usize;
+        let targets = index::sample(self.rng_mut(), size, count).into_vec();
+
+        let mut moved = 0;
+        for target in targets {
+            let rng = self.rng_mut();
+            // Three other particles, skipping the target
+            let donors: Vec<_> = index::sample(rng, size - 1, 3)
+                .into_iter()
+                .map(|j| if j >= target { j + 1 } else { j })
+                .collect();
+            let forced = rng.gen_range(0, dim);
+            let crossed: Vec<_> = (0..dim)
+                .map(|d| d == forced || rng.gen::<f64>() < differential.crossover)
+                .collect();
+
+            let particles = self.particles();
+            let bests: Vec<_> = donors.iter().map(|&j| particles[j].best()).collect();
+            let own = particles[target].best();
+            let mut trial = Array1::from_shape_fn(dim, |d| {
+                if crossed[d] {
+                    bests[0][d] + differential.scale * (bests[1][d] - bests[2][d])
+                } else {
+                    own[d]
+                }
+            });
+            self.fix_position(&mut trial);
+
+            if self.offer_trial(target, trial, LineageEvent::Recombined) {
+                moved += 1;
+            }
+        }
+
+        moved
+    }
+}
@@ -0,0 +1,291 @@
+use crate::fitness::Fitness;
+use crate::island::{Migrant, Transport};
+use ndarray::Array1;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Frames bigger than this are rejected, no position gets near it.
+const MAX_FRAME: u32 = 64 * 1024 * 1024;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Task {
+    value: Array1<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Outcome {
+    /// Bits of the fitness, JSON has no infinities nor NaN.
+    fitness: u64,
+}
+
+/// Writes `message` as a frame: its length as a big-endian `u32` followed
+/// by its JSON encoding.
+fn write_frame<W: Write, T: Serialize>(writer: &mut W, message: &T) -> io::Result<()> {
+    let body = serde_json::to_vec(message)?;
+    let len = u32::try_from(body.len())
+        .ok()
+        .filter(|&len| len <= MAX_FRAME)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "frame too big"))?;
+
+    writer.write_all(&len.to_be_bytes())?;
+    writer.write_all(&body)?;
+    writer.flush()
+}
+
+/// Reads a frame written by [`write_frame`], `None` if the connection was
+/// closed before it started.
+fn read_frame<R: Read, T: DeserializeOwned>(reader: &mut R) -> io::Result<Option<T>> {
+    let mut len = [0; 4];
+    match reader.read_exact(&mut len) {
+        Ok(()) => {}
+        Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(err) => return Err(err),
+    }
+
+    let len = u32::from_be_bytes(len);
+    if len > MAX_FRAME {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "frame too big"));
+    }
+
+    let mut body = vec![0; len as usize];
+    reader.read_exact(&mut body)?;
+    Ok(Some(serde_json::from_slice(&body)?))
+}
+
+/// Evaluates positions sent by a [`Cluster`], e.g. on another machine.
+pub struct Worker<F> {
+    fitness: Arc<F>,
+}
+
+impl<F> Worker<F>
+where
+    F: Fn(&Array1<f64>) -> f64 + Send + Sync + 'static,
+{
+    pub fn new(fitness: F) -> Self {
+        Self {
+            fitness: Arc::new(fitness),
+        }
+    }
+
+    /// Serves every connection to `listener` on its own thread, only
+    /// returns if accepting fails.
+    pub fn serve(&self, listener: TcpListener) -> io::Result<()> {
+        for stream in listener.incoming() {
+            let worker = Worker {
+                fitness: Arc::clone(&self.fitness),
+            };
+            let stream = stream?;
+            thread::spawn(move || worker.handle(stream));
+        }
+
+        Ok(())
+    }
+
+    /// Answers the positions sent through `stream` until it's closed.
+    pub fn handle(&self, stream: TcpStream) -> io::Result<()> {
+        stream.set_nodelay(true)?;
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let mut writer = BufWriter::new(stream);
+
+        while let Some(Task { value }) = read_frame(&mut reader)? {
+            let fitness = (self.fitness)(&value);
+            let outcome = Outcome {
+                fitness: fitness.to_bits(),
+            };
+            write_frame(&mut writer, &outcome)?;
+        }
+
+        Ok(())
+    }
+}
+
+struct Connection {
+    reader: BufReader<TcpStream>,
+    writer: BufWriter<TcpStream>,
+}
+
+impl Connection {
+    fn open(stream: TcpStream) -> io::Result<Self> {
+        stream.set_nodelay(true)?;
+        Ok(Self {
+            reader: BufReader::new(stream.try_clone()?),
+            writer: BufWriter::new(stream),
+        })
+    }
+
+    fn evaluate(&mut self, value: &Array1<f64>) -> io::Result<f64> {
+        write_frame(
+            &mut self.writer,
+            &Task {
+                value: value.clone(),
+            },
+        )?;
+
+        match read_frame::<_, Outcome>(&mut self.reader)? {
+            Some(outcome) => Ok(f64::from_bits(outcome.fitness)),
+            None => Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "worker closed the connection",
+            )),
+        }
+    }
+}
+
+struct Pool {
+    idle: Vec<Connection>,
+    /// Connections alive, idle or busy.
+    alive: usize,
+}
+
+/// Connections to remote [`Worker`]s. Every evaluation takes the first idle
+/// worker, so faster workers take more of the load. With
+/// [`Parallelism::Parallel`](crate::pso::Parallelism::Parallel) a step keeps
+/// every worker busy, serial steps use one worker at a time.
+#[derive(Clone)]
+pub struct Cluster {
+    pool: Arc<(Mutex<Pool>, Condvar)>,
+}
+
+impl Cluster {
+    /// Connects to every worker at `addrs`.
+    pub fn connect<A: ToSocketAddrs>(addrs: &[A]) -> io::Result<Self> {
+        let idle = addrs
+            .iter()
+            .map(|addr| Connection::open(TcpStream::connect(addr)?))
+            .collect::<io::Result<Vec<_>>>()?;
+
+        if idle.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "at least one worker is needed",
+            ));
+        }
+
+        let alive = idle.len();
+        Ok(Self {
+            pool: Arc::new((Mutex::new(Pool { idle, alive }), Condvar::new())),
+        })
+    }
+
+    /// Workers still connected.
+    pub fn workers(&self) -> usize {
+        self.pool.0.lock().expect("Poisoned worker pool").alive
+    }
+
+    /// Evaluates `value` on the first idle worker. A worker that fails is
+    /// dropped and the position is sent to another one, it's an error only
+    /// once no workers are left.
+    pub fn evaluate(&self, value: &Array1<f64>) -> io::Result<f64> {
+        let (lock, available) = &*self.pool;
+
+        loop {
+            let mut connection = {
+                let mut pool = lock.lock().expect("Poisoned worker pool");
+                loop {
+                    if let Some(connection) = pool.idle.pop() {
+                        break connection;
+                    }
+                    if pool.alive == 0 {
+                        return Err(io::Error::new(
+                            io::ErrorKind::NotConnected,
+                            "every worker disconnected",
+                        ));
+                    }
+
+                    pool = available.wait(pool).expect("Poisoned worker pool");
+                }
+            };
+
+            let result = connection.evaluate(value);
+
+            let mut pool = lock.lock().expect("Poisoned worker pool");
+            match result {
+                Ok(fitness) => {
+                    pool.idle.push(connection);
+                    available.notify_one();
+                    return Ok(fitness);
+                }
+                Err(_) => {
+                    pool.alive -= 1;
+                    // Waiters have to notice when no workers are left
+                    available.notify_all();
+                }
+            }
+        }
+    }
+
+    /// Shared fitness evaluated by the cluster.
+    ///
+    /// # Panics
+    ///
+    /// When evaluating once every worker disconnected.
+    pub fn fitness(&self, minimization: bool) -> Fitness<'static, Array1<f64>> {
+        let cluster = self.clone();
+        Fitness::shared(
+            move |x: &Array1<f64>| {
+                cluster
+                    .evaluate(x)
+                    .unwrap_or_else(|err| panic!("Remote evaluation failed: {}", err))
+            },
+            minimization,
+        )
+    }
+}
+
+/// How long [`TcpTransport::connect`] waits for the next island to listen.
+const PATIENCE: Duration = Duration::from_secs(30);
+
+/// Island transport over TCP, each island connects to the listener of the
+/// next one in the ring.
+pub struct TcpTransport {
+    outgoing: Connection,
+    incoming: Connection,
+}
+
+impl TcpTransport {
+    /// Connects to the next island at `next` and accepts the previous one on
+    /// `listener`. The next island may start a bit later, refused
+    /// connections are retried for 30 seconds.
+    pub fn connect<A: ToSocketAddrs>(listener: &TcpListener, next: A) -> io::Result<Self> {
+        let start = Instant::now();
+        let outgoing = loop {
+            match TcpStream::connect(&next) {
+                Ok(stream) => break stream,
+                Err(err)
+                    if err.kind() == io::ErrorKind::ConnectionRefused
+                        && start.elapsed() < PATIENCE =>
+                {
+                    thread::sleep(Duration::from_millis(50));
+                }
+                Err(err) => return Err(err),
+            }
+        };
+        let (incoming, _) = listener.accept()?;
+
+        Ok(Self {
+            outgoing: Connection::open(outgoing)?,
+            incoming: Connection::open(incoming)?,
+        })
+    }
+}
+
+impl Transport for TcpTransport {
+    fn send(&mut self, migrants: Vec<Migrant>) -> io::Result<()> {
+        write_frame(&mut self.outgoing.writer, &migrants)
+    }
+
+    fn receive(&mut self) -> io::Result<Vec<Migrant>> {
+        read_frame(&mut self.incoming.reader)?.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "previous island closed the connection",
+            )
+        })
+    }
+}
@@ -0,0 +1,146 @@
+use crate::lineage::LineageEvent;
+use crate::pso::Particles;
+use ndarray::Array1;
+use serde::{Deserialize, Serialize};
+
+/// How spread out the current positions are, see [`Particles::diversity`].
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Diversity {
+    /// Mean distance to the centroid, with the swarm's metric.
+    pub mean_distance: f64,
+    /// Particles sitting on top of a better one, their evaluations are
+    /// wasted.
+    pub duplicates: usize,
+    /// Duplicates re-dispersed since the swarm was created.
+    pub dispersed: usize,
+}
+
+/// Positions of the particles along one dimension, see
+/// [`Particles::dimension_stats`].
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DimensionStats {
+    pub mean: f64,
+    /// Population standard deviation.
+    pub std: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl DimensionStats {
+    pub fn range(&self) -> f64 {
+        self.max - self.min
+    }
+
+    /// Whether the particles agree on this dimension up to `tolerance`,
+    /// otherwise it's still being explored.
+    pub fn converged(&self, tolerance: f64) -> bool {
+        self.std <= tolerance
+    }
+}
+
+impl<'a> Particles<'a> {
+    /// Statistics of every dimension, a dimension that converges much
+    /// earlier or later than the others usually points to a badly scaled
+    /// objective.
+    pub fn dimension_stats(&self) -> Vec<DimensionStats> {
+        let particles = self.particles();
+        let n = particles.len() as f64;
+
+        (0..self.best().len())
+            .map(|d| {
+                let values = particles.iter().map(|p| p.value()[d]);
+                let mean = values.clone().sum::<f64>() / n;
+                let variance = values.clone().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+                let (min, max) = values
+                    .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| {
+                        (min.min(v), max.max(v))
+                    });
+
+                DimensionStats {
+                    mean,
+                    std: variance.sqrt(),
+                    min,
+                    max,
+                }
+            })
+            .collect()
+    }
+
+    /// Indices of the dimensions that converged up to `tolerance`.
+    pub fn converged_dimensions(&self, tolerance: f64) -> Vec<usize> {
+        self.dimension_stats()
+            .iter()
+            .enumerate()
+            .filter(|(_, stats)| stats.converged(tolerance))
+            .map(|(d, _)| d)
+            .collect()
+    }
+
+    /// Indices of the particles within `tolerance` (with the swarm's
+    /// [metric](Particles::distance)) of another one with a better current
+    /// fitness. Of every group of
+    /// collapsed particles only the best one isn't reported.
+    pub fn duplicates(&self, tolerance: f64) -> Vec<usize> {
+        let particles = self.particles();
+
+        let mut order: Vec<_> = (0..particles.len()).collect();
+        order.sort_by(|&a, &b| self.compare(particles[b].fitness(), particles[a].fitness()));
+
+        let mut kept: Vec<usize> = Vec::new();
+        let mut duplicates = Vec::new();
+        for i in order {
+            let value = particles[i].value();
+            if kept
+                .iter()
+                .any(|&k| self.distance(value, particles[k].value()) <= tolerance)
+            {
+                duplicates.push(i);
+            } else {
+                kept.push(i);
+            }
+        }
+
+        duplicates.sort_unstable();
+        duplicates
+    }
+
+    pub fn diversity(&self, tolerance: f64) -> Diversity {
+        let particles = self.particles();
+        let n = particles.len() as f64;
+
+        let mut centroid = Array1::zeros(self.best().len());
+        for particle in particles {
+            centroid += particle.value();
+        }
+        centroid /= n;
+
+        let mean_distance = particles
+            .iter()
+            .map(|p| self.distance(p.value(), &centroid))
+            .sum::<f64>()
+            / n;
+
+        let dispersed = self
+            .lineage()
+            .iter()
+            .filter(|l| l.event == LineageEvent::Dispersed)
+            .count();
+
+        Diversity {
+            mean_distance,
+            duplicates: self.duplicates(tolerance).len(),
+            dispersed,
+        }
+    }
+
+    /// Re-initializes every duplicate with the initial distributions, the
+    /// global best is kept. Returns how many were moved.
+    pub fn disperse_duplicates(&mut self, tolerance: f64) -> usize {
+        let duplicates = self.duplicates(tolerance);
+        for &i in &duplicates {
+            self.resample_particle(i, LineageEvent::Dispersed);
+        }
+
+        duplicates.len()
+    }
+}
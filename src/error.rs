@@ -0,0 +1,40 @@
+use plotters::drawing::DrawingAreaErrorKind;
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PsoError {
+    /// The configuration violates the given rule.
+    InvalidOptions(String),
+    /// The drawing backend failed, with its message.
+    Plot(String),
+    /// Formatting a report failed.
+    Format,
+    /// Exchanging migrants with other swarms failed, with its message.
+    Transport(String),
+}
+
+impl fmt::Display for PsoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PsoError::InvalidOptions(rule) => write!(f, "invalid options: {}", rule),
+            PsoError::Plot(message) => write!(f, "plotting failed: {}", message),
+            PsoError::Format => write!(f, "formatting failed"),
+            PsoError::Transport(message) => write!(f, "migration failed: {}", message),
+        }
+    }
+}
+
+impl Error for PsoError {}
+
+impl<E: Error + Send + Sync> From<DrawingAreaErrorKind<E>> for PsoError {
+    fn from(err: DrawingAreaErrorKind<E>) -> Self {
+        PsoError::Plot(err.to_string())
+    }
+}
+
+impl From<fmt::Error> for PsoError {
+    fn from(_: fmt::Error) -> Self {
+        PsoError::Format
+    }
+}
@@ -0,0 +1,599 @@
+use crate::error::PsoError;
+use crate::fitness::Scaling;
+use crate::history::History;
+use crate::pso::{Options, Particles, PLOT_SIZE};
+use crate::surrogate::erf;
+use plotters::coord::Shift;
+use plotters::drawing::backend::DrawingBackend;
+use plotters::prelude::*;
+use prettytable::{row, Table};
+use std::path::Path;
+
+type Configure = Box<dyn Fn(&mut Particles)>;
+
+/// A named configuration to compare against others.
+pub struct Variant {
+    pub name: String,
+    pub options: Options,
+    configure: Option<Configure>,
+}
+
+impl Variant {
+    pub fn new(name: impl Into<String>, options: Options) -> Self {
+        Self {
+            name: name.into(),
+            options,
+            configure: None,
+        }
+    }
+
+    /// Adjusts every swarm of this variant before running it (e.g. the
+    /// tie-breaking policy or exclusion zones).
+    pub fn configure<F>(mut self, configure: F) -> Self
+    where
+        F: Fn(&mut Particles) + 'static,
+    {
+        self.configure = Some(Box::new(configure));
+        self
+    }
+}
+
+/// Outcome of every seed of a variant.
+#[derive(Debug, Clone)]
+pub struct VariantResult {
+    pub name: String,
+    /// Best fitness at the end of every run.
+    pub finals: Vec<f64>,
+    /// Best fitness on every iteration of every run.
+    pub convergence: Vec<Vec<f64>>,
+}
+
+impl VariantResult {
+    pub fn mean(&self) -> f64 {
+        self.finals.iter().sum::<f64>() / self.finals.len() as f64
+    }
+
+    pub fn std(&self) -> f64 {
+        let mean = self.mean();
+        let var =
+            self.finals.iter().map(|f| (f - mean).powi(2)).sum::<f64>() / self.finals.len() as f64;
+
+        var.sqrt()
+    }
+
+    pub fn median(&self) -> f64 {
+        median(&self.finals)
+    }
+
+    /// Median best fitness on every iteration across the runs.
+    pub fn median_convergence(&self) -> Vec<f64> {
+        self.convergence_by(median)
+    }
+
+    /// `p`-th percentile (0 to 100) of the best fitness on every iteration
+    /// across the runs, interpolating between runs.
+    pub fn percentile_convergence(&self, p: f64) -> Vec<f64> {
+        self.convergence_by(|values| percentile(values, p))
+    }
+
+    /// Median, interquartile and 5-95 percentile bands of the best fitness
+    /// on every iteration.
+    pub fn bands(&self) -> ConvergenceBands {
+        ConvergenceBands {
+            median: self.median_convergence(),
+            quartiles: (
+                self.percentile_convergence(25.0),
+                self.percentile_convergence(75.0),
+            ),
+            outer: (
+                self.percentile_convergence(5.0),
+                self.percentile_convergence(95.0),
+            ),
+        }
+    }
+
+    /// `reduce` of the best fitness of every run, on every iteration all of
+    /// them reached.
+    fn convergence_by<F>(&self, reduce: F) -> Vec<f64>
+    where
+        F: Fn(&[f64]) -> f64,
+    {
+        let iters = self.convergence.iter().map(Vec::len).min().unwrap_or(0);
+
+        (0..iters)
+            .map(|i| {
+                let values: Vec<_> = self.convergence.iter().map(|c| c[i]).collect();
+                reduce(&values)
+            })
+            .collect()
+    }
+
+    fn min(&self) -> f64 {
+        self.finals.iter().copied().fold(f64::INFINITY, f64::min)
+    }
+
+    fn max(&self) -> f64 {
+        self.finals
+            .iter()
+            .copied()
+            .fold(f64::NEG_INFINITY, f64::max)
+    }
+}
+
+pub(crate) fn median(values: &[f64]) -> f64 {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).expect("Received a NaN"));
+
+    let n = sorted.len();
+    if n == 0 {
+        f64::NAN
+    } else if n % 2 == 1 {
+        sorted[n / 2]
+    } else {
+        (sorted[n / 2 - 1] + sorted[n / 2]) / 2.0
+    }
+}
+
+/// Percentile `p` (0 to 100) interpolating linearly between the closest
+/// ranks, NaN if there are no values.
+pub(crate) fn percentile(values: &[f64], p: f64) -> f64 {
+    assert!(
+        (0.0..=100.0).contains(&p),
+        "Percentile must be between 0 and 100"
+    );
+
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).expect("Received a NaN"));
+
+    if sorted.is_empty() {
+        return f64::NAN;
+    }
+
+    let rank = p / 100.0 * (sorted.len() - 1) as f64;
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+/// Spread of the convergence across seeds, see [`VariantResult::bands`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConvergenceBands {
+    pub median: Vec<f64>,
+    /// 25th and 75th percentiles.
+    pub quartiles: (Vec<f64>, Vec<f64>),
+    /// 5th and 95th percentiles.
+    pub outer: (Vec<f64>, Vec<f64>),
+}
+
+impl ConvergenceBands {
+    fn scale(&self, scaling: Scaling) -> Self {
+        let scale = |values: &Vec<f64>| scaling.apply(values);
+
+        Self {
+            median: scale(&self.median),
+            quartiles: (scale(&self.quartiles.0), scale(&self.quartiles.1)),
+            outer: (scale(&self.outer.0), scale(&self.outer.1)),
+        }
+    }
+}
+
+/// Largest product of the sample sizes for which the p-value of
+/// [`rank_sum_test`] is exact.
+const EXACT_LIMIT: usize = 400;
+
+/// Magnitude of a Vargha-Delaney A12 effect size.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Effect {
+    Negligible,
+    Small,
+    Medium,
+    Large,
+}
+
+/// Wilcoxon rank-sum (Mann-Whitney U) test of whether two samples come
+/// from the same distribution, see [`rank_sum_test`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct RankSumTest {
+    /// U statistic of the first sample, pairs where it's greater, ties
+    /// counting half.
+    pub u: f64,
+    /// Two-sided p-value, exact for small samples without ties.
+    pub p_value: f64,
+    /// Vargha-Delaney A12, probability that a value of the first sample is
+    /// greater than one of the second, ties counting half. 0.5 means no
+    /// difference.
+    pub a12: f64,
+}
+
+impl RankSumTest {
+    /// Whether the difference is significant at level `alpha`.
+    pub fn significant(&self, alpha: f64) -> bool {
+        self.p_value < alpha
+    }
+
+    /// Vargha and Delaney's thresholds, 0.56, 0.64 and 0.71.
+    pub fn effect(&self) -> Effect {
+        let distance = (self.a12 - 0.5).abs();
+        if distance >= 0.21 {
+            Effect::Large
+        } else if distance >= 0.14 {
+            Effect::Medium
+        } else if distance >= 0.06 {
+            Effect::Small
+        } else {
+            Effect::Negligible
+        }
+    }
+}
+
+/// Two-sided Wilcoxon rank-sum test between `a` and `b`. For larger samples
+/// or with ties the p-value comes from the normal approximation, with tie
+/// and continuity corrections.
+///
+/// # Panics
+///
+/// If a sample is empty or has a NaN.
+pub fn rank_sum_test(a: &[f64], b: &[f64]) -> RankSumTest {
+    assert!(!a.is_empty() && !b.is_empty(), "Samples can't be empty");
+
+    let (m, n) = (a.len(), b.len());
+    let mut values: Vec<_> = a
+        .iter()
+        .map(|&v| (v, 0))
+        .chain(b.iter().map(|&v| (v, 1)))
+        .collect();
+    values.sort_by(|x, y| x.0.partial_cmp(&y.0).expect("Received a NaN"));
+
+    // Average ranks of tied values, and the tie correction of the variance
+    let mut rank_sum = 0.0;
+    let mut ties = 0.0;
+    let mut start = 0;
+    while start < values.len() {
+        let end = (start..values.len())
+            .find(|&i| values[i].0 != values[start].0)
+            .unwrap_or(values.len());
+        let rank = (start + end + 1) as f64 / 2.0;
+        let tied = (end - start) as f64;
+        ties += tied.powi(3) - tied;
+
+        rank_sum += rank * values[start..end].iter().filter(|v| v.1 == 0).count() as f64;
+        start = end;
+    }
+
+    let (mf, nf) = (m as f64, n as f64);
+    let u = rank_sum - mf * (mf + 1.0) / 2.0;
+    let mean = mf * nf / 2.0;
+
+    let p_value = if ties == 0.0 && m * n <= EXACT_LIMIT {
+        exact_p_value(u, m, n)
+    } else {
+        let total = mf + nf;
+        let variance = mf * nf / 12.0 * ((total + 1.0) - ties / (total * (total - 1.0)));
+        if variance <= 0.0 {
+            1.0
+        } else {
+            let z = ((u - mean).abs() - 0.5).max(0.0) / variance.sqrt();
+            1.0 - erf(z / 2f64.sqrt())
+        }
+    };
+
+    RankSumTest {
+        u,
+        p_value: p_value.min(1.0),
+        a12: u / (mf * nf),
+    }
+}
+
+/// Two-sided p-value of `u` from the distribution of U without ties.
+fn exact_p_value(u: f64, m: usize, n: usize) -> f64 {
+    // counts[j][k] holds the arrangements of j values of the first sample
+    // and the second one with U = k, built up one value of the second
+    // sample at a time
+    let max = m * n;
+    let mut counts = vec![vec![0.0; max + 1]; m + 1];
+    for row in counts.iter_mut() {
+        row[0] = 1.0;
+    }
+    for i in 1..=n {
+        let mut next = vec![vec![0.0; max + 1]; m + 1];
+        next[0][0] = 1.0;
+        for j in 1..=m {
+            for k in 0..=max {
+                // The largest value is from the first sample, beating all
+                // i values of the second, or from the second
+                let first = if k >= i { next[j - 1][k - i] } else { 0.0 };
+                next[j][k] = first + counts[j][k];
+            }
+        }
+        counts = next;
+    }
+
+    let distribution = &counts[m];
+    let total: f64 = distribution.iter().sum();
+    let u = u.round() as usize;
+    let lower: f64 = distribution[..=u].iter().sum();
+    let upper: f64 = distribution[u..].iter().sum();
+
+    2.0 * lower.min(upper) / total
+}
+
+#[derive(Debug, Clone)]
+pub struct Comparison {
+    pub results: Vec<VariantResult>,
+    pub minimization: bool,
+}
+
+impl Comparison {
+    /// Side by side statistics of the final best fitness of every variant.
+    pub fn report(&self) -> String {
+        let mut table = Table::new();
+        table.set_titles(row!["Variant", "Mean", "Std", "Median", "Best", "Worst"]);
+
+        for result in &self.results {
+            let (best, worst) = if self.minimization {
+                (result.min(), result.max())
+            } else {
+                (result.max(), result.min())
+            };
+
+            table.add_row(row![
+                result.name,
+                result.mean(),
+                result.std(),
+                result.median(),
+                best,
+                worst
+            ]);
+        }
+
+        table.to_string()
+    }
+
+    /// Rank-sum test between the final best fitness of the `a`-th and
+    /// `b`-th variants. The effect size is the probability that a run of
+    /// the `a`-th ends better than one of the `b`-th.
+    pub fn test(&self, a: usize, b: usize) -> RankSumTest {
+        let mut test = rank_sum_test(&self.results[a].finals, &self.results[b].finals);
+        if self.minimization {
+            let pairs = (self.results[a].finals.len() * self.results[b].finals.len()) as f64;
+            test.u = pairs - test.u;
+            test.a12 = 1.0 - test.a12;
+        }
+
+        test
+    }
+
+    /// Rank-sum test between every pair of variants, the first of the pair
+    /// being better when A12 is above 0.5.
+    pub fn significance_report(&self) -> String {
+        let mut table = Table::new();
+        table.set_titles(row!["Variants", "U", "p-value", "A12", "Effect"]);
+
+        for a in 0..self.results.len() {
+            for b in (a + 1)..self.results.len() {
+                let test = self.test(a, b);
+                table.add_row(row![
+                    format!("{} vs {}", self.results[a].name, self.results[b].name),
+                    test.u,
+                    test.p_value,
+                    test.a12,
+                    format!("{:?}", test.effect())
+                ]);
+            }
+        }
+
+        table.to_string()
+    }
+
+    /// Overlaid median convergence of every variant.
+    pub fn plot<D>(&self, root: &DrawingArea<D, Shift>) -> Result<(), PsoError>
+    where
+        D: DrawingBackend,
+        <D as DrawingBackend>::ErrorType: 'static,
+    {
+        let curves: Vec<_> = self
+            .results
+            .iter()
+            .map(VariantResult::median_convergence)
+            .collect();
+
+        let iters = curves.iter().map(Vec::len).max().unwrap_or(1);
+        let (min, max) = curves
+            .iter()
+            .flatten()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &v| {
+                (min.min(v), max.max(v))
+            });
+        let (min, max) = if min < max {
+            (min, max)
+        } else {
+            (min - 1.0, max + 1.0)
+        };
+
+        root.fill(&WHITE)?;
+
+        let mut chart = ChartBuilder::on(root)
+            .set_label_area_size(LabelAreaPosition::Left, 60)
+            .set_label_area_size(LabelAreaPosition::Bottom, 50)
+            .caption("Median convergence", ("sans-serif", 40))
+            .build_ranged(0..iters, min..max)?;
+
+        chart.configure_mesh().draw()?;
+        for (i, (result, curve)) in self.results.iter().zip(curves).enumerate() {
+            let color = Palette99::pick(i);
+            chart
+                .draw_series(LineSeries::new(curve.into_iter().enumerate(), &color))?
+                .label(result.name.clone())
+                .legend(move |(x, y)| {
+                    PathElement::new(vec![(x, y), (x + 20, y)], &Palette99::pick(i))
+                });
+        }
+        chart.configure_series_labels().draw()?;
+
+        Ok(root.present()?)
+    }
+
+    /// Median convergence of every variant over shaded interquartile and
+    /// 5-95 percentile bands across seeds. `scaling` should keep the order
+    /// of the values (`Identity` or `Log`), it's applied to every curve on
+    /// its own.
+    pub fn plot_bands<D>(
+        &self,
+        root: &DrawingArea<D, Shift>,
+        scaling: Scaling,
+    ) -> Result<(), PsoError>
+    where
+        D: DrawingBackend,
+        <D as DrawingBackend>::ErrorType: 'static,
+    {
+        let bands: Vec<_> = self
+            .results
+            .iter()
+            .map(|result| result.bands().scale(scaling))
+            .collect();
+
+        let iters = bands.iter().map(|b| b.median.len()).max().unwrap_or(1);
+        let (min, max) = bands
+            .iter()
+            .flat_map(|b| b.outer.0.iter().chain(&b.outer.1))
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &v| {
+                (min.min(v), max.max(v))
+            });
+        let (min, max) = if min < max {
+            (min, max)
+        } else {
+            (min - 1.0, max + 1.0)
+        };
+
+        root.fill(&WHITE)?;
+
+        let mut chart = ChartBuilder::on(root)
+            .set_label_area_size(LabelAreaPosition::Left, 60)
+            .set_label_area_size(LabelAreaPosition::Bottom, 50)
+            .caption("Convergence across seeds", ("sans-serif", 40))
+            .build_ranged(0..iters, min..max)?;
+
+        chart.configure_mesh().draw()?;
+        for (i, (result, bands)) in self.results.iter().zip(bands).enumerate() {
+            let color = Palette99::pick(i);
+
+            // Lower curve forth and upper curve back
+            let band = |(lower, upper): (Vec<f64>, Vec<f64>)| {
+                let back = upper.into_iter().enumerate().rev();
+                lower
+                    .into_iter()
+                    .enumerate()
+                    .chain(back)
+                    .collect::<Vec<_>>()
+            };
+            chart.draw_series(std::iter::once(Polygon::new(
+                band(bands.outer),
+                color.mix(0.15).filled(),
+            )))?;
+            chart.draw_series(std::iter::once(Polygon::new(
+                band(bands.quartiles),
+                color.mix(0.3).filled(),
+            )))?;
+
+            chart
+                .draw_series(LineSeries::new(
+                    bands.median.into_iter().enumerate(),
+                    &color,
+                ))?
+                .label(result.name.clone())
+                .legend(move |(x, y)| {
+                    PathElement::new(vec![(x, y), (x + 20, y)], &Palette99::pick(i))
+                });
+        }
+        chart.configure_series_labels().draw()?;
+
+        Ok(root.present()?)
+    }
+
+    /// Same as [`plot_bands`](Self::plot_bands), but as a standalone SVG
+    /// file.
+    pub fn plot_bands_to_svg<P: AsRef<Path>>(
+        &self,
+        path: P,
+        scaling: Scaling,
+    ) -> Result<(), PsoError> {
+        let root = SVGBackend::new(path.as_ref(), PLOT_SIZE).into_drawing_area();
+        self.plot_bands(&root, scaling)
+    }
+}
+
+/// Runs `variant` once per seed on swarms created by `make` (which receives
+/// the seed), for `iters` iterations.
+pub fn ensemble<'a, F>(
+    make: F,
+    variant: &Variant,
+    seeds: &[u64],
+    iters: usize,
+) -> Result<VariantResult, PsoError>
+where
+    F: Fn(u64) -> Particles<'a>,
+{
+    Ok(run_seeds(&make, variant, seeds, iters)?.0)
+}
+
+/// The results of every seed, and whether the fitness is minimized.
+fn run_seeds<'a, F>(
+    make: &F,
+    variant: &Variant,
+    seeds: &[u64],
+    iters: usize,
+) -> Result<(VariantResult, bool), PsoError>
+where
+    F: Fn(u64) -> Particles<'a>,
+{
+    let mut finals = Vec::with_capacity(seeds.len());
+    let mut convergence = Vec::with_capacity(seeds.len());
+    let mut minimization = true;
+
+    for &seed in seeds {
+        let mut particles = make(seed);
+        if let Some(configure) = &variant.configure {
+            configure(&mut particles);
+        }
+
+        let mut history = History::new();
+        particles.run_with(variant.options, iters, &mut [&mut history])?;
+
+        minimization = particles.fitness().is_minimization();
+        finals.push(particles.best_fitness());
+        convergence.push(history.convergence());
+    }
+
+    let result = VariantResult {
+        name: variant.name.clone(),
+        finals,
+        convergence,
+    };
+
+    Ok((result, minimization))
+}
+
+/// Runs every variant once per seed on swarms created by `make` (which
+/// receives the seed), for `iters` iterations.
+pub fn compare<'a, F>(
+    make: F,
+    variants: &[Variant],
+    seeds: &[u64],
+    iters: usize,
+) -> Result<Comparison, PsoError>
+where
+    F: Fn(u64) -> Particles<'a>,
+{
+    let mut results = Vec::with_capacity(variants.len());
+    let mut minimization = true;
+
+    for variant in variants {
+        let (result, minimizes) = run_seeds(&make, variant, seeds, iters)?;
+        minimization = minimizes;
+        results.push(result);
+    }
+
+    Ok(Comparison {
+        results,
+        minimization,
+    })
+}
@@ -0,0 +1,197 @@
+#[cfg(feature = "parquet")]
+use crate::history::History;
+use crate::manifest::Manifest;
+use crate::observer::{Control, Observer};
+use crate::pso::Particles;
+use crate::report::SwarmSummary;
+use serde::Serialize;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+#[derive(Debug, Serialize)]
+struct IterationLine {
+    iter: usize,
+    #[serde(flatten)]
+    summary: SwarmSummary,
+}
+
+/// Streams one JSON record per iteration (newline delimited JSON), works as
+/// an observer of [`Particles::run_with`].
+///
+/// Observers can't fail, so the first I/O error stops the run and is kept
+/// until [`finish`](Self::finish) is called.
+pub struct NdjsonWriter<W: Write> {
+    writer: W,
+    error: Option<io::Error>,
+}
+
+impl NdjsonWriter<BufWriter<File>> {
+    pub fn create<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(Self::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W: Write> NdjsonWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            error: None,
+        }
+    }
+
+    pub fn write(&mut self, iter: usize, particles: &Particles) -> io::Result<()> {
+        let line = IterationLine {
+            iter,
+            summary: SwarmSummary::from(particles),
+        };
+
+        serde_json::to_writer(&mut self.writer, &line)?;
+        writeln!(self.writer)
+    }
+
+    /// Writes `manifest` as a record of its own, `{"manifest": {..}}`,
+    /// usually before the first iteration.
+    pub fn write_manifest(&mut self, manifest: &Manifest) -> io::Result<()> {
+        serde_json::to_writer(
+            &mut self.writer,
+            &serde_json::json!({ "manifest": manifest }),
+        )?;
+        writeln!(self.writer)
+    }
+
+    /// Flushes the writer and returns it, or the first error found while
+    /// observing.
+    pub fn finish(mut self) -> io::Result<W> {
+        if let Some(err) = self.error.take() {
+            return Err(err);
+        }
+
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    fn observe(&mut self, iter: usize, particles: &Particles) -> Control {
+        match self.write(iter, particles) {
+            Ok(()) => Control::Continue,
+            Err(err) => {
+                self.error = Some(err);
+                Control::Stop
+            }
+        }
+    }
+}
+
+impl<W: Write> Observer for NdjsonWriter<W> {
+    fn on_start(&mut self, particles: &Particles) -> Control {
+        self.observe(0, particles)
+    }
+
+    fn on_step(&mut self, iter: usize, particles: &Particles) -> Control {
+        if self.error.is_some() {
+            return Control::Stop;
+        }
+
+        self.observe(iter, particles)
+    }
+}
+
+/// Converts the history to an Arrow table in long format, one row per
+/// particle per iteration, with one column per dimension (`x_0`, `v_0`,
+/// `best_0`, ...).
+#[cfg(feature = "parquet")]
+pub fn history_to_arrow(
+    history: &History,
+) -> Result<arrow_array::RecordBatch, arrow_schema::ArrowError> {
+    use arrow_array::{ArrayRef, BooleanArray, Float64Array, RecordBatch, UInt64Array};
+    use arrow_schema::{DataType, Field, Schema};
+    use std::sync::Arc;
+
+    let rows = history.records().iter().flat_map(|record| {
+        record
+            .particles
+            .iter()
+            .enumerate()
+            .map(move |(i, particle)| (record, i, particle))
+    });
+
+    let dim = history
+        .records()
+        .front()
+        .map_or(0, |record| record.best.len());
+
+    let mut iters = Vec::new();
+    let mut indices = Vec::new();
+    let mut ids = Vec::new();
+    let mut omegas = Vec::new();
+    let mut fitnesses = Vec::new();
+    let mut improved = Vec::new();
+    let mut values = vec![Vec::new(); dim];
+    let mut velocities = vec![Vec::new(); dim];
+    let mut bests = vec![Vec::new(); dim];
+
+    for (record, i, particle) in rows {
+        iters.push(record.iter as u64);
+        indices.push(i as u64);
+        ids.push(particle.id as u64);
+        omegas.push(record.omega);
+        fitnesses.push(particle.fitness);
+        improved.push(particle.improved);
+
+        for d in 0..dim {
+            values[d].push(particle.value[d]);
+            velocities[d].push(particle.velocity[d]);
+            bests[d].push(particle.best[d]);
+        }
+    }
+
+    let mut fields = vec![
+        Field::new("iter", DataType::UInt64, false),
+        Field::new("particle", DataType::UInt64, false),
+        Field::new("id", DataType::UInt64, false),
+        Field::new("omega", DataType::Float64, true),
+        Field::new("fitness", DataType::Float64, false),
+        Field::new("improved", DataType::Boolean, false),
+    ];
+
+    let mut columns: Vec<ArrayRef> = vec![
+        Arc::new(UInt64Array::from(iters)),
+        Arc::new(UInt64Array::from(indices)),
+        Arc::new(UInt64Array::from(ids)),
+        Arc::new(Float64Array::from(omegas)),
+        Arc::new(Float64Array::from(fitnesses)),
+        Arc::new(BooleanArray::from(improved)),
+    ];
+
+    for (prefix, data) in [("x", values), ("v", velocities), ("best", bests)] {
+        for (d, column) in data.into_iter().enumerate() {
+            fields.push(Field::new(
+                format!("{}_{}", prefix, d),
+                DataType::Float64,
+                false,
+            ));
+            columns.push(Arc::new(Float64Array::from(column)));
+        }
+    }
+
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), columns)
+}
+
+/// Writes the history as a Parquet file, see [`history_to_arrow`] for the
+/// layout.
+#[cfg(feature = "parquet")]
+pub fn write_parquet<P: AsRef<Path>>(
+    history: &History,
+    path: P,
+) -> Result<(), parquet::errors::ParquetError> {
+    use parquet::arrow::ArrowWriter;
+
+    let batch = history_to_arrow(history)?;
+    let file = File::create(path)?;
+
+    let mut writer = ArrowWriter::try_new(file, batch.schema(), None)?;
+    writer.write(&batch)?;
+    writer.close()?;
+
+    Ok(())
+}
@@ -0,0 +1,119 @@
+use crate::builder::SwarmBuilder;
+use crate::error::PsoError;
+use crate::fitness::Fitness;
+use crate::pso::Options;
+use ndarray::Array1;
+
+/// Fitted parameters of a model, see [`Fitter`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FitResult {
+    pub params: Array1<f64>,
+    /// `y_i - model(x_i)` for every data point.
+    pub residuals: Array1<f64>,
+    /// Sum of squared residuals.
+    pub sse: f64,
+}
+
+impl FitResult {
+    /// Root mean squared residual.
+    pub fn rmse(&self) -> f64 {
+        (self.sse / self.residuals.len() as f64).sqrt()
+    }
+}
+
+/// Least squares fitting of a parametric model `y = model(x, params)` to
+/// data points, using SPSO-2011 under the hood.
+#[derive(Debug, Clone)]
+pub struct Fitter {
+    range: (f64, f64),
+    iters: usize,
+    seed: Option<u64>,
+}
+
+impl Default for Fitter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Fitter {
+    pub fn new() -> Self {
+        Self {
+            range: (-10.0, 10.0),
+            iters: 500,
+            seed: None,
+        }
+    }
+
+    /// Range every parameter is searched in, `(-10, 10)` by default.
+    pub fn range(mut self, min: f64, max: f64) -> Self {
+        self.range = (min, max);
+        self
+    }
+
+    pub fn iters(mut self, iters: usize) -> Self {
+        self.iters = iters;
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Minimizes the sum of squared errors of `model` with `params`
+    /// parameters over the points `(x_i, y_i)`.
+    pub fn fit<F>(
+        &self,
+        x: &[f64],
+        y: &[f64],
+        params: usize,
+        model: F,
+    ) -> Result<FitResult, PsoError>
+    where
+        F: Fn(f64, &Array1<f64>) -> f64,
+    {
+        if x.len() != y.len() || x.is_empty() {
+            return Err(PsoError::InvalidOptions(format!(
+                "x and y must have the same non-zero length, got {} and {}",
+                x.len(),
+                y.len()
+            )));
+        }
+
+        let residuals = |p: &Array1<f64>| -> Array1<f64> {
+            x.iter().zip(y).map(|(&x, &y)| y - model(x, p)).collect()
+        };
+        let sse = |p: &Array1<f64>| residuals(p).mapv(|r| r * r).sum();
+
+        let builder = SwarmBuilder::spso_2011(Fitness::new(sse, true))
+            .dim(params)
+            .value_range(self.range.0, self.range.1);
+        let builder = match self.seed {
+            Some(seed) => builder.seed(seed),
+            None => builder,
+        };
+
+        let mut particles = builder.build()?;
+        particles.set_verbose(false);
+        particles.run(Options::spso_2011(), self.iters)?;
+
+        let params = particles.best().clone();
+        let residuals = residuals(&params);
+        let sse = residuals.mapv(|r| r * r).sum();
+
+        Ok(FitResult {
+            params,
+            residuals,
+            sse,
+        })
+    }
+}
+
+/// Same as [`Fitter::fit`] with the default settings.
+pub fn fit<F>(x: &[f64], y: &[f64], params: usize, model: F) -> Result<FitResult, PsoError>
+where
+    F: Fn(f64, &Array1<f64>) -> f64,
+{
+    Fitter::new().fit(x, y, params, model)
+}
@@ -1,6 +1,129 @@
+use crate::objective::Terms;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+/// How fitness values are ordered, see
+/// [`Particles::set_comparison`](crate::pso::Particles::set_comparison).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Comparison {
+    /// IEEE 754 comparisons, a NaN fitness panics when ranking particles.
+    #[default]
+    Partial,
+    /// Total order of [`f64::total_cmp`] with NaN worse than any other
+    /// fitness, in either direction. Runs never panic, and rank `-0.0` and
+    /// `0.0` the same way on every platform.
+    Total,
+}
+
+impl Comparison {
+    /// Orders two fitness values already converted for maximization.
+    pub fn compare(self, a: f64, b: f64) -> Ordering {
+        match self {
+            Comparison::Partial => a.partial_cmp(&b).expect("Received a NaN"),
+            Comparison::Total => match (a.is_nan(), b.is_nan()) {
+                (true, true) => Ordering::Equal,
+                (true, false) => Ordering::Less,
+                (false, true) => Ordering::Greater,
+                (false, false) => a.total_cmp(&b),
+            },
+        }
+    }
+
+    /// Whether `fitness` improves on `best`, both for maximization. Nothing
+    /// improves on a NaN with partial comparisons.
+    pub fn improves(self, fitness: f64, best: f64) -> bool {
+        match self {
+            Comparison::Partial => fitness > best,
+            Comparison::Total => self.compare(fitness, best) == Ordering::Greater,
+        }
+    }
+}
+
+/// Compensated (Neumaier) sum in iteration order, far more accurate than a
+/// plain sum when the values span several orders of magnitude.
+pub fn stable_sum<I: IntoIterator<Item = f64>>(values: I) -> f64 {
+    let mut sum = 0.0;
+    let mut compensation = 0.0;
+    for value in values {
+        let total = sum + value;
+        compensation += if sum.abs() >= value.abs() {
+            (sum - total) + value
+        } else {
+            (value - total) + sum
+        };
+        sum = total;
+    }
+
+    sum + compensation
+}
+
+/// Transformation applied to fitness values before presenting them, every
+/// variant preserves the order, so comparisons between positions are the same
+/// with or without scaling.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum Scaling {
+    #[default]
+    Identity,
+    /// Symmetric log, `sign(f) * log10(1 + |f|)`, for objectives spanning
+    /// orders of magnitude.
+    Log,
+    /// Rank of every value in the population, normalized to `[0, 1]`.
+    Rank,
+    /// Distance to the population mean in standard deviations.
+    Sigma,
+}
+
+impl Scaling {
+    pub fn apply(&self, values: &[f64]) -> Vec<f64> {
+        match self {
+            Scaling::Identity => values.to_vec(),
+            Scaling::Log => values
+                .iter()
+                .map(|v| v.signum() * v.abs().ln_1p() / std::f64::consts::LN_10)
+                .collect(),
+            Scaling::Rank => {
+                let mut order: Vec<_> = (0..values.len()).collect();
+                order.sort_by(|&a, &b| values[a].partial_cmp(&values[b]).expect("Received a NaN"));
+
+                let max = values.len().saturating_sub(1).max(1) as f64;
+                let mut ranks = vec![0.0; values.len()];
+                for (rank, i) in order.into_iter().enumerate() {
+                    ranks[i] = rank as f64 / max;
+                }
+
+                ranks
+            }
+            Scaling::Sigma => {
+                let n = values.len() as f64;
+                let mean = values.iter().sum::<f64>() / n;
+                let var = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+                let std = var.sqrt();
+
+                values
+                    .iter()
+                    .map(|v| if std > 0.0 { (v - mean) / std } else { 0.0 })
+                    .collect()
+            }
+        }
+    }
+}
+
+type LocalFn<'a, T> = Box<dyn Fn(&T) -> f64 + 'a>;
+type SharedFn<'a, T> = Box<dyn Fn(&T) -> f64 + Send + Sync + 'a>;
+type ConstraintFn<'a, T> = Arc<dyn Fn(&T) -> Vec<f64> + Send + Sync + 'a>;
+
+enum Func<'a, T> {
+    Local(LocalFn<'a, T>),
+    Shared(SharedFn<'a, T>),
+}
+
 pub struct Fitness<'a, T> {
-    func: Box<dyn Fn(&T) -> f64 + 'a>,
+    func: Func<'a, T>,
     minimization: bool,
+    scaling: Scaling,
+    terms: Option<Arc<Terms<'a, T>>>,
+    constraints: Option<ConstraintFn<'a, T>>,
 }
 
 impl<'a, T> Fitness<'a, T> {
@@ -9,20 +132,134 @@ impl<'a, T> Fitness<'a, T> {
         F: Fn(&T) -> f64 + 'a,
     {
         Self {
-            func: Box::new(func),
+            func: Func::Local(Box::new(func)),
+            minimization,
+            scaling: Scaling::default(),
+            terms: None,
+            constraints: None,
+        }
+    }
+
+    /// Same as [`new`](Self::new), but the function can be called from
+    /// several threads at once, needed for parallel evaluation.
+    pub fn shared<F>(func: F, minimization: bool) -> Self
+    where
+        F: Fn(&T) -> f64 + Send + Sync + 'a,
+    {
+        Self {
+            func: Func::Shared(Box::new(func)),
             minimization,
+            scaling: Scaling::default(),
+            terms: None,
+            constraints: None,
         }
     }
 
+    pub fn is_shared(&self) -> bool {
+        matches!(self.func, Func::Shared(_))
+    }
+
+    /// The function, if it can be called from several threads.
+    pub fn as_shared(&self) -> Option<&(dyn Fn(&T) -> f64 + Send + Sync + 'a)> {
+        match &self.func {
+            Func::Shared(func) => Some(func.as_ref()),
+            Func::Local(_) => None,
+        }
+    }
+
+    /// Evaluates `map(x)` instead of `x`, keeping the direction, scaling,
+    /// constraints and whether the function is shared. The terms of a
+    /// composite objective are dropped, since they'd receive the unmapped
+    /// values.
+    pub fn map_input<G>(self, map: G) -> Self
+    where
+        T: 'a,
+        G: Fn(&T) -> T + Send + Sync + 'a,
+    {
+        let map = Arc::new(map);
+        let constraints = self.constraints.map(|constraints| {
+            let map = Arc::clone(&map);
+            Arc::new(move |x: &T| constraints(&map(x))) as ConstraintFn<'a, T>
+        });
+
+        let func = match self.func {
+            Func::Local(func) => Func::Local(Box::new(move |x: &T| func(&map(x)))),
+            Func::Shared(func) => Func::Shared(Box::new(move |x: &T| func(&map(x)))),
+        };
+
+        Self {
+            func,
+            minimization: self.minimization,
+            scaling: self.scaling,
+            terms: None,
+            constraints,
+        }
+    }
+
+    pub fn with_scaling(mut self, scaling: Scaling) -> Self {
+        self.scaling = scaling;
+        self
+    }
+
+    pub(crate) fn with_terms(mut self, terms: Arc<Terms<'a, T>>) -> Self {
+        self.terms = Some(terms);
+        self
+    }
+
+    /// Terms of a composite objective, built with
+    /// [`ObjectiveBuilder`](crate::objective::ObjectiveBuilder).
+    pub fn terms(&self) -> Option<&Terms<'a, T>> {
+        self.terms.as_deref()
+    }
+
+    /// Constraints `g_i(x) <= 0` the fitness handles somehow (e.g. with a
+    /// penalty), only used to report violations in summaries, histories
+    /// and results.
+    pub fn with_constraints<F>(mut self, constraints: F) -> Self
+    where
+        F: Fn(&T) -> Vec<f64> + Send + Sync + 'a,
+    {
+        self.constraints = Some(Arc::new(constraints));
+        self
+    }
+
+    pub fn has_constraints(&self) -> bool {
+        self.constraints.is_some()
+    }
+
+    /// How much `val` violates every constraint, `max(0, g_i(x))`, `None`
+    /// without constraints.
+    pub fn violations(&self, val: &T) -> Option<Vec<f64>> {
+        let constraints = self.constraints.as_ref()?;
+        Some(constraints(val).into_iter().map(|g| g.max(0.0)).collect())
+    }
+
+    pub fn scaling(&self) -> Scaling {
+        self.scaling
+    }
+
+    /// Applies the configured scaling to already calculated fitness values.
+    pub fn scale(&self, values: &[f64]) -> Vec<f64> {
+        self.scaling.apply(values)
+    }
+
     pub fn calculate(&self, val: &T) -> f64 {
-        (self.func)(val)
+        match &self.func {
+            Func::Local(func) => func(val),
+            Func::Shared(func) => func(val),
+        }
     }
 
     pub fn calculate_for_maximization(&self, val: &T) -> f64 {
+        self.to_maximization(self.calculate(val))
+    }
+
+    /// Converts an already calculated fitness for maximization.
+    pub fn to_maximization(&self, fitness: f64) -> f64 {
         if self.minimization {
-            -(self.func)(val)
+            -fitness
         } else {
-            (self.func)(val)
+            fitness
         }
     }
 
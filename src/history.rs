@@ -0,0 +1,529 @@
+use crate::diversity::DimensionStats;
+use crate::error::PsoError;
+use crate::fitness::{Fitness, Scaling};
+use crate::lineage::Lineage;
+use crate::observer::{Control, Observer};
+use crate::pso::{Particles, PLOT_SIZE};
+use crate::report::NumberFormat;
+use ndarray::Array1;
+use plotters::coord::Shift;
+use plotters::drawing::backend::DrawingBackend;
+use plotters::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt;
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParticleRecord {
+    pub id: usize,
+    pub value: Array1<f64>,
+    pub velocity: Array1<f64>,
+    pub best: Array1<f64>,
+    #[serde(with = "crate::checkpoint::nonfinite")]
+    pub fitness: f64,
+    /// Whether this position improved the particle's best.
+    pub improved: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IterationRecord {
+    /// Iteration number, 0 is the initial swarm.
+    pub iter: usize,
+    pub omega: Option<f64>,
+    pub best: Array1<f64>,
+    #[serde(with = "crate::checkpoint::nonfinite")]
+    pub best_fitness: f64,
+    pub particles: Vec<ParticleRecord>,
+    /// Lineage events since the previous record.
+    #[serde(default)]
+    pub events: Vec<Lineage>,
+    /// Value of every term of the global best, for composite objectives.
+    #[serde(default)]
+    pub terms: Option<Vec<f64>>,
+    /// How much the global best violates every constraint, for fitnesses
+    /// with constraints, see [`Fitness::with_constraints`].
+    #[serde(default)]
+    pub violations: Option<Vec<f64>>,
+    /// Amount of particles at a feasible position, for fitnesses with
+    /// constraints.
+    #[serde(default)]
+    pub feasible: Option<usize>,
+    /// Statistics of the positions on every dimension, kept even without
+    /// the particles.
+    #[serde(default)]
+    pub dimensions: Vec<DimensionStats>,
+}
+
+impl IterationRecord {
+    pub fn mean_fitness(&self) -> f64 {
+        let sum: f64 = self.particles.iter().map(|p| p.fitness).sum();
+        sum / self.particles.len() as f64
+    }
+
+    /// Amount of particles that improved their best on this iteration.
+    pub fn improvements(&self) -> usize {
+        self.particles.iter().filter(|p| p.improved).count()
+    }
+}
+
+/// Which records a [`History`] keeps in memory.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Retention {
+    #[default]
+    All,
+    /// Every record, without the state of the particles.
+    BestOnly,
+    /// Records of iterations multiple of `n`, the initial swarm included.
+    EveryNth(usize),
+    /// Ring buffer of the last `k` records.
+    Last(usize),
+}
+
+impl Retention {
+    pub fn validate(&self) -> Result<(), PsoError> {
+        match self {
+            Retention::EveryNth(0) => Err(PsoError::InvalidOptions(
+                "can't keep every 0th record".to_string(),
+            )),
+            Retention::Last(0) => Err(PsoError::InvalidOptions(
+                "can't keep the last 0 records".to_string(),
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+struct Stream {
+    writer: Box<dyn Write + Send>,
+    error: Option<io::Error>,
+}
+
+/// Shared between clones of the history, records are written once.
+#[derive(Clone)]
+struct Sink(Arc<Mutex<Stream>>);
+
+impl fmt::Debug for Sink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Sink")
+    }
+}
+
+/// Records the state of the swarm on every iteration, works as an observer
+/// of [`Particles::run_with`] or by calling [`record`](Self::record) manually.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct History {
+    records: VecDeque<IterationRecord>,
+    #[serde(default)]
+    retention: Retention,
+    #[serde(skip)]
+    sink: Option<Sink>,
+    /// Lineage events already recorded.
+    #[serde(default)]
+    seen: usize,
+    #[serde(default)]
+    term_names: Vec<String>,
+    /// Number format of the recorded swarm, used on plots.
+    #[serde(default)]
+    number_format: NumberFormat,
+}
+
+impl History {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_retention(retention: Retention) -> Result<Self, PsoError> {
+        retention.validate()?;
+
+        Ok(Self {
+            retention,
+            ..Self::default()
+        })
+    }
+
+    pub fn retention(&self) -> Retention {
+        self.retention
+    }
+
+    /// Also writes every full record as a JSON line to `writer` (e.g. a
+    /// file) before the retention policy applies, so large runs can be kept
+    /// on disk only. I/O errors stop the run, see [`finish`](Self::finish).
+    pub fn stream_to<W: Write + Send + 'static>(mut self, writer: W) -> Self {
+        let stream = Stream {
+            writer: Box::new(writer),
+            error: None,
+        };
+
+        self.sink = Some(Sink(Arc::new(Mutex::new(stream))));
+        self
+    }
+
+    /// Flushes the stream, or returns the first error found while writing.
+    pub fn finish(&mut self) -> io::Result<()> {
+        match &self.sink {
+            Some(Sink(stream)) => {
+                let mut stream = stream.lock().expect("Stream lock was poisoned");
+                if let Some(err) = stream.error.take() {
+                    return Err(err);
+                }
+
+                stream.writer.flush()
+            }
+            None => Ok(()),
+        }
+    }
+
+    fn stream(&self, record: &IterationRecord) -> Control {
+        let stream = match &self.sink {
+            Some(Sink(stream)) => stream,
+            None => return Control::Continue,
+        };
+
+        let mut stream = stream.lock().expect("Stream lock was poisoned");
+        if stream.error.is_some() {
+            return Control::Stop;
+        }
+
+        let written = serde_json::to_writer(&mut stream.writer, record)
+            .map_err(io::Error::from)
+            .and_then(|()| writeln!(stream.writer));
+
+        match written {
+            Ok(()) => Control::Continue,
+            Err(err) => {
+                stream.error = Some(err);
+                Control::Stop
+            }
+        }
+    }
+
+    /// Records the swarm, keeping it in memory according to the retention
+    /// policy. Stops when streaming fails.
+    pub fn record(&mut self, iter: usize, particles: &Particles) -> Control {
+        let records = particles
+            .particles()
+            .iter()
+            .map(|particle| ParticleRecord {
+                id: particle.id(),
+                value: particle.value().clone(),
+                velocity: particle.velocity().clone(),
+                best: particle.best().clone(),
+                fitness: particle
+                    .stats()
+                    .fitness()
+                    .last()
+                    .copied()
+                    .unwrap_or(f64::NAN),
+                improved: particle.stats().improved(),
+            })
+            .collect();
+
+        let lineage = particles.lineage();
+        let events = lineage.get(self.seen..).unwrap_or_default().to_vec();
+        self.seen = lineage.len();
+
+        let terms = particles.fitness().terms().map(|terms| {
+            if self.term_names.is_empty() {
+                self.term_names = terms.names().into_iter().map(String::from).collect();
+            }
+
+            terms.values(particles.best())
+        });
+
+        self.number_format = particles.number_format();
+        let mut record = IterationRecord {
+            iter,
+            omega: particles.omega(),
+            best: particles.best().clone(),
+            best_fitness: particles.best_fitness(),
+            particles: records,
+            events,
+            terms,
+            violations: particles.best_violations(),
+            feasible: particles.feasible_particles(),
+            dimensions: particles.dimension_stats(),
+        };
+        let control = self.stream(&record);
+
+        match self.retention {
+            Retention::All => self.records.push_back(record),
+            Retention::BestOnly => {
+                record.particles.clear();
+                self.records.push_back(record);
+            }
+            Retention::EveryNth(n) => {
+                if iter.is_multiple_of(n) {
+                    self.records.push_back(record);
+                }
+            }
+            Retention::Last(k) => {
+                self.records.push_back(record);
+                if self.records.len() > k {
+                    self.records.pop_front();
+                }
+            }
+        }
+
+        control
+    }
+
+    pub fn records(&self) -> &VecDeque<IterationRecord> {
+        &self.records
+    }
+
+    /// Names of the terms in [`IterationRecord::terms`], empty if the
+    /// objective isn't composite.
+    pub fn term_names(&self) -> &[String] {
+        &self.term_names
+    }
+
+    /// Best fitness on every iteration.
+    pub fn convergence(&self) -> Vec<f64> {
+        self.records.iter().map(|r| r.best_fitness).collect()
+    }
+
+    /// For every dimension, the iteration since which its standard deviation
+    /// stayed within `tolerance` until the last record, `None` if it's still
+    /// being explored.
+    pub fn converged_since(&self, tolerance: f64) -> Vec<Option<usize>> {
+        let dim = self.records.back().map_or(0, |r| r.dimensions.len());
+
+        (0..dim)
+            .map(|d| {
+                let mut since = None;
+                for record in &self.records {
+                    match record.dimensions.get(d) {
+                        Some(stats) if stats.converged(tolerance) => {
+                            since = since.or(Some(record.iter));
+                        }
+                        _ => since = None,
+                    }
+                }
+
+                since
+            })
+            .collect()
+    }
+
+    /// Best and mean fitness on every iteration, after scaling both curves.
+    pub fn plot_convergence<D>(
+        &self,
+        root: &DrawingArea<D, Shift>,
+        scaling: Scaling,
+    ) -> Result<(), PsoError>
+    where
+        D: DrawingBackend,
+        <D as DrawingBackend>::ErrorType: 'static,
+    {
+        let best = scaling.apply(&self.convergence());
+        let mean: Vec<_> = self.records.iter().map(|r| r.mean_fitness()).collect();
+        let mean = scaling.apply(&mean);
+
+        let (min, max) = best
+            .iter()
+            .chain(mean.iter())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &v| {
+                (min.min(v), max.max(v))
+            });
+        let (min, max) = if min < max {
+            (min, max)
+        } else {
+            (min - 1.0, max + 1.0)
+        };
+
+        root.fill(&WHITE)?;
+
+        let mut chart = ChartBuilder::on(root)
+            .set_label_area_size(LabelAreaPosition::Left, 60)
+            .set_label_area_size(LabelAreaPosition::Bottom, 50)
+            .caption("Convergence", ("sans-serif", 40))
+            .build_ranged(0..self.records.len().max(1), min..max)?;
+
+        let format = |v: &f64| self.number_format.format(*v);
+        chart.configure_mesh().y_label_formatter(&format).draw()?;
+        chart
+            .draw_series(LineSeries::new(best.into_iter().enumerate(), &BLUE))?
+            .label("best")
+            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &BLUE));
+        chart
+            .draw_series(LineSeries::new(mean.into_iter().enumerate(), &RED))?
+            .label("mean")
+            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &RED));
+        chart.configure_series_labels().draw()?;
+
+        Ok(root.present()?)
+    }
+
+    /// Same as [`plot_convergence`](Self::plot_convergence), but as a
+    /// standalone SVG file.
+    pub fn plot_convergence_to_svg<P: AsRef<Path>>(
+        &self,
+        path: P,
+        scaling: Scaling,
+    ) -> Result<(), PsoError> {
+        let root = SVGBackend::new(path.as_ref(), PLOT_SIZE).into_drawing_area();
+        self.plot_convergence(&root, scaling)
+    }
+
+    /// Position of the particle with the given ID on every iteration it
+    /// was part of the swarm.
+    pub fn trajectory(&self, id: usize) -> Vec<(usize, Array1<f64>)> {
+        self.records
+            .iter()
+            .filter_map(|record| {
+                let particle = record.particles.iter().find(|p| p.id == id)?;
+                Some((record.iter, particle.value.clone()))
+            })
+            .collect()
+    }
+
+    /// Global best on every recorded iteration.
+    pub fn best_trajectory(&self) -> Vec<(usize, Array1<f64>)> {
+        self.records
+            .iter()
+            .map(|record| (record.iter, record.best.clone()))
+            .collect()
+    }
+
+    /// Path of the global best over filled fitness contours, on the first
+    /// two dimensions (the rest are fixed to the final best). The path goes
+    /// from blue on the first iteration to red on the last one.
+    pub fn plot_best_trajectory<D>(
+        &self,
+        root: &DrawingArea<D, Shift>,
+        fitness: &Fitness<Array1<f64>>,
+        x_range: (f64, f64),
+        y_range: (f64, f64),
+    ) -> Result<(), PsoError>
+    where
+        D: DrawingBackend,
+        <D as DrawingBackend>::ErrorType: 'static,
+    {
+        const RESOLUTION: usize = 100;
+        const LEVELS: f64 = 10.0;
+
+        let trajectory = self.best_trajectory();
+        let last = match trajectory.last() {
+            Some((_, best)) => best.clone(),
+            None => return Err(PsoError::InvalidOptions("history is empty".to_string())),
+        };
+
+        root.fill(&WHITE)?;
+
+        let (x_min, x_max) = x_range;
+        let (y_min, y_max) = y_range;
+        let mut chart = ChartBuilder::on(root)
+            .set_label_area_size(LabelAreaPosition::Left, 50)
+            .set_label_area_size(LabelAreaPosition::Bottom, 50)
+            .caption("Global best", ("sans-serif", 40))
+            .build_ranged(x_min..x_max, y_min..y_max)?;
+
+        let format = |v: &f64| self.number_format.format(*v);
+        chart
+            .configure_mesh()
+            .disable_mesh()
+            .x_label_formatter(&format)
+            .y_label_formatter(&format)
+            .draw()?;
+
+        // Contours are the quantiles of the fitness, darker is better
+        let step = (
+            (x_max - x_min) / RESOLUTION as f64,
+            (y_max - y_min) / RESOLUTION as f64,
+        );
+        let cells: Vec<_> = (0..RESOLUTION * RESOLUTION)
+            .map(|k| {
+                let x = x_min + (k / RESOLUTION) as f64 * step.0;
+                let y = y_min + (k % RESOLUTION) as f64 * step.1;
+                (x, y)
+            })
+            .collect();
+        let values: Vec<_> = cells
+            .iter()
+            .map(|&(x, y)| {
+                let mut point = last.clone();
+                point[0] = x + step.0 / 2.0;
+                point[1] = y + step.1 / 2.0;
+                fitness.calculate_for_maximization(&point)
+            })
+            .collect();
+        let ranks = Scaling::Rank.apply(&values);
+
+        chart.draw_series(cells.iter().zip(ranks).map(|(&(x, y), rank)| {
+            let level = (rank * LEVELS).floor().min(LEVELS - 1.0) / LEVELS;
+            let color = HSLColor(0.6, 0.4, 0.9 - 0.5 * level);
+            Rectangle::new([(x, y), (x + step.0, y + step.1)], color.filled())
+        }))?;
+
+        let segments = trajectory.len().saturating_sub(1).max(1) as f64;
+        chart.draw_series(trajectory.windows(2).enumerate().map(|(i, pair)| {
+            let color = HSLColor(0.7 * (1.0 - i as f64 / segments), 1.0, 0.45);
+            let points: Vec<_> = pair.iter().map(|(_, best)| (best[0], best[1])).collect();
+            PathElement::new(points, color.stroke_width(2))
+        }))?;
+        chart.draw_series(std::iter::once(Circle::new(
+            (last[0], last[1]),
+            5,
+            RED.filled(),
+        )))?;
+
+        Ok(root.present()?)
+    }
+
+    pub fn plot_best_trajectory_to_svg<P: AsRef<Path>>(
+        &self,
+        path: P,
+        fitness: &Fitness<Array1<f64>>,
+        x_range: (f64, f64),
+        y_range: (f64, f64),
+    ) -> Result<(), PsoError> {
+        let root = SVGBackend::new(path.as_ref(), PLOT_SIZE).into_drawing_area();
+        self.plot_best_trajectory(&root, fitness, x_range, y_range)
+    }
+
+    /// Lineage events of the particle with the given ID, with the iteration
+    /// they were recorded on.
+    pub fn lineage(&self, id: usize) -> Vec<(usize, Lineage)> {
+        self.records
+            .iter()
+            .flat_map(|record| {
+                record
+                    .events
+                    .iter()
+                    .filter(move |event| event.id == id)
+                    .map(move |&event| (record.iter, event))
+            })
+            .collect()
+    }
+
+    /// Total improvements of the best made by every particle, by ID, over
+    /// the records kept. Empty with [`Retention::BestOnly`], which doesn't
+    /// keep the particles.
+    pub fn improvements_per_particle(&self) -> BTreeMap<usize, usize> {
+        let mut improvements = BTreeMap::new();
+
+        for record in &self.records {
+            for particle in &record.particles {
+                let count = improvements.entry(particle.id).or_insert(0);
+                if particle.improved {
+                    *count += 1;
+                }
+            }
+        }
+
+        improvements
+    }
+}
+
+impl Observer for History {
+    fn on_start(&mut self, particles: &Particles) -> Control {
+        self.record(0, particles)
+    }
+
+    fn on_step(&mut self, iter: usize, particles: &Particles) -> Control {
+        self.record(iter, particles)
+    }
+}
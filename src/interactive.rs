@@ -0,0 +1,93 @@
+use crate::error::PsoError;
+use crate::pso::{Options, Particles};
+use crate::reconfigure::Reconfiguration;
+
+/// Run that hands control back to the caller after every step, so the
+/// swarm and the options can be inspected and changed in between (e.g. for
+/// teaching demos and GUIs), see [`Particles::stepper`].
+pub struct Stepper<'p, 'a> {
+    particles: &'p mut Particles<'a>,
+    options: Options,
+    iters: usize,
+    done: usize,
+}
+
+impl<'a> Particles<'a> {
+    /// Starts a run of up to `iters` steps that only advances when
+    /// [`Stepper::step`] is called.
+    pub fn stepper(&mut self, options: Options, iters: usize) -> Result<Stepper<'_, 'a>, PsoError> {
+        options.validate()?;
+
+        Ok(Stepper {
+            particles: self,
+            options,
+            iters,
+            done: 0,
+        })
+    }
+}
+
+impl<'p, 'a> Stepper<'p, 'a> {
+    /// Does one step, returns its number (starting at 1) or `None` once
+    /// every iteration was done.
+    pub fn step(&mut self) -> Option<usize> {
+        if self.done >= self.iters {
+            return None;
+        }
+
+        self.particles.step(self.options);
+        self.done += 1;
+        Some(self.done)
+    }
+
+    /// Does every remaining step, returns how many were done.
+    pub fn finish(mut self) -> usize {
+        let mut steps = 0;
+        while self.step().is_some() {
+            steps += 1;
+        }
+
+        steps
+    }
+
+    pub fn particles(&self) -> &Particles<'a> {
+        self.particles
+    }
+
+    /// The swarm, to restart, mutate or migrate particles between steps.
+    pub fn particles_mut(&mut self) -> &mut Particles<'a> {
+        self.particles
+    }
+
+    pub fn options(&self) -> Options {
+        self.options
+    }
+
+    /// Options used from the next step on.
+    pub fn set_options(&mut self, options: Options) -> Result<(), PsoError> {
+        options.validate()?;
+        self.options = options;
+        Ok(())
+    }
+
+    /// Changes the options and the swarm from the next step on, see
+    /// [`Reconfiguration`].
+    pub fn reconfigure(&mut self, reconfiguration: &Reconfiguration) -> Result<(), PsoError> {
+        reconfiguration.apply(self.particles, &mut self.options)
+    }
+
+    /// Steps done so far.
+    pub fn done(&self) -> usize {
+        self.done
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.iters - self.done
+    }
+
+    /// Changes the total amount of iterations, it can't go below the steps
+    /// already done.
+    pub fn set_iters(&mut self, iters: usize) {
+        self.iters = iters.max(self.done);
+    }
+}
@@ -0,0 +1,218 @@
+use crate::error::PsoError;
+use crate::lineage::LineageEvent;
+use crate::pso::{Options, Particles};
+use ndarray::Array1;
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::sync::mpsc::{self, Receiver, Sender};
+
+/// Particle sent from one island to another.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Migrant {
+    /// Island it comes from.
+    pub swarm: usize,
+    /// Its ID in that island.
+    pub id: usize,
+    pub value: Array1<f64>,
+    pub velocity: Array1<f64>,
+}
+
+/// Carries migrants between neighboring islands, e.g. through in-process
+/// channels ([`ring`]) or TCP
+/// ([`TcpTransport`](crate::distributed::TcpTransport)).
+pub trait Transport {
+    /// Sends migrants to the next island.
+    fn send(&mut self, migrants: Vec<Migrant>) -> io::Result<()>;
+
+    /// Waits for the migrants of the previous island.
+    fn receive(&mut self) -> io::Result<Vec<Migrant>>;
+}
+
+/// In-process transport, see [`ring`].
+pub struct ChannelTransport {
+    sender: Sender<Vec<Migrant>>,
+    receiver: Receiver<Vec<Migrant>>,
+}
+
+/// Transports connecting `n` islands in a ring, island `i` sends to island
+/// `i + 1` and the last one to the first.
+pub fn ring(n: usize) -> Vec<ChannelTransport> {
+    let (senders, receivers): (Vec<_>, Vec<_>) = (0..n).map(|_| mpsc::channel()).unzip();
+
+    // Island i receives on channel i and sends on channel i + 1
+    let mut receivers = receivers.into_iter().map(Some).collect::<Vec<_>>();
+    (0..n)
+        .map(|i| ChannelTransport {
+            sender: senders[(i + 1) % n].clone(),
+            receiver: receivers[i].take().expect("Receiver taken twice"),
+        })
+        .collect()
+}
+
+impl Transport for ChannelTransport {
+    fn send(&mut self, migrants: Vec<Migrant>) -> io::Result<()> {
+        self.sender
+            .send(migrants)
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "next island is gone"))
+    }
+
+    fn receive(&mut self) -> io::Result<Vec<Migrant>> {
+        self.receiver
+            .recv()
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "previous island is gone"))
+    }
+}
+
+/// Migration schedule: every `every` steps an island sends its `count` best
+/// particles, which replace the `count` worst particles of its neighbor.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Migration {
+    pub every: usize,
+    pub count: usize,
+}
+
+impl Default for Migration {
+    /// 2 migrants every 10 steps.
+    fn default() -> Self {
+        Self {
+            every: 10,
+            count: 2,
+        }
+    }
+}
+
+impl Migration {
+    pub fn validate(&self) -> Result<(), PsoError> {
+        if self.every == 0 {
+            return Err(PsoError::InvalidOptions(
+                "migration interval must be at least 1".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Swarm of an island model, each one runs on its own thread or process
+/// and exchanges migrants with its neighbors. Islands block until their
+/// neighbor's migrants arrive, so with seeded swarms the runs are
+/// deterministic whatever the timing.
+pub struct Island<T> {
+    id: usize,
+    transport: T,
+    migration: Migration,
+    received: usize,
+}
+
+impl<T: Transport> Island<T> {
+    pub fn new(id: usize, transport: T, migration: Migration) -> Self {
+        Self {
+            id,
+            transport,
+            migration,
+            received: 0,
+        }
+    }
+
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    pub fn migration(&self) -> Migration {
+        self.migration
+    }
+
+    /// Migrants received so far.
+    pub fn received(&self) -> usize {
+        self.received
+    }
+
+    /// Runs `iters` steps of `particles`, exchanging migrants on schedule.
+    /// Every island of the model has to run the same amount of steps with
+    /// the same schedule, otherwise they wait for each other forever.
+    pub fn run(
+        &mut self,
+        particles: &mut Particles,
+        options: Options,
+        iters: usize,
+    ) -> Result<usize, PsoError> {
+        options.validate()?;
+        self.migration.validate()?;
+        if self.migration.count > particles.particles().len() {
+            return Err(PsoError::InvalidOptions(format!(
+                "can't send {} migrants from a swarm of {}",
+                self.migration.count,
+                particles.particles().len()
+            )));
+        }
+
+        for done in 1..=iters {
+            particles.step(options);
+            if done % self.migration.every == 0 {
+                self.exchange(particles)?;
+            }
+        }
+
+        Ok(iters)
+    }
+
+    /// Sends the best particles and replaces the worst ones with the
+    /// migrants received.
+    pub fn exchange(&mut self, particles: &mut Particles) -> Result<usize, PsoError> {
+        let ranking = particles.ranking();
+        let count = self.migration.count.min(ranking.len());
+
+        let migrants = ranking[..count]
+            .iter()
+            .map(|&i| {
+                let particle = &particles.particles()[i];
+                Migrant {
+                    swarm: self.id,
+                    id: particle.id(),
+                    value: particle.value().clone(),
+                    velocity: particle.velocity().clone(),
+                }
+            })
+            .collect();
+
+        let failed = |err: io::Error| PsoError::Transport(err.to_string());
+        self.transport.send(migrants).map_err(failed)?;
+        let migrants = self.transport.receive().map_err(failed)?;
+
+        let slots = ranking.iter().rev();
+        let mut received = 0;
+        for (&i, migrant) in slots.zip(migrants) {
+            particles.immigrate(i, migrant);
+            received += 1;
+        }
+
+        self.received += received;
+        Ok(received)
+    }
+
+    pub fn into_transport(self) -> T {
+        self.transport
+    }
+}
+
+impl<'a> Particles<'a> {
+    /// Replaces the i-th particle with `migrant`, see
+    /// [`migrate`](Self::migrate).
+    pub fn immigrate(&mut self, i: usize, migrant: Migrant) {
+        let event = LineageEvent::Migrated {
+            swarm: migrant.swarm,
+            id: migrant.id,
+        };
+        self.replace_particle(i, migrant.value, migrant.velocity, event);
+    }
+
+    /// Indices of the particles, from the best current fitness to the
+    /// worst.
+    fn ranking(&self) -> Vec<usize> {
+        let particles = self.particles();
+        let mut order: Vec<_> = (0..particles.len()).collect();
+        order.sort_by(|&a, &b| self.compare(particles[b].fitness(), particles[a].fitness()));
+
+        order
+    }
+}
@@ -0,0 +1,107 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "full")]
+pub mod anytime;
+#[cfg(feature = "full")]
+pub mod archive;
+#[cfg(feature = "full")]
+pub mod benchmark;
+#[cfg(feature = "full")]
+pub mod budget;
+#[cfg(feature = "full")]
+pub mod builder;
+#[cfg(feature = "full")]
+pub mod checkpoint;
+#[cfg(feature = "full")]
+pub mod clustering;
+#[cfg(feature = "full")]
+pub mod competitive;
+#[cfg(feature = "full")]
+pub mod constraints;
+#[cfg(feature = "full")]
+pub mod coordinates;
+#[cfg(feature = "full")]
+pub mod density;
+#[cfg(feature = "full")]
+pub mod diff;
+#[cfg(feature = "full")]
+pub mod differential;
+#[cfg(feature = "distributed")]
+pub mod distributed;
+#[cfg(feature = "full")]
+pub mod diversity;
+#[cfg(feature = "full")]
+pub mod error;
+#[cfg(feature = "full")]
+pub mod experiment;
+#[cfg(feature = "full")]
+pub mod export;
+#[cfg(feature = "full")]
+pub mod fit;
+#[cfg(feature = "full")]
+pub mod fitness;
+#[cfg(feature = "full")]
+pub mod history;
+#[cfg(feature = "full")]
+pub mod interactive;
+#[cfg(feature = "full")]
+pub mod island;
+#[cfg(feature = "full")]
+pub mod lineage;
+pub mod lite;
+#[cfg(feature = "full")]
+pub mod manifest;
+#[cfg(feature = "full")]
+pub mod manifold;
+#[cfg(feature = "full")]
+pub mod meta;
+#[cfg(feature = "full")]
+pub mod metric;
+#[cfg(feature = "full")]
+pub mod mlp;
+#[cfg(feature = "full")]
+pub mod objective;
+#[cfg(feature = "full")]
+pub mod observer;
+#[cfg(feature = "full")]
+pub mod operator;
+#[cfg(feature = "full")]
+pub mod problems;
+#[cfg(feature = "full")]
+pub mod pso;
+#[cfg(feature = "full")]
+pub mod reconfigure;
+#[cfg(feature = "full")]
+pub mod report;
+#[cfg(feature = "full")]
+pub mod restart;
+#[cfg(feature = "full")]
+pub mod robust;
+#[cfg(feature = "full")]
+pub mod screening;
+#[cfg(feature = "full")]
+pub mod sensitivity;
+#[cfg(feature = "full")]
+pub mod social;
+#[cfg(feature = "full")]
+pub mod stability;
+#[cfg(feature = "full")]
+pub mod surrogate;
+#[cfg(feature = "full")]
+pub mod telemetry;
+#[cfg(feature = "full")]
+pub mod termination;
+#[cfg(feature = "full")]
+pub mod topology;
+#[cfg(feature = "full")]
+pub mod transcript;
+#[cfg(feature = "full")]
+pub mod tuner;
+#[cfg(feature = "full")]
+pub mod velocity;
+#[cfg(feature = "full")]
+pub mod warmup;
+#[cfg(feature = "full")]
+pub mod zones;
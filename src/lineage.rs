@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+/// Something that happened to a particle besides its regular movement.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LineageEvent {
+    /// Sampled again from the initial distributions, keeping its ID.
+    Reinitialized,
+    /// Arrived from another swarm, where it had the given ID.
+    Migrated { swarm: usize, id: usize },
+    /// Its position was altered outside of the velocity update.
+    Mutated,
+    /// Sampled again because it had collapsed onto a better particle.
+    Dispersed,
+    /// Joined the swarm after it was created, with the given position.
+    Added,
+    /// Left the swarm, its ID isn't used again.
+    Removed,
+    /// Moved to a trial position recombined from other personal bests, see
+    /// [`Differential`](crate::differential::Differential).
+    Recombined,
+    /// Moved to the position proposed by a surrogate model, see
+    /// [`Surrogate`](crate::surrogate::Surrogate).
+    Scouted,
+}
+
+/// A lineage event of the particle with the given ID.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lineage {
+    pub id: usize,
+    /// Steps done by the swarm when it happened.
+    pub step: usize,
+    pub event: LineageEvent,
+}
@@ -0,0 +1,486 @@
+//! Minimal PSO core with positions as `Vec<f64>` and hand-rolled arithmetic,
+//! it's the only module available without the `full` feature (no ndarray,
+//! plotting nor serialization). It only needs `alloc`, so it also works on
+//! `no_std` targets with an injected RNG and [`Clock`]. It's generic over
+//! the float type, `f32` halves the memory of very large swarms. Positions
+//! are `Vec`s of any dimension, fixed-size arrays for low dimensions, see
+//! [`SmallSwarm`], which never allocate while stepping, or quantized to a
+//! fraction of the memory for gigantic swarms, see [`PackedSwarm`].
+
+use alloc::vec::Vec;
+use core::time::Duration;
+use num_traits::float::FloatCore;
+use rand::distributions::uniform::SampleUniform;
+use rand::{Rng, RngCore, SeedableRng};
+use rand_pcg::Pcg64;
+
+/// Source of the elapsed time for [`Swarm::run_for`], e.g. a hardware timer.
+pub trait Clock {
+    /// Time since an arbitrary fixed point, it must never go backwards.
+    fn now(&self) -> Duration;
+}
+
+/// Clock backed by `std::time::Instant`, counting from its creation.
+#[cfg(feature = "std")]
+#[derive(Debug, Copy, Clone)]
+pub struct SystemClock {
+    start: std::time::Instant,
+}
+
+#[cfg(feature = "std")]
+impl SystemClock {
+    pub fn new() -> Self {
+        Self {
+            start: std::time::Instant::now(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+/// Coefficients of the classic velocity update, `omega: None` draws a random
+/// inertia on every step.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Options<T = f64> {
+    pub omega: Option<T>,
+    pub phi_1: T,
+    pub phi_2: T,
+}
+
+impl<T: FloatCore> Options<T> {
+    /// Constriction coefficients of Clerc and Kennedy.
+    pub fn canonical() -> Self {
+        let cast = |v: f64| T::from(v).expect("Coefficient out of range");
+
+        Self {
+            omega: Some(cast(0.72984)),
+            phi_1: cast(1.49618),
+            phi_2: cast(1.49618),
+        }
+    }
+}
+
+/// Storage of the coordinates of a particle.
+pub trait Position<T>: Clone {
+    /// Position with `dim` coordinates, taken in order from `coordinate`.
+    fn from_fn(dim: usize, coordinate: impl FnMut() -> T) -> Self;
+
+    fn dim(&self) -> usize;
+
+    fn get(&self, d: usize) -> T;
+
+    fn set(&mut self, d: usize, value: T);
+
+    /// Every coordinate, decoded into `buffer` when they aren't stored as
+    /// `T`.
+    fn decode<'p>(&'p self, buffer: &'p mut Vec<T>) -> &'p [T];
+}
+
+impl<T: Copy> Position<T> for Vec<T> {
+    fn from_fn(dim: usize, mut coordinate: impl FnMut() -> T) -> Self {
+        (0..dim).map(|_| coordinate()).collect()
+    }
+
+    fn dim(&self) -> usize {
+        self.len()
+    }
+
+    fn get(&self, d: usize) -> T {
+        self[d]
+    }
+
+    fn set(&mut self, d: usize, value: T) {
+        self[d] = value;
+    }
+
+    fn decode<'p>(&'p self, _: &'p mut Vec<T>) -> &'p [T] {
+        self
+    }
+}
+
+/// Lives inline, `dim` must be `D`.
+impl<T: Copy, const D: usize> Position<T> for [T; D] {
+    fn from_fn(dim: usize, mut coordinate: impl FnMut() -> T) -> Self {
+        assert_eq!(dim, D, "Dimension doesn't match the array");
+        core::array::from_fn(|_| coordinate())
+    }
+
+    fn dim(&self) -> usize {
+        D
+    }
+
+    fn get(&self, d: usize) -> T {
+        self[d]
+    }
+
+    fn set(&mut self, d: usize, value: T) {
+        self[d] = value;
+    }
+
+    fn decode<'p>(&'p self, _: &'p mut Vec<T>) -> &'p [T] {
+        self
+    }
+}
+
+/// Lossy compact encoding of a coordinate.
+pub trait Quantized<T>: Copy {
+    fn quantize(value: T) -> Self;
+
+    fn value(self) -> T;
+}
+
+/// bfloat16, the upper half of an `f32`: its whole range with 8 bits of
+/// precision, about 3 significant digits.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Bf16(pub u16);
+
+impl<T: FloatCore> Quantized<T> for Bf16 {
+    fn quantize(value: T) -> Self {
+        let single = value.to_f32().unwrap_or(f32::NAN);
+        let bits = single.to_bits();
+        if single.is_nan() {
+            // Truncating could leave an infinity, keep it a quiet NaN
+            return Bf16((bits >> 16) as u16 | 0x0040);
+        }
+
+        // Round to nearest, ties to even
+        let rounding = 0x7fff + ((bits >> 16) & 1);
+        Bf16(((bits + rounding) >> 16) as u16)
+    }
+
+    fn value(self) -> T {
+        let single = f32::from_bits(u32::from(self.0) << 16);
+        T::from(single).unwrap_or_else(T::nan)
+    }
+}
+
+/// Fixed point in an `i32` with `FRAC` fractional bits. Values outside its
+/// range saturate and NaN becomes 0.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Fixed<const FRAC: u32>(pub i32);
+
+impl<const FRAC: u32> Fixed<FRAC> {
+    const SCALE: f64 = (1u64 << FRAC) as f64;
+}
+
+impl<T: FloatCore, const FRAC: u32> Quantized<T> for Fixed<FRAC> {
+    fn quantize(value: T) -> Self {
+        let scaled = value.to_f64().unwrap_or(0.0) * Self::SCALE;
+        // Casts saturate and take NaN to 0
+        Fixed(FloatCore::round(scaled) as i32)
+    }
+
+    fn value(self) -> T {
+        T::from(f64::from(self.0) / Self::SCALE).expect("Fixed point out of range")
+    }
+}
+
+/// Coordinates stored quantized as `Q`, converted on every access.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Packed<Q>(pub Vec<Q>);
+
+impl<T: Copy, Q: Quantized<T>> Position<T> for Packed<Q> {
+    fn from_fn(dim: usize, mut coordinate: impl FnMut() -> T) -> Self {
+        Packed((0..dim).map(|_| Q::quantize(coordinate())).collect())
+    }
+
+    fn dim(&self) -> usize {
+        self.0.len()
+    }
+
+    fn get(&self, d: usize) -> T {
+        self.0[d].value()
+    }
+
+    fn set(&mut self, d: usize, value: T) {
+        self.0[d] = Q::quantize(value);
+    }
+
+    fn decode<'p>(&'p self, buffer: &'p mut Vec<T>) -> &'p [T] {
+        buffer.clear();
+        buffer.extend(self.0.iter().map(|q| q.value()));
+        buffer
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Particle<T = f64, P = Vec<T>> {
+    pub value: P,
+    pub velocity: P,
+    pub fitness: T,
+    pub best: P,
+    pub best_fitness: T,
+}
+
+/// Swarm with a global topology, the fitness is minimized.
+pub struct Swarm<F, R = Pcg64, T = f64, P = Vec<T>> {
+    particles: Vec<Particle<T, P>>,
+    fitness: F,
+    best: P,
+    best_fitness: T,
+    rng: R,
+    /// Decoded coordinates of quantized positions.
+    buffer: Vec<T>,
+}
+
+/// Swarm of `D` dimensions with positions in arrays, for the small
+/// dimensions of demos and teaching. Steps are the same as the ones of a
+/// [`Swarm`] with the same seed.
+pub type SmallSwarm<F, const D: usize, R = Pcg64, T = f64> = Swarm<F, R, T, [T; D]>;
+
+/// Swarm with positions and velocities quantized as `Q`, e.g. [`Bf16`] in
+/// a quarter of the memory of `f64`. The arithmetic is done in `T` and the
+/// fitness is evaluated on the stored coordinates, so the bests are exact
+/// for what's kept.
+pub type PackedSwarm<F, Q, R = Pcg64, T = f64> = Swarm<F, R, T, Packed<Q>>;
+
+impl<F, T> Swarm<F, Pcg64, T>
+where
+    F: Fn(&[T]) -> T,
+    T: FloatCore + SampleUniform,
+{
+    /// Creates `size` particles with positions and velocities sampled
+    /// uniformly from the given `(min, max)` ranges.
+    pub fn new(
+        size: usize,
+        dim: usize,
+        value_range: (T, T),
+        velocity_range: (T, T),
+        fitness: F,
+        seed: u64,
+    ) -> Self {
+        let rng = Pcg64::seed_from_u64(seed);
+        Self::with_rng(size, dim, value_range, velocity_range, fitness, rng)
+    }
+}
+
+impl<F, R, T> Swarm<F, R, T>
+where
+    F: Fn(&[T]) -> T,
+    R: RngCore,
+    T: FloatCore + SampleUniform,
+{
+    /// Same as [`new`](Swarm::new), but every random number is drawn from
+    /// `rng`.
+    pub fn with_rng(
+        size: usize,
+        dim: usize,
+        value_range: (T, T),
+        velocity_range: (T, T),
+        fitness: F,
+        rng: R,
+    ) -> Self {
+        Self::sample(size, dim, value_range, velocity_range, fitness, rng)
+    }
+}
+
+impl<F, T, const D: usize> SmallSwarm<F, D, Pcg64, T>
+where
+    F: Fn(&[T]) -> T,
+    T: FloatCore + SampleUniform,
+{
+    /// Same as [`Swarm::new`] with `D` dimensions.
+    pub fn fixed(
+        size: usize,
+        value_range: (T, T),
+        velocity_range: (T, T),
+        fitness: F,
+        seed: u64,
+    ) -> Self {
+        let rng = Pcg64::seed_from_u64(seed);
+        Self::fixed_with_rng(size, value_range, velocity_range, fitness, rng)
+    }
+}
+
+impl<F, R, T, const D: usize> SmallSwarm<F, D, R, T>
+where
+    F: Fn(&[T]) -> T,
+    R: RngCore,
+    T: FloatCore + SampleUniform,
+{
+    /// Same as [`Swarm::with_rng`] with `D` dimensions.
+    pub fn fixed_with_rng(
+        size: usize,
+        value_range: (T, T),
+        velocity_range: (T, T),
+        fitness: F,
+        rng: R,
+    ) -> Self {
+        Self::sample(size, D, value_range, velocity_range, fitness, rng)
+    }
+}
+
+impl<F, T, Q> PackedSwarm<F, Q, Pcg64, T>
+where
+    F: Fn(&[T]) -> T,
+    T: FloatCore + SampleUniform,
+    Q: Quantized<T>,
+{
+    /// Same as [`Swarm::new`] with quantized coordinates.
+    pub fn packed(
+        size: usize,
+        dim: usize,
+        value_range: (T, T),
+        velocity_range: (T, T),
+        fitness: F,
+        seed: u64,
+    ) -> Self {
+        let rng = Pcg64::seed_from_u64(seed);
+        Self::packed_with_rng(size, dim, value_range, velocity_range, fitness, rng)
+    }
+}
+
+impl<F, R, T, Q> PackedSwarm<F, Q, R, T>
+where
+    F: Fn(&[T]) -> T,
+    R: RngCore,
+    T: FloatCore + SampleUniform,
+    Q: Quantized<T>,
+{
+    /// Same as [`Swarm::with_rng`] with quantized coordinates.
+    pub fn packed_with_rng(
+        size: usize,
+        dim: usize,
+        value_range: (T, T),
+        velocity_range: (T, T),
+        fitness: F,
+        rng: R,
+    ) -> Self {
+        Self::sample(size, dim, value_range, velocity_range, fitness, rng)
+    }
+}
+
+impl<F, R, T, P> Swarm<F, R, T, P>
+where
+    F: Fn(&[T]) -> T,
+    R: RngCore,
+    T: FloatCore + SampleUniform,
+    P: Position<T>,
+{
+    fn sample(
+        size: usize,
+        dim: usize,
+        value_range: (T, T),
+        velocity_range: (T, T),
+        fitness: F,
+        mut rng: R,
+    ) -> Self {
+        assert!(size > 0, "Swarm size must be positive");
+
+        let mut sample = |(min, max): (T, T)| P::from_fn(dim, || rng.gen_range(min, max));
+
+        let mut buffer = Vec::new();
+        let particles: Vec<_> = (0..size)
+            .map(|_| {
+                let value = sample(value_range);
+                let velocity = sample(velocity_range);
+                let fitness = fitness(value.decode(&mut buffer));
+
+                Particle {
+                    best: value.clone(),
+                    best_fitness: fitness,
+                    value,
+                    velocity,
+                    fitness,
+                }
+            })
+            .collect();
+
+        let best = particles
+            .iter()
+            .min_by(|a, b| a.fitness.partial_cmp(&b.fitness).expect("Received a NaN"))
+            .expect("Population is empty");
+        let (best, best_fitness) = (best.value.clone(), best.fitness);
+
+        Self {
+            particles,
+            fitness,
+            best,
+            best_fitness,
+            rng,
+            buffer,
+        }
+    }
+
+    pub fn particles(&self) -> &[Particle<T, P>] {
+        &self.particles
+    }
+
+    pub fn best(&self) -> &P {
+        &self.best
+    }
+
+    pub fn best_fitness(&self) -> T {
+        self.best_fitness
+    }
+
+    /// `v = ω v + φ_1 r_1 (p - x) + φ_2 r_2 (g - x)`, `x = x + v`.
+    pub fn step(&mut self, options: Options<T>) {
+        let (zero, one) = (T::zero(), T::one());
+        let omega = match options.omega {
+            Some(omega) => omega,
+            None => self.rng.gen_range(zero, one),
+        };
+
+        let dim = self.best.dim();
+        for particle in &mut self.particles {
+            let r_1 = self.rng.gen_range(zero, one);
+            let r_2 = self.rng.gen_range(zero, one);
+
+            for d in 0..dim {
+                let x = particle.value.get(d);
+                let v = omega * particle.velocity.get(d)
+                    + options.phi_1 * r_1 * (particle.best.get(d) - x)
+                    + options.phi_2 * r_2 * (self.best.get(d) - x);
+
+                particle.velocity.set(d, v);
+                particle.value.set(d, x + v);
+            }
+
+            particle.fitness = (self.fitness)(particle.value.decode(&mut self.buffer));
+            if particle.fitness < particle.best_fitness {
+                particle.best.clone_from(&particle.value);
+                particle.best_fitness = particle.fitness;
+            }
+        }
+
+        for particle in &self.particles {
+            if particle.best_fitness < self.best_fitness {
+                self.best.clone_from(&particle.best);
+                self.best_fitness = particle.best_fitness;
+            }
+        }
+    }
+
+    pub fn run(&mut self, options: Options<T>, iters: usize) {
+        for _ in 0..iters {
+            self.step(options);
+        }
+    }
+
+    /// Steps until `budget` has elapsed on `clock`, returns the amount of
+    /// steps done.
+    pub fn run_for(&mut self, options: Options<T>, budget: Duration, clock: &impl Clock) -> usize {
+        let start = clock.now();
+
+        let mut iters = 0;
+        while clock.now() - start < budget {
+            self.step(options);
+            iters += 1;
+        }
+
+        iters
+    }
+}
@@ -1,14 +1,42 @@
-pub mod fitness;
-pub mod pso;
-
-use anyhow::Error;
-use fitness::Fitness;
+use anyhow::{anyhow, Error};
 use ndarray::Array1;
-use ndarray_rand::rand_distr::Uniform;
 use plotters::prelude::*;
-use pso::{Options, Particles};
+use pso::benchmark::rastrigin;
+use pso::builder::SwarmBuilder;
+use pso::constraints::ConstraintComparison;
+use pso::fitness::{Fitness, Scaling};
+use pso::history::History;
+use pso::problems::{decode, BinaryProblem, Knapsack, Overweight, PressureVessel, Problem};
+use pso::pso::{Options, Particles, PLOT_SIZE};
+use pso::report::NumberFormat;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+/// Demos of the gallery, run with `pso <demo> [--json]`.
+const DEMOS: [(&str, &str); 4] = [
+    (
+        "booth-2d",
+        "función de Booth en 2D, con animación (por defecto)",
+    ),
+    (
+        "rastrigin-10d",
+        "función de Rastrigin en 10D, con convergencia",
+    ),
+    (
+        "constrained-design",
+        "recipiente a presión con restricciones y penalización",
+    ),
+    (
+        "binary-knapsack",
+        "mochila binaria de 12 objetos, umbral en 0.5",
+    ),
+];
+
+/// Plots of every demo but booth-2d are written here.
+const GALLERY: &str = "gallery";
 
-fn f(vec: &Array1<f64>) -> f64 {
+fn booth(vec: &Array1<f64>) -> f64 {
     let x = vec[0];
     let y = vec[1];
 
@@ -16,14 +44,83 @@ fn f(vec: &Array1<f64>) -> f64 {
 }
 
 fn main() -> Result<(), Error> {
+    let args: Vec<_> = env::args().skip(1).collect();
+
+    // With --json only one JSON summary per iteration is printed
+    let json = args.iter().any(|arg| arg == "--json");
+    let demo = args
+        .iter()
+        .find(|arg| !arg.starts_with("--"))
+        .map_or("booth-2d", String::as_str);
+
+    match demo {
+        "booth-2d" => booth_2d(json),
+        "rastrigin-10d" => rastrigin_10d(json),
+        "constrained-design" => constrained_design(json),
+        "binary-knapsack" => binary_knapsack(json),
+        "list" => {
+            print_demos();
+            Ok(())
+        }
+        other => {
+            print_demos();
+            Err(anyhow!("demo desconocida: {}", other))
+        }
+    }
+}
+
+fn print_demos() {
+    println!("Uso: pso [demo] [--json]");
+    println!("Demos:");
+    for (name, description) in DEMOS.iter() {
+        println!("- {}: {}", name, description);
+    }
+}
+
+/// Path of an output of a demo, inside the gallery directory.
+fn output(demo: &str, name: &str) -> Result<PathBuf, Error> {
+    fs::create_dir_all(GALLERY)?;
+    Ok(PathBuf::from(GALLERY).join(format!("{}-{}", demo, name)))
+}
+
+/// Runs `iters` steps recording the history, prints the final summary and
+/// plots the convergence.
+fn run_demo(
+    demo: &str,
+    particles: &mut Particles,
+    options: Options,
+    iters: usize,
+    json: bool,
+) -> Result<History, Error> {
+    particles.set_verbose(false);
+
+    let mut history = History::new();
+    particles.run_with(options, iters, &mut [&mut history])?;
+
+    if json {
+        println!("{}", particles.summary_json());
+    } else {
+        println!("{}", particles.summary(false)?);
+    }
+
+    let path = output(demo, "convergence.svg")?;
+    history.plot_convergence_to_svg(&path, Scaling::Log)?;
+    if !json {
+        println!("\n>>> Convergencia: {}", path.display());
+    }
+
+    Ok(history)
+}
+
+fn booth_2d(json: bool) -> Result<(), Error> {
     // Define some parameters
     let size = 10;
     let dim = 2;
     let iters = 80;
 
-    let value_distr = Uniform::new(-10., 10.);
+    let value_range = (-10.0, 10.0);
     let velocity_range = (-1.0, 1.0);
-    let velocity_distr = Uniform::new(velocity_range.0, velocity_range.1);
+    let format = NumberFormat::new().significant(6).scientific(1e6);
 
     let options = Options {
         omega: None,
@@ -32,42 +129,173 @@ fn main() -> Result<(), Error> {
     };
 
     // Show the parameters
-    println!("Parámetros:");
-    println!("- Tamaño de la población: {}", size);
-    println!("- Velocidad inicial entre {:?}", velocity_range);
-    println!("- Omega (ω): Aleatorio entre 0 y 1 para cada iteración");
-    println!("- rand1, rand2: Aleatoria entre 0 y 1 para cada individuo");
-    println!("- Phi_1 (φ_1): {}", options.phi_1);
-    println!("- Phi_2 (φ_2): {}", options.phi_2);
-    println!("- Cantidad de iteraciones: {}", iters);
-    println!();
+    if !json {
+        print_parameters(size, velocity_range, &options, iters);
+    }
+
+    options.validate()?;
 
     // Generate initial particles
-    let mut particles = Particles::new(
-        size,
-        dim,
-        value_distr,
-        velocity_distr,
-        Fitness::new(f, true),
-    );
+    let mut particles = SwarmBuilder::new(Fitness::new(booth, true))
+        .size(size)
+        .dim(dim)
+        .value_range(value_range.0, value_range.1)
+        .velocity_range(velocity_range.0, velocity_range.1)
+        .number_format(format)
+        .build()?;
+    particles.set_verbose(!json);
 
     // Show initial particles, fitnesses and best locals
-    println!("{}", particles.summary(true)?);
+    if json {
+        println!("{}", particles.summary_json());
+    } else {
+        println!("{}", particles.summary(true)?);
+    }
 
-    let root = BitMapBackend::gif("animation.gif", (600, 600), 250)?.into_drawing_area();
+    let root = BitMapBackend::gif("animation.gif", PLOT_SIZE, 250)?.into_drawing_area();
     particles.plot(&root, 0)?;
 
     // Run a step 'iters' times
     for i in 0..iters {
-        println!("\n>>>> Iteración {} <<<<", i + 1);
-        particles.step(options);
-        println!("{}", particles.summary(false)?);
+        if json {
+            particles.step(options);
+            println!("{}", particles.summary_json());
+        } else {
+            println!("\n>>>> Iteración {} <<<<", i + 1);
+            particles.step(options);
+            println!("{}", particles.summary(false)?);
+        }
         particles.plot(&root, i + 1)?;
     }
 
     // Show global best
-    let best = particles.best();
-    println!("\n>>> Mejor global: x: {}, fitness: {}", best, f(&best));
+    if !json {
+        let best = particles.best();
+        println!(
+            "\n>>> Mejor global: x: {}, fitness: {}",
+            format.array(best),
+            format.format(booth(best))
+        );
+    }
 
     Ok(())
 }
+
+fn rastrigin_10d(json: bool) -> Result<(), Error> {
+    let mut particles = SwarmBuilder::spso_2011(Fitness::new(rastrigin, true))
+        .dim(10)
+        .value_range(-5.12, 5.12)
+        .number_format(NumberFormat::new().significant(6))
+        .build()?;
+
+    let history = run_demo(
+        "rastrigin-10d",
+        &mut particles,
+        Options::spso_2011(),
+        1000,
+        json,
+    )?;
+
+    if !json {
+        let converged = history.converged_since(1e-8);
+        let dims = converged.iter().filter(|since| since.is_some()).count();
+        println!(">>> Dimensiones convergidas: {} de 10", dims);
+    }
+
+    Ok(())
+}
+
+fn constrained_design(json: bool) -> Result<(), Error> {
+    let problem = PressureVessel;
+    let (min, max) = problem.bounds();
+
+    let fitness = ConstraintComparison::Penalty(1e6)
+        .fitness(&problem)
+        .expect("Penalties have a fitness");
+    let mut particles = SwarmBuilder::spso_2011(fitness)
+        .dim(problem.dim())
+        .normalize(min, max)
+        .build()?;
+
+    run_demo(
+        "constrained-design",
+        &mut particles,
+        Options::spso_2011(),
+        1000,
+        json,
+    )?;
+
+    if !json {
+        let best = particles.denormalize(particles.best());
+        println!("\n>>> Diseño (T_s, T_h, R, L): {}", best);
+        println!(
+            ">>> Costo: {} (mejor conocido: {})",
+            problem.objective(&best),
+            problem.best_known()
+        );
+        println!(
+            ">>> Restricciones (g <= 0): {:?}",
+            problem.constraints(&best)
+        );
+        println!(">>> Factible: {}", problem.is_feasible(&best, 1e-6));
+    }
+
+    Ok(())
+}
+
+fn binary_knapsack(json: bool) -> Result<(), Error> {
+    let problem = Knapsack::new(
+        vec![
+            23.0, 31.0, 29.0, 44.0, 53.0, 38.0, 63.0, 85.0, 89.0, 82.0, 12.0, 47.0,
+        ],
+        vec![
+            92.0, 57.0, 49.0, 68.0, 60.0, 43.0, 67.0, 84.0, 87.0, 72.0, 30.0, 55.0,
+        ],
+        240.0,
+    )
+    .overweight(Overweight::Penalty(1000.0));
+
+    let fitness = Fitness::new(|x: &Array1<f64>| problem.evaluate(x), false);
+    let mut particles = SwarmBuilder::spso_2011(fitness)
+        .dim(problem.dim())
+        .value_range(0.0, 1.0)
+        .build()?;
+
+    run_demo(
+        "binary-knapsack",
+        &mut particles,
+        Options::spso_2011(),
+        300,
+        json,
+    )?;
+
+    if !json {
+        let bits = decode(particles.best());
+        let items: Vec<_> = (0..bits.len()).filter(|&i| bits[i]).collect();
+        println!("\n>>> Objetos: {:?}", items);
+        println!(
+            ">>> Peso: {} de {}, valor: {} (óptimo: {:?})",
+            problem.weight(&bits),
+            problem.capacity(),
+            problem.value(&bits),
+            problem.best_known()
+        );
+    }
+
+    Ok(())
+}
+
+fn print_parameters(size: usize, velocity_range: (f64, f64), options: &Options, iters: usize) {
+    println!("Parámetros:");
+    println!("- Tamaño de la población: {}", size);
+    println!("- Velocidad inicial entre {:?}", velocity_range);
+    println!("- Omega (ω): Aleatorio entre 0 y 1 para cada iteración");
+    println!("- rand1, rand2: Aleatoria entre 0 y 1 para cada individuo");
+    println!("- Phi_1 (φ_1): {}", options.phi_1);
+    println!("- Phi_2 (φ_2): {}", options.phi_2);
+    println!("- Cantidad de iteraciones: {}", iters);
+    for warning in options.warnings() {
+        println!("- Advertencia: {}", warning);
+    }
+    println!();
+}
@@ -0,0 +1,136 @@
+use crate::pso::{Options, Particles};
+use serde::{Deserialize, Serialize};
+use std::env::consts;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Fields of a [`SwarmState`](crate::pso::SwarmState) that change while
+/// running, left out of the configuration hash along with the seed.
+const PROGRESS: [&str; 14] = [
+    "particles",
+    "global_best",
+    "global_best_fitness",
+    "omega",
+    "rng",
+    "seed",
+    "stagnant_steps",
+    "memory",
+    "evaluations_used",
+    "archive",
+    "verbose",
+    "next_id",
+    "steps",
+    "lineage",
+];
+
+/// Environment and reproducibility metadata of a run, to trace where a
+/// result came from and repeat it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub crate_version: String,
+    /// Commit checked out in the working directory, if it's a git
+    /// repository and git is installed.
+    pub git_hash: Option<String>,
+    /// `None` when the swarm was seeded from the OS, the run can't be
+    /// repeated then.
+    pub seed: Option<u64>,
+    /// FNV-1a hash of the options and the swarm configuration, as 16 hex
+    /// digits. Runs with the same hash were configured the same way.
+    pub config_hash: String,
+    pub os: String,
+    pub arch: String,
+    /// Threads available to the run.
+    pub threads: usize,
+    /// Milliseconds since the Unix epoch.
+    pub started: u64,
+    pub finished: u64,
+}
+
+impl Manifest {
+    /// Manifest of a run of `particles` with `options`, started at
+    /// `started` and finishing now.
+    pub fn new(particles: &Particles, options: Options, started: SystemTime) -> Self {
+        Self {
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            git_hash: git_hash(),
+            seed: particles.seed(),
+            config_hash: format!("{:016x}", config_hash(particles, options)),
+            os: consts::OS.to_string(),
+            arch: consts::ARCH.to_string(),
+            threads: threads(),
+            started: millis(started),
+            finished: millis(SystemTime::now()),
+        }
+    }
+
+    /// Wall-clock duration of the run in milliseconds.
+    pub fn duration(&self) -> u64 {
+        self.finished.saturating_sub(self.started)
+    }
+}
+
+impl<'a> Particles<'a> {
+    /// See [`Manifest::new`].
+    pub fn manifest(&self, options: Options, started: SystemTime) -> Manifest {
+        Manifest::new(self, options, started)
+    }
+}
+
+fn git_hash() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let hash = String::from_utf8(output.stdout).ok()?;
+    Some(hash.trim().to_string()).filter(|hash| !hash.is_empty())
+}
+
+fn config_hash(particles: &Particles, options: Options) -> u64 {
+    // Values can't hold the u128 state of the RNG, going through a string
+    // turns it into a float, which is dropped anyway
+    let state = serde_json::to_string(&particles.state()).expect("Swarm state is serializable");
+    let mut state: serde_json::Value =
+        serde_json::from_str(&state).expect("Swarm state is valid JSON");
+    if let Some(fields) = state.as_object_mut() {
+        for field in PROGRESS.iter() {
+            fields.remove(*field);
+        }
+        let dim = particles.best().len();
+        fields.insert("size".to_string(), particles.particles().len().into());
+        fields.insert("dim".to_string(), dim.into());
+    }
+
+    let options = serde_json::to_value(options).expect("Options are serializable");
+    let config = serde_json::json!({ "options": options, "swarm": state });
+
+    fnv1a(config.to_string().as_bytes())
+}
+
+/// 64-bit FNV-1a, stable across platforms and Rust versions unlike
+/// `DefaultHasher`.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+fn threads() -> usize {
+    #[cfg(feature = "parallel")]
+    {
+        rayon::current_num_threads()
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    {
+        std::thread::available_parallelism().map_or(1, |n| n.get())
+    }
+}
+
+fn millis(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis() as u64)
+}
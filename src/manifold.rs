@@ -0,0 +1,40 @@
+use ndarray::Array1;
+use serde::{Deserialize, Serialize};
+
+/// Space the positions live in.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Manifold {
+    /// Unconstrained positions.
+    #[default]
+    Euclidean,
+    /// Positions are kept on the unit hypersphere (`‖x‖ = 1`), velocities are
+    /// projected onto the tangent space before moving and the new position
+    /// is normalized, e.g. for direction-finding problems.
+    Sphere,
+}
+
+impl Manifold {
+    /// Removes the components of `v` that leave the manifold at `x`.
+    pub fn tangent(self, x: &Array1<f64>, v: &mut Array1<f64>) {
+        if self == Manifold::Sphere {
+            let radial = v.dot(x);
+            v.scaled_add(-radial, x);
+        }
+    }
+
+    /// Moves `x` back onto the manifold, the velocity is projected onto the
+    /// tangent space of the new position.
+    pub fn retract(self, x: &mut Array1<f64>, v: &mut Array1<f64>) {
+        if self == Manifold::Sphere {
+            let norm = x.dot(x).sqrt();
+            if norm > 0.0 {
+                *x /= norm;
+            } else {
+                x.fill(0.0);
+                x[0] = 1.0;
+            }
+
+            self.tangent(x, v);
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use crate::builder::SwarmBuilder;
+use crate::error::PsoError;
+use crate::experiment::median;
+use crate::fitness::Fitness;
+use crate::pso::{Options, Particles};
+use ndarray::Array1;
+use rand::{Rng, SeedableRng};
+use rand_pcg::Pcg64;
+
+/// How the outer search explores the parameters.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MetaStrategy {
+    /// A small PSO over the (normalized) parameters.
+    Pso { size: usize, iters: usize },
+    /// Uniformly sampled configurations.
+    RandomSearch { samples: usize },
+}
+
+/// Tunes omega, phi_1, phi_2 and the swarm size of the inner PSO, every
+/// configuration is scored by the median final fitness over the seeds.
+#[derive(Debug, Clone)]
+pub struct MetaPso {
+    pub omega: (f64, f64),
+    pub phi_1: (f64, f64),
+    pub phi_2: (f64, f64),
+    pub size: (usize, usize),
+    /// Seeds every configuration is run with.
+    pub seeds: Vec<u64>,
+    /// Iterations of every inner run.
+    pub iters: usize,
+    pub strategy: MetaStrategy,
+    /// Seed of the outer search.
+    pub seed: u64,
+}
+
+impl Default for MetaPso {
+    fn default() -> Self {
+        Self {
+            omega: (0.2, 1.0),
+            phi_1: (0.5, 2.5),
+            phi_2: (0.5, 2.5),
+            size: (5, 40),
+            seeds: (0..5).collect(),
+            iters: 50,
+            strategy: MetaStrategy::Pso { size: 8, iters: 10 },
+            seed: 0,
+        }
+    }
+}
+
+/// Best configuration found by [`MetaPso::tune`].
+#[derive(Debug, Copy, Clone)]
+pub struct Tuned {
+    pub options: Options,
+    pub size: usize,
+    /// Median final fitness over the seeds.
+    pub score: f64,
+}
+
+impl MetaPso {
+    /// Finds the best configuration for the swarms created by `make`, which
+    /// receives the swarm size and the seed.
+    pub fn tune<'a, F>(&self, make: F) -> Result<Tuned, PsoError>
+    where
+        F: Fn(usize, u64) -> Particles<'a>,
+    {
+        if self.seeds.is_empty() {
+            return Err(PsoError::InvalidOptions(
+                "at least one seed is needed".to_string(),
+            ));
+        }
+
+        if self.size.0 == 0 || self.size.0 > self.size.1 {
+            return Err(PsoError::InvalidOptions(
+                "size range must satisfy 0 < min <= max".to_string(),
+            ));
+        }
+
+        let minimization = make(self.size.0, self.seeds[0]).fitness().is_minimization();
+        let worst = if minimization {
+            f64::INFINITY
+        } else {
+            f64::NEG_INFINITY
+        };
+
+        // Unstable configurations get the worst possible score
+        let score = |x: &Array1<f64>| {
+            let (options, size) = self.decode(x);
+            self.score(&make, options, size).unwrap_or(worst)
+        };
+
+        let best = match self.strategy {
+            MetaStrategy::Pso { size, iters } => {
+                let mut outer = SwarmBuilder::new(Fitness::new(score, minimization))
+                    .size(size)
+                    .dim(4)
+                    .value_range(0.0, 1.0)
+                    .velocity_range(-0.1, 0.1)
+                    .seed(self.seed)
+                    .build()?;
+
+                outer.run(Options::canonical(), iters)?;
+                outer.best().clone()
+            }
+            MetaStrategy::RandomSearch { samples } => {
+                let mut rng = Pcg64::seed_from_u64(self.seed);
+                let fitness = Fitness::new(score, minimization);
+
+                (0..samples.max(1))
+                    .map(|_| Array1::from_shape_fn(4, |_| rng.gen_range(0.0, 1.0)))
+                    .map(|x| (fitness.calculate_for_maximization(&x), x))
+                    .max_by(|a, b| a.0.partial_cmp(&b.0).expect("Received a NaN"))
+                    .map(|(_, x)| x)
+                    .expect("No samples were evaluated")
+            }
+        };
+
+        let (options, size) = self.decode(&best);
+        let score = self.score(&make, options, size)?;
+
+        Ok(Tuned {
+            options,
+            size,
+            score,
+        })
+    }
+
+    /// Maps a point of `[0, 1]^4` to a configuration.
+    fn decode(&self, x: &Array1<f64>) -> (Options, usize) {
+        let lerp = |(min, max): (f64, f64), t: f64| min + (max - min) * t.clamp(0.0, 1.0);
+
+        let options = Options {
+            omega: Some(lerp(self.omega, x[0])),
+            phi_1: lerp(self.phi_1, x[1]),
+            phi_2: lerp(self.phi_2, x[2]),
+        };
+
+        let size = lerp((self.size.0 as f64, self.size.1 as f64), x[3]).round() as usize;
+        (options, size)
+    }
+
+    fn score<'a, F>(&self, make: &F, options: Options, size: usize) -> Result<f64, PsoError>
+    where
+        F: Fn(usize, u64) -> Particles<'a>,
+    {
+        let mut finals = Vec::with_capacity(self.seeds.len());
+        for &seed in &self.seeds {
+            let mut particles = make(size, seed);
+            particles.run(options, self.iters)?;
+            finals.push(particles.best_fitness());
+        }
+
+        Ok(median(&finals))
+    }
+}
@@ -0,0 +1,55 @@
+use ndarray::Array1;
+use serde::{Deserialize, Serialize};
+
+/// Distance between two positions.
+pub trait Distance {
+    fn distance(&self, a: &Array1<f64>, b: &Array1<f64>) -> f64;
+}
+
+impl<F> Distance for F
+where
+    F: Fn(&Array1<f64>, &Array1<f64>) -> f64,
+{
+    fn distance(&self, a: &Array1<f64>, b: &Array1<f64>) -> f64 {
+        self(a, b)
+    }
+}
+
+/// User defined metric of a swarm, see
+/// [`Particles::set_custom_metric`](crate::pso::Particles::set_custom_metric).
+pub type CustomMetric<'a> = Box<dyn Fn(&Array1<f64>, &Array1<f64>) -> f64 + Send + Sync + 'a>;
+
+/// Built-in metrics, used by duplicate detection, diversity, tie breaks
+/// and archives.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Metric {
+    #[default]
+    Euclidean,
+    /// Sum of the absolute differences.
+    Manhattan,
+    /// Largest absolute difference.
+    Chebyshev,
+    /// `1 - cos θ`, only the directions matter. The zero vector is at
+    /// distance 1 of everything but itself.
+    Cosine,
+}
+
+impl Distance for Metric {
+    fn distance(&self, a: &Array1<f64>, b: &Array1<f64>) -> f64 {
+        match self {
+            Metric::Euclidean => (a - b).mapv(|v| v * v).sum().sqrt(),
+            Metric::Manhattan => (a - b).mapv(f64::abs).sum(),
+            Metric::Chebyshev => (a - b).iter().fold(0.0, |max, v| v.abs().max(max)),
+            Metric::Cosine => {
+                let norms = a.dot(a).sqrt() * b.dot(b).sqrt();
+                if norms > 0.0 {
+                    1.0 - a.dot(b) / norms
+                } else if a == b {
+                    0.0
+                } else {
+                    1.0
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use crate::fitness::Fitness;
+use ndarray::{s, Array1, Array2, ArrayView1, ArrayView2, Axis};
+
+/// Tiny multilayer perceptron whose weights are a flat position, so a swarm
+/// can train it. Hidden layers use `tanh` and the output layer is linear.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mlp {
+    sizes: Vec<usize>,
+}
+
+impl Mlp {
+    /// `sizes` goes from the inputs to the outputs, e.g. `[2, 4, 1]`.
+    pub fn new(sizes: Vec<usize>) -> Self {
+        assert!(sizes.len() >= 2, "At least the input and output are needed");
+        assert!(sizes.iter().all(|&n| n > 0), "Layers can't be empty");
+
+        Self { sizes }
+    }
+
+    pub fn sizes(&self) -> &[usize] {
+        &self.sizes
+    }
+
+    /// Length of the flattened weights, the dimension to optimize.
+    pub fn dim(&self) -> usize {
+        self.sizes.windows(2).map(|w| (w[0] + 1) * w[1]).sum()
+    }
+
+    /// Weight matrix (`outputs × inputs`) and biases of every layer, in order.
+    fn layers<'w>(
+        &self,
+        weights: &'w Array1<f64>,
+    ) -> Vec<(ArrayView2<'w, f64>, ArrayView1<'w, f64>)> {
+        assert_eq!(weights.len(), self.dim(), "Wrong number of weights");
+
+        let mut offset = 0;
+        self.sizes
+            .windows(2)
+            .map(|w| {
+                let (inputs, outputs) = (w[0], w[1]);
+                let matrix = weights
+                    .slice(s![offset..offset + inputs * outputs])
+                    .into_shape((outputs, inputs))
+                    .expect("Weights are contiguous");
+                offset += inputs * outputs;
+
+                let biases = weights.slice(s![offset..offset + outputs]);
+                offset += outputs;
+
+                (matrix, biases)
+            })
+            .collect()
+    }
+
+    /// Outputs for a batch of inputs, one sample per row.
+    pub fn forward(&self, weights: &Array1<f64>, inputs: &Array2<f64>) -> Array2<f64> {
+        let layers = self.layers(weights);
+        let last = layers.len() - 1;
+
+        let mut activations = inputs.clone();
+        for (i, (matrix, biases)) in layers.into_iter().enumerate() {
+            activations = activations.dot(&matrix.t()) + biases;
+            if i < last {
+                activations.mapv_inplace(f64::tanh);
+            }
+        }
+
+        activations
+    }
+
+    /// Mean squared error over the whole batch.
+    pub fn mse(&self, weights: &Array1<f64>, inputs: &Array2<f64>, targets: &Array2<f64>) -> f64 {
+        let errors = self.forward(weights, inputs) - targets;
+        errors.mapv(|e| e * e).sum() / inputs.len_of(Axis(0)) as f64
+    }
+
+    /// Fitness to minimize the error on the dataset, it's shared so it can
+    /// be evaluated in parallel.
+    pub fn fitness<'a>(
+        &'a self,
+        inputs: &'a Array2<f64>,
+        targets: &'a Array2<f64>,
+    ) -> Fitness<'a, Array1<f64>> {
+        assert_eq!(inputs.ncols(), self.sizes[0], "Wrong number of inputs");
+        assert_eq!(
+            targets.ncols(),
+            *self.sizes.last().unwrap(),
+            "Wrong number of outputs"
+        );
+
+        Fitness::shared(move |weights| self.mse(weights, inputs, targets), true)
+    }
+}
@@ -0,0 +1,218 @@
+use crate::error::PsoError;
+use crate::fitness::Fitness;
+use std::f64::consts::PI;
+use std::sync::Arc;
+
+type TermFn<'a, T> = Box<dyn Fn(&T) -> f64 + Send + Sync + 'a>;
+
+struct Term<'a, T> {
+    name: String,
+    weight: f64,
+    scale: f64,
+    func: TermFn<'a, T>,
+}
+
+/// Terms of a composite objective, see [`ObjectiveBuilder`].
+pub struct Terms<'a, T> {
+    terms: Vec<Term<'a, T>>,
+}
+
+impl<'a, T> Terms<'a, T> {
+    pub fn names(&self) -> Vec<&str> {
+        self.terms.iter().map(|term| term.name.as_str()).collect()
+    }
+
+    /// Weighted (and scaled) contribution of every term, they add up to the
+    /// objective value.
+    pub fn values(&self, val: &T) -> Vec<f64> {
+        self.terms
+            .iter()
+            .map(|term| term.weight * (term.func)(val) / term.scale)
+            .collect()
+    }
+}
+
+/// Combines several closures into a single [`Fitness`], e.g. cost + penalty +
+/// regularization. The objective is `Σ w_i f_i(x) / s_i`, and the value of
+/// every term is recorded in the history.
+pub struct ObjectiveBuilder<'a, T> {
+    terms: Vec<Term<'a, T>>,
+    normalize: bool,
+    minimization: bool,
+}
+
+impl<'a, T: 'a> ObjectiveBuilder<'a, T> {
+    pub fn new(minimization: bool) -> Self {
+        Self {
+            terms: Vec::new(),
+            normalize: false,
+            minimization,
+        }
+    }
+
+    pub fn term<F>(mut self, name: impl Into<String>, weight: f64, func: F) -> Self
+    where
+        F: Fn(&T) -> f64 + Send + Sync + 'a,
+    {
+        self.terms.push(Term {
+            name: name.into(),
+            weight,
+            scale: 1.0,
+            func: Box::new(func),
+        });
+
+        self
+    }
+
+    /// Divides the last added term by `scale` (e.g. its typical magnitude),
+    /// so terms of different units are comparable.
+    pub fn scale(mut self, scale: f64) -> Self {
+        assert!(scale > 0.0, "Scale must be positive");
+
+        let term = self.terms.last_mut().expect("No terms were added");
+        term.scale = scale;
+        self
+    }
+
+    /// Divides the weights by their sum, so they add up to one.
+    pub fn normalize(mut self, normalize: bool) -> Self {
+        self.normalize = normalize;
+        self
+    }
+
+    pub fn build(mut self) -> Fitness<'a, T> {
+        assert!(!self.terms.is_empty(), "No terms were added");
+
+        if self.normalize {
+            let total: f64 = self.terms.iter().map(|term| term.weight).sum();
+            assert!(total != 0.0, "Weights add up to zero");
+
+            for term in &mut self.terms {
+                term.weight /= total;
+            }
+        }
+
+        let terms = Arc::new(Terms { terms: self.terms });
+        let objective = Arc::clone(&terms);
+
+        Fitness::shared(
+            move |val: &T| objective.values(val).into_iter().sum(),
+            self.minimization,
+        )
+        .with_terms(terms)
+    }
+}
+
+/// Reduces several objectives, all minimized, to a single one. Negate the
+/// ones to maximize.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Scalarization {
+    /// `Σ w_i f_i`.
+    WeightedSum(Vec<f64>),
+    /// `max_i w_i |f_i - z_i|`, `z` being an ideal point. Unlike the
+    /// weighted sum it also reaches the non-convex parts of the front.
+    Chebyshev { weights: Vec<f64>, ideal: Vec<f64> },
+    /// Objectives by priority, the i-th one is compared in steps of the
+    /// i-th tolerance and only ties go to the next one. There's a tolerance
+    /// for every objective but the last. Lower priorities lose resolution
+    /// when the values are large.
+    Lexicographic(Vec<f64>),
+}
+
+impl Scalarization {
+    /// Amount of objectives expected.
+    pub fn objectives(&self) -> usize {
+        match self {
+            Scalarization::WeightedSum(weights) => weights.len(),
+            Scalarization::Chebyshev { weights, .. } => weights.len(),
+            Scalarization::Lexicographic(tolerances) => tolerances.len() + 1,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), PsoError> {
+        if self.objectives() == 0 {
+            return Err(PsoError::InvalidOptions(
+                "at least one objective is needed".to_string(),
+            ));
+        }
+
+        let (name, values, rule) = match self {
+            Scalarization::WeightedSum(weights) => ("weights", weights, "non-negative"),
+            Scalarization::Chebyshev { weights, ideal } => {
+                if ideal.len() != weights.len() || ideal.iter().any(|z| !z.is_finite()) {
+                    return Err(PsoError::InvalidOptions(format!(
+                        "ideal point must have {} finite values",
+                        weights.len()
+                    )));
+                }
+
+                ("weights", weights, "non-negative")
+            }
+            Scalarization::Lexicographic(tolerances) => ("tolerances", tolerances, "positive"),
+        };
+
+        let invalid = values.iter().find(|&&v| {
+            let valid = if rule == "positive" {
+                v > 0.0
+            } else {
+                v >= 0.0
+            };
+            !(v.is_finite() && valid)
+        });
+
+        match invalid {
+            Some(value) => Err(PsoError::InvalidOptions(format!(
+                "{} must be finite and {}, got {}",
+                name, rule, value
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// The single objective of `values`.
+    ///
+    /// # Panics
+    ///
+    /// If there aren't as many values as [`objectives`](Self::objectives).
+    pub fn apply(&self, values: &[f64]) -> f64 {
+        assert_eq!(
+            values.len(),
+            self.objectives(),
+            "Expected {} objectives",
+            self.objectives()
+        );
+
+        match self {
+            Scalarization::WeightedSum(weights) => {
+                weights.iter().zip(values).map(|(w, f)| w * f).sum()
+            }
+            Scalarization::Chebyshev { weights, ideal } => weights
+                .iter()
+                .zip(ideal)
+                .zip(values)
+                .map(|((w, z), f)| w * (f - z).abs())
+                .fold(f64::NEG_INFINITY, f64::max),
+            Scalarization::Lexicographic(tolerances) => {
+                // Squashed into [0, 1) so it never outweighs a step of the
+                // previous objective
+                let squash = |x: f64| 0.5 + x.atan() / PI;
+
+                let last = values[values.len() - 1];
+                tolerances
+                    .iter()
+                    .zip(values)
+                    .rev()
+                    .fold(last, |next, (t, f)| (f / t).floor() + squash(next))
+            }
+        }
+    }
+
+    /// Minimized fitness of the vector objective `func`.
+    pub fn fitness<'a, T, F>(self, func: F) -> Result<Fitness<'a, T>, PsoError>
+    where
+        F: Fn(&T) -> Vec<f64> + Send + Sync + 'a,
+    {
+        self.validate()?;
+        Ok(Fitness::shared(move |val: &T| self.apply(&func(val)), true))
+    }
+}
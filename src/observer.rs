@@ -0,0 +1,38 @@
+use crate::pso::Particles;
+
+/// What the run driver should do after an observer hook.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Control {
+    /// Keep running.
+    Continue,
+    /// Stop the run after this hook.
+    Stop,
+    /// Re-initialize the i-th particle.
+    RestartParticle(usize),
+    /// Re-initialize the k particles with the worst current fitness.
+    ReSampleWorst(usize),
+}
+
+/// Hooks called by [`Particles::run_with`], every hook can steer the run.
+pub trait Observer {
+    /// Called once before the first step.
+    fn on_start(&mut self, _particles: &Particles) -> Control {
+        Control::Continue
+    }
+
+    /// Called after every step, `iter` starts at 1.
+    fn on_step(&mut self, iter: usize, particles: &Particles) -> Control;
+
+    /// Called once when the run ends, either by exhausting the iterations or
+    /// because an observer asked to stop.
+    fn on_finish(&mut self, _particles: &Particles) {}
+}
+
+impl<F> Observer for F
+where
+    F: FnMut(usize, &Particles) -> Control,
+{
+    fn on_step(&mut self, iter: usize, particles: &Particles) -> Control {
+        self(iter, particles)
+    }
+}
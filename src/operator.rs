@@ -0,0 +1,232 @@
+use crate::clustering::ClusterRestart;
+use crate::differential::Differential;
+use crate::error::PsoError;
+use crate::lineage::LineageEvent;
+use crate::pso::{clamp_to, Particles};
+use crate::surrogate::Surrogate;
+use ndarray::Array1;
+use ndarray_rand::rand_distr::StandardNormal;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Enhancement applied after the particles moved on every step. The
+/// operators of a swarm form an ordered pipeline, see
+/// [`Particles::set_operators`].
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Operator {
+    /// See [`ClusterRestart`].
+    ClusterRestart(ClusterRestart),
+    /// See [`Differential`].
+    Differential(Differential),
+    /// Moves every particle with `probability`, adding gaussian noise with a
+    /// deviation of `sigma` times the width of the value range.
+    Mutation { probability: f64, sigma: f64 },
+    /// Opposition-based learning, with `probability` a particle evaluates
+    /// its opposite position `min + max - x` and moves there if it improves
+    /// its personal best.
+    Opposition { probability: f64 },
+    /// Gaussian hill climbing of `samples` evaluations around the best
+    /// personal best, with a deviation of `radius` times the width of the
+    /// value range.
+    LocalSearch { samples: usize, radius: f64 },
+    /// Clamps the particles outside of the value range back into it, NaN
+    /// coordinates go to its middle.
+    Repair,
+    /// See [`Particles::disperse_duplicates`].
+    Deduplication { tolerance: f64 },
+    /// Re-initializes a `fraction` of the swarm, the particles with the
+    /// worst fitness, once the global best didn't improve for `stagnation`
+    /// steps.
+    Restart { stagnation: usize, fraction: f64 },
+    /// See [`Surrogate`].
+    Surrogate(Surrogate),
+}
+
+impl Operator {
+    /// Latest evaluations the swarm has to remember for this operator.
+    pub fn memory(&self) -> usize {
+        match self {
+            Operator::Surrogate(surrogate) => surrogate.memory,
+            _ => 0,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), PsoError> {
+        let probability = |name: &str, value: f64| {
+            if (0.0..=1.0).contains(&value) {
+                Ok(())
+            } else {
+                Err(PsoError::InvalidOptions(format!(
+                    "{} must be in [0, 1], got {}",
+                    name, value
+                )))
+            }
+        };
+        let deviation = |name: &str, value: f64| {
+            if value.is_finite() && value >= 0.0 {
+                Ok(())
+            } else {
+                Err(PsoError::InvalidOptions(format!(
+                    "{} must be finite and non-negative, got {}",
+                    name, value
+                )))
+            }
+        };
+
+        match *self {
+            Operator::Differential(differential) => differential.validate(),
+            Operator::Mutation {
+                probability: p,
+                sigma,
+            } => {
+                probability("mutation probability", p)?;
+                deviation("sigma", sigma)
+            }
+            Operator::Opposition { probability: p } => probability("opposition probability", p),
+            Operator::LocalSearch { radius, .. } => deviation("radius", radius),
+            Operator::Repair => Ok(()),
+            Operator::Deduplication { tolerance } => {
+                if tolerance >= 0.0 && tolerance.is_finite() {
+                    Ok(())
+                } else {
+                    Err(PsoError::InvalidOptions(format!(
+                        "deduplication tolerance must be non-negative, got {}",
+                        tolerance
+                    )))
+                }
+            }
+            Operator::Restart {
+                stagnation,
+                fraction,
+            } => {
+                if stagnation == 0 {
+                    return Err(PsoError::InvalidOptions(
+                        "stagnation must be at least 1".to_string(),
+                    ));
+                }
+
+                probability("restart fraction", fraction)
+            }
+            Operator::Surrogate(surrogate) => surrogate.validate(),
+            Operator::ClusterRestart(restart) => restart.validate(),
+        }
+    }
+}
+
+impl<'a> Particles<'a> {
+    /// Applies `operator` once, returns how many particles it changed.
+    pub fn apply_operator(&mut self, operator: &Operator) -> usize {
+        match *operator {
+            Operator::Differential(differential) => self.apply_differential(&differential),
+            Operator::Mutation { probability, sigma } => self.mutate_randomly(probability, sigma),
+            Operator::Opposition { probability } => self.oppose(probability),
+            Operator::LocalSearch { samples, radius } => self.search_locally(samples, radius),
+            Operator::Repair => self.clamp_all(),
+            Operator::Deduplication { tolerance } => self.disperse_duplicates(tolerance),
+            Operator::Restart {
+                stagnation,
+                fraction,
+            } => {
+                if self.stagnant_steps() < stagnation {
+                    return 0;
+                }
+
+                let k = (fraction * self.particles().len() as f64).round() as usize;
+                self.resample_worst(k);
+                self.reset_stagnation();
+                k
+            }
+            Operator::Surrogate(surrogate) => self.scout(&surrogate) as usize,
+            Operator::ClusterRestart(restart) => self.restart_clusters(&restart),
+        }
+    }
+
+    /// Gaussian noise of `sigma` times the width of the value range.
+    fn noise(&mut self, dim: usize, sigma: f64) -> Array1<f64> {
+        let (min, max) = self.value_range();
+        let rng = self.rng_mut();
+
+        Array1::from_shape_fn(dim, |_| {
+            let z: f64 = rng.sample(StandardNormal);
+            z * sigma * (max - min)
+        })
+    }
+
+    fn mutate_randomly(&mut self, probability: f64, sigma: f64) -> usize {
+        let dim = self.best().len();
+
+        let mut mutated = 0;
+        for i in 0..self.particles().len() {
+            if self.rng_mut().gen::<f64>() >= probability {
+                continue;
+            }
+
+            let noise = self.noise(dim, sigma);
+            let mut value = self.particles()[i].value() + &noise;
+            self.fix_position(&mut value);
+            self.mutate(i, |x| x.assign(&value));
+            mutated += 1;
+        }
+
+        mutated
+    }
+
+    fn oppose(&mut self, probability: f64) -> usize {
+        let (min, max) = self.value_range();
+
+        let mut moved = 0;
+        for i in 0..self.particles().len() {
+            if self.rng_mut().gen::<f64>() >= probability {
+                continue;
+            }
+
+            let mut opposite = self.particles()[i].value().mapv(|x| min + max - x);
+            self.fix_position(&mut opposite);
+            if self.offer_trial(i, opposite, LineageEvent::Mutated) {
+                moved += 1;
+            }
+        }
+
+        moved
+    }
+
+    fn search_locally(&mut self, samples: usize, radius: f64) -> usize {
+        let dim = self.best().len();
+        let best = (0..self.particles().len()).max_by(|&a, &b| {
+            let particles = self.particles();
+            self.compare(particles[a].best_fitness(), particles[b].best_fitness())
+        });
+        let best = match best {
+            Some(best) => best,
+            None => return 0,
+        };
+
+        let mut improved = false;
+        for _ in 0..samples {
+            let noise = self.noise(dim, radius);
+            let mut sample = self.particles()[best].best() + &noise;
+            self.fix_position(&mut sample);
+            improved |= self.offer_trial(best, sample, LineageEvent::Mutated);
+        }
+
+        improved as usize
+    }
+
+    fn clamp_all(&mut self) -> usize {
+        let range = self.value_range();
+
+        let mut clamped = 0;
+        for i in 0..self.particles().len() {
+            let outside = self.particles()[i]
+                .value()
+                .iter()
+                .any(|x| !(range.0..=range.1).contains(x));
+            if outside {
+                self.mutate(i, |x| x.mapv_inplace(|v| clamp_to(v, range)));
+                clamped += 1;
+            }
+        }
+
+        clamped
+    }
+}
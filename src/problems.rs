@@ -0,0 +1,441 @@
+use ndarray::{array, Array1};
+use rand::Rng;
+use std::f64::consts::PI;
+
+/// Constrained benchmark, minimize `objective(x)` subject to every
+/// constraint being `<= 0` and `x` inside the bounds.
+pub trait Problem {
+    fn name(&self) -> &str;
+
+    /// Lower and upper bound of every dimension.
+    fn bounds(&self) -> (Array1<f64>, Array1<f64>);
+
+    fn objective(&self, x: &Array1<f64>) -> f64;
+
+    /// Value of every constraint `g_i(x)`, satisfied when `<= 0`.
+    fn constraints(&self, x: &Array1<f64>) -> Vec<f64>;
+
+    /// Best objective value reported in the literature.
+    fn best_known(&self) -> f64;
+
+    fn dim(&self) -> usize {
+        self.bounds().0.len()
+    }
+
+    fn is_feasible(&self, x: &Array1<f64>, tolerance: f64) -> bool {
+        self.constraints(x).iter().all(|&g| g <= tolerance)
+    }
+
+    /// Static penalty `f(x) + weight * Σ max(0, g_i(x))²`.
+    fn penalized(&self, x: &Array1<f64>, weight: f64) -> f64 {
+        let violation: f64 = self
+            .constraints(x)
+            .iter()
+            .map(|&g| g.max(0.0).powi(2))
+            .sum();
+
+        self.objective(x) + weight * violation
+    }
+}
+
+/// Cylindrical pressure vessel with shell thickness, head thickness, inner
+/// radius and length `(T_s, T_h, R, L)`, minimizing material, forming and
+/// welding cost. Continuous version.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct PressureVessel;
+
+impl Problem for PressureVessel {
+    fn name(&self) -> &str {
+        "pressure vessel"
+    }
+
+    fn bounds(&self) -> (Array1<f64>, Array1<f64>) {
+        (
+            array![0.0, 0.0, 10.0, 10.0],
+            array![99.0, 99.0, 200.0, 200.0],
+        )
+    }
+
+    fn objective(&self, x: &Array1<f64>) -> f64 {
+        let (ts, th, r, l) = (x[0], x[1], x[2], x[3]);
+        0.6224 * ts * r * l + 1.7781 * th * r * r + 3.1661 * ts * ts * l + 19.84 * ts * ts * r
+    }
+
+    fn constraints(&self, x: &Array1<f64>) -> Vec<f64> {
+        let (ts, th, r, l) = (x[0], x[1], x[2], x[3]);
+        vec![
+            -ts + 0.0193 * r,
+            -th + 0.00954 * r,
+            -PI * r * r * l - 4.0 / 3.0 * PI * r.powi(3) + 1_296_000.0,
+            l - 240.0,
+        ]
+    }
+
+    fn best_known(&self) -> f64 {
+        5885.3328
+    }
+}
+
+/// Welded beam with weld thickness, weld length, bar height and bar
+/// thickness `(h, l, t, b)`, minimizing fabrication cost subject to shear
+/// stress, bending stress, buckling load and deflection.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct WeldedBeam;
+
+impl Problem for WeldedBeam {
+    fn name(&self) -> &str {
+        "welded beam"
+    }
+
+    fn bounds(&self) -> (Array1<f64>, Array1<f64>) {
+        (array![0.1, 0.1, 0.1, 0.1], array![2.0, 10.0, 10.0, 2.0])
+    }
+
+    fn objective(&self, x: &Array1<f64>) -> f64 {
+        let (h, l, t, b) = (x[0], x[1], x[2], x[3]);
+        1.10471 * h * h * l + 0.04811 * t * b * (14.0 + l)
+    }
+
+    fn constraints(&self, x: &Array1<f64>) -> Vec<f64> {
+        const P: f64 = 6000.0;
+        const L: f64 = 14.0;
+        const E: f64 = 30e6;
+        const G: f64 = 12e6;
+        const TAU_MAX: f64 = 13_600.0;
+        const SIGMA_MAX: f64 = 30_000.0;
+        const DELTA_MAX: f64 = 0.25;
+
+        let (h, l, t, b) = (x[0], x[1], x[2], x[3]);
+
+        let m = P * (L + l / 2.0);
+        let r = (l * l / 4.0 + ((h + t) / 2.0).powi(2)).sqrt();
+        let j = 2.0 * (2f64.sqrt() * h * l * (l * l / 12.0 + ((h + t) / 2.0).powi(2)));
+
+        let tau_1 = P / (2f64.sqrt() * h * l);
+        let tau_2 = m * r / j;
+        let tau = (tau_1 * tau_1 + tau_1 * tau_2 * l / r + tau_2 * tau_2).sqrt();
+
+        let sigma = 6.0 * P * L / (b * t * t);
+        let delta = 4.0 * P * L.powi(3) / (E * t.powi(3) * b);
+        let buckling = 4.013 * E * (t * t * b.powi(6) / 36.0).sqrt() / (L * L)
+            * (1.0 - t / (2.0 * L) * (E / (4.0 * G)).sqrt());
+
+        vec![
+            tau - TAU_MAX,
+            sigma - SIGMA_MAX,
+            h - b,
+            0.10471 * h * h + 0.04811 * t * b * (14.0 + l) - 5.0,
+            0.125 - h,
+            delta - DELTA_MAX,
+            P - buckling,
+        ]
+    }
+
+    fn best_known(&self) -> f64 {
+        1.724852
+    }
+}
+
+/// Tension/compression spring with wire diameter, coil diameter and number
+/// of active coils `(d, D, N)`, minimizing its weight subject to
+/// deflection, shear stress and surge frequency.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Spring;
+
+impl Problem for Spring {
+    fn name(&self) -> &str {
+        "tension/compression spring"
+    }
+
+    fn bounds(&self) -> (Array1<f64>, Array1<f64>) {
+        (array![0.05, 0.25, 2.0], array![2.0, 1.3, 15.0])
+    }
+
+    fn objective(&self, x: &Array1<f64>) -> f64 {
+        let (d, coil, n) = (x[0], x[1], x[2]);
+        (n + 2.0) * coil * d * d
+    }
+
+    fn constraints(&self, x: &Array1<f64>) -> Vec<f64> {
+        let (d, coil, n) = (x[0], x[1], x[2]);
+        vec![
+            1.0 - coil.powi(3) * n / (71785.0 * d.powi(4)),
+            (4.0 * coil * coil - d * coil) / (12566.0 * (coil * d.powi(3) - d.powi(4)))
+                + 1.0 / (5108.0 * d * d)
+                - 1.0,
+            1.0 - 140.45 * d / (coil * coil * n),
+            (coil + d) / 1.5 - 1.0,
+        ]
+    }
+
+    fn best_known(&self) -> f64 {
+        0.012665
+    }
+}
+
+/// Benchmark over bit strings, maximize `score(bits)`. Swarms search the
+/// unit cube and [`decode`] their positions, see [`evaluate`](Self::evaluate).
+pub trait BinaryProblem {
+    fn name(&self) -> &str;
+
+    fn dim(&self) -> usize;
+
+    fn score(&self, bits: &[bool]) -> f64;
+
+    /// Best score, when it can be computed.
+    fn best_known(&self) -> Option<f64>;
+
+    /// Score of a position of the unit cube, pass it to
+    /// [`Fitness::new`](crate::fitness::Fitness::new) as a maximization.
+    fn evaluate(&self, x: &Array1<f64>) -> f64 {
+        self.score(&decode(x))
+    }
+}
+
+/// Bits of a position of the unit cube, set where it's above 0.5.
+pub fn decode(x: &Array1<f64>) -> Vec<bool> {
+    x.iter().map(|&v| v > 0.5).collect()
+}
+
+/// How [`Knapsack`] scores selections over the capacity.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Overweight {
+    /// Value minus `weight` times the excess of weight.
+    Penalty(f64),
+    /// Value of the selection after dropping the items of lowest value per
+    /// weight until it fits.
+    Repair,
+}
+
+/// 0/1 knapsack, choose items of most value with a total weight within the
+/// capacity.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Knapsack {
+    weights: Vec<f64>,
+    values: Vec<f64>,
+    capacity: f64,
+    overweight: Overweight,
+}
+
+impl Knapsack {
+    /// Repairs overweight selections by default.
+    pub fn new(weights: Vec<f64>, values: Vec<f64>, capacity: f64) -> Self {
+        assert_eq!(weights.len(), values.len(), "Every item needs a value");
+        assert!(
+            weights
+                .iter()
+                .chain(&values)
+                .all(|&v| v.is_finite() && v > 0.0),
+            "Weights and values must be finite and positive"
+        );
+        assert!(capacity >= 0.0, "Capacity can't be negative");
+
+        Self {
+            weights,
+            values,
+            capacity,
+            overweight: Overweight::Repair,
+        }
+    }
+
+    /// `items` items of integer weight and value in `[1, 100]`, the capacity
+    /// half the total weight.
+    pub fn random(items: usize, rng: &mut impl Rng) -> Self {
+        let weights: Vec<_> = (0..items)
+            .map(|_| f64::from(rng.gen_range(1, 101)))
+            .collect();
+        let values = (0..items)
+            .map(|_| f64::from(rng.gen_range(1, 101)))
+            .collect();
+        let capacity = (weights.iter().sum::<f64>() / 2.0).floor();
+
+        Self::new(weights, values, capacity)
+    }
+
+    pub fn overweight(mut self, overweight: Overweight) -> Self {
+        self.overweight = overweight;
+        self
+    }
+
+    pub fn capacity(&self) -> f64 {
+        self.capacity
+    }
+
+    pub fn weight(&self, bits: &[bool]) -> f64 {
+        self.total(&self.weights, bits)
+    }
+
+    pub fn value(&self, bits: &[bool]) -> f64 {
+        self.total(&self.values, bits)
+    }
+
+    fn total(&self, amounts: &[f64], bits: &[bool]) -> f64 {
+        assert_eq!(bits.len(), amounts.len(), "Expected {} bits", amounts.len());
+
+        amounts
+            .iter()
+            .zip(bits)
+            .filter(|(_, &taken)| taken)
+            .map(|(amount, _)| amount)
+            .sum()
+    }
+
+    /// `bits` without the items of lowest value per weight that don't fit.
+    pub fn repair(&self, bits: &[bool]) -> Vec<bool> {
+        let mut bits = bits.to_vec();
+        let mut taken: Vec<_> = (0..bits.len()).filter(|&i| bits[i]).collect();
+        taken.sort_by(|&a, &b| {
+            let ratio = |i: usize| self.values[i] / self.weights[i];
+            ratio(b).total_cmp(&ratio(a))
+        });
+
+        let mut weight = self.weight(&bits);
+        while weight > self.capacity {
+            let i = taken.pop().expect("An empty selection fits");
+            bits[i] = false;
+            weight -= self.weights[i];
+        }
+
+        bits
+    }
+
+    /// Best value by dynamic programming over the capacity, only when every
+    /// weight is an integer.
+    pub fn optimum(&self) -> Option<f64> {
+        if self.weights.iter().any(|w| w.fract() != 0.0) {
+            return None;
+        }
+
+        let capacity = self.capacity.floor() as usize;
+        let mut best = vec![0.0; capacity + 1];
+        for (&weight, &value) in self.weights.iter().zip(&self.values) {
+            let weight = weight as usize;
+            for room in (weight..=capacity).rev() {
+                best[room] = f64::max(best[room], best[room - weight] + value);
+            }
+        }
+
+        Some(best[capacity])
+    }
+}
+
+impl BinaryProblem for Knapsack {
+    fn name(&self) -> &str {
+        "0/1 knapsack"
+    }
+
+    fn dim(&self) -> usize {
+        self.weights.len()
+    }
+
+    fn score(&self, bits: &[bool]) -> f64 {
+        match self.overweight {
+            Overweight::Penalty(weight) => {
+                let excess = (self.weight(bits) - self.capacity).max(0.0);
+                self.value(bits) - weight * excess
+            }
+            Overweight::Repair => self.value(&self.repair(bits)),
+        }
+    }
+
+    fn best_known(&self) -> Option<f64> {
+        self.optimum()
+    }
+}
+
+type ScoreFn<'a> = Box<dyn Fn(&[usize]) -> f64 + Send + Sync + 'a>;
+
+/// Feature selection, choose the subset of features that scores best (e.g.
+/// the accuracy of a model trained on them), each one selected costing
+/// `cost`.
+pub struct FeatureSelection<'a> {
+    features: usize,
+    score: ScoreFn<'a>,
+    cost: f64,
+    relevant: Option<Vec<usize>>,
+}
+
+impl<'a> FeatureSelection<'a> {
+    /// `score` receives the indices of the selected features.
+    pub fn new<F>(features: usize, score: F) -> Self
+    where
+        F: Fn(&[usize]) -> f64 + Send + Sync + 'a,
+    {
+        Self {
+            features,
+            score: Box::new(score),
+            cost: 0.0,
+            relevant: None,
+        }
+    }
+
+    /// Synthetic problem of `features` features where only `relevant` of
+    /// them, chosen at random, score in proportion to a random relevance.
+    /// The rest only cost, so the best subset is the relevant features.
+    pub fn synthetic(features: usize, relevant: usize, rng: &mut impl Rng) -> Self {
+        assert!(relevant <= features, "More relevant features than features");
+
+        let mut indices: Vec<_> = (0..features).collect();
+        for i in 0..relevant {
+            let j = rng.gen_range(i, features);
+            indices.swap(i, j);
+        }
+        indices.truncate(relevant);
+        indices.sort_unstable();
+
+        let mut relevance = vec![0.0; features];
+        for &i in &indices {
+            relevance[i] = rng.gen_range(0.5, 1.0);
+        }
+        let total: f64 = relevance.iter().sum();
+
+        // Every relevant feature scores more than the cost of selecting it
+        let cost = 0.25 / features as f64;
+        let score = move |selected: &[usize]| {
+            selected.iter().map(|&i| relevance[i]).sum::<f64>() / total.max(f64::MIN_POSITIVE)
+        };
+
+        let mut selection = Self::new(features, score).cost(cost);
+        selection.relevant = Some(indices);
+        selection
+    }
+
+    pub fn cost(mut self, cost: f64) -> Self {
+        assert!(cost >= 0.0, "Cost can't be negative");
+
+        self.cost = cost;
+        self
+    }
+
+    /// Features that score, for synthetic problems.
+    pub fn relevant(&self) -> Option<&[usize]> {
+        self.relevant.as_deref()
+    }
+
+    /// Indices of the set bits.
+    pub fn selected(bits: &[bool]) -> Vec<usize> {
+        (0..bits.len()).filter(|&i| bits[i]).collect()
+    }
+}
+
+impl<'a> BinaryProblem for FeatureSelection<'a> {
+    fn name(&self) -> &str {
+        "feature selection"
+    }
+
+    fn dim(&self) -> usize {
+        self.features
+    }
+
+    fn score(&self, bits: &[bool]) -> f64 {
+        assert_eq!(bits.len(), self.features, "Expected {} bits", self.features);
+
+        let selected = Self::selected(bits);
+        (self.score)(&selected) - self.cost * selected.len() as f64
+    }
+
+    fn best_known(&self) -> Option<f64> {
+        let relevant = self.relevant.as_ref()?;
+        Some((self.score)(relevant) - self.cost * relevant.len() as f64)
+    }
+}
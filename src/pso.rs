@@ -1,170 +1,2634 @@
-use crate::fitness::Fitness;
-use anyhow::Error;
+use crate::archive::Archive;
+use crate::budget::Priority;
+use crate::constraints::{ConstraintHandling, RepairHook};
+use crate::coordinates::Coordinates;
+use crate::differential::Differential;
+use crate::error::PsoError;
+use crate::fitness::{Comparison, Fitness};
+use crate::lineage::{Lineage, LineageEvent};
+use crate::manifold::Manifold;
+use crate::metric::{CustomMetric, Distance, Metric};
+use crate::observer::{Control, Observer};
+use crate::operator::Operator;
+use crate::report::{Language, NumberFormat, Summary, SwarmSummary};
+use crate::robust::RobustSelection;
+use crate::screening::Screening;
+use crate::sensitivity::{self, Sensitivity, SensitivityAnalysis};
+use crate::stability::{self, Condition};
+use crate::topology::Topology;
+use crate::transcript::Transcript;
+use crate::velocity::{Coefficients, VelocityPolicy};
+use crate::warmup::Warmup;
+use crate::zones::{Zone, ZoneHandling};
 use ndarray::Array1;
 use ndarray_rand::rand_distr::Uniform;
 use ndarray_rand::RandomExt;
 use plotters::coord::Shift;
 use plotters::drawing::backend::DrawingBackend;
 use plotters::prelude::*;
-use rand::{thread_rng, Rng};
-use std::fmt::Error as FmtError;
+use rand::{thread_rng, Rng, SeedableRng};
+use rand_pcg::Pcg64;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt;
+use std::mem;
+use std::path::Path;
 
-#[derive(Debug, Clone)]
+/// Size in pixels of the plots written straight to a file.
+pub const PLOT_SIZE: (u32, u32) = (600, 600);
+
+/// Fitness trajectory of a particle and how often it improved its best.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ParticleStats {
+    #[serde(with = "crate::checkpoint::nonfinite::seq")]
+    fitness: Vec<f64>,
+    improvements: usize,
+    last_improvement: Option<usize>,
+}
+
+impl ParticleStats {
+    fn record(&mut self, fitness: f64, improved: bool) {
+        if improved {
+            self.improvements += 1;
+            self.last_improvement = Some(self.fitness.len());
+        }
+
+        self.fitness.push(fitness);
+    }
+
+    /// Fitness of every evaluated position, starting with the initial one.
+    pub fn fitness(&self) -> &[f64] {
+        &self.fitness
+    }
+
+    pub fn improvements(&self) -> usize {
+        self.improvements
+    }
+
+    /// Index in the trajectory of the last improvement of the best.
+    pub fn last_improvement(&self) -> Option<usize> {
+        self.last_improvement
+    }
+
+    /// Whether the last evaluated position improved the best.
+    pub fn improved(&self) -> bool {
+        self.fitness.len().checked_sub(1) == self.last_improvement
+    }
+
+    /// Fraction of the moves that improved the best.
+    pub fn improvement_rate(&self) -> f64 {
+        let moves = self.fitness.len().saturating_sub(1);
+        if moves == 0 {
+            0.0
+        } else {
+            self.improvements as f64 / moves as f64
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Particle {
+    id: usize,
     curr_value: Array1<f64>,
     best_value: Array1<f64>,
+    #[serde(with = "crate::checkpoint::nonfinite")]
+    best_fitness: f64,
     velocity: Array1<f64>,
+    stats: ParticleStats,
+    #[serde(default)]
+    estimated: bool,
+}
+
+impl Particle {
+    /// Position and velocity drawn from the initial distributions.
+    fn sample(
+        dim: usize,
+        value_range: (f64, f64),
+        velocity_range: (f64, f64),
+        rng: &mut impl Rng,
+    ) -> (Array1<f64>, Array1<f64>) {
+        let value_distr = Uniform::new(value_range.0, value_range.1);
+        let velocity_distr = Uniform::new(velocity_range.0, velocity_range.1);
+
+        let value = Array1::random_using((dim,), value_distr, rng);
+        let velocity = Array1::random_using((dim,), velocity_distr, rng);
+        (value, velocity)
+    }
+
+    /// Particle at the given position with the given fitness, which is also
+    /// its best.
+    fn at(id: usize, value: Array1<f64>, velocity: Array1<f64>, fitness: f64) -> Self {
+        let mut stats = ParticleStats::default();
+        stats.record(fitness, false);
+
+        Self {
+            id,
+            curr_value: value.clone(),
+            best_value: value,
+            best_fitness: fitness,
+            velocity,
+            stats,
+            estimated: false,
+        }
+    }
+
+    /// Unique within its swarm and kept across re-initializations.
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    pub fn best(&self) -> &Array1<f64> {
+        &self.best_value
+    }
+
+    pub fn value(&self) -> &Array1<f64> {
+        &self.curr_value
+    }
+
+    pub fn velocity(&self) -> &Array1<f64> {
+        &self.velocity
+    }
+
+    /// Fitness of the current position.
+    pub fn fitness(&self) -> f64 {
+        self.stats.fitness.last().copied().unwrap_or(f64::NAN)
+    }
+
+    pub fn best_fitness(&self) -> f64 {
+        self.best_fitness
+    }
+
+    pub fn stats(&self) -> &ParticleStats {
+        &self.stats
+    }
+
+    /// Whether the fitness of the current position is an estimate, see
+    /// [`Screening`].
+    pub fn is_estimated(&self) -> bool {
+        self.estimated
+    }
+
+    fn update_velocity(
+        &mut self,
+        attractor: &Array1<f64>,
+        rands: &[f64],
+        options: &Options,
+        coefficients: Option<&Coefficients>,
+        policy: VelocityPolicy,
+    ) {
+        policy.update(
+            &self.curr_value,
+            &mut self.velocity,
+            &self.best_value,
+            attractor,
+            rands,
+            options,
+            coefficients,
+        );
+    }
+
+    fn update_value(&mut self) {
+        self.curr_value += &self.velocity;
+    }
+
+    fn update_best(&mut self, fitness: f64, minimization: bool, comparison: Comparison) {
+        self.estimated = false;
+        let improved = if minimization {
+            comparison.improves(-fitness, -self.best_fitness)
+        } else {
+            comparison.improves(fitness, self.best_fitness)
+        };
+
+        if improved {
+            copy_into(&mut self.best_value, &self.curr_value);
+            self.best_fitness = fitness;
+        }
+
+        self.stats.record(fitness, improved);
+    }
+}
+
+impl fmt::Display for Particle {
+    /// `x: .., v: .., fitness: ..`, the precision applies to every number.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match f.precision() {
+            Some(p) => write!(
+                f,
+                "x: {:.*}, v: {:.*}, fitness: {:.*}",
+                p,
+                self.curr_value,
+                p,
+                self.velocity,
+                p,
+                self.fitness()
+            ),
+            None => write!(
+                f,
+                "x: {}, v: {}, fitness: {}",
+                self.curr_value,
+                self.velocity,
+                self.fitness()
+            ),
+        }
+    }
+}
+
+/// Read-only state shared by every particle while moving them on a step.
+struct Movement<'s> {
+    global_best: &'s Array1<f64>,
+    /// Attractor of every particle, empty for the global topology.
+    local_bests: &'s [Array1<f64>],
+    options: &'s Options,
+    coefficients: Option<&'s Coefficients>,
+    velocity_policy: VelocityPolicy,
+    placement: Placement<'s>,
+    /// Particles that move on this step, `None` when all of them do.
+    scheduled: Option<&'s [bool]>,
+}
+
+impl Movement<'_> {
+    fn apply<F>(&self, i: usize, particle: &mut Particle, rands: &[f64], evaluate: F)
+    where
+        F: Fn(&Array1<f64>) -> f64,
+    {
+        if matches!(self.scheduled, Some(scheduled) if !scheduled[i]) {
+            return;
+        }
+
+        let attractor = self.local_bests.get(i).unwrap_or(self.global_best);
+        particle.update_velocity(
+            attractor,
+            rands,
+            self.options,
+            self.coefficients,
+            self.velocity_policy,
+        );
+        self.placement.apply(particle, evaluate);
+    }
+}
+
+/// Moves a particle along its velocity, keeping it on the manifold, inside
+/// the bounds and out of the zones, then evaluates it.
+struct Placement<'s> {
+    confinement: Confinement,
+    manifold: Manifold,
+    value_range: (f64, f64),
+    zones: &'s [Zone],
+    zone_handling: ZoneHandling,
+    constraints: Option<&'s ConstraintHandling>,
+    repair: Option<&'s RepairHook<'s>>,
+    frozen: &'s BTreeMap<usize, f64>,
+    minimization: bool,
+    comparison: Comparison,
+    /// Particles are only moved, they're evaluated afterwards.
+    deferred: bool,
+}
+
+impl Placement<'_> {
+    fn apply<F>(&self, particle: &mut Particle, evaluate: F)
+    where
+        F: Fn(&Array1<f64>) -> f64,
+    {
+        self.manifold
+            .tangent(&particle.curr_value, &mut particle.velocity);
+        particle.update_value();
+        self.fix(&mut particle.curr_value, &mut particle.velocity);
+
+        if self.deferred {
+            return;
+        }
+
+        let penalty = self
+            .confinement
+            .penalty(&particle.curr_value, self.value_range);
+        let fitness = penalize(evaluate(&particle.curr_value), penalty, self.minimization);
+        particle.update_best(fitness, self.minimization, self.comparison);
+    }
+
+    /// Brings a new position back to the manifold, the value range, out of
+    /// the zones and into the constraints, then runs the repair hook and
+    /// pins the frozen dimensions.
+    fn fix(&self, x: &mut Array1<f64>, v: &mut Array1<f64>) {
+        self.manifold.retract(x, v);
+        self.confinement.apply(x, v, self.value_range);
+
+        for zone in self.zones {
+            zone.push_out(x, v, self.zone_handling);
+        }
+
+        if let Some(constraints) = self.constraints {
+            *x = constraints.repair(x);
+        }
+
+        if let Some(repair) = self.repair {
+            repair(x);
+        }
+
+        for (&d, &value) in self.frozen {
+            x[d] = value;
+            v[d] = 0.0;
+        }
+    }
+}
+
+/// Fitness of `value`, with the penalty of a soft wall.
+fn penalized(
+    fitness: &Fitness<Array1<f64>>,
+    confinement: Confinement,
+    value_range: (f64, f64),
+    value: &Array1<f64>,
+) -> f64 {
+    let penalty = confinement.penalty(value, value_range);
+    penalize(fitness.calculate(value), penalty, fitness.is_minimization())
+}
+
+/// `fitness` worsened by `penalty`.
+fn penalize(fitness: f64, penalty: f64, minimization: bool) -> f64 {
+    if penalty == 0.0 {
+        fitness
+    } else if minimization {
+        fitness + penalty
+    } else {
+        fitness - penalty
+    }
+}
+
+/// Checks that a swarm with this shape can be created.
+pub(crate) fn validate_shape(
+    size: usize,
+    dim: usize,
+    value_range: (f64, f64),
+    velocity_range: (f64, f64),
+) -> Result<(), PsoError> {
+    if size == 0 {
+        return Err(PsoError::InvalidOptions(
+            "swarm size must be positive".to_string(),
+        ));
+    }
+
+    if dim == 0 {
+        return Err(PsoError::InvalidOptions(
+            "dim must be at least 1".to_string(),
+        ));
+    }
+
+    validate_range("value", value_range)?;
+    validate_range("velocity", velocity_range)
+}
+
+fn validate_range(name: &str, (min, max): (f64, f64)) -> Result<(), PsoError> {
+    if !min.is_finite() || !max.is_finite() || min >= max {
+        return Err(PsoError::InvalidOptions(format!(
+            "{} range must satisfy min < max, got ({}, {})",
+            name, min, max
+        )));
+    }
+
+    Ok(())
+}
+
+/// Draws the random numbers of every particle, in order, reusing the
+/// buffers of `rands`.
+fn draw_rands(
+    rng: &mut impl Rng,
+    rands: &mut Vec<Vec<f64>>,
+    size: usize,
+    dim: usize,
+    policy: VelocityPolicy,
+) {
+    rands.resize_with(size, Vec::new);
+    for rands in rands.iter_mut() {
+        policy.draw(dim, rng, rands);
+    }
+}
+
+#[cfg(feature = "parallel")]
+mod parallel {
+    use super::{Movement, Particle};
+    use ndarray::Array1;
+    use rand::thread_rng;
+    use rayon::prelude::*;
+
+    /// Moves every particle on rayon's thread pool, with the given random
+    /// coefficients or drawing them on every thread. Returns the ones used.
+    pub(super) fn move_particles(
+        particles: &mut [Particle],
+        movement: &Movement,
+        rands: Option<Vec<Vec<f64>>>,
+        evaluate: &(dyn Fn(&Array1<f64>) -> f64 + Send + Sync),
+    ) -> Vec<Vec<f64>> {
+        match rands {
+            Some(rands) => {
+                particles
+                    .par_iter_mut()
+                    .zip(rands.par_iter())
+                    .enumerate()
+                    .for_each(|(i, (particle, rands))| {
+                        movement.apply(i, particle, rands, evaluate)
+                    });
+
+                rands
+            }
+            None => particles
+                .par_iter_mut()
+                .enumerate()
+                .map(|(i, particle)| {
+                    let dim = particle.curr_value.len();
+                    let mut rands = Vec::new();
+                    movement
+                        .velocity_policy
+                        .draw(dim, &mut thread_rng(), &mut rands);
+                    movement.apply(i, particle, &rands, evaluate);
+                    rands
+                })
+                .collect(),
+        }
+    }
 }
 
-impl Particle {
-    fn new(dim: usize, value_distr: &Uniform<f64>, velocity_distr: &Uniform<f64>) -> Self {
-        let value = Array1::random((dim,), value_distr);
-        let velocity = Array1::random((dim,), velocity_distr);
+/// How particles are moved and evaluated on every step.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Parallelism {
+    #[default]
+    Serial,
+    /// On rayon's thread pool, random numbers are drawn on every thread so
+    /// runs aren't reproducible even with a seed.
+    Parallel,
+    /// On rayon's thread pool, but random numbers are drawn upfront from the
+    /// swarm's RNG, giving exactly the same results as [`Serial`](Self::Serial).
+    Deterministic,
+}
+
+/// What happens to particles leaving the value range.
+#[derive(Debug, Copy, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub enum Confinement {
+    /// Nothing, particles roam freely.
+    #[default]
+    Free,
+    /// Clamped to the value range, reversing and halving the velocity of
+    /// every clamped dimension (SPSO-2011).
+    Bounded,
+    /// Clamped to the value range, stopping every clamped dimension
+    /// (SPSO-2006 and SPSO-2007).
+    Absorbing,
+    /// Bounced off the bound like a ball off a wall: the overshoot is
+    /// mirrored and the velocity reversed, both scaled by `restitution`, from
+    /// 0 (absorbing) to 1 (elastic). Positions still outside after bouncing
+    /// are clamped.
+    Reflecting { restitution: f64 },
+    /// Soft wall: positions aren't confined, but the fitness of particles
+    /// outside worsens by `weight` times their distance to the value range,
+    /// so they may cross it when that pays off. Bests found outside keep
+    /// the penalty in their fitness.
+    Penalized { weight: f64 },
+}
+
+impl Confinement {
+    pub fn validate(&self) -> Result<(), PsoError> {
+        match *self {
+            Confinement::Reflecting { restitution } if !(0.0..=1.0).contains(&restitution) => {
+                Err(PsoError::InvalidOptions(format!(
+                    "restitution must be in [0, 1], got {}",
+                    restitution
+                )))
+            }
+            Confinement::Penalized { weight } if !(weight >= 0.0 && weight.is_finite()) => {
+                Err(PsoError::InvalidOptions(format!(
+                    "penalty weight must be finite and non-negative, got {}",
+                    weight
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Penalty of `value` with a [soft wall](Confinement::Penalized), its
+    /// Euclidean distance to `range` times the weight, 0 for the rest.
+    /// Coordinates that aren't numbers are infinitely far.
+    pub fn penalty(self, value: &Array1<f64>, (min, max): (f64, f64)) -> f64 {
+        let weight = match self {
+            Confinement::Penalized { weight } => weight,
+            _ => return 0.0,
+        };
+
+        let distance = value
+            .iter()
+            .map(|&x| {
+                if x.is_nan() {
+                    f64::INFINITY
+                } else {
+                    (min - x).max(x - max).max(0.0)
+                }
+            })
+            .map(|excess| excess * excess)
+            .sum::<f64>()
+            .sqrt();
+
+        if distance == 0.0 {
+            0.0
+        } else {
+            weight * distance
+        }
+    }
+
+    /// Brings `value` back into `range`, adjusting the velocity of every
+    /// dimension that left it. Coordinates that aren't numbers count as
+    /// outside and are moved to the middle of the range, velocities that
+    /// aren't finite are stopped.
+    pub fn apply(self, value: &mut Array1<f64>, velocity: &mut Array1<f64>, range: (f64, f64)) {
+        let (rebound, restitution) = match self {
+            Confinement::Free | Confinement::Penalized { .. } => return,
+            Confinement::Bounded => (-0.5, 0.0),
+            Confinement::Absorbing => (0.0, 0.0),
+            Confinement::Reflecting { restitution } => (-restitution, restitution),
+        };
+
+        for (x, v) in value.iter_mut().zip(velocity.iter_mut()) {
+            if !(range.0..=range.1).contains(x) {
+                *x = clamp_to(reflect(*x, range, restitution), range);
+                *v *= rebound;
+            }
+            if !v.is_finite() {
+                *v = 0.0;
+            }
+        }
+    }
+}
+
+/// `x` mirrored on the bound of `range` it crossed, its overshoot scaled
+/// by `restitution`.
+fn reflect(x: f64, (min, max): (f64, f64), restitution: f64) -> f64 {
+    if restitution == 0.0 {
+        x
+    } else if x > max {
+        max - restitution * (x - max)
+    } else if x < min {
+        min + restitution * (min - x)
+    } else {
+        x
+    }
+}
+
+/// Copies `src` to `dst`, reusing its buffer when they have the same length.
+fn copy_into(dst: &mut Array1<f64>, src: &Array1<f64>) {
+    if dst.len() == src.len() {
+        dst.assign(src);
+    } else {
+        *dst = src.clone();
+    }
+}
+
+/// Closest value to `x` in `range`, its middle when `x` is NaN.
+pub(crate) fn clamp_to(x: f64, (min, max): (f64, f64)) -> f64 {
+    if x.is_nan() {
+        // Halving first, the width of the range may overflow
+        min / 2.0 + max / 2.0
+    } else {
+        x.clamp(min, max)
+    }
+}
+
+/// How particles move on every step.
+#[derive(Debug, Copy, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub enum Algorithm {
+    /// Attracted to their personal and neighborhood bests, following the
+    /// options, velocity policy and topology of the swarm.
+    #[default]
+    Standard,
+    /// Competitive swarm optimizer (Cheng and Jin, 2015), particles are
+    /// paired at random and the loser of every pair learns from the winner
+    /// and, weighted by `phi`, from the mean position of the swarm. Winners
+    /// don't move, so only half of the swarm is evaluated on every step.
+    Competitive { phi: f64 },
+    /// Social learning PSO (Cheng and Jin, 2015), every particle but the
+    /// best learns, with a probability that grows the worse it ranks, from
+    /// a better demonstrator picked on every dimension and from the mean
+    /// position of the swarm. `alpha` scales the learning probability and
+    /// `beta` the pull of the mean.
+    SocialLearning { alpha: f64, beta: f64 },
+}
+
+impl Algorithm {
+    /// SL-PSO with the parameters of the paper, `alpha = 0.5` and
+    /// `beta = 0.01`.
+    pub fn social_learning() -> Self {
+        Algorithm::SocialLearning {
+            alpha: 0.5,
+            beta: 0.01,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), PsoError> {
+        let params: &[(&str, f64)] = match self {
+            Algorithm::Standard => &[],
+            Algorithm::Competitive { phi } => &[("phi", *phi)],
+            Algorithm::SocialLearning { alpha, beta } => &[("alpha", *alpha), ("beta", *beta)],
+        };
+
+        for (name, value) in params {
+            if !value.is_finite() || *value < 0.0 {
+                return Err(PsoError::InvalidOptions(format!(
+                    "{} must be finite and non-negative, got {}",
+                    name, value
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Options {
+    pub omega: Option<f64>,
+    pub phi_1: f64,
+    pub phi_2: f64,
+}
+
+/// Which attraction terms of the velocity update are active.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Model {
+    /// Both the cognitive (`phi_1`) and the social (`phi_2`) terms.
+    Full,
+    /// Only the cognitive term (`phi_2 = 0`), particles never share information.
+    CognitionOnly,
+    /// Only the social term (`phi_1 = 0`), particles ignore their own best.
+    SocialOnly,
+    /// Neither term, particles just drift with their (damped) velocity.
+    InertiaOnly,
+}
+
+impl Options {
+    /// Inertia weight equivalent of Clerc's constriction coefficients
+    /// (`chi = 0.72984`, `c_1 = c_2 = 2.05`).
+    pub fn canonical() -> Self {
+        Self {
+            omega: Some(0.72984),
+            phi_1: 1.49618,
+            phi_2: 1.49618,
+        }
+    }
+
+    /// SPSO-2011 parameters, `ω = 1 / (2 ln 2)` and `φ1 = φ2 = 0.5 + ln 2`.
+    pub fn spso_2011() -> Self {
+        let ln_2 = std::f64::consts::LN_2;
+
+        Self {
+            omega: Some(1.0 / (2.0 * ln_2)),
+            phi_1: 0.5 + ln_2,
+            phi_2: 0.5 + ln_2,
+        }
+    }
+
+    /// Cognition-only model, every particle behaves as an independent hill climber.
+    pub fn cognition_only(omega: Option<f64>, phi_1: f64) -> Self {
+        Self {
+            omega,
+            phi_1,
+            phi_2: 0.0,
+        }
+    }
+
+    /// Social-only model, every particle is only attracted to the global best.
+    pub fn social_only(omega: Option<f64>, phi_2: f64) -> Self {
+        Self {
+            omega,
+            phi_1: 0.0,
+            phi_2,
+        }
+    }
+
+    /// Checks the coefficients, reporting the first violated rule.
+    ///
+    /// Stability follows the order-1 condition `phi_1 + phi_2 < 4 (1 + omega)`,
+    /// when omega is generated on each iteration its mean (0.5) is used.
+    pub fn validate(&self) -> Result<(), PsoError> {
+        if let Some(omega) = self.omega {
+            if !(0.0..=1.2).contains(&omega) {
+                return Err(PsoError::InvalidOptions(format!(
+                    "omega must be in [0, 1.2], got {}",
+                    omega
+                )));
+            }
+        }
+
+        for (name, phi) in &[("phi_1", self.phi_1), ("phi_2", self.phi_2)] {
+            if !phi.is_finite() || *phi < 0.0 {
+                return Err(PsoError::InvalidOptions(format!(
+                    "{} must be finite and non-negative, got {}",
+                    name, phi
+                )));
+            }
+        }
+
+        let omega = self.omega.unwrap_or(0.5);
+        let phi = self.phi_1 + self.phi_2;
+        if let Some((_, max)) = stability::phi_range(omega, Condition::Order1) {
+            if phi >= max {
+                return Err(PsoError::InvalidOptions(format!(
+                    "phi_1 + phi_2 must be less than 4 (1 + omega) = {} for stability, got {}",
+                    max, phi
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn model(&self) -> Model {
+        match (self.phi_1 != 0.0, self.phi_2 != 0.0) {
+            (true, true) => Model::Full,
+            (true, false) => Model::CognitionOnly,
+            (false, true) => Model::SocialOnly,
+            (false, false) => Model::InertiaOnly,
+        }
+    }
+
+    /// Known pitfalls of the configuration, empty for the full model.
+    pub fn warnings(&self) -> Vec<&'static str> {
+        let mut warnings = match self.model() {
+            Model::Full => vec![],
+            Model::CognitionOnly => vec![
+                "cognition-only model: particles don't share information, \
+                 the global best is never used to guide the search",
+            ],
+            Model::SocialOnly => vec![
+                "social-only model: the swarm is prone to premature convergence \
+                 around the first global best found",
+            ],
+            Model::InertiaOnly => vec![
+                "phi_1 and phi_2 are both zero: particles are not attracted \
+                 to any best and the search is a random walk",
+            ],
+        };
+
+        if matches!(self.omega, Some(omega) if omega >= 1.0) {
+            warnings.push("omega >= 1: velocities never decay and the swarm won't converge");
+        }
+
+        warnings
+    }
+}
+
+/// How to pick the global best among particles with the same fitness.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TieBreak {
+    /// The one with the lowest index.
+    #[default]
+    First,
+    /// A random one, drawn from the swarm's RNG.
+    Random,
+    /// The one closest to the previous global best.
+    ClosestToPrevious,
+    /// The one closest to the centroid of the swarm.
+    MostCentral,
+}
+
+/// Everything about a swarm but its fitness function, see
+/// [`Particles::state`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwarmState {
+    particles: Vec<Particle>,
+    global_best: Array1<f64>,
+    #[serde(with = "crate::checkpoint::nonfinite")]
+    global_best_fitness: f64,
+    value_range: (f64, f64),
+    velocity_range: (f64, f64),
+    omega: Option<f64>,
+    tie_break: TieBreak,
+    rng: Pcg64,
+    seed: Option<u64>,
+    zones: Vec<Zone>,
+    zone_handling: ZoneHandling,
+    constraints: Option<ConstraintHandling>,
+    parallelism: Parallelism,
+    topology: Topology,
+    neighborhoods: Option<Vec<Vec<usize>>>,
+    algorithm: Algorithm,
+    velocity_policy: VelocityPolicy,
+    coefficients: Option<Coefficients>,
+    confinement: Confinement,
+    manifold: Manifold,
+    operators: Vec<Operator>,
+    stagnant_steps: usize,
+    #[serde(with = "crate::checkpoint::nonfinite::evaluations")]
+    memory: VecDeque<(Array1<f64>, f64)>,
+    evaluation_budget: Option<usize>,
+    evaluations_used: usize,
+    priority: Priority,
+    metric: Metric,
+    comparison: Comparison,
+    normalization: Option<Coordinates>,
+    frozen: BTreeMap<usize, f64>,
+    archive: Option<Archive>,
+    warmup: Option<Warmup>,
+    screening: Option<Screening>,
+    sensitivity_analysis: Option<SensitivityAnalysis>,
+    robust: Option<RobustSelection>,
+    language: Language,
+    number_format: NumberFormat,
+    verbose: bool,
+    next_id: usize,
+    steps: usize,
+    lineage: Vec<Lineage>,
+}
+
+impl SwarmState {
+    pub fn particles(&self) -> &[Particle] {
+        &self.particles
+    }
+
+    pub fn best(&self) -> &Array1<f64> {
+        &self.global_best
+    }
+
+    pub fn best_fitness(&self) -> f64 {
+        self.global_best_fitness
+    }
+
+    /// Steps done when the snapshot was taken.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+}
+
+/// Buffers reused by every step instead of allocating them again, they
+/// aren't part of the state.
+#[derive(Debug, Clone, Default)]
+struct Scratch {
+    rands: Vec<Vec<f64>>,
+    local_bests: Vec<Array1<f64>>,
+    ties: Vec<usize>,
+}
+
+pub struct Particles<'a> {
+    particles: Vec<Particle>,
+    fitness: Fitness<'a, Array1<f64>>,
+    global_best: Array1<f64>,
+    global_best_fitness: f64,
+    value_range: (f64, f64),
+    velocity_range: (f64, f64),
+    omega: Option<f64>,
+    tie_break: TieBreak,
+    rng: Pcg64,
+    seed: Option<u64>,
+    zones: Vec<Zone>,
+    zone_handling: ZoneHandling,
+    constraints: Option<ConstraintHandling>,
+    repair: Option<RepairHook<'a>>,
+    custom_metric: Option<CustomMetric<'a>>,
+    transcript: Option<Transcript>,
+    parallelism: Parallelism,
+    topology: Topology,
+    neighborhoods: Option<Vec<Vec<usize>>>,
+    algorithm: Algorithm,
+    velocity_policy: VelocityPolicy,
+    coefficients: Option<Coefficients>,
+    confinement: Confinement,
+    manifold: Manifold,
+    operators: Vec<Operator>,
+    stagnant_steps: usize,
+    memory: VecDeque<(Array1<f64>, f64)>,
+    evaluation_budget: Option<usize>,
+    evaluations_used: usize,
+    priority: Priority,
+    metric: Metric,
+    comparison: Comparison,
+    normalization: Option<Coordinates>,
+    frozen: BTreeMap<usize, f64>,
+    archive: Option<Archive>,
+    warmup: Option<Warmup>,
+    screening: Option<Screening>,
+    sensitivity_analysis: Option<SensitivityAnalysis>,
+    robust: Option<RobustSelection>,
+    language: Language,
+    number_format: NumberFormat,
+    verbose: bool,
+    next_id: usize,
+    steps: usize,
+    lineage: Vec<Lineage>,
+    scratch: Scratch,
+}
+
+impl<'a> Particles<'a> {
+    /// Creates `size` particles with positions and velocities sampled
+    /// uniformly from the given `(min, max)` ranges.
+    ///
+    /// # Panics
+    ///
+    /// On an empty swarm, no dimensions or an empty range, see
+    /// [`try_new`](Self::try_new).
+    pub fn new(
+        size: usize,
+        dim: usize,
+        value_range: (f64, f64),
+        velocity_range: (f64, f64),
+        fitness: Fitness<'a, Array1<f64>>,
+    ) -> Self {
+        Self::try_new(size, dim, value_range, velocity_range, fitness)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Same as [`new`](Self::new), but degenerate swarms are an error.
+    pub fn try_new(
+        size: usize,
+        dim: usize,
+        value_range: (f64, f64),
+        velocity_range: (f64, f64),
+        fitness: Fitness<'a, Array1<f64>>,
+    ) -> Result<Self, PsoError> {
+        validate_shape(size, dim, value_range, velocity_range)?;
+
+        let rng = Pcg64::from_rng(thread_rng()).expect("Couldn't seed the RNG");
+        Ok(Self::with_rng(
+            size,
+            dim,
+            value_range,
+            velocity_range,
+            fitness,
+            rng,
+        ))
+    }
+
+    /// Same as [`new`](Self::new), but every random number is drawn from a
+    /// RNG seeded with `seed`, making runs reproducible.
+    ///
+    /// # Panics
+    ///
+    /// Same as [`new`](Self::new), see [`try_with_seed`](Self::try_with_seed).
+    pub fn with_seed(
+        size: usize,
+        dim: usize,
+        value_range: (f64, f64),
+        velocity_range: (f64, f64),
+        fitness: Fitness<'a, Array1<f64>>,
+        seed: u64,
+    ) -> Self {
+        Self::try_with_seed(size, dim, value_range, velocity_range, fitness, seed)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Same as [`with_seed`](Self::with_seed), but degenerate swarms are an
+    /// error.
+    pub fn try_with_seed(
+        size: usize,
+        dim: usize,
+        value_range: (f64, f64),
+        velocity_range: (f64, f64),
+        fitness: Fitness<'a, Array1<f64>>,
+        seed: u64,
+    ) -> Result<Self, PsoError> {
+        validate_shape(size, dim, value_range, velocity_range)?;
+
+        let rng = Pcg64::seed_from_u64(seed);
+        let mut particles = Self::with_rng(size, dim, value_range, velocity_range, fitness, rng);
+        particles.seed = Some(seed);
+        Ok(particles)
+    }
+
+    fn with_rng(
+        size: usize,
+        dim: usize,
+        value_range: (f64, f64),
+        velocity_range: (f64, f64),
+        fitness: Fitness<'a, Array1<f64>>,
+        mut rng: Pcg64,
+    ) -> Self {
+        let particles: Vec<_> = (0..size)
+            // Not confined yet, and drawn inside the range anyway
+            .map(|id| {
+                let (value, velocity) =
+                    Particle::sample(dim, value_range, velocity_range, &mut rng);
+                let fitness = fitness.calculate(&value);
+                Particle::at(id, value, velocity, fitness)
+            })
+            .collect();
+
+        let mut particles = Self {
+            particles,
+            fitness,
+            global_best: Array1::zeros(dim),
+            global_best_fitness: f64::NAN,
+            value_range,
+            velocity_range,
+            omega: None,
+            tie_break: TieBreak::default(),
+            rng,
+            seed: None,
+            zones: Vec::new(),
+            zone_handling: ZoneHandling::default(),
+            constraints: None,
+            repair: None,
+            custom_metric: None,
+            transcript: None,
+            parallelism: Parallelism::default(),
+            topology: Topology::default(),
+            neighborhoods: None,
+            algorithm: Algorithm::default(),
+            velocity_policy: VelocityPolicy::default(),
+            coefficients: None,
+            confinement: Confinement::default(),
+            manifold: Manifold::default(),
+            operators: Vec::new(),
+            stagnant_steps: 0,
+            memory: VecDeque::new(),
+            evaluation_budget: None,
+            evaluations_used: size,
+            priority: Priority::default(),
+            metric: Metric::default(),
+            comparison: Comparison::default(),
+            normalization: None,
+            frozen: BTreeMap::new(),
+            archive: None,
+            warmup: None,
+            screening: None,
+            sensitivity_analysis: None,
+            robust: None,
+            language: Language::default(),
+            number_format: NumberFormat::default(),
+            verbose: true,
+            next_id: size,
+            steps: 0,
+            lineage: Vec::new(),
+            scratch: Scratch::default(),
+        };
+
+        // The comparison isn't configured yet, a NaN fitness can't panic
+        // before it is
+        particles.comparison = Comparison::Total;
+        let best = particles
+            .best_particle(false)
+            .expect("No particles were created");
+        particles.set_global_best(best);
+        particles.comparison = Comparison::default();
+        particles
+    }
+
+    /// Snapshot of the swarm, including its RNG, so a run can be resumed
+    /// exactly with [`from_state`](Self::from_state).
+    pub fn state(&self) -> SwarmState {
+        SwarmState {
+            particles: self.particles.clone(),
+            global_best: self.global_best.clone(),
+            global_best_fitness: self.global_best_fitness,
+            value_range: self.value_range,
+            velocity_range: self.velocity_range,
+            omega: self.omega,
+            tie_break: self.tie_break,
+            rng: self.rng.clone(),
+            seed: self.seed,
+            zones: self.zones.clone(),
+            zone_handling: self.zone_handling,
+            constraints: self.constraints.clone(),
+            parallelism: self.parallelism,
+            topology: self.topology,
+            neighborhoods: self.neighborhoods.clone(),
+            algorithm: self.algorithm,
+            velocity_policy: self.velocity_policy,
+            coefficients: self.coefficients.clone(),
+            confinement: self.confinement,
+            manifold: self.manifold,
+            operators: self.operators.clone(),
+            stagnant_steps: self.stagnant_steps,
+            memory: self.memory.clone(),
+            evaluation_budget: self.evaluation_budget,
+            evaluations_used: self.evaluations_used,
+            priority: self.priority,
+            metric: self.metric,
+            comparison: self.comparison,
+            normalization: self.normalization.clone(),
+            frozen: self.frozen.clone(),
+            archive: self.archive.clone(),
+            warmup: self.warmup,
+            screening: self.screening,
+            sensitivity_analysis: self.sensitivity_analysis,
+            robust: self.robust,
+            language: self.language,
+            number_format: self.number_format,
+            verbose: self.verbose,
+            next_id: self.next_id,
+            steps: self.steps,
+            lineage: self.lineage.clone(),
+        }
+    }
+
+    /// Restores a swarm from a snapshot, the fitness must be the same one
+    /// used when the snapshot was taken. Repair hooks, custom metrics and
+    /// transcripts aren't part of the snapshot and have to be set again.
+    /// Parallel snapshots restored with a fitness that isn't shared are
+    /// evaluated serially.
+    pub fn from_state(state: SwarmState, fitness: Fitness<'a, Array1<f64>>) -> Self {
+        let parallelism = if fitness.is_shared() {
+            state.parallelism
+        } else {
+            Parallelism::Serial
+        };
+
+        Self {
+            particles: state.particles,
+            fitness,
+            global_best: state.global_best,
+            global_best_fitness: state.global_best_fitness,
+            value_range: state.value_range,
+            velocity_range: state.velocity_range,
+            omega: state.omega,
+            tie_break: state.tie_break,
+            rng: state.rng,
+            seed: state.seed,
+            zones: state.zones,
+            zone_handling: state.zone_handling,
+            constraints: state.constraints,
+            repair: None,
+            custom_metric: None,
+            transcript: None,
+            parallelism,
+            topology: state.topology,
+            neighborhoods: state.neighborhoods,
+            algorithm: state.algorithm,
+            velocity_policy: state.velocity_policy,
+            coefficients: state.coefficients,
+            confinement: state.confinement,
+            manifold: state.manifold,
+            operators: state.operators,
+            stagnant_steps: state.stagnant_steps,
+            memory: state.memory,
+            evaluation_budget: state.evaluation_budget,
+            evaluations_used: state.evaluations_used,
+            priority: state.priority,
+            metric: state.metric,
+            comparison: state.comparison,
+            normalization: state.normalization,
+            frozen: state.frozen,
+            archive: state.archive,
+            warmup: state.warmup,
+            screening: state.screening,
+            sensitivity_analysis: state.sensitivity_analysis,
+            robust: state.robust,
+            language: state.language,
+            number_format: state.number_format,
+            verbose: state.verbose,
+            next_id: state.next_id,
+            steps: state.steps,
+            lineage: state.lineage,
+            scratch: Scratch::default(),
+        }
+    }
+
+    pub fn parallelism(&self) -> Parallelism {
+        self.parallelism
+    }
+
+    /// Fails if the fitness isn't [shared](Fitness::shared) or the crate was
+    /// built without the `parallel` feature.
+    pub fn set_parallelism(&mut self, parallelism: Parallelism) -> Result<(), PsoError> {
+        if parallelism != Parallelism::Serial {
+            if cfg!(not(feature = "parallel")) {
+                return Err(PsoError::InvalidOptions(
+                    "parallel evaluation needs the `parallel` feature".to_string(),
+                ));
+            }
+
+            if !self.fitness.is_shared() {
+                return Err(PsoError::InvalidOptions(
+                    "parallel evaluation needs a shared fitness".to_string(),
+                ));
+            }
+        }
+
+        self.parallelism = parallelism;
+        Ok(())
+    }
+
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    /// Fails if the topology doesn't fit the swarm size.
+    pub fn set_topology(&mut self, topology: Topology) -> Result<(), PsoError> {
+        topology.validate(self.particles.len())?;
+
+        self.topology = topology;
+        self.neighborhoods = topology.neighborhoods(self.particles.len(), &mut self.rng);
+        Ok(())
+    }
+
+    /// Neighborhood of every particle, including itself, `None` for the
+    /// global topology.
+    pub fn neighborhoods(&self) -> Option<&[Vec<usize>]> {
+        self.neighborhoods.as_deref()
+    }
+
+    /// Copies the best personal best in the neighborhood of every particle
+    /// to the scratch buffers, none for the global topology.
+    fn update_local_bests(&mut self) {
+        let mut local_bests = mem::take(&mut self.scratch.local_bests);
+        let neighborhoods = match &self.neighborhoods {
+            Some(neighborhoods) => neighborhoods,
+            None => {
+                local_bests.clear();
+                self.scratch.local_bests = local_bests;
+                return;
+            }
+        };
+
+        local_bests.truncate(neighborhoods.len());
+        for (i, neighbors) in neighborhoods.iter().enumerate() {
+            let best = neighbors
+                .iter()
+                .map(|&j| &self.particles[j])
+                .fold(None, |best: Option<&Particle>, particle| match best {
+                    Some(best) if !self.improves(particle.best_fitness, best.best_fitness) => {
+                        Some(best)
+                    }
+                    _ => Some(particle),
+                })
+                .expect("Empty neighborhood");
+
+            match local_bests.get_mut(i) {
+                Some(local) => copy_into(local, &best.best_value),
+                None => local_bests.push(best.best_value.clone()),
+            }
+        }
+
+        self.scratch.local_bests = local_bests;
+    }
+
+    pub fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    /// Changes how particles move from the next step on.
+    pub fn set_algorithm(&mut self, algorithm: Algorithm) -> Result<(), PsoError> {
+        algorithm.validate()?;
+        self.algorithm = algorithm;
+        Ok(())
+    }
+
+    pub fn velocity_policy(&self) -> VelocityPolicy {
+        self.velocity_policy
+    }
+
+    pub fn set_velocity_policy(&mut self, policy: VelocityPolicy) {
+        self.velocity_policy = policy;
+    }
+
+    pub fn coefficients(&self) -> Option<&Coefficients> {
+        self.coefficients.as_ref()
+    }
+
+    /// Per-dimension coefficients used instead of the scalar ones of the
+    /// options on every step, `None` goes back to the scalar ones.
+    pub fn set_coefficients(&mut self, coefficients: Option<Coefficients>) -> Result<(), PsoError> {
+        if let Some(coefficients) = &coefficients {
+            coefficients.validate(self.global_best.len())?;
+        }
+
+        self.coefficients = coefficients;
+        Ok(())
+    }
+
+    pub fn value_range(&self) -> (f64, f64) {
+        self.value_range
+    }
+
+    /// Changes the range new positions are sampled from (and confined to,
+    /// when bounded). Particles whose position or best falls outside are
+    /// clamped into it, evaluated again and their bests reset. If the
+    /// global best falls outside too, it's replaced by the best personal
+    /// best.
+    pub fn set_value_range(&mut self, min: f64, max: f64) -> Result<(), PsoError> {
+        validate_range("value", (min, max))?;
+        self.value_range = (min, max);
+
+        let inside = |x: &Array1<f64>| x.iter().all(|v| (min..=max).contains(v));
+        for i in 0..self.particles.len() {
+            let particle = &self.particles[i];
+            if inside(particle.value()) && inside(particle.best()) {
+                continue;
+            }
+
+            let value = particle.value().mapv(|v| v.clamp(min, max));
+            let velocity = particle.velocity.clone();
+            self.particles[i] = self.particle_at(particle.id, value, velocity);
+            self.transcribe(i);
+            self.log(i, LineageEvent::Mutated);
+        }
+
+        if !inside(&self.global_best) {
+            let best = self
+                .particles
+                .iter()
+                .max_by(|a, b| self.compare(a.best_fitness, b.best_fitness))
+                .expect("Population is empty");
+
+            self.global_best = best.best_value.clone();
+            self.global_best_fitness = best.best_fitness;
+        }
+
+        Ok(())
+    }
+
+    pub fn velocity_range(&self) -> (f64, f64) {
+        self.velocity_range
+    }
+
+    /// Changes the range new velocities are sampled from, current ones are
+    /// kept.
+    pub fn set_velocity_range(&mut self, min: f64, max: f64) -> Result<(), PsoError> {
+        validate_range("velocity", (min, max))?;
+        self.velocity_range = (min, max);
+        Ok(())
+    }
+
+    pub fn confinement(&self) -> Confinement {
+        self.confinement
+    }
+
+    pub fn set_confinement(&mut self, confinement: Confinement) -> Result<(), PsoError> {
+        confinement.validate()?;
+        self.confinement = confinement;
+        Ok(())
+    }
+
+    pub fn manifold(&self) -> Manifold {
+        self.manifold
+    }
+
+    /// Sets the space positions live in, the current particles are moved
+    /// onto it right away and the global best is recomputed from them.
+    pub fn set_manifold(&mut self, manifold: Manifold) {
+        self.manifold = manifold;
+
+        for i in 0..self.particles.len() {
+            let particle = &self.particles[i];
+            let mut value = particle.value().clone();
+            let mut velocity = particle.velocity.clone();
+            manifold.retract(&mut value, &mut velocity);
+
+            self.particles[i] = self.particle_at(particle.id, value, velocity);
+        }
+
+        self.transcribe_all();
+        let best = self.best_particle(false).expect("Population is empty");
+        self.set_global_best(best);
+    }
+
+    /// Mapping from the unit hypercube the swarm moves in to the original
+    /// bounds, see [`SwarmBuilder::normalize`](crate::builder::SwarmBuilder::normalize).
+    pub fn normalization(&self) -> Option<&Coordinates> {
+        self.normalization.as_ref()
+    }
+
+    pub(crate) fn set_normalization(&mut self, normalization: Option<Coordinates>) {
+        self.normalization = normalization;
+    }
+
+    /// Original value of a position of the swarm, e.g. the best, it's the
+    /// same position when the swarm isn't normalized.
+    pub fn denormalize(&self, x: &Array1<f64>) -> Array1<f64> {
+        match &self.normalization {
+            Some(coordinates) => coordinates.decode(x),
+            None => x.clone(),
+        }
+    }
+
+    /// Dimensions locked to a fixed value, see [`freeze`](Self::freeze).
+    pub fn frozen(&self) -> &BTreeMap<usize, f64> {
+        &self.frozen
+    }
+
+    /// Locks the dimension `d` to `value` until it's unfrozen: every
+    /// particle is moved there with no velocity on it, so only the other
+    /// dimensions are searched. Particles are evaluated again and their
+    /// bests reset, as the problem changed.
+    pub fn freeze(&mut self, d: usize, value: f64) -> Result<(), PsoError> {
+        let dim = self.global_best.len();
+        if d >= dim {
+            return Err(PsoError::InvalidOptions(format!(
+                "can't freeze dimension {} of {}",
+                d, dim
+            )));
+        }
+
+        if !value.is_finite() {
+            return Err(PsoError::InvalidOptions(format!(
+                "frozen value must be finite, got {}",
+                value
+            )));
+        }
+
+        self.frozen.insert(d, value);
+        for i in 0..self.particles.len() {
+            let particle = &self.particles[i];
+            let mut position = particle.curr_value.clone();
+            let mut velocity = particle.velocity.clone();
+            position[d] = value;
+            velocity[d] = 0.0;
+
+            self.particles[i] = self.particle_at(particle.id, position, velocity);
+        }
+
+        self.transcribe_all();
+        let best = self.best_particle(false).expect("Population is empty");
+        self.set_global_best(best);
+        Ok(())
+    }
+
+    /// Releases the dimension `d`, its velocities are sampled again from the
+    /// initial range, since every particle agrees on it. Returns the value
+    /// it was locked to.
+    pub fn unfreeze(&mut self, d: usize) -> Option<f64> {
+        let value = self.frozen.remove(&d)?;
+
+        let (min, max) = self.velocity_range;
+        for particle in &mut self.particles {
+            particle.velocity[d] = self.rng.gen_range(min, max);
+        }
+
+        Some(value)
+    }
+
+    pub fn metric(&self) -> Metric {
+        self.metric
+    }
+
+    /// Metric used for duplicate detection, diversity and tie breaks,
+    /// Euclidean by default. Clears any custom metric.
+    pub fn set_metric(&mut self, metric: Metric) {
+        self.metric = metric;
+        self.custom_metric = None;
+        if let Some(archive) = self.archive.take() {
+            self.archive = Some(archive.with_metric(metric));
+        }
+    }
+
+    /// Same as [`set_metric`](Self::set_metric), with a user defined
+    /// metric.
+    pub fn set_custom_metric<F>(&mut self, metric: F)
+    where
+        F: Fn(&Array1<f64>, &Array1<f64>) -> f64 + Send + Sync + 'a,
+    {
+        self.custom_metric = Some(Box::new(metric));
+    }
+
+    pub fn has_custom_metric(&self) -> bool {
+        self.custom_metric.is_some()
+    }
+
+    /// Distance between two positions with the swarm's metric.
+    pub fn distance(&self, a: &Array1<f64>, b: &Array1<f64>) -> f64 {
+        match &self.custom_metric {
+            Some(metric) => metric(a, b),
+            None => self.metric.distance(a, b),
+        }
+    }
+
+    pub fn comparison(&self) -> Comparison {
+        self.comparison
+    }
+
+    /// How fitness values are ranked everywhere the swarm compares them,
+    /// partial by default. With [`Comparison::Total`] NaN fitness is just
+    /// the worst instead of a panic, and results agree bit for bit across
+    /// platforms.
+    pub fn set_comparison(&mut self, comparison: Comparison) {
+        self.comparison = comparison;
+    }
+
+    /// Orders two fitness values, the better one is greater whatever the
+    /// direction of the optimization.
+    pub fn compare(&self, a: f64, b: f64) -> Ordering {
+        let fitness = &self.fitness;
+        self.comparison
+            .compare(fitness.to_maximization(a), fitness.to_maximization(b))
+    }
+
+    /// Whether `fitness` is strictly better than `best`.
+    pub(crate) fn improves(&self, fitness: f64, best: f64) -> bool {
+        let direction = &self.fitness;
+        self.comparison.improves(
+            direction.to_maximization(fitness),
+            direction.to_maximization(best),
+        )
+    }
+
+    /// Pipeline applied, in order, after the particles moved on every step.
+    pub fn operators(&self) -> &[Operator] {
+        &self.operators
+    }
+
+    /// Replaces the pipeline, which is left untouched if any operator is
+    /// invalid.
+    pub fn set_operators(&mut self, operators: Vec<Operator>) -> Result<(), PsoError> {
+        for operator in &operators {
+            operator.validate()?;
+        }
+
+        self.operators = operators;
+        self.resize_memory();
+        Ok(())
+    }
+
+    /// Appends an operator at the end of the pipeline.
+    pub fn add_operator(&mut self, operator: Operator) -> Result<(), PsoError> {
+        operator.validate()?;
+        self.operators.push(operator);
+        self.resize_memory();
+        Ok(())
+    }
+
+    /// Latest evaluations, oldest first, kept as long as the pipeline has
+    /// an operator that needs them, see [`Operator::memory`].
+    pub fn evaluations(&self) -> &VecDeque<(Array1<f64>, f64)> {
+        &self.memory
+    }
+
+    fn memory_capacity(&self) -> usize {
+        let screening = self.screening.map_or(0, |screening| screening.memory());
+        self.operators
+            .iter()
+            .map(Operator::memory)
+            .fold(screening, usize::max)
+    }
+
+    /// Drops the evaluations the pipeline doesn't need anymore, a memory
+    /// that was empty starts with the current positions.
+    fn resize_memory(&mut self) {
+        let capacity = self.memory_capacity();
+        if capacity > 0 && self.memory.is_empty() {
+            for particle in &self.particles {
+                self.memory
+                    .push_back((particle.curr_value.clone(), particle.fitness()));
+            }
+        }
+
+        while self.memory.len() > capacity {
+            self.memory.pop_front();
+        }
+    }
+
+    /// Keeps an evaluation in the memory, if there's one.
+    fn remember(&mut self, value: &Array1<f64>, fitness: f64) {
+        let capacity = self.memory_capacity();
+        if capacity == 0 {
+            return;
+        }
+
+        if self.memory.len() == capacity {
+            self.memory.pop_front();
+        }
+        self.memory.push_back((value.clone(), fitness));
+    }
+
+    /// Replaces the first operator of the pipeline that `matches` with
+    /// `operator`, appending it if there's none. Every matching operator is
+    /// removed when `operator` is `None`.
+    fn replace_operator(&mut self, matches: fn(&Operator) -> bool, operator: Option<Operator>) {
+        match (operator, self.operators.iter().position(matches)) {
+            (Some(operator), Some(i)) => self.operators[i] = operator,
+            (Some(operator), None) => self.operators.push(operator),
+            (None, _) => self.operators.retain(|operator| !matches(operator)),
+        }
+    }
+
+    /// The differential evolution operator of the pipeline, if any.
+    pub fn differential(&self) -> Option<Differential> {
+        self.operators.iter().find_map(|operator| match operator {
+            Operator::Differential(differential) => Some(*differential),
+            _ => None,
+        })
+    }
+
+    /// Sets the differential evolution operator of the pipeline, see
+    /// [`apply_differential`](Self::apply_differential).
+    pub fn set_differential(&mut self, differential: Option<Differential>) -> Result<(), PsoError> {
+        if let Some(differential) = &differential {
+            differential.validate()?;
+        }
+
+        self.replace_operator(
+            |operator| matches!(operator, Operator::Differential(_)),
+            differential.map(Operator::Differential),
+        );
+        self.resize_memory();
+        Ok(())
+    }
+
+    /// Tolerance of the deduplication operator of the pipeline, if any.
+    pub fn deduplication(&self) -> Option<f64> {
+        self.operators.iter().find_map(|operator| match operator {
+            Operator::Deduplication { tolerance } => Some(*tolerance),
+            _ => None,
+        })
+    }
+
+    /// When set, particles that collapse within this distance of a better
+    /// one are re-dispersed after every step, see
+    /// [`disperse_duplicates`](Self::disperse_duplicates).
+    pub fn set_deduplication(&mut self, tolerance: Option<f64>) {
+        self.replace_operator(
+            |operator| matches!(operator, Operator::Deduplication { .. }),
+            tolerance.map(|tolerance| Operator::Deduplication { tolerance }),
+        );
+        self.resize_memory();
+    }
+
+    pub fn warmup(&self) -> Option<Warmup> {
+        self.warmup
+    }
+
+    /// Explores with a fixed inertia and resampling the worst particles
+    /// during the first steps, see [`Warmup`]. Steps already done count.
+    pub fn set_warmup(&mut self, warmup: Option<Warmup>) -> Result<(), PsoError> {
+        if let Some(warmup) = &warmup {
+            warmup.validate()?;
+        }
+
+        self.warmup = warmup;
+        Ok(())
+    }
+
+    pub fn screening(&self) -> Option<Screening> {
+        self.screening
+    }
+
+    /// Evaluates only the most promising particles on every step and
+    /// estimates the fitness of the rest, see [`Screening`].
+    pub fn set_screening(&mut self, screening: Option<Screening>) -> Result<(), PsoError> {
+        if let Some(screening) = &screening {
+            screening.validate()?;
+        }
+
+        self.screening = screening;
+        self.resize_memory();
+        Ok(())
+    }
+
+    pub fn sensitivity_analysis(&self) -> Option<SensitivityAnalysis> {
+        self.sensitivity_analysis
+    }
+
+    /// Analyzes the sensitivity of the best reported by
+    /// [`optimize_for`](Self::optimize_for), see [`sensitivity`](Self::sensitivity).
+    pub fn set_sensitivity_analysis(
+        &mut self,
+        analysis: Option<SensitivityAnalysis>,
+    ) -> Result<(), PsoError> {
+        if let Some(analysis) = &analysis {
+            analysis.validate()?;
+        }
+
+        self.sensitivity_analysis = analysis;
+        Ok(())
+    }
+
+    pub fn robust(&self) -> Option<RobustSelection> {
+        self.robust
+    }
+
+    /// Picks the best reported by [`optimize_for`](Self::optimize_for) by
+    /// re-evaluation, see [`robust_best`](Self::robust_best).
+    pub fn set_robust(&mut self, robust: Option<RobustSelection>) -> Result<(), PsoError> {
+        if let Some(robust) = &robust {
+            robust.validate()?;
+        }
+
+        self.robust = robust;
+        Ok(())
+    }
+
+    pub fn evaluation_budget(&self) -> Option<usize> {
+        self.evaluation_budget
+    }
+
+    /// Maximum amount of fitness evaluations, counting the ones of the
+    /// initial swarm. A step that can't afford every particle only moves
+    /// the first ones by [`Priority`], and once the budget is spent steps do
+    /// nothing and runs stop. Operators are skipped when it's spent, but one
+    /// already running may go over it.
+    pub fn set_evaluation_budget(&mut self, budget: Option<usize>) {
+        self.evaluation_budget = budget;
+    }
+
+    /// Reserves room in the fitness trajectory of every particle for `steps`
+    /// more steps, so it doesn't grow while stepping.
+    pub fn reserve_steps(&mut self, steps: usize) {
+        for particle in &mut self.particles {
+            particle.stats.fitness.reserve(steps);
+        }
+    }
+
+    /// Fitness evaluations of particles' positions so far.
+    pub fn evaluations_used(&self) -> usize {
+        self.evaluations_used
+    }
+
+    /// Evaluations left in the budget, if there's one.
+    pub fn evaluations_left(&self) -> Option<usize> {
+        self.evaluation_budget
+            .map(|budget| budget.saturating_sub(self.evaluations_used))
+    }
+
+    pub fn budget_exhausted(&self) -> bool {
+        self.evaluations_left() == Some(0)
+    }
+
+    pub fn priority(&self) -> Priority {
+        self.priority
+    }
+
+    /// Which particles move first when the budget can't afford all of them.
+    pub fn set_priority(&mut self, priority: Priority) {
+        self.priority = priority;
+    }
+
+    /// Steps since the global best last improved.
+    pub fn stagnant_steps(&self) -> usize {
+        self.stagnant_steps
+    }
+
+    pub(crate) fn reset_stagnation(&mut self) {
+        self.stagnant_steps = 0;
+    }
+
+    /// Re-samples every velocity as in SPSO-2011, uniformly between
+    /// `min - x` and `max - x` per dimension, `(min, max)` being the value
+    /// range.
+    pub fn resample_velocities(&mut self) {
+        let (min, max) = self.value_range;
+        for particle in &mut self.particles {
+            let rng = &mut self.rng;
+            particle.velocity = particle
+                .curr_value
+                .mapv(|x| rng.gen_range(min - x, max - x));
+        }
+    }
+
+    /// Moves every particle to a uniformly sampled position within
+    /// `center ± spread * (max - min)`, the first one exactly to `center`,
+    /// then confined and repaired like any other move. Bests are reset to
+    /// the new positions and the global best recomputed.
+    pub fn seed_around(&mut self, center: &Array1<f64>, spread: f64) -> Result<(), PsoError> {
+        let dim = self.global_best.len();
+        if center.len() != dim {
+            return Err(PsoError::InvalidOptions(format!(
+                "center has {} dimensions, expected {}",
+                center.len(),
+                dim
+            )));
+        }
+
+        if !(spread >= 0.0 && spread.is_finite()) {
+            return Err(PsoError::InvalidOptions(format!(
+                "spread must be non-negative, got {}",
+                spread
+            )));
+        }
+
+        let (min, max) = self.value_range;
+        let radius = spread * (max - min);
+        let distr = Uniform::new_inclusive(-radius, radius);
+
+        for i in 0..self.particles.len() {
+            let mut value = if i == 0 {
+                center.clone()
+            } else {
+                center + &Array1::random_using(dim, distr, &mut self.rng)
+            };
+
+            let mut velocity = self.particles[i].velocity.clone();
+            self.fix(&mut value, &mut velocity);
+            self.particles[i] = self.particle_at(self.particles[i].id, value, velocity);
+        }
+
+        self.transcribe_all();
+        let best = self.best_particle(false).expect("Population is empty");
+        self.set_global_best(best);
+        Ok(())
+    }
+
+    /// Best distinct solutions found so far, if an archive was set.
+    pub fn archive(&self) -> Option<&Archive> {
+        self.archive.as_ref()
+    }
+
+    /// Keeps an archive of distinct good solutions while running, starting
+    /// with the current particles. It measures distances with the swarm's
+    /// metric.
+    pub fn set_archive(&mut self, archive: Option<Archive>) {
+        self.archive = archive.map(|archive| archive.with_metric(self.metric));
+        self.update_archive();
+    }
+
+    fn update_archive(&mut self) {
+        if let Some(mut archive) = self.archive.take() {
+            let minimization = self.fitness.is_minimization();
+            let metric = |a: &Array1<f64>, b: &Array1<f64>| self.distance(a, b);
+            for particle in self.particles.iter().filter(|p| !p.estimated) {
+                archive.offer_with(&metric, particle.value(), particle.fitness(), minimization);
+            }
+
+            self.archive = Some(archive);
+        }
+    }
+
+    pub fn tie_break(&self) -> TieBreak {
+        self.tie_break
+    }
+
+    pub fn set_tie_break(&mut self, tie_break: TieBreak) {
+        self.tie_break = tie_break;
+    }
+
+    /// Registers an exclusion zone, particles landing inside it are pushed
+    /// out according to the zone handling.
+    pub fn add_zone(&mut self, zone: Zone) -> Result<(), PsoError> {
+        zone.validate(self.global_best.len())?;
+        self.zones.push(zone);
+        Ok(())
+    }
+
+    pub fn zones(&self) -> &[Zone] {
+        &self.zones
+    }
+
+    pub fn clear_zones(&mut self) {
+        self.zones.clear();
+    }
+
+    pub fn zone_handling(&self) -> ZoneHandling {
+        self.zone_handling
+    }
+
+    pub fn set_zone_handling(&mut self, handling: ZoneHandling) {
+        self.zone_handling = handling;
+    }
+
+    /// Sets how constraints are handled, the current particles are repaired
+    /// right away and the global best is recomputed from them.
+    pub fn set_constraint_handling(&mut self, handling: Option<ConstraintHandling>) {
+        self.constraints = handling;
+
+        if let Some(constraints) = &self.constraints {
+            for i in 0..self.particles.len() {
+                let particle = &self.particles[i];
+                let value = constraints.repair(particle.value());
+                let velocity = particle.velocity.clone();
+                self.particles[i] = self.particle_at(particle.id, value, velocity);
+            }
+
+            self.transcribe_all();
+            let best = self.best_particle(false).expect("Population is empty");
+            self.set_global_best(best);
+        }
+    }
+
+    pub fn constraint_handling(&self) -> Option<&ConstraintHandling> {
+        self.constraints.as_ref()
+    }
+
+    /// Sets a hook that fixes every new position in place, after the
+    /// constraints are repaired. The current particles are repaired right
+    /// away and the global best is recomputed from them.
+    pub fn set_repair_hook<F>(&mut self, hook: F)
+    where
+        F: Fn(&mut Array1<f64>) + Send + Sync + 'a,
+    {
+        for i in 0..self.particles.len() {
+            let particle = &self.particles[i];
+            let mut value = particle.value().clone();
+            hook(&mut value);
+
+            let velocity = particle.velocity.clone();
+            self.particles[i] = self.particle_at(particle.id, value, velocity);
+        }
+
+        self.transcribe_all();
+        let best = self.best_particle(false).expect("Population is empty");
+        self.set_global_best(best);
+        self.repair = Some(Box::new(hook));
+    }
+
+    pub fn clear_repair_hook(&mut self) {
+        self.repair = None;
+    }
+
+    pub fn has_repair_hook(&self) -> bool {
+        self.repair.is_some()
+    }
+
+    /// Index of the particle with the best current fitness, ties are broken
+    /// with the configured policy.
+    fn best_particle(&mut self, closest_to_best: bool) -> Option<usize> {
+        // Estimated fitness can't make a particle the best
+        let best = self
+            .particles
+            .iter()
+            .filter(|p| !p.estimated)
+            .map(|p| p.fitness())
+            .max_by(|&a, &b| self.compare(a, b))?;
+
+        let mut ties = mem::take(&mut self.scratch.ties);
+        ties.clear();
+        ties.extend((0..self.particles.len()).filter(|&i| {
+            let particle = &self.particles[i];
+            !particle.estimated && self.compare(particle.fitness(), best) == Ordering::Equal
+        }));
+
+        let closest_to = |target: &Array1<f64>| {
+            let dist = |i: usize| self.distance(self.particles[i].value(), target);
 
-        Self {
-            curr_value: value.clone(),
-            best_value: value,
-            velocity,
+            ties.iter()
+                .copied()
+                .min_by(|&a, &b| dist(a).total_cmp(&dist(b)))
+        };
+
+        let chosen = match (self.tie_break, closest_to_best) {
+            (_, _) if ties.len() == 1 => Some(ties[0]),
+            (TieBreak::Random, _) => Some(ties[self.rng.gen_range(0, ties.len())]),
+            (TieBreak::ClosestToPrevious, true) => closest_to(&self.global_best),
+            (TieBreak::MostCentral, _) => {
+                let mut centroid = Array1::zeros(self.global_best.len());
+                for particle in &self.particles {
+                    centroid += particle.value();
+                }
+
+                closest_to(&(centroid / self.particles.len() as f64))
+            }
+            _ => Some(ties[0]),
+        };
+
+        self.scratch.ties = ties;
+        chosen
+    }
+
+    /// Moves every particle once, `options` are ignored by algorithms other
+    /// than [`Algorithm::Standard`].
+    ///
+    /// Buffers are reused across steps: once room for the trajectories is
+    /// [reserved](Self::reserve_steps), steps of the standard algorithm
+    /// with the classic or componentwise velocities and a static topology
+    /// don't allocate, unless there's a budget, operators, an archive, a
+    /// transcript, a memory, verbose output or an allocating fitness.
+    pub fn step(&mut self, mut options: Options) {
+        if self.budget_exhausted() {
+            return;
+        }
+
+        match self.algorithm {
+            Algorithm::Standard => {}
+            Algorithm::Competitive { phi } => {
+                self.steps += 1;
+                self.compete(phi);
+                self.conclude_step();
+                return;
+            }
+            Algorithm::SocialLearning { alpha, beta } => {
+                self.steps += 1;
+                self.learn_socially(alpha, beta);
+                self.conclude_step();
+                return;
+            }
+        }
+
+        // During the warmup the inertia is fixed to explore
+        if let Some(warmup) = self.warmup.filter(|_| self.warming_up()) {
+            options.omega = Some(warmup.omega);
+        }
+
+        // If not provided with an omega, generate one for this iteration
+        options.omega = if let Some(omega) = options.omega {
+            Some(omega)
+        } else {
+            Some(self.rng.gen_range(0.0, 1.0))
+        };
+
+        if self.verbose {
+            let omega = options.omega.unwrap();
+            println!("Omega (ω): {}", self.number_format.format(omega));
+        }
+
+        self.omega = options.omega;
+        self.steps += 1;
+
+        self.update_local_bests();
+        let scheduled = self.schedule();
+        let mut rands = mem::take(&mut self.scratch.rands);
+        let movement = Movement {
+            global_best: &self.global_best,
+            local_bests: &self.scratch.local_bests,
+            options: &options,
+            coefficients: self.coefficients.as_ref(),
+            velocity_policy: self.velocity_policy,
+            placement: Placement {
+                confinement: self.confinement,
+                manifold: self.manifold,
+                value_range: self.value_range,
+                zones: &self.zones,
+                zone_handling: self.zone_handling,
+                constraints: self.constraints.as_ref(),
+                repair: self.repair.as_ref(),
+                frozen: &self.frozen,
+                minimization: self.fitness.is_minimization(),
+                comparison: self.comparison,
+                deferred: self.screening.is_some(),
+            },
+            scheduled: scheduled.as_deref(),
+        };
+
+        let (size, dim) = (self.particles.len(), self.global_best.len());
+        let policy = self.velocity_policy;
+        match self.parallelism {
+            #[cfg(feature = "parallel")]
+            Parallelism::Parallel | Parallelism::Deterministic => {
+                let evaluate = self
+                    .fitness
+                    .as_shared()
+                    .expect("Parallel evaluation needs a shared fitness");
+
+                let drawn = match self.parallelism {
+                    Parallelism::Deterministic => {
+                        draw_rands(&mut self.rng, &mut rands, size, dim, policy);
+                        Some(rands)
+                    }
+                    _ => None,
+                };
+
+                rands = parallel::move_particles(&mut self.particles, &movement, drawn, evaluate);
+            }
+            _ => {
+                let fitness = &self.fitness;
+                draw_rands(&mut self.rng, &mut rands, size, dim, policy);
+                for (i, (particle, rands)) in self.particles.iter_mut().zip(&rands).enumerate() {
+                    movement.apply(i, particle, rands, |x| fitness.calculate(x));
+                }
+            }
+        }
+
+        let particles = if self.verbose {
+            &self.particles[..]
+        } else {
+            &[]
+        };
+        let format = self.number_format;
+        for (i, (particle, rands)) in particles.iter().zip(&rands).enumerate() {
+            if policy == VelocityPolicy::Classic {
+                println!("rand_1: {}", format.format(rands[0]));
+                println!("rand_2: {}", format.format(rands[1]));
+            }
+            println!(
+                "{}) x: {}, v: {}",
+                i + 1,
+                format.array(particle.value()),
+                format.array(particle.velocity())
+            );
+        }
+
+        self.scratch.rands = rands;
+        if let Some(screening) = self.screening {
+            self.screen(&screening, scheduled.as_deref());
+        } else {
+            for i in 0..size {
+                if scheduled.as_ref().is_none_or(|scheduled| scheduled[i]) {
+                    self.transcribe(i);
+                }
+            }
         }
+
+        self.conclude_step();
     }
 
-    pub fn best(&self) -> &Array1<f64> {
-        &self.best_value
+    /// Common end of every step once the particles moved: applies the
+    /// operators, archives and updates the global best and the neighborhoods.
+    fn conclude_step(&mut self) {
+        if let Some(warmup) = self.warmup.filter(|warmup| warmup.covers(self.steps)) {
+            self.resample_for_warmup(&warmup);
+        }
+
+        for operator in self.operators.clone() {
+            if self.budget_exhausted() {
+                break;
+            }
+
+            self.apply_operator(&operator);
+        }
+
+        self.update_archive();
+
+        let format = self.number_format;
+        let local_best = self.best_particle(true).expect("Population is empty");
+
+        let local_fitness = self.particles[local_best].fitness();
+        if self.verbose {
+            println!(
+                "{}: x: {}, fitness: {}",
+                self.language.iteration_best(),
+                format.array(self.particles[local_best].value()),
+                format.format(local_fitness)
+            );
+        }
+
+        if self.improves(local_fitness, self.global_best_fitness) {
+            if self.verbose {
+                println!("{}", self.language.global_best_changed());
+            }
+
+            self.set_global_best(local_best);
+            self.stagnant_steps = 0;
+        } else {
+            self.stagnant_steps += 1;
+            if self.topology.is_adaptive() {
+                self.neighborhoods = self
+                    .topology
+                    .neighborhoods(self.particles.len(), &mut self.rng);
+            }
+        }
+
+        if let Topology::Hierarchy { branching } = self.topology {
+            self.swap_hierarchy(branching);
+        }
     }
 
-    pub fn value(&self) -> &Array1<f64> {
-        &self.curr_value
+    /// Top-down pass over the H-PSO tree, every parent swaps places with its
+    /// best child if the child's best is better. A particle moves at most
+    /// one level down per pass.
+    fn swap_hierarchy(&mut self, branching: usize) {
+        let size = self.particles.len();
+        let mut demoted = vec![false; size];
+
+        for node in 0..size {
+            if demoted[node] {
+                continue;
+            }
+
+            let children = (node * branching + 1)..(node * branching + branching + 1).min(size);
+            let best_child = children.max_by(|&a, &b| {
+                self.compare(
+                    self.particles[a].best_fitness,
+                    self.particles[b].best_fitness,
+                )
+            });
+
+            if let Some(child) = best_child {
+                let parent_fitness = self.particles[node].best_fitness;
+                let child_fitness = self.particles[child].best_fitness;
+
+                if self.improves(child_fitness, parent_fitness) {
+                    self.particles.swap(node, child);
+                    demoted[child] = true;
+                }
+            }
+        }
     }
 
-    pub fn velocity(&self) -> &Array1<f64> {
-        &self.velocity
+    /// Runs `iters` steps after validating the options, returns the amount
+    /// of steps done.
+    pub fn run(&mut self, options: Options, iters: usize) -> Result<usize, PsoError> {
+        self.run_with(options, iters, &mut [])
     }
 
-    fn update_velocity(&mut self, global_best: &Array1<f64>, mut rng: impl Rng, options: &Options) {
-        let omega = options.omega.expect("Omega was None");
-        let phi_1 = options.phi_1;
-        let phi_2 = options.phi_2;
+    /// Same as [`run`](Self::run), but calling the observers' hooks and
+    /// applying their control signals. A signal for a particle that doesn't
+    /// exist stops the run, its observers still finish, and is returned as
+    /// an error.
+    pub fn run_with(
+        &mut self,
+        options: Options,
+        iters: usize,
+        observers: &mut [&mut dyn Observer],
+    ) -> Result<usize, PsoError> {
+        options.validate()?;
+
+        let mut stop = false;
+        let mut error = None;
+        for observer in observers.iter_mut() {
+            let control = observer.on_start(self);
+            match self.apply(control) {
+                Ok(stopped) => stop |= stopped,
+                Err(err) => {
+                    error.get_or_insert(err);
+                }
+            }
+        }
 
-        let fst_term = self.velocity.mapv(|v| v * omega);
+        let mut done = 0;
+        while !stop && error.is_none() && done < iters && !self.budget_exhausted() {
+            self.step(options);
+            done += 1;
+
+            for observer in observers.iter_mut() {
+                let control = observer.on_step(done, self);
+                match self.apply(control) {
+                    Ok(stopped) => stop |= stopped,
+                    Err(err) => {
+                        error.get_or_insert(err);
+                    }
+                }
+            }
+        }
 
-        let rand_1 = rng.gen_range(0.0, 1.0);
-        let snd_term = (&self.best_value - &self.curr_value).mapv(|v| v * phi_1 * rand_1);
+        for observer in observers.iter_mut() {
+            observer.on_finish(self);
+        }
 
-        let rand_2 = rng.gen_range(0.0, 1.0);
-        let trd_term = (global_best - &self.curr_value).mapv(|v| v * phi_2 * rand_2);
+        match error {
+            Some(err) => Err(err),
+            None => Ok(done),
+        }
+    }
 
-        println!("rand_1: {}", rand_1);
-        println!("rand_2: {}", rand_2);
+    /// Applies a control signal, returns whether the run should stop.
+    fn apply(&mut self, control: Control) -> Result<bool, PsoError> {
+        match control {
+            Control::Continue => Ok(false),
+            Control::Stop => Ok(true),
+            Control::RestartParticle(i) => {
+                if i >= self.particles.len() {
+                    return Err(PsoError::InvalidOptions(format!(
+                        "can't restart particle {} of {}",
+                        i,
+                        self.particles.len()
+                    )));
+                }
 
-        self.velocity = fst_term + snd_term + trd_term;
+                self.restart_particle(i);
+                Ok(false)
+            }
+            Control::ReSampleWorst(k) => {
+                self.resample_worst(k);
+                Ok(false)
+            }
+        }
     }
 
-    fn update_value(&mut self) {
-        self.curr_value = &self.curr_value + &self.velocity;
+    /// Re-initializes the i-th particle with the initial distributions,
+    /// the global best is kept. The new position goes through the same
+    /// fixes as moved particles, so frozen dimensions stay locked.
+    pub fn restart_particle(&mut self, i: usize) {
+        self.resample_particle(i, LineageEvent::Reinitialized);
     }
 
-    fn update_best(&mut self, fitness: &Fitness<Array1<f64>>) {
-        let curr_fitness = fitness.calculate_for_maximization(&self.curr_value);
-        let best_fitness = fitness.calculate_for_maximization(&self.best_value);
+    pub(crate) fn resample_particle(&mut self, i: usize, event: LineageEvent) {
+        let dim = self.global_best.len();
+        let (mut value, mut velocity) =
+            Particle::sample(dim, self.value_range, self.velocity_range, &mut self.rng);
+        self.fix(&mut value, &mut velocity);
+        self.particles[i] = self.particle_at(self.particles[i].id, value, velocity);
 
-        if best_fitness < curr_fitness {
-            self.best_value = self.curr_value.clone();
+        self.transcribe(i);
+        self.log(i, event);
+    }
+
+    /// Re-initializes the whole swarm with the initial distributions,
+    /// forgetting the global best.
+    pub fn reinitialize(&mut self) {
+        self.reinitialize_avoiding(|_| false);
+    }
+
+    /// Same as [`reinitialize`](Self::reinitialize), but positions for which
+    /// `reject` returns true, or inside an exclusion zone, are sampled again
+    /// (up to 100 times per particle).
+    pub fn reinitialize_avoiding<F>(&mut self, reject: F)
+    where
+        F: Fn(&Array1<f64>) -> bool,
+    {
+        const MAX_ATTEMPTS: usize = 100;
+
+        let dim = self.global_best.len();
+        let value_distr = Uniform::new(self.value_range.0, self.value_range.1);
+        let velocity_distr = Uniform::new(self.velocity_range.0, self.velocity_range.1);
+
+        for i in 0..self.particles.len() {
+            let mut value = Array1::random_using((dim,), value_distr, &mut self.rng);
+            for _ in 1..MAX_ATTEMPTS {
+                let excluded = self.zones.iter().any(|zone| zone.contains(&value));
+                if !excluded && !reject(&value) {
+                    break;
+                }
+
+                value = Array1::random_using((dim,), value_distr, &mut self.rng);
+            }
+
+            let mut velocity = Array1::random_using((dim,), velocity_distr, &mut self.rng);
+            self.fix(&mut value, &mut velocity);
+            self.particles[i] = self.particle_at(self.particles[i].id, value, velocity);
+            self.log(i, LineageEvent::Reinitialized);
         }
+
+        self.transcribe_all();
+        let best = self.best_particle(false).expect("Population is empty");
+        self.set_global_best(best);
     }
-}
 
-#[derive(Debug, Copy, Clone)]
-pub struct Options {
-    pub omega: Option<f64>,
-    pub phi_1: f64,
-    pub phi_2: f64,
-}
+    /// Replaces the i-th particle with a copy of `particle`, coming from the
+    /// swarm `from`. It gets a new ID and is evaluated with this swarm's
+    /// fitness, the global best is kept.
+    pub fn migrate(&mut self, i: usize, particle: &Particle, from: usize) {
+        let event = LineageEvent::Migrated {
+            swarm: from,
+            id: particle.id,
+        };
 
-pub struct Particles<'a> {
-    particles: Vec<Particle>,
-    fitness: Fitness<'a, Array1<f64>>,
-    global_best: Array1<f64>,
-}
+        let value = particle.curr_value.clone();
+        let velocity = particle.velocity.clone();
+        self.replace_particle(i, value, velocity, event);
+    }
 
-impl<'a> Particles<'a> {
-    pub fn new(
-        size: usize,
-        dim: usize,
-        value_range: Uniform<f64>,
-        velocity_range: Uniform<f64>,
-        fitness: Fitness<'a, Array1<f64>>,
-    ) -> Self {
-        let particles: Vec<_> = (0..size)
-            .map(|_| Particle::new(dim, &value_range, &velocity_range))
-            .collect();
+    /// Replaces the i-th particle with a new one at `value`, moving with
+    /// `velocity`. It gets a new ID and is evaluated right away.
+    pub(crate) fn replace_particle(
+        &mut self,
+        i: usize,
+        value: Array1<f64>,
+        velocity: Array1<f64>,
+        event: LineageEvent,
+    ) {
+        let id = self.next_id;
+        self.next_id += 1;
 
-        let global_best = particles
-            .iter()
-            .max_by(|a, b| {
-                let fa = fitness.calculate_for_maximization(&a.value());
-                let fb = fitness.calculate_for_maximization(&b.value());
+        self.particles[i] = self.particle_at(id, value, velocity);
+        self.transcribe(i);
+        self.log(i, event);
+    }
 
-                fa.partial_cmp(&fb).expect("Received a NaN")
-            })
-            .expect("No particles were created")
-            .curr_value
-            .clone();
+    /// Adds a particle at `value` moving with `velocity`, e.g. a solution
+    /// found elsewhere. It's evaluated right away and becomes the global
+    /// best if it's better, returns its ID.
+    pub fn add_particle(
+        &mut self,
+        value: Array1<f64>,
+        velocity: Array1<f64>,
+    ) -> Result<usize, PsoError> {
+        let dim = self.global_best.len();
+        if value.len() != dim || velocity.len() != dim {
+            return Err(PsoError::InvalidOptions(format!(
+                "particles must have {} dimensions",
+                dim
+            )));
+        }
 
-        Self {
-            particles,
-            fitness,
-            global_best,
+        let size = self.particles.len() + 1;
+        self.topology.validate(size)?;
+
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let particle = self.particle_at(id, value, velocity);
+        self.particles.push(particle);
+        self.neighborhoods = self.topology.neighborhoods(size, &mut self.rng);
+        self.transcribe(size - 1);
+        self.log(size - 1, LineageEvent::Added);
+
+        let fitness = self.particles[size - 1].fitness();
+        if self.improves(fitness, self.global_best_fitness) {
+            self.set_global_best(size - 1);
         }
+
+        Ok(id)
     }
 
-    pub fn step(&mut self, mut options: Options) {
-        let mut rng = thread_rng();
+    /// Removes the particle with the given ID and returns it, the global
+    /// best is kept even if this particle found it.
+    pub fn remove_particle(&mut self, id: usize) -> Result<Particle, PsoError> {
+        let i = self
+            .particles
+            .iter()
+            .position(|p| p.id == id)
+            .ok_or_else(|| PsoError::InvalidOptions(format!("no particle has ID {}", id)))?;
 
-        // If not provided with an omega, generate one for this iteration
-        options.omega = if let Some(omega) = options.omega {
-            Some(omega)
-        } else {
-            Some(rng.gen_range(0.0, 1.0))
-        };
+        let size = self.particles.len() - 1;
+        if size == 0 {
+            return Err(PsoError::InvalidOptions(
+                "the last particle can't be removed".to_string(),
+            ));
+        }
+        self.topology.validate(size)?;
 
-        println!("Omega (ω): {}", options.omega.unwrap());
+        self.log(i, LineageEvent::Removed);
+        let particle = self.particles.remove(i);
+        self.neighborhoods = self.topology.neighborhoods(size, &mut self.rng);
 
-        for (i, particle) in &mut self.particles.iter_mut().enumerate() {
-            particle.update_velocity(&self.global_best, &mut rng, &options);
-            particle.update_value();
-            particle.update_best(&self.fitness);
-            println!(
-                "{}) x: {}, v: {}",
-                i + 1,
+        Ok(particle)
+    }
+
+    /// Alters the position of the i-th particle with `mutation`, it's
+    /// evaluated again and its best updated, the global best is kept.
+    pub fn mutate<F>(&mut self, i: usize, mutation: F)
+    where
+        F: FnOnce(&mut Array1<f64>),
+    {
+        mutation(&mut self.particles[i].curr_value);
+
+        let fitness = self.evaluate(&self.particles[i].curr_value);
+        let particle = &mut self.particles[i];
+        particle.update_best(fitness, self.fitness.is_minimization(), self.comparison);
+        self.transcribe(i);
+        self.log(i, LineageEvent::Mutated);
+    }
+
+    /// Every lineage event so far, in order.
+    pub fn lineage(&self) -> &[Lineage] {
+        &self.lineage
+    }
+
+    /// Seed of the RNG, `None` when it was seeded from the OS.
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// Steps done since the swarm was created.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Counts the last evaluation of the i-th particle, logging it if
+    /// there's a transcript and keeping it in the memory.
+    fn transcribe(&mut self, i: usize) {
+        self.evaluations_used += 1;
+
+        let particle = &self.particles[i];
+        if let Some(transcript) = &mut self.transcript {
+            transcript.write(
+                self.steps,
+                particle.id,
                 particle.value(),
-                particle.velocity()
+                particle.fitness(),
             );
         }
 
-        let local_best = self
-            .particles
-            .iter()
-            .max_by(|a, b| {
-                let fa = self.fitness.calculate_for_maximization(&a.value());
-                let fb = self.fitness.calculate_for_maximization(&b.value());
+        if self.memory_capacity() > 0 {
+            let (value, fitness) = (particle.curr_value.clone(), particle.fitness());
+            self.remember(&value, fitness);
+        }
+    }
 
-                fa.partial_cmp(&fb).expect("Received a NaN")
-            })
-            .expect("Population is empty")
-            .curr_value
-            .clone();
-
-        let best_fitness = self.fitness.calculate_for_maximization(&self.global_best);
-        let local_fitness = self.fitness.calculate_for_maximization(&local_best);
-        println!(
-            "Mejor en esta iteración: x: {}, fitness: {}",
-            local_best,
-            self.fitness.calculate(&local_best)
-        );
+    /// Evaluates the i-th particle where it is, updating its best.
+    pub(crate) fn evaluate_particle(&mut self, i: usize) {
+        let fitness = self.evaluate(&self.particles[i].curr_value);
+        let minimization = self.fitness.is_minimization();
+        self.particles[i].update_best(fitness, minimization, self.comparison);
+        self.transcribe(i);
+    }
+
+    /// Records `fitness` as the estimated fitness of the i-th particle, its
+    /// best isn't touched.
+    pub(crate) fn estimate_particle(&mut self, i: usize, fitness: f64) {
+        let particle = &mut self.particles[i];
+        particle.stats.record(fitness, false);
+        particle.estimated = true;
+    }
+
+    /// Fitness of `value`, with the penalty of a soft wall.
+    fn evaluate(&self, value: &Array1<f64>) -> f64 {
+        penalized(&self.fitness, self.confinement, self.value_range, value)
+    }
+
+    /// Particle at `value`, evaluated with the penalty of a soft wall.
+    fn particle_at(&self, id: usize, value: Array1<f64>, velocity: Array1<f64>) -> Particle {
+        let fitness = self.evaluate(&value);
+        Particle::at(id, value, velocity, fitness)
+    }
+
+    /// Moves the i-th particle to `value` and evaluates it.
+    pub(crate) fn place(&mut self, i: usize, value: Array1<f64>, event: LineageEvent) {
+        let fitness = self.evaluate(&value);
+        let particle = &mut self.particles[i];
+        particle.curr_value = value;
+        particle.update_best(fitness, self.fitness.is_minimization(), self.comparison);
+        self.transcribe(i);
+        self.log(i, event);
+    }
+
+    /// Runs `x`, a position found by an operator, through the same fixes as
+    /// the positions of moved particles.
+    pub(crate) fn fix_position(&self, x: &mut Array1<f64>) {
+        let mut velocity = Array1::zeros(x.len());
+        self.fix(x, &mut velocity);
+    }
+
+    /// Same as [`fix_position`](Self::fix_position), adjusting the velocity
+    /// `v` along.
+    fn fix(&self, x: &mut Array1<f64>, v: &mut Array1<f64>) {
+        let placement = Placement {
+            confinement: self.confinement,
+            manifold: self.manifold,
+            value_range: self.value_range,
+            zones: &self.zones,
+            zone_handling: self.zone_handling,
+            constraints: self.constraints.as_ref(),
+            repair: self.repair.as_ref(),
+            frozen: &self.frozen,
+            minimization: self.fitness.is_minimization(),
+            comparison: self.comparison,
+            deferred: true,
+        };
+
+        placement.fix(x, v);
+    }
+
+    /// Moves the i-th particle along `velocity`, which replaces its own,
+    /// and evaluates it.
+    pub(crate) fn relocate(&mut self, i: usize, velocity: Array1<f64>) {
+        let placement = Placement {
+            confinement: self.confinement,
+            manifold: self.manifold,
+            value_range: self.value_range,
+            zones: &self.zones,
+            zone_handling: self.zone_handling,
+            constraints: self.constraints.as_ref(),
+            repair: self.repair.as_ref(),
+            frozen: &self.frozen,
+            minimization: self.fitness.is_minimization(),
+            comparison: self.comparison,
+            deferred: false,
+        };
+
+        let fitness = &self.fitness;
+        let particle = &mut self.particles[i];
+        particle.velocity = velocity;
+        placement.apply(particle, |x| fitness.calculate(x));
+        self.transcribe(i);
+    }
+
+    /// Evaluates `trial` for the i-th particle, which only moves there if
+    /// it improves its personal best. Returns whether it moved.
+    pub(crate) fn offer_trial(
+        &mut self,
+        i: usize,
+        trial: Array1<f64>,
+        event: LineageEvent,
+    ) -> bool {
+        let fitness = self.evaluate(&trial);
+        self.evaluations_used += 1;
+        if let Some(transcript) = &mut self.transcript {
+            transcript.write(self.steps, self.particles[i].id, &trial, fitness);
+        }
+        self.remember(&trial, fitness);
+
+        let improves = self.improves(fitness, self.particles[i].best_fitness);
+        if improves {
+            let particle = &mut self.particles[i];
+            particle.curr_value = trial;
+            particle.update_best(fitness, self.fitness.is_minimization(), self.comparison);
+            self.log(i, event);
+        }
+
+        improves
+    }
+
+    pub(crate) fn rng_mut(&mut self) -> &mut Pcg64 {
+        &mut self.rng
+    }
+
+    /// Logs the initial swarm, already counted, in the transcript.
+    pub(crate) fn transcribe_initial(&mut self) {
+        if let Some(transcript) = &mut self.transcript {
+            for particle in &self.particles {
+                transcript.write(
+                    self.steps,
+                    particle.id,
+                    particle.value(),
+                    particle.fitness(),
+                );
+            }
+        }
+    }
+
+    pub(crate) fn transcribe_all(&mut self) {
+        for i in 0..self.particles.len() {
+            self.transcribe(i);
+        }
+    }
+
+    /// Logs every evaluation of a particle's position from now on (not the
+    /// ones of analyses like [`sensitivity`](Self::sensitivity)), returns
+    /// the previous transcript. It isn't part of the state snapshots.
+    pub fn set_transcript(&mut self, transcript: Option<Transcript>) -> Option<Transcript> {
+        std::mem::replace(&mut self.transcript, transcript)
+    }
+
+    pub fn transcript_mut(&mut self) -> Option<&mut Transcript> {
+        self.transcript.as_mut()
+    }
+
+    fn log(&mut self, i: usize, event: LineageEvent) {
+        self.lineage.push(Lineage {
+            id: self.particles[i].id,
+            step: self.steps,
+            event,
+        });
+    }
+
+    /// Re-initializes the `k` particles with the worst current fitness.
+    pub fn resample_worst(&mut self, k: usize) {
+        let mut indices: Vec<_> = (0..self.particles.len()).collect();
+        indices.sort_by(|&a, &b| {
+            self.compare(self.particles[a].fitness(), self.particles[b].fitness())
+        });
 
-        if best_fitness < local_fitness {
-            println!("El mejor global cambió");
-            self.global_best = local_best;
+        for i in indices.into_iter().take(k) {
+            self.restart_particle(i);
         }
     }
 
@@ -172,64 +2636,135 @@ impl<'a> Particles<'a> {
         &self.global_best
     }
 
+    pub fn best_fitness(&self) -> f64 {
+        self.global_best_fitness
+    }
+
+    fn set_global_best(&mut self, i: usize) {
+        copy_into(&mut self.global_best, &self.particles[i].curr_value);
+        self.global_best_fitness = self.particles[i].fitness();
+    }
+
+    /// Re-evaluates every personal best and the global best, e.g. after the
+    /// fitness changed, and makes the best personal best the global best if
+    /// it's better now. Every evaluation counts against the budget.
+    pub(crate) fn reevaluate_bests(&mut self) {
+        for i in 0..self.particles.len() {
+            self.particles[i].best_fitness = self.evaluate(&self.particles[i].best_value);
+        }
+        self.global_best_fitness = self.evaluate(&self.global_best);
+        self.evaluations_used += self.particles.len() + 1;
+
+        let best = (0..self.particles.len()).max_by(|&a, &b| {
+            self.compare(
+                self.particles[a].best_fitness,
+                self.particles[b].best_fitness,
+            )
+        });
+        if let Some(i) = best {
+            if self.improves(self.particles[i].best_fitness, self.global_best_fitness) {
+                self.promote_best(i);
+            }
+        }
+    }
+
+    /// Makes the personal best of the i-th particle the global best, even
+    /// if its fitness is worse.
+    pub(crate) fn promote_best(&mut self, i: usize) {
+        self.global_best = self.particles[i].best_value.clone();
+        self.global_best_fitness = self.particles[i].best_fitness;
+    }
+
+    pub fn fitness(&self) -> &Fitness<'a, Array1<f64>> {
+        &self.fitness
+    }
+
+    /// Omega used on the last step, `None` before the first one.
+    pub fn omega(&self) -> Option<f64> {
+        self.omega
+    }
+
     pub fn particles(&self) -> &Vec<Particle> {
         &self.particles
     }
 
-    pub fn summary(&self, show_particles: bool) -> Result<String, FmtError> {
+    pub fn summary(&self, show_particles: bool) -> Result<String, PsoError> {
         use std::fmt::Write;
 
-        let mut particles_out = String::new();
-        let mut fitness_out = String::new();
-        let mut blocals_out = String::new();
+        let mut out = String::new();
+        write!(out, "{}", self.summary_with().particles(show_particles))?;
+        Ok(out)
+    }
 
-        if show_particles {
-            writeln!(particles_out, ">>> Cúmulo de partículas <<<")?;
-        }
+    /// Configurable summary, implements [`Display`](fmt::Display).
+    pub fn summary_with(&self) -> Summary<'_, 'a> {
+        Summary::new(self)
+    }
 
-        writeln!(fitness_out, ">>> Fitness <<<")?;
-        writeln!(blocals_out, ">>> Mejores locales <<<")?;
+    /// Particles, fitnesses, bests and global best in a form scripts can
+    /// parse, see [`SwarmSummary`].
+    pub fn summary_json(&self) -> serde_json::Value {
+        serde_json::to_value(SwarmSummary::from(self)).expect("Summaries are always valid JSON")
+    }
 
-        for (i, particle) in self.particles.iter().enumerate() {
-            if show_particles {
-                writeln!(
-                    particles_out,
-                    "{}) x: {},  v: {}",
-                    i + 1,
-                    particle.value(),
-                    particle.velocity()
-                )?;
-            }
+    /// Samples a latin hypercube of `samples` points around the global
+    /// best, `radius` is relative to the width of the value range. The
+    /// radius reported is in the original coordinates, also for normalized
+    /// swarms.
+    pub fn sensitivity(&mut self, radius: f64, samples: usize) -> Result<Sensitivity, PsoError> {
+        SensitivityAnalysis { radius, samples }.validate()?;
 
-            let fitness = self.fitness.calculate(particle.value());
-            writeln!(fitness_out, "{}) {}", i + 1, fitness)?;
+        let (min, max) = self.value_range;
+        let radius = Array1::from_elem(self.global_best.len(), radius * (max - min));
+        let mut sensitivity = sensitivity::sensitivity(
+            &self.fitness,
+            &self.global_best,
+            &radius,
+            samples,
+            &mut self.rng,
+        )?;
 
-            let best_fitness = self.fitness.calculate(particle.best());
-            writeln!(
-                blocals_out,
-                "{}) x: {}, fitness: {}",
-                i + 1,
-                particle.best(),
-                best_fitness
-            )?;
+        if let Some(coordinates) = &self.normalization {
+            let edge = coordinates.decode(&(&self.global_best + &radius));
+            sensitivity.radius = (edge - coordinates.decode(&self.global_best)).mapv(f64::abs);
         }
 
-        let best_fitness = self.fitness.calculate(self.best());
-        let best_global = format!(
-            ">>> Mejor global: x: {}, fitness: {}",
-            self.best(),
-            best_fitness
-        );
+        Ok(sensitivity)
+    }
 
-        let out = format!(
-            "{}{}{}{}",
-            particles_out, fitness_out, blocals_out, best_global
-        );
+    pub fn language(&self) -> Language {
+        self.language
+    }
 
-        Ok(out)
+    /// Language of the summaries and of the messages printed on every step.
+    pub fn set_language(&mut self, language: Language) {
+        self.language = language;
+    }
+
+    pub fn number_format(&self) -> NumberFormat {
+        self.number_format
+    }
+
+    /// Format of the numbers in summaries, plots and the progress of every
+    /// step.
+    pub fn set_number_format(&mut self, format: NumberFormat) {
+        self.number_format = format;
+    }
+
+    pub fn is_verbose(&self) -> bool {
+        self.verbose
+    }
+
+    /// Whether every step prints its progress, enabled by default.
+    pub fn set_verbose(&mut self, verbose: bool) {
+        self.verbose = verbose;
     }
 
-    pub fn plot<D>(&self, root: &DrawingArea<D, Shift>, i: usize) -> Result<(), Error>
+    /// Draws the positions on the first two dimensions, as the i-th frame,
+    /// with the best and mean fitness, diversity and omega in a corner.
+    /// Works with any plotters backend: bitmaps, GIF frames, SVG, Cairo or
+    /// an HTML canvas.
+    pub fn plot<D>(&self, root: &DrawingArea<D, Shift>, i: usize) -> Result<(), PsoError>
     where
         D: DrawingBackend,
         <D as DrawingBackend>::ErrorType: 'static,
@@ -243,13 +2778,49 @@ impl<'a> Particles<'a> {
             .caption(format!("PSO (iter = {})", i), ("sans-serif", 50))
             .build_ranged(-5.0..5.0, -5.0..5.0)?;
 
-        chart.configure_mesh().draw()?;
+        let format = |v: &f64| self.number_format.format(*v);
+        chart
+            .configure_mesh()
+            .x_label_formatter(&format)
+            .y_label_formatter(&format)
+            .draw()?;
         chart.draw_series(self.particles.iter().map(|p| {
             let value = p.value();
             let center = (value[0], value[1]);
             Circle::new(center, 5, ShapeStyle::from(&BLUE).filled())
         }))?;
 
+        let n = self.particles.len() as f64;
+        let mean = self.particles.iter().map(Particle::fitness).sum::<f64>() / n;
+        let omega = self.omega.map_or("-".to_string(), |omega| format(&omega));
+        let lines = [
+            format!("best: {}", format(&self.global_best_fitness)),
+            format!("mean: {}", format(&mean)),
+            format!("diversity: {}", format(&self.diversity(0.0).mean_distance)),
+            format!("omega: {}", omega),
+        ];
+
+        let (x, y) = chart.plotting_area().get_base_pixel();
+        let (root_x, root_y) = root.get_base_pixel();
+        let (x, y) = (x - root_x + 8, y - root_y + 8);
+        let height = 18 * lines.len() as i32 + 8;
+        root.draw(&Rectangle::new(
+            [(x, y), (x + 180, y + height)],
+            WHITE.mix(0.8).filled(),
+        ))?;
+        root.draw(&Rectangle::new([(x, y), (x + 180, y + height)], &BLACK))?;
+        for (k, line) in lines.iter().enumerate() {
+            let position = (x + 6, y + 6 + 18 * k as i32);
+            root.draw(&Text::new(line.as_str(), position, ("sans-serif", 15)))?;
+        }
+
         Ok(root.present()?)
     }
+
+    /// Same as [`plot`](Self::plot), but as a standalone SVG file, for
+    /// vector frames instead of the GIF bitmaps.
+    pub fn plot_to_svg<P: AsRef<Path>>(&self, path: P, i: usize) -> Result<(), PsoError> {
+        let root = SVGBackend::new(path.as_ref(), PLOT_SIZE).into_drawing_area();
+        self.plot(&root, i)
+    }
 }
@@ -0,0 +1,130 @@
+use crate::error::PsoError;
+use crate::pso::{Algorithm, Options, Particles};
+use crate::topology::Topology;
+use crate::velocity::Coefficients;
+
+/// Changes to a run in progress, e.g. between steps or when resuming from a
+/// checkpoint. Settings left unset are kept. Everything is validated before
+/// anything changes, then the swarm is migrated:
+///
+/// - Options apply from the next step on.
+/// - A new topology rebuilds the neighborhoods, personal bests are kept.
+/// - Coefficients replace the previous ones, `Some(None)` goes back to the
+///   scalar ones of the options.
+/// - See [`Particles::set_value_range`] and
+///   [`Particles::set_velocity_range`] for the ranges.
+#[derive(Debug, Clone, Default)]
+pub struct Reconfiguration {
+    options: Option<Options>,
+    topology: Option<Topology>,
+    algorithm: Option<Algorithm>,
+    coefficients: Option<Option<Coefficients>>,
+    value_range: Option<(f64, f64)>,
+    velocity_range: Option<(f64, f64)>,
+}
+
+impl Reconfiguration {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn options(mut self, options: Options) -> Self {
+        self.options = Some(options);
+        self
+    }
+
+    pub fn topology(mut self, topology: Topology) -> Self {
+        self.topology = Some(topology);
+        self
+    }
+
+    pub fn algorithm(mut self, algorithm: Algorithm) -> Self {
+        self.algorithm = Some(algorithm);
+        self
+    }
+
+    pub fn coefficients(mut self, coefficients: Option<Coefficients>) -> Self {
+        self.coefficients = Some(coefficients);
+        self
+    }
+
+    pub fn value_range(mut self, min: f64, max: f64) -> Self {
+        self.value_range = Some((min, max));
+        self
+    }
+
+    pub fn velocity_range(mut self, min: f64, max: f64) -> Self {
+        self.velocity_range = Some((min, max));
+        self
+    }
+
+    /// Checks every change against the swarm without applying any.
+    pub fn validate(&self, particles: &Particles) -> Result<(), PsoError> {
+        if let Some(options) = &self.options {
+            options.validate()?;
+        }
+
+        if let Some(topology) = &self.topology {
+            topology.validate(particles.particles().len())?;
+        }
+
+        if let Some(algorithm) = &self.algorithm {
+            algorithm.validate()?;
+        }
+
+        if let Some(Some(coefficients)) = &self.coefficients {
+            coefficients.validate(particles.best().len())?;
+        }
+
+        let ranges = [
+            ("value", self.value_range),
+            ("velocity", self.velocity_range),
+        ];
+
+        for (name, range) in ranges.iter() {
+            match range {
+                Some((min, max)) if !(min.is_finite() && max.is_finite() && min < max) => {
+                    return Err(PsoError::InvalidOptions(format!(
+                        "{} range must satisfy min < max, got ({}, {})",
+                        name, min, max
+                    )));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Applies the changes to `particles` and `options`, which are left
+    /// untouched if any change is invalid.
+    pub fn apply(&self, particles: &mut Particles, options: &mut Options) -> Result<(), PsoError> {
+        self.validate(particles)?;
+
+        if let Some(new) = self.options {
+            *options = new;
+        }
+
+        if let Some(topology) = self.topology {
+            particles.set_topology(topology)?;
+        }
+
+        if let Some(algorithm) = self.algorithm {
+            particles.set_algorithm(algorithm)?;
+        }
+
+        if let Some(coefficients) = &self.coefficients {
+            particles.set_coefficients(coefficients.clone())?;
+        }
+
+        if let Some((min, max)) = self.value_range {
+            particles.set_value_range(min, max)?;
+        }
+
+        if let Some((min, max)) = self.velocity_range {
+            particles.set_velocity_range(min, max)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,337 @@
+use crate::pso::Particles;
+use ndarray::Array1;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Language of the text output of the library.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    Spanish,
+    English,
+}
+
+impl Language {
+    pub(crate) fn swarm(self) -> &'static str {
+        match self {
+            Language::Spanish => "Cúmulo de partículas",
+            Language::English => "Swarm",
+        }
+    }
+
+    pub(crate) fn personal_bests(self) -> &'static str {
+        match self {
+            Language::Spanish => "Mejores locales",
+            Language::English => "Personal bests",
+        }
+    }
+
+    pub(crate) fn global_best(self) -> &'static str {
+        match self {
+            Language::Spanish => "Mejor global",
+            Language::English => "Global best",
+        }
+    }
+
+    pub(crate) fn iteration_best(self) -> &'static str {
+        match self {
+            Language::Spanish => "Mejor en esta iteración",
+            Language::English => "Best on this iteration",
+        }
+    }
+
+    pub(crate) fn violations(self) -> &'static str {
+        match self {
+            Language::Spanish => "Violación de restricciones",
+            Language::English => "Constraint violations",
+        }
+    }
+
+    pub(crate) fn feasible(self, feasible: bool) -> &'static str {
+        match (self, feasible) {
+            (Language::Spanish, true) => "factible",
+            (Language::Spanish, false) => "infactible",
+            (Language::English, true) => "feasible",
+            (Language::English, false) => "infeasible",
+        }
+    }
+
+    pub(crate) fn feasible_particles(self) -> &'static str {
+        match self {
+            Language::Spanish => "Partículas factibles",
+            Language::English => "Feasible particles",
+        }
+    }
+
+    pub(crate) fn global_best_changed(self) -> &'static str {
+        match self {
+            Language::Spanish => "El mejor global cambió",
+            Language::English => "The global best changed",
+        }
+    }
+}
+
+/// How numbers are written in summaries, plots and the progress of every
+/// step. By default they're written in full, as `f64`'s `Display` does.
+#[derive(Debug, Copy, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct NumberFormat {
+    significant: Option<usize>,
+    scientific: Option<f64>,
+}
+
+impl NumberFormat {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rounds every number to `digits` significant digits.
+    pub fn significant(mut self, digits: usize) -> Self {
+        assert!(digits > 0, "At least one significant digit is needed");
+
+        self.significant = Some(digits);
+        self
+    }
+
+    /// Numbers with absolute value at least `threshold` or below
+    /// `1 / threshold` are written in scientific notation.
+    pub fn scientific(mut self, threshold: f64) -> Self {
+        assert!(
+            threshold > 1.0,
+            "Scientific threshold must be greater than 1"
+        );
+
+        self.scientific = Some(threshold);
+        self
+    }
+
+    pub fn format(&self, value: f64) -> String {
+        if value == 0.0 || !value.is_finite() {
+            return value.to_string();
+        }
+
+        let abs = value.abs();
+        let scientific = self
+            .scientific
+            .is_some_and(|threshold| abs >= threshold || abs < 1.0 / threshold);
+
+        match (self.significant, scientific) {
+            (Some(digits), true) => format!("{:.*e}", digits - 1, value),
+            (None, true) => format!("{:e}", value),
+            (Some(digits), false) => {
+                let exponent = abs.log10().floor() as i64;
+                let decimals = (digits as i64 - 1 - exponent).max(0) as usize;
+                format!("{:.*}", decimals, value)
+            }
+            (None, false) => value.to_string(),
+        }
+    }
+
+    /// `[a, b, ...]` with every component formatted.
+    pub fn array(&self, values: &Array1<f64>) -> String {
+        let values: Vec<_> = values.iter().map(|&v| self.format(v)).collect();
+        format!("[{}]", values.join(", "))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParticleSummary {
+    pub id: usize,
+    pub x: Vec<f64>,
+    pub v: Vec<f64>,
+    pub fitness: f64,
+    pub best: Vec<f64>,
+    pub best_fitness: f64,
+}
+
+/// Machine-readable summary of a swarm, see [`Particles::summary_json`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SwarmSummary {
+    pub steps: usize,
+    pub omega: Option<f64>,
+    pub particles: Vec<ParticleSummary>,
+    pub best: Vec<f64>,
+    pub best_fitness: f64,
+    /// How much the best violates every constraint, for fitnesses with
+    /// constraints.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub violations: Option<Vec<f64>>,
+    /// Amount of particles at a feasible position, for fitnesses with
+    /// constraints.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub feasible: Option<usize>,
+}
+
+impl From<&Particles<'_>> for SwarmSummary {
+    fn from(particles: &Particles) -> Self {
+        Self {
+            steps: particles.steps(),
+            omega: particles.omega(),
+            particles: particles
+                .particles()
+                .iter()
+                .map(|p| ParticleSummary {
+                    id: p.id(),
+                    x: p.value().to_vec(),
+                    v: p.velocity().to_vec(),
+                    fitness: p.fitness(),
+                    best: p.best().to_vec(),
+                    best_fitness: p.best_fitness(),
+                })
+                .collect(),
+            best: particles.best().to_vec(),
+            best_fitness: particles.best_fitness(),
+            violations: particles.best_violations(),
+            feasible: particles.feasible_particles(),
+        }
+    }
+}
+
+/// Human readable summary of a swarm with aligned columns, created with
+/// [`Particles::summary_with`].
+pub struct Summary<'s, 'a> {
+    particles: &'s Particles<'a>,
+    language: Language,
+    format: NumberFormat,
+    precision: Option<usize>,
+    show_particles: bool,
+}
+
+impl<'s, 'a> Summary<'s, 'a> {
+    pub(crate) fn new(particles: &'s Particles<'a>) -> Self {
+        Self {
+            particles,
+            language: particles.language(),
+            format: particles.number_format(),
+            precision: None,
+            show_particles: false,
+        }
+    }
+
+    pub fn language(mut self, language: Language) -> Self {
+        self.language = language;
+        self
+    }
+
+    pub fn number_format(mut self, format: NumberFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Decimals of every number, overrides the number format.
+    pub fn precision(mut self, precision: usize) -> Self {
+        self.precision = Some(precision);
+        self
+    }
+
+    /// Whether positions and velocities are included.
+    pub fn particles(mut self, show: bool) -> Self {
+        self.show_particles = show;
+        self
+    }
+
+    fn number(&self, value: f64) -> String {
+        match self.precision {
+            Some(precision) => format!("{:.*}", precision, value),
+            None => self.format.format(value),
+        }
+    }
+
+    /// Every component right aligned to `width`.
+    fn array(&self, values: &Array1<f64>, width: usize) -> String {
+        let values: Vec<_> = values
+            .iter()
+            .map(|&v| format!("{:>w$}", self.number(v), w = width))
+            .collect();
+
+        format!("[{}]", values.join(", "))
+    }
+}
+
+impl fmt::Display for Summary<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let particles = self.particles.particles();
+        let index = particles.len().to_string().len();
+
+        let components = particles
+            .iter()
+            .flat_map(|p| p.value().iter().chain(p.velocity()).chain(p.best()))
+            .chain(self.particles.best());
+        let width = components.map(|&v| self.number(v).len()).max().unwrap_or(0);
+
+        let fitness = particles
+            .iter()
+            .flat_map(|p| vec![p.fitness(), p.best_fitness()]);
+        let fitness_width = fitness.map(|v| self.number(v).len()).max().unwrap_or(0);
+
+        if self.show_particles {
+            writeln!(f, ">>> {} <<<", self.language.swarm())?;
+            for (i, particle) in particles.iter().enumerate() {
+                writeln!(
+                    f,
+                    "{:>iw$}) x: {},  v: {}",
+                    i + 1,
+                    self.array(particle.value(), width),
+                    self.array(particle.velocity(), width),
+                    iw = index
+                )?;
+            }
+        }
+
+        writeln!(f, ">>> Fitness <<<")?;
+        for (i, particle) in particles.iter().enumerate() {
+            writeln!(
+                f,
+                "{:>iw$}) {:>fw$}",
+                i + 1,
+                self.number(particle.fitness()),
+                iw = index,
+                fw = fitness_width
+            )?;
+        }
+
+        writeln!(f, ">>> {} <<<", self.language.personal_bests())?;
+        for (i, particle) in particles.iter().enumerate() {
+            writeln!(
+                f,
+                "{:>iw$}) x: {}, fitness: {:>fw$}",
+                i + 1,
+                self.array(particle.best(), width),
+                self.number(particle.best_fitness()),
+                iw = index,
+                fw = fitness_width
+            )?;
+        }
+
+        write!(
+            f,
+            ">>> {}: x: {}, fitness: {}",
+            self.language.global_best(),
+            self.array(self.particles.best(), width),
+            self.number(self.particles.best_fitness())
+        )?;
+
+        if let Some(violations) = self.particles.best_violations() {
+            let feasible = violations.iter().all(|&v| v <= 0.0);
+            let violations = Array1::from(violations);
+            write!(
+                f,
+                "\n>>> {}: {} ({})",
+                self.language.violations(),
+                self.array(&violations, 0),
+                self.language.feasible(feasible)
+            )?;
+        }
+
+        if let Some(feasible) = self.particles.feasible_particles() {
+            write!(
+                f,
+                "\n>>> {}: {} / {}",
+                self.language.feasible_particles(),
+                feasible,
+                particles.len()
+            )?;
+        }
+
+        Ok(())
+    }
+}
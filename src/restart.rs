@@ -0,0 +1,89 @@
+use crate::error::PsoError;
+use crate::pso::{Options, Particles};
+use crate::zones::Zone;
+use ndarray::Array1;
+
+#[derive(Debug, Copy, Clone)]
+pub struct RestartOptions {
+    /// Total amount of iterations, shared by all the restarts.
+    pub budget: usize,
+    /// Iterations without improving the global best before restarting.
+    pub stagnation: usize,
+    /// When set, every archived optimum becomes a spherical exclusion zone
+    /// of this radius, so the same basins aren't explored again. The zones
+    /// are kept on the swarm after the run.
+    pub tabu_radius: Option<f64>,
+}
+
+/// Best solution found by one of the restarts.
+#[derive(Debug, Clone)]
+pub struct Optimum {
+    pub value: Array1<f64>,
+    pub fitness: f64,
+    /// Index of the restart that found it, 0 is the initial swarm.
+    pub restart: usize,
+    /// Iteration (counted from the start of the run) where it was archived.
+    pub iter: usize,
+}
+
+impl<'a> Particles<'a> {
+    /// Runs until the budget is spent, archiving the global best and
+    /// re-initializing the swarm every time it stagnates. The archived optima
+    /// are returned sorted from best to worst.
+    pub fn run_with_restarts(
+        &mut self,
+        options: Options,
+        restart: RestartOptions,
+    ) -> Result<Vec<Optimum>, PsoError> {
+        options.validate()?;
+
+        if restart.stagnation == 0 {
+            return Err(PsoError::InvalidOptions(
+                "stagnation must be at least 1".to_string(),
+            ));
+        }
+
+        let mut archive = Vec::new();
+        let mut best = self.best_fitness();
+        let mut since_improvement = 0;
+
+        for iter in 1..=restart.budget {
+            self.step(options);
+
+            let fitness = self.best_fitness();
+            if self.improves(fitness, best) {
+                best = fitness;
+                since_improvement = 0;
+            } else {
+                since_improvement += 1;
+            }
+
+            if since_improvement >= restart.stagnation && iter < restart.budget {
+                if let Some(radius) = restart.tabu_radius {
+                    self.add_zone(Zone::sphere(self.best().clone(), radius))?;
+                }
+
+                archive.push(self.optimum(archive.len(), iter));
+                self.reinitialize();
+
+                best = self.best_fitness();
+                since_improvement = 0;
+            }
+        }
+
+        archive.push(self.optimum(archive.len(), restart.budget));
+
+        archive.sort_by(|a, b| self.compare(b.fitness, a.fitness));
+
+        Ok(archive)
+    }
+
+    fn optimum(&self, restart: usize, iter: usize) -> Optimum {
+        Optimum {
+            value: self.best().clone(),
+            fitness: self.best_fitness(),
+            restart,
+            iter,
+        }
+    }
+}
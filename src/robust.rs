@@ -0,0 +1,101 @@
+use crate::error::PsoError;
+use crate::fitness::{stable_sum, Comparison};
+use crate::pso::Particles;
+use ndarray::Array1;
+use serde::{Deserialize, Serialize};
+
+/// Reports the best of `candidates` distinct personal bests by their mean
+/// fitness over `repeats` evaluations, see [`Particles::set_robust`].
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RobustSelection {
+    pub candidates: usize,
+    pub repeats: usize,
+}
+
+impl RobustSelection {
+    pub fn validate(&self) -> Result<(), PsoError> {
+        if self.candidates == 0 {
+            return Err(PsoError::InvalidOptions(
+                "robust selection needs at least one candidate".to_string(),
+            ));
+        }
+
+        if self.repeats < 2 {
+            return Err(PsoError::InvalidOptions(format!(
+                "robust selection needs at least 2 repeats, got {}",
+                self.repeats
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// Solution re-evaluated several times, see [`Particles::robust_best`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Candidate {
+    /// In the original coordinates, also for normalized swarms.
+    pub value: Array1<f64>,
+    /// Mean fitness over every evaluation.
+    pub mean: f64,
+    /// Standard error of the mean.
+    pub std_error: f64,
+    pub evaluations: usize,
+}
+
+impl<'a> Particles<'a> {
+    /// For noisy objectives, re-evaluates the `k` best distinct personal
+    /// bests `repeats` times each and returns them sorted by mean fitness,
+    /// so the reported best isn't just a lucky evaluation.
+    pub fn robust_best(&self, k: usize, repeats: usize) -> Result<Vec<Candidate>, PsoError> {
+        RobustSelection {
+            candidates: k,
+            repeats,
+        }
+        .validate()?;
+
+        let fitness = self.fitness();
+        let comparison = self.comparison();
+        let mut bests: Vec<_> = self.particles().iter().collect();
+        bests.sort_by(|a, b| self.compare(b.best_fitness(), a.best_fitness()));
+
+        // Particles sharing a best can be anywhere in the order, their noisy
+        // fitnesses differ
+        let mut seen: Vec<&Array1<f64>> = Vec::new();
+        bests.retain(|particle| {
+            let distinct = !seen.contains(&particle.best());
+            if distinct {
+                seen.push(particle.best());
+            }
+            distinct
+        });
+
+        let mut candidates: Vec<_> = bests
+            .into_iter()
+            .take(k)
+            .map(|particle| {
+                let values: Vec<_> = (0..repeats)
+                    .map(|_| fitness.calculate(particle.best()))
+                    .collect();
+
+                let n = repeats as f64;
+                let mean = match comparison {
+                    Comparison::Partial => values.iter().sum::<f64>() / n,
+                    Comparison::Total => stable_sum(values.iter().copied()) / n,
+                };
+                let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0);
+
+                Candidate {
+                    value: self.denormalize(particle.best()),
+                    mean,
+                    std_error: (variance / n).sqrt(),
+                    evaluations: repeats,
+                }
+            })
+            .collect();
+
+        candidates.sort_by(|a, b| self.compare(b.mean, a.mean));
+
+        Ok(candidates)
+    }
+}
@@ -0,0 +1,146 @@
+use crate::error::PsoError;
+use crate::pso::Particles;
+use crate::surrogate::GaussianProcess;
+use serde::{Deserialize, Serialize};
+
+/// How the particles that aren't evaluated get their fitness estimated.
+#[derive(Debug, Copy, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub enum Estimate {
+    /// The fitness of their previous position.
+    #[default]
+    Previous,
+    /// Mean of a gaussian process fitted on the latest `memory` true
+    /// evaluations, see [`Surrogate`](crate::surrogate::Surrogate) for the
+    /// length scale. The previous fitness while it can't be fitted.
+    Surrogate { memory: usize, length_scale: f64 },
+}
+
+/// Selective evaluation for expensive objectives: every particle moves on
+/// each step, but only the `evaluated` most promising ones by their
+/// estimate get the true fitness, the rest keep the estimate as their
+/// fitness and are [marked](crate::pso::Particle::is_estimated).
+///
+/// Estimates never update the personal or global bests, nor reach the
+/// archive, the transcript or the memory of evaluations, so bests are
+/// always true evaluations. Only true evaluations count against the budget.
+/// Other algorithms than the standard one evaluate every particle.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Screening {
+    pub evaluated: usize,
+    pub estimate: Estimate,
+}
+
+impl Screening {
+    /// Evaluates the `evaluated` particles with the best previous fitness.
+    pub fn new(evaluated: usize) -> Self {
+        Self {
+            evaluated,
+            estimate: Estimate::default(),
+        }
+    }
+
+    /// Ranks the particles by the prediction of a gaussian process fitted
+    /// on the latest `memory` evaluations.
+    pub fn surrogate(mut self, memory: usize, length_scale: f64) -> Self {
+        self.estimate = Estimate::Surrogate {
+            memory,
+            length_scale,
+        };
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), PsoError> {
+        if self.evaluated == 0 {
+            return Err(PsoError::InvalidOptions(
+                "screening must evaluate at least one particle".to_string(),
+            ));
+        }
+
+        if let Estimate::Surrogate {
+            memory,
+            length_scale,
+        } = self.estimate
+        {
+            if memory < 2 {
+                return Err(PsoError::InvalidOptions(format!(
+                    "screening memory must be at least 2, got {}",
+                    memory
+                )));
+            }
+
+            if !(length_scale.is_finite() && length_scale > 0.0) {
+                return Err(PsoError::InvalidOptions(format!(
+                    "length scale must be finite and positive, got {}",
+                    length_scale
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Latest evaluations the swarm has to remember for the estimates.
+    pub fn memory(&self) -> usize {
+        match self.estimate {
+            Estimate::Previous => 0,
+            Estimate::Surrogate { memory, .. } => memory,
+        }
+    }
+}
+
+impl<'a> Particles<'a> {
+    /// Evaluates the most promising of the particles that `moved` (all of
+    /// them when `None`) and estimates the rest, see [`Screening`].
+    pub(crate) fn screen(&mut self, screening: &Screening, moved: Option<&[bool]>) {
+        let size = self.particles().len();
+        let process = match screening.estimate {
+            Estimate::Previous => None,
+            Estimate::Surrogate { length_scale, .. } => self.fit_estimator(length_scale),
+        };
+
+        let mut estimates: Vec<_> = (0..size)
+            .filter(|&i| moved.is_none_or(|moved| moved[i]))
+            .map(|i| {
+                let particle = &self.particles()[i];
+                let estimate = match &process {
+                    Some(process) => {
+                        let (mean, _) = process.predict(particle.value());
+                        self.fitness().to_maximization(mean)
+                    }
+                    None => particle.fitness(),
+                };
+
+                (i, estimate)
+            })
+            .collect();
+
+        // Best estimates first
+        estimates.sort_by(|a, b| self.compare(b.1, a.1));
+
+        let mut evaluated = screening.evaluated;
+        if let Some(left) = self.evaluations_left() {
+            evaluated = evaluated.min(left);
+        }
+
+        for (k, &(i, estimate)) in estimates.iter().enumerate() {
+            if k < evaluated {
+                self.evaluate_particle(i);
+            } else {
+                self.estimate_particle(i, estimate);
+            }
+        }
+    }
+
+    /// Gaussian process on the remembered evaluations, for maximization.
+    fn fit_estimator(&self, length_scale: f64) -> Option<GaussianProcess> {
+        let fitness = self.fitness();
+        let (inputs, outputs): (Vec<_>, Vec<_>) = self
+            .evaluations()
+            .iter()
+            .map(|(x, f)| (x.clone(), fitness.to_maximization(*f)))
+            .unzip();
+
+        let (min, max) = self.value_range();
+        GaussianProcess::fit(&inputs, &outputs, length_scale * (max - min))
+    }
+}
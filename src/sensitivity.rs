@@ -0,0 +1,140 @@
+use crate::error::PsoError;
+use crate::fitness::Fitness;
+use ndarray::{Array1, Array2};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Samples `samples` points around the best at the end of a run, in a box of
+/// half width `radius` times the width of the value range, see
+/// [`Particles::set_sensitivity_analysis`](crate::pso::Particles::set_sensitivity_analysis).
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SensitivityAnalysis {
+    pub radius: f64,
+    pub samples: usize,
+}
+
+impl SensitivityAnalysis {
+    pub fn validate(&self) -> Result<(), PsoError> {
+        if !(self.radius.is_finite() && self.radius >= 0.0) {
+            return Err(PsoError::InvalidOptions(format!(
+                "sensitivity radius must be finite and non-negative, got {}",
+                self.radius
+            )));
+        }
+
+        if self.samples < 4 {
+            return Err(PsoError::InvalidOptions(format!(
+                "sensitivity analysis needs at least 4 samples, got {}",
+                self.samples
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// How the fitness varies around a solution, see
+/// [`Particles::sensitivity`](crate::pso::Particles::sensitivity).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Sensitivity {
+    /// Half width of the sampled box on every dimension.
+    pub radius: Array1<f64>,
+    pub samples: usize,
+    /// Share of the local fitness variance explained by every dimension
+    /// alone (correlation ratio), between 0 and 1.
+    pub indices: Array1<f64>,
+    /// Mean absolute fitness change from the solution.
+    pub mean_change: f64,
+    /// Largest absolute fitness change from the solution.
+    pub max_change: f64,
+}
+
+/// `samples` points of the unit hypercube, every dimension has exactly one
+/// point on each of its `samples` strata.
+pub fn latin_hypercube(samples: usize, dim: usize, rng: &mut impl Rng) -> Array2<f64> {
+    let mut points = Array2::zeros((samples, dim));
+    let mut strata: Vec<usize> = (0..samples).collect();
+
+    for d in 0..dim {
+        strata.shuffle(rng);
+        for (i, &stratum) in strata.iter().enumerate() {
+            points[(i, d)] = (stratum as f64 + rng.gen_range(0.0, 1.0)) / samples as f64;
+        }
+    }
+
+    points
+}
+
+/// Evaluates a latin hypercube sample of the box `center ± radius`, of at
+/// least 4 points.
+pub fn sensitivity(
+    fitness: &Fitness<Array1<f64>>,
+    center: &Array1<f64>,
+    radius: &Array1<f64>,
+    samples: usize,
+    rng: &mut impl Rng,
+) -> Result<Sensitivity, PsoError> {
+    if samples < 4 {
+        return Err(PsoError::InvalidOptions(format!(
+            "sensitivity analysis needs at least 4 samples, got {}",
+            samples
+        )));
+    }
+
+    if center.len() != radius.len() {
+        return Err(PsoError::InvalidOptions(format!(
+            "radius has {} dimensions, the center has {}",
+            radius.len(),
+            center.len()
+        )));
+    }
+
+    let dim = center.len();
+    let points = latin_hypercube(samples, dim, rng);
+    let points = points.mapv(|t| 2.0 * t - 1.0) * radius + center;
+
+    let reference = fitness.calculate(center);
+    let values: Vec<f64> = points
+        .outer_iter()
+        .map(|x| fitness.calculate(&x.to_owned()))
+        .collect();
+
+    let mean = values.iter().sum::<f64>() / samples as f64;
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / samples as f64;
+
+    // Variance of the mean fitness on every stratum of a dimension, strata
+    // hold the same amount of points thanks to the latin hypercube
+    let bins = ((samples as f64).sqrt() as usize).max(2);
+    let indices = Array1::from_shape_fn(dim, |d| {
+        if variance == 0.0 {
+            return 0.0;
+        }
+
+        let mut order: Vec<usize> = (0..samples).collect();
+        order.sort_by(|&a, &b| {
+            points[(a, d)]
+                .partial_cmp(&points[(b, d)])
+                .expect("Received a NaN")
+        });
+
+        let explained: f64 = order
+            .chunks(samples.div_ceil(bins))
+            .map(|bin| {
+                let bin_mean = bin.iter().map(|&i| values[i]).sum::<f64>() / bin.len() as f64;
+                bin.len() as f64 * (bin_mean - mean).powi(2)
+            })
+            .sum();
+
+        (explained / samples as f64 / variance).min(1.0)
+    });
+
+    let changes = values.iter().map(|v| (v - reference).abs());
+    Ok(Sensitivity {
+        radius: radius.clone(),
+        samples,
+        indices,
+        mean_change: changes.clone().sum::<f64>() / samples as f64,
+        max_change: changes.fold(0.0, f64::max),
+    })
+}
@@ -0,0 +1,62 @@
+use crate::pso::Particles;
+use ndarray::Array1;
+use rand::Rng;
+
+/// Base swarm size of SL-PSO, the learning probability only drops below 1
+/// for problems with more dimensions than this.
+const BASE_SIZE: f64 = 100.0;
+
+impl<'a> Particles<'a> {
+    /// One round of social learning, see
+    /// [`Algorithm::SocialLearning`](crate::pso::Algorithm::SocialLearning).
+    pub(crate) fn learn_socially(&mut self, alpha: f64, beta: f64) {
+        let size = self.particles().len();
+        let dim = self.best().len();
+
+        let mut mean = Array1::zeros(dim);
+        for particle in self.particles() {
+            mean += particle.value();
+        }
+        mean /= size as f64;
+
+        // From worst to best, the best one never learns
+        let mut ranking: Vec<_> = (0..size).collect();
+        ranking.sort_by(|&a, &b| {
+            let particles = self.particles();
+            self.compare(particles[a].fitness(), particles[b].fitness())
+        });
+
+        let exponent = alpha * (dim as f64 / BASE_SIZE).ceil().ln();
+        let epsilon = beta * dim as f64 / BASE_SIZE;
+
+        for rank in 0..size - 1 {
+            let probability = (1.0 - rank as f64 / size as f64).powf(exponent);
+            if self.budget_exhausted() {
+                break;
+            }
+            if self.rng_mut().gen::<f64>() >= probability {
+                continue;
+            }
+
+            let rng = self.rng_mut();
+            let draws: Vec<(usize, f64, f64, f64)> = (0..dim)
+                .map(|_| {
+                    let demonstrator = ranking[rng.gen_range(rank + 1, size)];
+                    (demonstrator, rng.gen(), rng.gen(), rng.gen())
+                })
+                .collect();
+
+            let i = ranking[rank];
+            let particles = self.particles();
+            let (x, v) = (particles[i].value(), particles[i].velocity());
+            let velocity = Array1::from_shape_fn(dim, |d| {
+                let (k, r_1, r_2, r_3) = draws[d];
+                let demonstrator = particles[k].value()[d];
+
+                r_1 * v[d] + r_2 * (demonstrator - x[d]) + r_3 * epsilon * (mean[d] - x[d])
+            });
+
+            self.relocate(i, velocity);
+        }
+    }
+}
@@ -0,0 +1,59 @@
+//! Convergence regions of the inertia weight model, `phi` always refers to
+//! `phi_1 + phi_2` and omegas are restricted to `[0, 1)`.
+
+/// Stability condition used to compute the regions.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Condition {
+    /// Convergence of the expected position, `0 < phi < 4 (1 + omega)`.
+    Order1,
+    /// Convergence of the position variance (Poli, 2009),
+    /// `0 < phi < 24 (1 - omega^2) / (7 - 5 omega)`.
+    Order2,
+}
+
+/// Open range of `phi` that converges for the given omega, `None` if there's none.
+pub fn phi_range(omega: f64, condition: Condition) -> Option<(f64, f64)> {
+    if !(0.0..1.0).contains(&omega) {
+        return None;
+    }
+
+    let max = match condition {
+        Condition::Order1 => 4.0 * (1.0 + omega),
+        Condition::Order2 => 24.0 * (1.0 - omega * omega) / (7.0 - 5.0 * omega),
+    };
+
+    Some((0.0, max))
+}
+
+/// Range of omega for which the given `phi` converges, `None` if there's none.
+pub fn omega_range(phi: f64, condition: Condition) -> Option<(f64, f64)> {
+    if !phi.is_finite() || phi <= 0.0 {
+        return None;
+    }
+
+    let (min, max) = match condition {
+        Condition::Order1 => (phi / 4.0 - 1.0, 1.0),
+        Condition::Order2 => {
+            // Roots of 24 omega^2 - 5 phi omega - (24 - 7 phi)
+            let disc = 25.0 * phi * phi - 672.0 * phi + 2304.0;
+            if disc < 0.0 {
+                return None;
+            }
+
+            let sqrt = disc.sqrt();
+            ((5.0 * phi - sqrt) / 48.0, (5.0 * phi + sqrt) / 48.0)
+        }
+    };
+
+    let (min, max) = (min.max(0.0), max.min(1.0));
+    if min < max {
+        Some((min, max))
+    } else {
+        None
+    }
+}
+
+/// Whether the given coefficients satisfy the condition.
+pub fn is_convergent(omega: f64, phi: f64, condition: Condition) -> bool {
+    matches!(phi_range(omega, condition), Some((min, max)) if min < phi && phi < max)
+}
@@ -0,0 +1,282 @@
+use crate::error::PsoError;
+use crate::lineage::LineageEvent;
+use crate::pso::Particles;
+use ndarray::{Array1, Array2};
+use ndarray_rand::rand_distr::StandardNormal;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+/// Kriging infill for expensive objectives. Every `every` steps a gaussian
+/// process is fitted on the latest `memory` evaluations of the swarm, and
+/// the particle with the worst fitness becomes a scout, sent to the
+/// candidate with the highest expected improvement among `candidates`
+/// random ones (half of them around the global best).
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Surrogate {
+    pub every: usize,
+    pub memory: usize,
+    pub candidates: usize,
+    /// Length scale of the squared exponential kernel, relative to the
+    /// width of the value range.
+    pub length_scale: f64,
+}
+
+impl Default for Surrogate {
+    /// A scout every 5 steps, fitted on 100 evaluations, picked among 500
+    /// candidates with a length scale of 0.2.
+    fn default() -> Self {
+        Self {
+            every: 5,
+            memory: 100,
+            candidates: 500,
+            length_scale: 0.2,
+        }
+    }
+}
+
+impl Surrogate {
+    pub fn validate(&self) -> Result<(), PsoError> {
+        let counts = [
+            ("every", self.every, 1),
+            ("memory", self.memory, 2),
+            ("candidates", self.candidates, 1),
+        ];
+
+        for (name, value, min) in counts.iter() {
+            if value < min {
+                return Err(PsoError::InvalidOptions(format!(
+                    "surrogate {} must be at least {}, got {}",
+                    name, min, value
+                )));
+            }
+        }
+
+        if !(self.length_scale.is_finite() && self.length_scale > 0.0) {
+            return Err(PsoError::InvalidOptions(format!(
+                "length scale must be finite and positive, got {}",
+                self.length_scale
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// Gaussian process regression with a squared exponential kernel of fixed
+/// length scale, on standardized outputs.
+#[derive(Debug, Clone)]
+pub struct GaussianProcess {
+    inputs: Vec<Array1<f64>>,
+    length_scale: f64,
+    mean: f64,
+    std: f64,
+    /// Lower Cholesky factor of the kernel matrix.
+    factor: Array2<f64>,
+    weights: Array1<f64>,
+}
+
+/// Added to the diagonal of the kernel matrix, to keep it positive definite.
+const NUGGET: f64 = 1e-8;
+
+impl GaussianProcess {
+    /// Fits the process on the given samples, repeated inputs are only used
+    /// once. `None` with less than two distinct inputs or a singular kernel
+    /// matrix.
+    pub fn fit(inputs: &[Array1<f64>], outputs: &[f64], length_scale: f64) -> Option<Self> {
+        let mut samples: Vec<(&Array1<f64>, f64)> = Vec::with_capacity(inputs.len());
+        for (x, &y) in inputs.iter().zip(outputs) {
+            if samples.iter().all(|(seen, _)| *seen != x) {
+                samples.push((x, y));
+            }
+        }
+
+        let n = samples.len();
+        if n < 2 {
+            return None;
+        }
+        let (inputs, outputs): (Vec<_>, Vec<_>) =
+            samples.into_iter().map(|(x, y)| (x.clone(), y)).unzip();
+
+        let mean = outputs.iter().sum::<f64>() / n as f64;
+        let variance = outputs.iter().map(|y| (y - mean).powi(2)).sum::<f64>() / n as f64;
+        let std = if variance > 0.0 { variance.sqrt() } else { 1.0 };
+
+        let mut process = Self {
+            inputs: inputs.clone(),
+            length_scale,
+            mean,
+            std,
+            factor: Array2::zeros((n, n)),
+            weights: Array1::zeros(n),
+        };
+
+        let kernel = Array2::from_shape_fn((n, n), |(i, j)| {
+            let k = process.kernel(&inputs[i], &inputs[j]);
+            if i == j {
+                k + NUGGET
+            } else {
+                k
+            }
+        });
+        process.factor = cholesky(&kernel)?;
+
+        let targets = Array1::from_shape_fn(n, |i| (outputs[i] - mean) / std);
+        let forward = solve_lower(&process.factor, &targets);
+        process.weights = solve_upper(&process.factor, &forward);
+
+        Some(process)
+    }
+
+    fn kernel(&self, a: &Array1<f64>, b: &Array1<f64>) -> f64 {
+        let distance = (a - b).mapv(|v| v * v).sum();
+        (-distance / (2.0 * self.length_scale * self.length_scale)).exp()
+    }
+
+    /// Predicted mean and standard deviation at `x`.
+    pub fn predict(&self, x: &Array1<f64>) -> (f64, f64) {
+        let k = Array1::from_shape_fn(self.inputs.len(), |i| self.kernel(&self.inputs[i], x));
+        let mean = k.dot(&self.weights);
+        let v = solve_lower(&self.factor, &k);
+        let variance = (1.0 - v.dot(&v)).max(0.0);
+
+        (self.mean + self.std * mean, self.std * variance.sqrt())
+    }
+}
+
+/// Expected improvement over `best` of a prediction, when maximizing.
+pub fn expected_improvement(mean: f64, std: f64, best: f64) -> f64 {
+    let gain = mean - best;
+    if std <= 0.0 {
+        return gain.max(0.0);
+    }
+
+    let z = gain / std;
+    let pdf = (-0.5 * z * z).exp() / (2.0 * PI).sqrt();
+    let cdf = 0.5 * (1.0 + erf(z / 2f64.sqrt()));
+
+    gain * cdf + std * pdf
+}
+
+/// Error function, Abramowitz and Stegun 7.1.26 (error below 1.5e-7).
+pub(crate) fn erf(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.327_591_1 * x.abs());
+    let poly = t
+        * (0.254_829_592
+            + t * (-0.284_496_736
+                + t * (1.421_413_741 + t * (-1.453_152_027 + t * 1.061_405_429))));
+    let y = 1.0 - poly * (-x * x).exp();
+
+    if x >= 0.0 {
+        y
+    } else {
+        -y
+    }
+}
+
+fn cholesky(matrix: &Array2<f64>) -> Option<Array2<f64>> {
+    let n = matrix.nrows();
+    let mut factor = Array2::zeros((n, n));
+
+    for j in 0..n {
+        let diagonal =
+            matrix[[j, j]] - (0..j).map(|k| factor[[j, k]] * factor[[j, k]]).sum::<f64>();
+        if diagonal <= 0.0 || !diagonal.is_finite() {
+            return None;
+        }
+        factor[[j, j]] = diagonal.sqrt();
+
+        for i in j + 1..n {
+            let dot = (0..j).map(|k| factor[[i, k]] * factor[[j, k]]).sum::<f64>();
+            factor[[i, j]] = (matrix[[i, j]] - dot) / factor[[j, j]];
+        }
+    }
+
+    Some(factor)
+}
+
+/// Solves `L x = b` for a lower triangular `L`.
+fn solve_lower(factor: &Array2<f64>, b: &Array1<f64>) -> Array1<f64> {
+    let mut x = Array1::zeros(b.len());
+    for i in 0..b.len() {
+        let dot = (0..i).map(|k| factor[[i, k]] * x[k]).sum::<f64>();
+        x[i] = (b[i] - dot) / factor[[i, i]];
+    }
+
+    x
+}
+
+/// Solves `Lᵀ x = b` for a lower triangular `L`.
+fn solve_upper(factor: &Array2<f64>, b: &Array1<f64>) -> Array1<f64> {
+    let n = b.len();
+    let mut x = Array1::zeros(n);
+    for i in (0..n).rev() {
+        let dot = (i + 1..n).map(|k| factor[[k, i]] * x[k]).sum::<f64>();
+        x[i] = (b[i] - dot) / factor[[i, i]];
+    }
+
+    x
+}
+
+impl<'a> Particles<'a> {
+    /// Sends the worst particle to the maximizer of the expected
+    /// improvement, see [`Surrogate`]. Returns whether it did, which
+    /// doesn't happen between scouts or when the process can't be fitted.
+    pub(crate) fn scout(&mut self, surrogate: &Surrogate) -> bool {
+        if !self.steps().is_multiple_of(surrogate.every) {
+            return false;
+        }
+
+        let fitness = self.fitness();
+        let (inputs, outputs): (Vec<_>, Vec<_>) = self
+            .evaluations()
+            .iter()
+            .map(|(x, f)| (x.clone(), fitness.to_maximization(*f)))
+            .unzip();
+
+        let (min, max) = self.value_range();
+        let spread = surrogate.length_scale * (max - min);
+        let process = match GaussianProcess::fit(&inputs, &outputs, spread) {
+            Some(process) => process,
+            None => return false,
+        };
+        let best = outputs.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+
+        let center = self.best().clone();
+        let dim = center.len();
+        let mut scout = None;
+        let mut top = f64::NEG_INFINITY;
+        for k in 0..surrogate.candidates {
+            let rng = self.rng_mut();
+            let mut candidate = if k % 2 == 0 {
+                Array1::from_shape_fn(dim, |_| rng.gen_range(min, max))
+            } else {
+                center.mapv(|x| {
+                    let z: f64 = rng.sample(StandardNormal);
+                    (x + z * spread).clamp(min, max)
+                })
+            };
+            self.fix_position(&mut candidate);
+
+            let (mean, std) = process.predict(&candidate);
+            let improvement = expected_improvement(mean, std, best);
+            if improvement > top {
+                top = improvement;
+                scout = Some(candidate);
+            }
+        }
+
+        let worst = (0..self.particles().len()).min_by(|&a, &b| {
+            let particles = self.particles();
+            self.compare(particles[a].fitness(), particles[b].fitness())
+        });
+
+        match (worst, scout) {
+            (Some(worst), Some(scout)) => {
+                self.place(worst, scout, LineageEvent::Scouted);
+                true
+            }
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,211 @@
+use crate::observer::{Control, Observer};
+use crate::pso::Particles;
+use serde::Serialize;
+use std::fmt::Write as _;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Instant;
+
+/// Latest values recorded by [`Metrics`].
+#[derive(Debug, Copy, Clone, Default, PartialEq, Serialize)]
+pub struct MetricsSnapshot {
+    /// Steps observed since the run started.
+    pub iterations: usize,
+    /// Mean steps per second since the run started.
+    pub iterations_per_second: f64,
+    /// Fitness evaluations of the swarm, including the initial ones.
+    pub evaluations: usize,
+    pub best_fitness: f64,
+    /// Mean distance to the centroid, see
+    /// [`Diversity`](crate::diversity::Diversity).
+    pub diversity: f64,
+    /// Whether a run is in progress.
+    pub running: bool,
+}
+
+struct State {
+    snapshot: MetricsSnapshot,
+    start: Instant,
+}
+
+/// Records metrics of a long run for observability, works as an observer
+/// of [`Particles::run_with`]. Clones share the same values, so a clone can
+/// be [served](Self::serve) on another thread as a Prometheus endpoint while
+/// the run goes on.
+#[derive(Clone)]
+pub struct Metrics {
+    state: Arc<Mutex<State>>,
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        let state = State {
+            snapshot: MetricsSnapshot::default(),
+            start: Instant::now(),
+        };
+
+        Self {
+            state: Arc::new(Mutex::new(state)),
+        }
+    }
+
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        self.state.lock().expect("Poisoned metrics").snapshot
+    }
+
+    fn record(&self, iterations: usize, particles: &Particles, running: bool) {
+        let mut state = self.state.lock().expect("Poisoned metrics");
+        if iterations == 0 {
+            state.start = Instant::now();
+        }
+
+        let elapsed = state.start.elapsed().as_secs_f64();
+        state.snapshot = MetricsSnapshot {
+            iterations,
+            iterations_per_second: if elapsed > 0.0 {
+                iterations as f64 / elapsed
+            } else {
+                0.0
+            },
+            evaluations: particles.evaluations_used(),
+            best_fitness: particles.best_fitness(),
+            diversity: particles.diversity(0.0).mean_distance,
+            running,
+        };
+    }
+
+    /// Current values in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let snapshot = self.snapshot();
+        let metrics = [
+            (
+                "pso_iterations_total",
+                "counter",
+                "Steps done since the run started.",
+                snapshot.iterations as f64,
+            ),
+            (
+                "pso_iterations_per_second",
+                "gauge",
+                "Mean steps per second since the run started.",
+                snapshot.iterations_per_second,
+            ),
+            (
+                "pso_evaluations_total",
+                "counter",
+                "Fitness evaluations of the swarm.",
+                snapshot.evaluations as f64,
+            ),
+            (
+                "pso_best_fitness",
+                "gauge",
+                "Fitness of the global best.",
+                snapshot.best_fitness,
+            ),
+            (
+                "pso_diversity",
+                "gauge",
+                "Mean distance of the particles to their centroid.",
+                snapshot.diversity,
+            ),
+            (
+                "pso_running",
+                "gauge",
+                "Whether a run is in progress.",
+                snapshot.running as u8 as f64,
+            ),
+        ];
+
+        let mut text = String::new();
+        for (name, kind, help, value) in metrics.iter() {
+            // Writing to a string can't fail
+            let _ = writeln!(text, "# HELP {} {}", name, help);
+            let _ = writeln!(text, "# TYPE {} {}", name, kind);
+            let _ = writeln!(text, "{} {}", name, prometheus_value(*value));
+        }
+
+        text
+    }
+
+    /// Answers `GET /metrics` on every connection to `listener`, each one
+    /// on its own thread. Only returns if accepting fails.
+    pub fn serve(&self, listener: TcpListener) -> io::Result<()> {
+        for stream in listener.incoming() {
+            let metrics = self.clone();
+            let stream = stream?;
+            thread::spawn(move || metrics.handle(stream));
+        }
+
+        Ok(())
+    }
+
+    /// Answers a single HTTP request and closes the connection.
+    pub fn handle(&self, stream: TcpStream) -> io::Result<()> {
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let mut request = String::new();
+        reader.read_line(&mut request)?;
+
+        // The headers are ignored, but have to be read before answering
+        let mut line = String::new();
+        while reader.read_line(&mut line)? > 0 && line.trim_end() != "" {
+            line.clear();
+        }
+
+        let mut parts = request.split_whitespace();
+        let (status, body) = match (parts.next(), parts.next()) {
+            (Some("GET"), Some("/metrics")) => ("200 OK", self.render()),
+            _ => ("404 Not Found", "not found\n".to_string()),
+        };
+
+        let mut stream = stream;
+        write!(
+            stream,
+            "HTTP/1.1 {}\r\n\
+             Content-Type: text/plain; version=0.0.4\r\n\
+             Content-Length: {}\r\n\
+             Connection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        )?;
+        stream.flush()
+    }
+}
+
+/// Prometheus spells the special values `+Inf`, `-Inf` and `NaN`.
+fn prometheus_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value == f64::INFINITY {
+        "+Inf".to_string()
+    } else if value == f64::NEG_INFINITY {
+        "-Inf".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+impl Observer for Metrics {
+    fn on_start(&mut self, particles: &Particles) -> Control {
+        self.record(0, particles, true);
+        Control::Continue
+    }
+
+    fn on_step(&mut self, iter: usize, particles: &Particles) -> Control {
+        self.record(iter, particles, true);
+        Control::Continue
+    }
+
+    fn on_finish(&mut self, particles: &Particles) {
+        let iterations = self.snapshot().iterations;
+        self.record(iterations, particles, false);
+    }
+}
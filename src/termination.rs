@@ -0,0 +1,123 @@
+use crate::observer::{Control, Observer};
+use crate::pso::Particles;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// What counts as no improvement for [`Plateau`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Tolerance {
+    /// `|f_now - f_before| <= rtol * |f_before|`.
+    Relative(f64),
+    /// `|f_now - f_before| <= atol`.
+    Absolute(f64),
+}
+
+/// Stops the run once the best fitness improved less than the tolerance
+/// over the last `window` iterations, works as an observer of
+/// [`Particles::run_with`].
+#[derive(Debug, Clone)]
+pub struct Plateau {
+    window: usize,
+    tolerance: Tolerance,
+    bests: VecDeque<f64>,
+}
+
+impl Plateau {
+    pub fn new(window: usize, tolerance: Tolerance) -> Self {
+        assert!(window > 0, "The window must have at least one iteration");
+
+        Self {
+            window,
+            tolerance,
+            bests: VecDeque::with_capacity(window + 1),
+        }
+    }
+
+    pub fn relative(window: usize, rtol: f64) -> Self {
+        Self::new(window, Tolerance::Relative(rtol))
+    }
+
+    pub fn absolute(window: usize, atol: f64) -> Self {
+        Self::new(window, Tolerance::Absolute(atol))
+    }
+
+    /// Records the current best fitness, returns whether there's a plateau.
+    pub fn update(&mut self, best: f64) -> bool {
+        if self.bests.len() > self.window {
+            self.bests.pop_front();
+        }
+
+        self.bests.push_back(best);
+        if self.bests.len() <= self.window {
+            return false;
+        }
+
+        let before = self.bests[0];
+        let improvement = (best - before).abs();
+
+        match self.tolerance {
+            Tolerance::Relative(rtol) => improvement <= rtol * before.abs(),
+            Tolerance::Absolute(atol) => improvement <= atol,
+        }
+    }
+}
+
+impl Observer for Plateau {
+    fn on_start(&mut self, particles: &Particles) -> Control {
+        self.bests.clear();
+        self.update(particles.best_fitness());
+        Control::Continue
+    }
+
+    fn on_step(&mut self, _iter: usize, particles: &Particles) -> Control {
+        if self.update(particles.best_fitness()) {
+            Control::Stop
+        } else {
+            Control::Continue
+        }
+    }
+}
+
+/// Stops the run before the next step would exceed the wall-clock budget,
+/// estimating it from the mean time per step so far.
+#[derive(Debug, Clone)]
+pub struct Deadline {
+    budget: Duration,
+    start: Instant,
+}
+
+impl Deadline {
+    pub fn new(budget: Duration) -> Self {
+        Self {
+            budget,
+            start: Instant::now(),
+        }
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+impl Observer for Deadline {
+    fn on_start(&mut self, _particles: &Particles) -> Control {
+        self.start = Instant::now();
+
+        if self.budget.is_zero() {
+            Control::Stop
+        } else {
+            Control::Continue
+        }
+    }
+
+    fn on_step(&mut self, iter: usize, _particles: &Particles) -> Control {
+        let elapsed = self.start.elapsed();
+        let per_step = elapsed / iter.max(1) as u32;
+
+        if elapsed + per_step > self.budget {
+            Control::Stop
+        } else {
+            Control::Continue
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use crate::error::PsoError;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Which particles inform each other, every particle is attracted to the
+/// best personal best of its neighborhood instead of the global best.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Topology {
+    /// Every particle informs every other one (gbest).
+    #[default]
+    Global,
+    /// Particles `i - radius ..= i + radius`, wrapping around (lbest).
+    Ring { radius: usize },
+    /// 2D lattice where every particle is informed by the ones above,
+    /// below, left and right of it. Particles are laid out row by row, so
+    /// `rows * cols` must be the swarm size.
+    VonNeumann {
+        rows: usize,
+        cols: usize,
+        wrap: bool,
+    },
+    /// Every particle informs itself and `k` random others (drawn with
+    /// replacement), the links are re-sampled after every step the global
+    /// best doesn't improve, as in SPSO-2011.
+    Random { k: usize },
+    /// H-PSO, particles are laid out as a tree where every node has up to
+    /// `branching` children (node `i` has parent `(i - 1) / branching`) and
+    /// are attracted to their parent. After every step, particles whose
+    /// best is better than their parent's swap places with it.
+    Hierarchy { branching: usize },
+}
+
+impl Topology {
+    /// Wrapping Von Neumann grid with the squarest shape for `size`.
+    pub fn von_neumann(size: usize) -> Self {
+        let rows = (1..=(size as f64).sqrt() as usize)
+            .rev()
+            .find(|rows| size.is_multiple_of(*rows))
+            .unwrap_or(1);
+
+        Topology::VonNeumann {
+            rows,
+            cols: size / rows.max(1),
+            wrap: true,
+        }
+    }
+
+    pub fn validate(&self, size: usize) -> Result<(), PsoError> {
+        match *self {
+            Topology::Global => Ok(()),
+            Topology::Ring { radius: 0 } => Err(PsoError::InvalidOptions(
+                "ring radius must be positive".to_string(),
+            )),
+            Topology::Ring { .. } => Ok(()),
+            Topology::VonNeumann { rows, cols, .. } if rows * cols != size => {
+                Err(PsoError::InvalidOptions(format!(
+                    "grid of {}x{} doesn't fit a swarm of {}",
+                    rows, cols, size
+                )))
+            }
+            Topology::VonNeumann { .. } => Ok(()),
+            Topology::Random { k: 0 } => Err(PsoError::InvalidOptions(
+                "random topology needs at least one informant".to_string(),
+            )),
+            Topology::Random { .. } => Ok(()),
+            Topology::Hierarchy { branching } if branching < 2 => Err(PsoError::InvalidOptions(
+                "hierarchy branching must be at least 2".to_string(),
+            )),
+            Topology::Hierarchy { .. } => Ok(()),
+        }
+    }
+
+    /// SPSO-2011 random topology, three informants per particle.
+    pub fn spso_2011() -> Self {
+        Topology::Random { k: 3 }
+    }
+
+    /// Whether the neighborhoods are re-sampled when the global best
+    /// doesn't improve.
+    pub fn is_adaptive(&self) -> bool {
+        matches!(self, Topology::Random { .. })
+    }
+
+    /// Neighborhood of every particle, including itself, `None` for the
+    /// global topology. `rng` is only used by the random topology.
+    pub fn neighborhoods(&self, size: usize, rng: &mut impl Rng) -> Option<Vec<Vec<usize>>> {
+        let neighborhoods = match *self {
+            Topology::Global => return None,
+            Topology::Ring { radius } => (0..size)
+                .map(|i| {
+                    let mut neighbors: Vec<_> = (0..=2 * radius)
+                        .map(|k| (i + size * radius + k - radius) % size)
+                        .collect();
+
+                    neighbors.sort_unstable();
+                    neighbors.dedup();
+                    neighbors
+                })
+                .collect(),
+            Topology::VonNeumann { rows, cols, wrap } => (0..size)
+                .map(|i| {
+                    let (r, c) = (i / cols, i % cols);
+                    let mut neighbors = vec![i];
+
+                    let moves = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+                    for (dr, dc) in moves.iter() {
+                        let (r, c) = (r as isize + dr, c as isize + dc);
+                        let (r, c) = if wrap {
+                            (r.rem_euclid(rows as isize), c.rem_euclid(cols as isize))
+                        } else if r < 0 || c < 0 || r >= rows as isize || c >= cols as isize {
+                            continue;
+                        } else {
+                            (r, c)
+                        };
+
+                        neighbors.push(r as usize * cols + c as usize);
+                    }
+
+                    neighbors.sort_unstable();
+                    neighbors.dedup();
+                    neighbors
+                })
+                .collect(),
+            Topology::Hierarchy { branching } => (0..size)
+                .map(|i| match parent(i, branching) {
+                    Some(parent) => vec![parent, i],
+                    None => vec![i],
+                })
+                .collect(),
+            Topology::Random { k } => {
+                let mut neighborhoods: Vec<_> = (0..size).map(|i| vec![i]).collect();
+                for i in 0..size {
+                    for _ in 0..k {
+                        neighborhoods[rng.gen_range(0, size)].push(i);
+                    }
+                }
+
+                for neighbors in &mut neighborhoods {
+                    neighbors.sort_unstable();
+                    neighbors.dedup();
+                }
+
+                neighborhoods
+            }
+        };
+
+        Some(neighborhoods)
+    }
+}
+
+/// Parent of node `i` in a tree where every node has `branching` children.
+fn parent(i: usize, branching: usize) -> Option<usize> {
+    if i == 0 {
+        None
+    } else {
+        Some((i - 1) / branching)
+    }
+}
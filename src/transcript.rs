@@ -0,0 +1,101 @@
+use ndarray::Array1;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// One evaluation of the fitness on a particle's position.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Evaluation {
+    /// Steps done by the swarm when it happened, 0 before the first one.
+    pub iter: usize,
+    pub id: usize,
+    pub value: Array1<f64>,
+    pub fitness: f64,
+    /// Seconds since the UNIX epoch.
+    pub timestamp: f64,
+}
+
+/// Append-only log of every evaluation of a swarm, as JSON lines, see
+/// [`Particles::set_transcript`](crate::pso::Particles::set_transcript).
+pub struct Transcript {
+    writer: Box<dyn Write + Send>,
+    error: Option<io::Error>,
+    written: usize,
+}
+
+impl fmt::Debug for Transcript {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Transcript")
+            .field("written", &self.written)
+            .finish()
+    }
+}
+
+impl Transcript {
+    pub fn new<W: Write + Send + 'static>(writer: W) -> Self {
+        Self {
+            writer: Box::new(writer),
+            error: None,
+            written: 0,
+        }
+    }
+
+    /// Appends to the file at `path`, creating it if needed, so a resumed
+    /// run keeps extending the same log.
+    pub fn append<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self::new(BufWriter::new(file)))
+    }
+
+    /// Every evaluation logged in the file at `path`.
+    pub fn read<P: AsRef<Path>>(path: P) -> io::Result<Vec<Evaluation>> {
+        BufReader::new(File::open(path)?)
+            .lines()
+            .map(|line| Ok(serde_json::from_str(&line?)?))
+            .collect()
+    }
+
+    /// Evaluations written so far.
+    pub fn written(&self) -> usize {
+        self.written
+    }
+
+    /// Logs an evaluation, after the first error nothing else is written.
+    pub fn write(&mut self, iter: usize, id: usize, value: &Array1<f64>, fitness: f64) {
+        if self.error.is_some() {
+            return;
+        }
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0.0, |elapsed| elapsed.as_secs_f64());
+        let evaluation = Evaluation {
+            iter,
+            id,
+            value: value.clone(),
+            fitness,
+            timestamp,
+        };
+
+        let written = serde_json::to_writer(&mut self.writer, &evaluation)
+            .map_err(io::Error::from)
+            .and_then(|()| writeln!(self.writer));
+
+        match written {
+            Ok(()) => self.written += 1,
+            Err(err) => self.error = Some(err),
+        }
+    }
+
+    /// Flushes the log, or returns the first error found while writing.
+    pub fn finish(&mut self) -> io::Result<()> {
+        if let Some(err) = self.error.take() {
+            return Err(err);
+        }
+
+        self.writer.flush()
+    }
+}
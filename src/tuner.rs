@@ -0,0 +1,244 @@
+use crate::builder::SwarmBuilder;
+use crate::coordinates::Coordinate;
+use crate::error::PsoError;
+use crate::fitness::Fitness;
+use crate::pso::{Confinement, Options};
+use ndarray::Array1;
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Value of every parameter by name, integers are already rounded.
+pub type Config = BTreeMap<String, f64>;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ParamKind {
+    Float,
+    Integer,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Param {
+    pub name: String,
+    pub min: f64,
+    pub max: f64,
+    pub kind: ParamKind,
+    /// Coordinate the range is searched in.
+    pub coordinate: Coordinate,
+}
+
+impl Param {
+    /// Value at `t` in `[0, 1]`, linear in the search coordinate.
+    fn value(&self, t: f64) -> f64 {
+        let (lo, hi) = (
+            self.coordinate.encode(self.min),
+            self.coordinate.encode(self.max),
+        );
+        let value = self.coordinate.decode(lo + t * (hi - lo));
+        let value = value.clamp(self.min, self.max);
+
+        match self.kind {
+            ParamKind::Float => value,
+            ParamKind::Integer => value.round(),
+        }
+    }
+
+    fn validate(&self) -> Result<(), PsoError> {
+        let invalid = |rule: &str| {
+            Err(PsoError::InvalidOptions(format!(
+                "parameter {} {}, got ({}, {})",
+                self.name, rule, self.min, self.max
+            )))
+        };
+
+        if !self.min.is_finite() || !self.max.is_finite() || self.min >= self.max {
+            return invalid("must satisfy min < max");
+        }
+
+        match self.coordinate {
+            Coordinate::Log10 if self.min <= 0.0 => invalid("needs a positive range"),
+            Coordinate::Logit if self.min <= 0.0 || self.max >= 1.0 => {
+                invalid("needs a range inside (0, 1)")
+            }
+            Coordinate::Affine { scale: 0.0, .. } => invalid("needs a non-zero affine scale"),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Hyperparameter search over named parameters (e.g. a learning rate
+/// searched in `Log10` and an integer amount of layers), the objective
+/// receives a [`Config`] and the swarm moves in the unit hypercube.
+#[derive(Debug, Clone)]
+pub struct Tuner {
+    params: Vec<Param>,
+    size: usize,
+    iters: usize,
+    seed: Option<u64>,
+    options: Options,
+}
+
+impl Default for Tuner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tuner {
+    pub fn new() -> Self {
+        Self {
+            params: Vec::new(),
+            size: 20,
+            iters: 50,
+            seed: None,
+            options: Options::canonical(),
+        }
+    }
+
+    pub fn float(self, name: impl Into<String>, min: f64, max: f64) -> Self {
+        self.param(name.into(), min, max, ParamKind::Float)
+    }
+
+    pub fn integer(self, name: impl Into<String>, min: f64, max: f64) -> Self {
+        self.param(name.into(), min, max, ParamKind::Integer)
+    }
+
+    fn param(mut self, name: String, min: f64, max: f64, kind: ParamKind) -> Self {
+        self.params.push(Param {
+            name,
+            min,
+            max,
+            kind,
+            coordinate: Coordinate::Linear,
+        });
+
+        self
+    }
+
+    /// Searches the last added parameter in the given coordinate, e.g.
+    /// `Log10` for learning rates.
+    pub fn transform(mut self, coordinate: Coordinate) -> Self {
+        let param = self.params.last_mut().expect("No parameters were added");
+        param.coordinate = coordinate;
+        self
+    }
+
+    pub fn size(mut self, size: usize) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn iters(mut self, iters: usize) -> Self {
+        self.iters = iters;
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn options(mut self, options: Options) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub fn params(&self) -> &[Param] {
+        &self.params
+    }
+
+    pub fn validate(&self) -> Result<(), PsoError> {
+        if self.params.is_empty() {
+            return Err(PsoError::InvalidOptions(
+                "at least one parameter is needed".to_string(),
+            ));
+        }
+
+        for (i, param) in self.params.iter().enumerate() {
+            param.validate()?;
+
+            if self.params[..i].iter().any(|p| p.name == param.name) {
+                return Err(PsoError::InvalidOptions(format!(
+                    "parameter {} is defined twice",
+                    param.name
+                )));
+            }
+        }
+
+        self.options.validate()
+    }
+
+    /// Configuration at the point `t` of the unit hypercube.
+    pub fn config(&self, t: &Array1<f64>) -> Config {
+        self.params
+            .iter()
+            .zip(t.iter())
+            .map(|(param, &t)| (param.name.clone(), param.value(t)))
+            .collect()
+    }
+
+    /// Searches the configuration with the best score.
+    pub fn tune<F>(&self, objective: F, minimization: bool) -> Result<TuningReport, PsoError>
+    where
+        F: Fn(&Config) -> f64,
+    {
+        self.validate()?;
+
+        let evaluations = Cell::new(0);
+        let fitness = Fitness::new(
+            |t: &Array1<f64>| {
+                evaluations.set(evaluations.get() + 1);
+                objective(&self.config(t))
+            },
+            minimization,
+        );
+
+        let builder = SwarmBuilder::new(fitness)
+            .size(self.size)
+            .dim(self.params.len())
+            .value_range(0.0, 1.0)
+            .velocity_range(-0.1, 0.1)
+            .confinement(Confinement::Bounded);
+        let builder = match self.seed {
+            Some(seed) => builder.seed(seed),
+            None => builder,
+        };
+
+        let mut particles = builder.build()?;
+        particles.set_verbose(false);
+        let iters = particles.run(self.options, self.iters)?;
+
+        Ok(TuningReport {
+            best: self.config(particles.best()),
+            score: particles.best_fitness(),
+            evaluations: evaluations.get(),
+            iters,
+        })
+    }
+}
+
+/// Result of [`Tuner::tune`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TuningReport {
+    pub best: Config,
+    pub score: f64,
+    pub evaluations: usize,
+    pub iters: usize,
+}
+
+impl fmt::Display for TuningReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Best configuration (score: {}, {} evaluations):",
+            self.score, self.evaluations
+        )?;
+
+        let width = self.best.keys().map(|name| name.len()).max().unwrap_or(0);
+        for (name, value) in &self.best {
+            writeln!(f, "  {:<w$} = {}", name, value, w = width)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,258 @@
+use crate::error::PsoError;
+use crate::pso::Options;
+use ndarray::{Array1, Array2, ArrayView1, Zip};
+use ndarray_rand::rand_distr::StandardNormal;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// How a particle's velocity is updated from its best `p` and the best of
+/// its neighborhood `l`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum VelocityPolicy {
+    /// `ω v + φ1 r1 (p - x) + φ2 r2 (l - x)`, one random coefficient per term.
+    /// Rotation invariant, but particles only move in the span of their
+    /// velocity and attractors.
+    #[default]
+    Classic,
+    /// Same as [`Classic`](Self::Classic) with one random coefficient per
+    /// dimension, the textbook update. Biased towards the coordinate axes,
+    /// so it's sensitive to rotations of the problem.
+    Componentwise,
+    /// Same as [`Componentwise`](Self::Componentwise), but the per-dimension
+    /// coefficients are applied on a random orthonormal basis (`R D Rᵀ`,
+    /// drawn for every term), which makes it rotation invariant.
+    RandomRotation,
+    /// SPSO-2011 update `ω v + x' - x`, where `x'` is sampled in the
+    /// hypersphere centered at `G = x + (φ1 (p - x) + φ2 (l - x)) / 3` with
+    /// radius `|G - x|` (`G = x + φ1 (p - x) / 2` when `l` is `p`).
+    Hypersphere,
+}
+
+/// Per-dimension `ω`, `φ1` and `φ2` used instead of the scalar ones in
+/// [`Options`], so stiff dimensions of anisotropic problems can be damped
+/// while the others keep exploring. `omega: None` uses the omega of the
+/// options on every dimension.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Coefficients {
+    pub omega: Option<Array1<f64>>,
+    pub phi_1: Array1<f64>,
+    pub phi_2: Array1<f64>,
+}
+
+impl Coefficients {
+    /// The scalar coefficients of `options` on each of the `dim` dimensions.
+    pub fn uniform(dim: usize, options: &Options) -> Self {
+        Self {
+            omega: options.omega.map(|omega| Array1::from_elem(dim, omega)),
+            phi_1: Array1::from_elem(dim, options.phi_1),
+            phi_2: Array1::from_elem(dim, options.phi_2),
+        }
+    }
+
+    pub fn validate(&self, dim: usize) -> Result<(), PsoError> {
+        let arrays = [
+            ("omega", self.omega.as_ref()),
+            ("phi_1", Some(&self.phi_1)),
+            ("phi_2", Some(&self.phi_2)),
+        ];
+
+        for (name, array) in arrays.iter() {
+            let array = match array {
+                Some(array) => array,
+                None => continue,
+            };
+
+            if array.len() != dim {
+                return Err(PsoError::InvalidOptions(format!(
+                    "{} has {} coefficients, expected {}",
+                    name,
+                    array.len(),
+                    dim
+                )));
+            }
+
+            if !array.iter().all(|c| c.is_finite() && *c >= 0.0) {
+                return Err(PsoError::InvalidOptions(format!(
+                    "{} coefficients must be non-negative",
+                    name
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl VelocityPolicy {
+    /// Replaces `rands` with the random numbers needed to move a particle
+    /// with `dim` dimensions.
+    pub(crate) fn draw(&self, dim: usize, rng: &mut impl Rng, rands: &mut Vec<f64>) {
+        rands.clear();
+        match self {
+            VelocityPolicy::Classic => {
+                rands.push(rng.gen_range(0.0, 1.0));
+                rands.push(rng.gen_range(0.0, 1.0));
+            }
+            VelocityPolicy::Componentwise => {
+                rands.extend((0..2 * dim).map(|_| rng.gen_range(0.0, 1.0)));
+            }
+            VelocityPolicy::RandomRotation => {
+                // Gaussian matrix and coefficients of both terms
+                for _ in 0..2 {
+                    rands.extend((0..dim * dim).map(|_| rng.sample::<f64, _>(StandardNormal)));
+                    rands.extend((0..dim).map(|_| rng.gen_range(0.0, 1.0)));
+                }
+            }
+            VelocityPolicy::Hypersphere => {
+                rands.extend((0..dim).map(|_| rng.sample::<f64, _>(StandardNormal)));
+                rands.push(rng.gen_range(0.0, 1.0));
+            }
+        }
+    }
+
+    /// Updates in place the velocity `v` of the particle at `x`, with random
+    /// numbers from [`draw`](Self::draw). Per-dimension coefficients take
+    /// precedence over the options.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn update(
+        &self,
+        x: &Array1<f64>,
+        v: &mut Array1<f64>,
+        p: &Array1<f64>,
+        l: &Array1<f64>,
+        rands: &[f64],
+        options: &Options,
+        coefficients: Option<&Coefficients>,
+    ) {
+        let omega = options.omega.expect("Omega was None");
+        // ω, φ1 and φ2 of the d-th dimension
+        let coefficients = |d: usize| match coefficients {
+            Some(c) => (
+                c.omega.as_ref().map_or(omega, |omega| omega[d]),
+                c.phi_1[d],
+                c.phi_2[d],
+            ),
+            None => (omega, options.phi_1, options.phi_2),
+        };
+
+        match self {
+            VelocityPolicy::Classic => {
+                let (rand_1, rand_2) = (rands[0], rands[1]);
+
+                Zip::indexed(v)
+                    .and(x)
+                    .and(p)
+                    .and(l)
+                    .apply(|d, v, &x, &p, &l| {
+                        let (omega, phi_1, phi_2) = coefficients(d);
+                        *v = *v * omega + (p - x) * phi_1 * rand_1 + (l - x) * phi_2 * rand_2;
+                    });
+            }
+            VelocityPolicy::Componentwise => {
+                let (rands_1, rands_2) = rands.split_at(x.len());
+
+                Zip::indexed(v)
+                    .and(x)
+                    .and(p)
+                    .and(l)
+                    .apply(|d, v, &x, &p, &l| {
+                        let (omega, phi_1, phi_2) = coefficients(d);
+                        *v = *v * omega
+                            + (p - x) * rands_1[d] * phi_1
+                            + (l - x) * rands_2[d] * phi_2;
+                    });
+            }
+            VelocityPolicy::RandomRotation => {
+                let dim = x.len();
+                let (rands_1, rands_2) = rands.split_at(dim * dim + dim);
+
+                let snd_term = rotated_scaling(&(p - x), rands_1);
+                let trd_term = rotated_scaling(&(l - x), rands_2);
+
+                Zip::indexed(v)
+                    .and(&snd_term)
+                    .and(&trd_term)
+                    .apply(|d, v, &snd, &trd| {
+                        let (omega, phi_1, phi_2) = coefficients(d);
+                        *v = *v * omega + snd * phi_1 + trd * phi_2;
+                    });
+            }
+            VelocityPolicy::Hypersphere => {
+                let informed = l != p;
+                let mut center = Array1::zeros(x.len());
+                Zip::indexed(&mut center)
+                    .and(x)
+                    .and(p)
+                    .and(l)
+                    .apply(|d, c, &x, &p, &l| {
+                        let (_, phi_1, phi_2) = coefficients(d);
+                        *c = x + if informed {
+                            ((p - x) * phi_1 + (l - x) * phi_2) / 3.0
+                        } else {
+                            (p - x) * (phi_1 / 2.0)
+                        };
+                    });
+
+                // Summed with ndarray, whose order of the additions seeded
+                // runs depend on
+                let mut squares = &center - x;
+                squares.mapv_inplace(|v| v * v);
+                let radius = squares.sum().sqrt();
+
+                let (direction, rand) = rands.split_at(x.len());
+                squares.assign(&ArrayView1::from(direction));
+                squares.mapv_inplace(|v| v * v);
+                let norm = squares.sum().sqrt();
+
+                // Uniform radius (not volume) as the reference implementation,
+                // which favours points closer to the center
+                let length = radius * rand[0];
+                let scale = if norm > 0.0 { length / norm } else { 0.0 };
+
+                Zip::indexed(v).and(x).and(&center).apply(|d, v, &x, &c| {
+                    let (omega, _, _) = coefficients(d);
+                    let sampled = if norm > 0.0 {
+                        c + direction[d] * scale
+                    } else {
+                        c
+                    };
+                    *v = *v * omega + sampled - x;
+                });
+            }
+        }
+    }
+}
+
+/// `R D Rᵀ y`, with `R` the orthonormalized Gaussian matrix in the first
+/// `dim²` random numbers and `D` the following `dim` ones.
+fn rotated_scaling(y: &Array1<f64>, rands: &[f64]) -> Array1<f64> {
+    let dim = y.len();
+    let (gaussian, scaling) = rands.split_at(dim * dim);
+
+    let basis = Array2::from_shape_vec((dim, dim), gaussian.to_vec())
+        .expect("Wrong amount of random numbers");
+    let basis = orthonormalize(basis);
+
+    // Rows are the basis vectors, so this is (R D Rᵀ) y with R = basisᵀ
+    let projected = basis.dot(y) * &Array1::from(scaling.to_vec());
+    basis.t().dot(&projected)
+}
+
+/// Gram-Schmidt on the rows of `basis`, a Gaussian matrix gives a uniformly
+/// distributed rotation.
+pub(crate) fn orthonormalize(mut basis: Array2<f64>) -> Array2<f64> {
+    for i in 0..basis.nrows() {
+        for j in 0..i {
+            let projection = basis.row(i).dot(&basis.row(j));
+            let previous = basis.row(j).to_owned();
+            basis.row_mut(i).scaled_add(-projection, &previous);
+        }
+
+        let norm = basis.row(i).dot(&basis.row(i)).sqrt();
+        if norm > 0.0 {
+            basis.row_mut(i).mapv_inplace(|v| v / norm);
+        }
+    }
+
+    basis
+}
@@ -0,0 +1,73 @@
+use crate::error::PsoError;
+use crate::pso::Particles;
+use serde::{Deserialize, Serialize};
+
+/// Exploratory phase at the start of a run: during the first `iters` steps
+/// the inertia is fixed to `omega`, overriding the options, and after every
+/// step a `resample` fraction of the swarm, the particles with the worst
+/// fitness, are sampled again from the initial distributions. Afterwards
+/// the swarm moves with the configured options.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Warmup {
+    pub iters: usize,
+    pub omega: f64,
+    pub resample: f64,
+}
+
+impl Default for Warmup {
+    /// 10 steps with `omega = 0.9`, resampling a fifth of the swarm.
+    fn default() -> Self {
+        Self {
+            iters: 10,
+            omega: 0.9,
+            resample: 0.2,
+        }
+    }
+}
+
+impl Warmup {
+    pub fn validate(&self) -> Result<(), PsoError> {
+        if !(0.0..=1.5).contains(&self.omega) {
+            return Err(PsoError::InvalidOptions(format!(
+                "warmup omega must be in [0, 1.5], got {}",
+                self.omega
+            )));
+        }
+
+        if !(0.0..=1.0).contains(&self.resample) {
+            return Err(PsoError::InvalidOptions(format!(
+                "warmup resample must be in [0, 1], got {}",
+                self.resample
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Whether the `step`-th step, counting from 1, is part of the warmup.
+    pub fn covers(&self, step: usize) -> bool {
+        step <= self.iters
+    }
+}
+
+impl<'a> Particles<'a> {
+    /// Whether the next step is still part of the warmup.
+    pub fn warming_up(&self) -> bool {
+        self.warmup()
+            .is_some_and(|warmup| warmup.covers(self.steps() + 1))
+    }
+
+    /// Resamples the worst particles after a warmup step, as many as the
+    /// budget still affords.
+    pub(crate) fn resample_for_warmup(&mut self, warmup: &Warmup) {
+        let size = self.particles().len();
+        let mut count = (warmup.resample * size as f64).round() as usize;
+        // Never the whole swarm, the best particle has to guide the rest
+        count = count.min(size.saturating_sub(1));
+        if let Some(left) = self.evaluations_left() {
+            count = count.min(left);
+        }
+
+        self.resample_worst(count);
+    }
+}
@@ -0,0 +1,116 @@
+use crate::error::PsoError;
+use ndarray::Array1;
+use serde::{Deserialize, Serialize};
+
+/// Region of the search space particles are not allowed to stay in.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Zone {
+    Sphere { center: Array1<f64>, radius: f64 },
+    Box { min: Array1<f64>, max: Array1<f64> },
+}
+
+/// What happens to a particle that lands inside a zone.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ZoneHandling {
+    /// Move it to the closest point on the zone's boundary.
+    #[default]
+    Repair,
+    /// Move it to the boundary and reflect the velocity components pointing
+    /// into the zone.
+    Repel,
+}
+
+impl Zone {
+    pub fn sphere(center: Array1<f64>, radius: f64) -> Self {
+        Zone::Sphere { center, radius }
+    }
+
+    pub fn cuboid(min: Array1<f64>, max: Array1<f64>) -> Self {
+        Zone::Box { min, max }
+    }
+
+    /// Checks that the zone lies in a space of `dim` dimensions.
+    pub fn validate(&self, dim: usize) -> Result<(), PsoError> {
+        let bounds = match self {
+            Zone::Sphere { center, .. } => [center, center],
+            Zone::Box { min, max } => [min, max],
+        };
+
+        match bounds.iter().find(|bound| bound.len() != dim) {
+            Some(bound) => Err(PsoError::InvalidOptions(format!(
+                "zone has {} dimensions, expected {}",
+                bound.len(),
+                dim
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    pub fn contains(&self, value: &Array1<f64>) -> bool {
+        match self {
+            Zone::Sphere { center, radius } => {
+                (value - center).mapv(|v| v * v).sum().sqrt() < *radius
+            }
+            Zone::Box { min, max } => value
+                .iter()
+                .zip(min.iter().zip(max.iter()))
+                .all(|(v, (lo, hi))| lo < v && v < hi),
+        }
+    }
+
+    /// Pushes the position out of the zone if it's inside.
+    pub fn push_out(
+        &self,
+        value: &mut Array1<f64>,
+        velocity: &mut Array1<f64>,
+        handling: ZoneHandling,
+    ) {
+        if !self.contains(value) {
+            return;
+        }
+
+        match self {
+            Zone::Sphere { center, radius } => {
+                let mut normal = &*value - center;
+                let norm = normal.mapv(|v| v * v).sum().sqrt();
+
+                // Exactly at the center, any direction works
+                if norm == 0.0 {
+                    normal[0] = 1.0;
+                } else {
+                    normal /= norm;
+                }
+
+                // Slightly outside, so rounding doesn't leave it inside
+                *value = center + &(&normal * (*radius * (1.0 + 1e-12)));
+
+                let inwards = velocity.dot(&normal);
+                if handling == ZoneHandling::Repel && inwards < 0.0 {
+                    velocity.scaled_add(-2.0 * inwards, &normal);
+                }
+            }
+            Zone::Box { min, max } => {
+                // Leave through the closest face
+                let (dim, to_min, to_max) = (0..value.len())
+                    .map(|d| (d, value[d] - min[d], max[d] - value[d]))
+                    .min_by(|a, b| {
+                        let da = a.1.min(a.2);
+                        let db = b.1.min(b.2);
+                        da.partial_cmp(&db).expect("Received a NaN")
+                    })
+                    .expect("Zone without dimensions");
+
+                let (face, outwards) = if to_min <= to_max {
+                    (min[dim], -1.0)
+                } else {
+                    (max[dim], 1.0)
+                };
+
+                value[dim] = face;
+                if handling == ZoneHandling::Repel && velocity[dim] * outwards < 0.0 {
+                    velocity[dim] = -velocity[dim];
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,85 @@
+#![cfg(feature = "full")]
+
+//! Counts the heap allocations of the current thread, the test harness may
+//! allocate on others while stepping.
+
+mod common;
+
+use common::quiet;
+use pso::benchmark::rastrigin;
+use pso::builder::SwarmBuilder;
+use pso::fitness::Fitness;
+use pso::pso::{Options, Particles};
+use pso::topology::Topology;
+use pso::velocity::VelocityPolicy;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+struct Counting;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(Cell::get)
+}
+
+fn swarm(topology: Topology, policy: VelocityPolicy) -> Particles<'static> {
+    quiet(
+        SwarmBuilder::new(Fitness::new(rastrigin, true))
+            .size(20)
+            .dim(10)
+            .value_range(-5.12, 5.12)
+            .topology(topology)
+            .velocity_policy(policy)
+            .seed(1),
+    )
+}
+
+#[test]
+fn steps_dont_allocate() {
+    let configurations = [
+        (Topology::Global, VelocityPolicy::Classic),
+        (Topology::Global, VelocityPolicy::Componentwise),
+        (Topology::Ring { radius: 1 }, VelocityPolicy::Classic),
+        (Topology::Ring { radius: 2 }, VelocityPolicy::Componentwise),
+    ];
+
+    for &(topology, policy) in configurations.iter() {
+        let mut particles = swarm(topology, policy);
+        let options = Options::canonical();
+
+        // The first step fills the scratch buffers
+        particles.step(options);
+        particles.reserve_steps(200);
+
+        let before = allocations();
+        for _ in 0..200 {
+            particles.step(options);
+        }
+        let steps = allocations() - before;
+
+        assert_eq!(steps, 0, "{:?} with {:?}", topology, policy);
+        assert_eq!(particles.steps(), 201);
+    }
+}
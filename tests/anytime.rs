@@ -0,0 +1,207 @@
+#![cfg(feature = "full")]
+
+mod common;
+
+use common::quiet;
+use ndarray::{array, Array1};
+use pso::benchmark::sphere;
+use pso::builder::SwarmBuilder;
+use pso::error::PsoError;
+use pso::fitness::Fitness;
+use pso::pso::{Confinement, Options};
+use pso::velocity::Coefficients;
+use std::thread;
+use std::time::Duration;
+
+fn slow_sphere(x: &Array1<f64>) -> f64 {
+    thread::sleep(Duration::from_micros(200));
+    x.mapv(|v| v * v).sum()
+}
+
+#[test]
+fn stays_within_budget() {
+    let mut particles = SwarmBuilder::new(Fitness::new(slow_sphere, true))
+        .size(5)
+        .seed(0)
+        .build()
+        .unwrap();
+
+    let initial = particles.best_fitness();
+    let budget = Duration::from_millis(100);
+    let result = particles
+        .optimize_for(Options::canonical(), budget)
+        .unwrap();
+
+    assert!(result.iters > 0);
+    assert!(result.elapsed <= budget * 2);
+    assert!(result.best_fitness <= initial);
+    assert_eq!(result.best, *particles.best());
+}
+
+#[test]
+fn zero_budget_does_nothing() {
+    let mut particles = SwarmBuilder::new(Fitness::new(slow_sphere, true))
+        .seed(0)
+        .build()
+        .unwrap();
+
+    let result = particles
+        .optimize_for(Options::canonical(), Duration::from_secs(0))
+        .unwrap();
+
+    assert_eq!(result.iters, 0);
+}
+
+#[test]
+fn warm_start_around_the_previous_best() {
+    let shifted = |shift: f64| move |x: &Array1<f64>| x.mapv(|v| (v - shift).powi(2)).sum();
+
+    let mut particles = quiet(
+        SwarmBuilder::new(Fitness::new(shifted(1.0), true))
+            .size(10)
+            .seed(18),
+    );
+    let result = particles
+        .optimize_for(Options::canonical(), Duration::from_millis(20))
+        .unwrap();
+    assert_eq!(result.options, Options::canonical());
+
+    // The problem changed slightly
+    let particles = SwarmBuilder::new(Fitness::new(shifted(1.1), true))
+        .size(10)
+        .value_range(-5.0, 5.0)
+        .seed(19)
+        .warm_start(&result)
+        .spread(0.05)
+        .build()
+        .unwrap();
+
+    assert_eq!(particles.particles()[0].value(), &result.best);
+    let radius = 0.05 * 10.0;
+    for particle in particles.particles() {
+        let distance = particle.value() - &result.best;
+        assert!(distance.iter().all(|d| d.abs() <= radius + 1e-12));
+    }
+    assert!(particles.best_fitness() < 0.1);
+    assert_eq!(
+        particles.coefficients(),
+        Some(&Coefficients::uniform(2, &result.options))
+    );
+
+    let wrong = SwarmBuilder::new(Fitness::new(shifted(0.0), true))
+        .dim(3)
+        .warm_start(&result)
+        .build();
+    assert!(wrong.is_err());
+}
+
+#[test]
+fn warm_start_keeps_explicit_coefficients() {
+    let mut particles = quiet(SwarmBuilder::new(Fitness::new(sphere, true)).seed(3));
+    let options = Options {
+        omega: Some(0.6),
+        phi_1: 1.2,
+        phi_2: 1.8,
+    };
+    let result = particles
+        .optimize_for(options, Duration::from_millis(5))
+        .unwrap();
+
+    let explicit = Coefficients::uniform(2, &Options::canonical());
+    for coefficients in [None, Some(explicit.clone())].iter() {
+        let mut builder = SwarmBuilder::new(Fitness::new(sphere, true)).warm_start(&result);
+        if let Some(coefficients) = coefficients {
+            builder = builder.coefficients(coefficients.clone());
+        }
+
+        let expected = coefficients
+            .clone()
+            .unwrap_or_else(|| Coefficients::uniform(2, &options));
+        assert_eq!(builder.build().unwrap().coefficients(), Some(&expected));
+    }
+}
+
+#[test]
+fn normalized_results_are_in_the_original_coordinates() {
+    let target = array![3.0, 150.0];
+    let shifted = {
+        let target = target.clone();
+        move |x: &Array1<f64>| (x - &target).mapv(|v| v * v).sum()
+    };
+    let (min, max) = (array![0.0, 100.0], array![10.0, 200.0]);
+
+    let mut particles = quiet(
+        SwarmBuilder::new(Fitness::new(shifted.clone(), true))
+            .normalize(min.clone(), max.clone())
+            .seed(4),
+    );
+    let result = particles
+        .optimize_for(Options::canonical(), Duration::from_millis(20))
+        .unwrap();
+
+    assert_eq!(result.best, particles.denormalize(particles.best()));
+    assert_eq!(shifted(&result.best), result.best_fitness);
+
+    let plain = SwarmBuilder::new(Fitness::new(shifted.clone(), true))
+        .value_range(0.0, 200.0)
+        .warm_start(&result)
+        .build()
+        .unwrap();
+    assert_eq!(plain.particles()[0].value(), &result.best);
+
+    let normalized = SwarmBuilder::new(Fitness::new(shifted, true))
+        .normalize(min, max)
+        .warm_start(&result)
+        .build()
+        .unwrap();
+    let seeded = normalized.denormalize(normalized.particles()[0].value());
+    assert!((seeded - &result.best).iter().all(|d| d.abs() < 1e-9));
+}
+
+#[test]
+fn warm_starts_near_the_boundary_stay_inside() {
+    let mut particles = quiet(SwarmBuilder::new(Fitness::new(sphere, true)).seed(5));
+    let mut result = particles
+        .optimize_for(Options::canonical(), Duration::from_millis(5))
+        .unwrap();
+    result.best = array![0.99, -0.99];
+
+    let particles = SwarmBuilder::new(Fitness::new(sphere, true))
+        .size(20)
+        .value_range(-1.0, 1.0)
+        .confinement(Confinement::Bounded)
+        .warm_start(&result)
+        .spread(0.2)
+        .seed(6)
+        .build()
+        .unwrap();
+
+    for particle in particles.particles() {
+        assert!(particle.value().iter().all(|x| (-1.0..=1.0).contains(x)));
+        assert!(particle.best().iter().all(|x| (-1.0..=1.0).contains(x)));
+    }
+}
+
+#[test]
+fn seeding_keeps_frozen_dimensions() {
+    let mut particles = quiet(
+        SwarmBuilder::new(Fitness::new(sphere, true))
+            .size(8)
+            .seed(7),
+    );
+    particles.freeze(1, 2.5).unwrap();
+
+    particles.seed_around(&array![1.0, 1.0], 0.1).unwrap();
+    for particle in particles.particles() {
+        assert_eq!(particle.value()[1], 2.5);
+    }
+
+    assert!(matches!(
+        particles.seed_around(&array![1.0, 1.0, 1.0], 0.1),
+        Err(PsoError::InvalidOptions(_))
+    ));
+    assert!(matches!(
+        particles.seed_around(&array![1.0, 1.0], -0.1),
+        Err(PsoError::InvalidOptions(_))
+    ));
+}
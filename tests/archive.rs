@@ -0,0 +1,91 @@
+#![cfg(feature = "full")]
+
+mod common;
+
+use common::quiet;
+use ndarray::{arr1, Array1};
+use pso::archive::Archive;
+use pso::builder::SwarmBuilder;
+use pso::error::PsoError;
+use pso::fitness::Fitness;
+use pso::metric::Metric;
+use pso::pso::Options;
+
+/// Four global minima at (±2, ±2).
+fn himmelblau_like(x: &Array1<f64>) -> f64 {
+    (x[0] * x[0] - 4.0).powi(2) + (x[1] * x[1] - 4.0).powi(2)
+}
+
+#[test]
+fn keeps_distinct_solutions() {
+    let mut archive = Archive::new(3, 1.0);
+
+    assert!(archive.offer(&arr1(&[0.0, 0.0]), 5.0, true));
+    assert!(archive.offer(&arr1(&[5.0, 0.0]), 3.0, true));
+    // Too close to a better one
+    assert!(!archive.offer(&arr1(&[5.2, 0.0]), 4.0, true));
+    // Too close to a worse one, replaces it
+    assert!(archive.offer(&arr1(&[0.3, 0.0]), 1.0, true));
+    assert!(archive.offer(&arr1(&[10.0, 0.0]), 2.0, true));
+    // Full and worse than every solution
+    assert!(!archive.offer(&arr1(&[20.0, 0.0]), 9.0, true));
+
+    let fitness: Vec<_> = archive.solutions().iter().map(|s| s.fitness).collect();
+    assert_eq!(fitness, vec![1.0, 2.0, 3.0]);
+    assert_eq!(archive.solutions()[0].value, arr1(&[0.3, 0.0]));
+}
+
+#[test]
+fn finds_several_optima() {
+    let mut particles = SwarmBuilder::new(Fitness::new(himmelblau_like, true))
+        .size(30)
+        .value_range(-4.0, 4.0)
+        .seed(11)
+        .archive(4, 1.0)
+        .build()
+        .unwrap();
+
+    particles.run(Options::canonical(), 100).unwrap();
+
+    let archive = particles.archive().unwrap();
+    assert_eq!(archive.solutions().len(), 4);
+    assert_eq!(archive.solutions()[0].fitness, particles.best_fitness());
+
+    for (i, a) in archive.solutions().iter().enumerate() {
+        for b in &archive.solutions()[i + 1..] {
+            let distance = (&a.value - &b.value).mapv(|v| v * v).sum().sqrt();
+            assert!(distance >= 1.0);
+        }
+    }
+}
+
+#[test]
+fn invalid_archives_are_rejected() {
+    for &(capacity, min_distance) in &[(0, 1.0), (3, -1.0), (3, f64::NAN)] {
+        let result = SwarmBuilder::new(Fitness::new(himmelblau_like, true))
+            .archive(capacity, min_distance)
+            .build();
+        assert!(matches!(result, Err(PsoError::InvalidOptions(_))));
+    }
+}
+
+#[test]
+fn archives_use_the_swarm_metric() {
+    let mut particles = quiet(
+        SwarmBuilder::new(Fitness::new(himmelblau_like, true))
+            .size(30)
+            .value_range(-4.0, 4.0)
+            .metric(Metric::Chebyshev)
+            .archive(10, 1.0)
+            .seed(11),
+    );
+    assert_eq!(particles.archive().unwrap().metric(), Metric::Chebyshev);
+
+    // Everything is close to everything else
+    particles.set_custom_metric(|_: &Array1<f64>, _: &Array1<f64>| 0.0);
+    particles.run(Options::canonical(), 20).unwrap();
+
+    let archive = particles.archive().unwrap();
+    assert_eq!(archive.solutions().len(), 1);
+    assert_eq!(archive.solutions()[0].fitness, particles.best_fitness());
+}
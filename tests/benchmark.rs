@@ -0,0 +1,216 @@
+#![cfg(feature = "full")]
+
+use ndarray::{Array1, Array2};
+use pso::benchmark::{
+    elliptic, generational_distance, inverted_generational_distance, rastrigin, rosenbrock, sphere,
+    Dtlz, DtlzKind, MultiObjective, Transform, Zdt, ZdtKind,
+};
+use pso::builder::SwarmBuilder;
+use pso::fitness::Fitness;
+use pso::objective::Scalarization;
+use pso::pso::{Confinement, Options};
+use rand::SeedableRng;
+use rand_pcg::Pcg64;
+
+#[test]
+fn base_minima() {
+    let zeros = Array1::zeros(4);
+    assert_eq!(sphere(&zeros), 0.0);
+    assert_eq!(elliptic(&zeros), 0.0);
+    assert_eq!(rastrigin(&zeros), 0.0);
+    assert_eq!(rosenbrock(&Array1::ones(4)), 0.0);
+}
+
+#[test]
+fn random_rotation_is_orthogonal() {
+    let mut rng = Pcg64::seed_from_u64(0);
+    let transform = Transform::new().random_rotation(5, &mut rng);
+
+    // Rotating doesn't change the norm
+    let x = Array1::from(vec![1.0, -2.0, 3.0, 0.5, 4.0]);
+    let rotated = transform.apply(&x);
+    assert!((sphere(&rotated) - sphere(&x)).abs() < 1e-9);
+}
+
+#[test]
+fn shift_moves_the_optimum() {
+    let mut rng = Pcg64::seed_from_u64(1);
+    let o = Array1::from(vec![10.0, -20.0, 30.0]);
+    let f = Transform::new()
+        .shift(o.clone())
+        .random_rotation(3, &mut rng)
+        .bias(-450.0)
+        .wrap(elliptic);
+
+    assert!((f(&o) + 450.0).abs() < 1e-9);
+    assert!(f(&Array1::zeros(3)) > -450.0);
+}
+
+#[test]
+fn identity_rotation_keeps_benchmark() {
+    let f = Transform::new().rotation(Array2::eye(3)).wrap(rastrigin);
+    let x = Array1::from(vec![0.3, -1.2, 2.5]);
+
+    assert_eq!(f(&x), rastrigin(&x));
+}
+
+#[test]
+fn swarm_finds_shifted_optimum() {
+    let mut rng = Pcg64::seed_from_u64(2);
+    let transform = Transform::new()
+        .random_shift(3, (-5.0, 5.0), &mut rng)
+        .random_rotation(3, &mut rng)
+        .bias(100.0);
+
+    let mut particles = SwarmBuilder::new(Fitness::new(transform.wrap(sphere), true))
+        .size(20)
+        .dim(3)
+        .seed(3)
+        .build()
+        .unwrap();
+
+    particles.run(Options::canonical(), 200).unwrap();
+
+    assert!((particles.best_fitness() - 100.0).abs() < 1e-6);
+}
+
+const ZDTS: [ZdtKind; 5] = [
+    ZdtKind::Zdt1,
+    ZdtKind::Zdt2,
+    ZdtKind::Zdt3,
+    ZdtKind::Zdt4,
+    ZdtKind::Zdt6,
+];
+
+const DTLZS: [DtlzKind; 4] = [
+    DtlzKind::Dtlz1,
+    DtlzKind::Dtlz2,
+    DtlzKind::Dtlz3,
+    DtlzKind::Dtlz4,
+];
+
+#[test]
+fn zdt_optima_are_on_the_front() {
+    for &kind in ZDTS.iter() {
+        let problem = Zdt::standard(kind);
+        let front = problem.front(2000);
+        assert!(!front.is_empty() && front.len() <= 2000);
+
+        // g is minimal with every variable but the first at 0
+        let optima: Vec<_> = (0..=20)
+            .map(|i| {
+                let mut x = Array1::zeros(problem.dim());
+                x[0] = f64::from(i) / 20.0;
+                problem.evaluate(&x)
+            })
+            .collect();
+
+        // ZDT3 optima off the pieces are dominated, only check the others
+        if kind != ZdtKind::Zdt3 {
+            let gd = generational_distance(&optima, &front);
+            assert!(gd < 1e-2, "{}: {}", problem.name(), gd);
+        }
+        for point in &front {
+            assert_eq!(point.len(), 2);
+        }
+    }
+}
+
+#[test]
+fn zdt4_bounds() {
+    let (min, max) = Zdt::standard(ZdtKind::Zdt4).bounds();
+    assert_eq!((min[0], max[0]), (0.0, 1.0));
+    assert_eq!((min[9], max[9]), (-5.0, 5.0));
+}
+
+#[test]
+fn dtlz_fronts_have_known_shape() {
+    for &kind in DTLZS.iter() {
+        let problem = Dtlz::standard(kind, 3);
+        let front = problem.front(100);
+
+        // 12 divisions, C(14, 2) points
+        assert_eq!(front.len(), 91);
+        for point in &front {
+            if kind == DtlzKind::Dtlz1 {
+                assert!((point.iter().sum::<f64>() - 0.5).abs() < 1e-12);
+            } else {
+                let norm: f64 = point.iter().map(|v| v * v).sum();
+                assert!((norm - 1.0).abs() < 1e-12);
+            }
+        }
+    }
+
+    assert!(Dtlz::standard(DtlzKind::Dtlz2, 3).front(2).is_empty());
+}
+
+#[test]
+fn dtlz_optima_are_on_the_front() {
+    let mut rng = Pcg64::seed_from_u64(4);
+    for &kind in DTLZS.iter() {
+        let problem = Dtlz::standard(kind, 3);
+        assert_eq!(problem.dim(), 2 + kind.standard_k());
+
+        // Distance variables at 0.5 put any position on the front
+        let optima: Vec<_> = (0..50)
+            .map(|_| {
+                let mut x = Array1::from_elem(problem.dim(), 0.5);
+                x[0] = rand::Rng::gen_range(&mut rng, 0.0, 1.0);
+                x[1] = rand::Rng::gen_range(&mut rng, 0.0, 1.0);
+                problem.evaluate(&x)
+            })
+            .collect();
+
+        for point in &optima {
+            if kind == DtlzKind::Dtlz1 {
+                assert!((point.iter().sum::<f64>() - 0.5).abs() < 1e-9);
+            } else {
+                let norm: f64 = point.iter().map(|v| v * v).sum();
+                assert!((norm - 1.0).abs() < 1e-9);
+            }
+        }
+
+        // Off the front everything is farther
+        let x = Array1::from_elem(problem.dim(), 0.9);
+        let front = problem.front(100);
+        assert!(generational_distance(&[problem.evaluate(&x)], &front) > 0.1);
+    }
+}
+
+#[test]
+fn indicators() {
+    let front = Zdt::standard(ZdtKind::Zdt1).front(100);
+    assert_eq!(generational_distance(&front, &front), 0.0);
+    assert_eq!(inverted_generational_distance(&front, &front), 0.0);
+
+    // A single point of the front converged, but doesn't cover it
+    let single = vec![front[50].clone()];
+    assert_eq!(generational_distance(&single, &front), 0.0);
+    assert!(inverted_generational_distance(&single, &front) > 0.1);
+
+    assert!(generational_distance(&[], &front).is_infinite());
+}
+
+#[test]
+fn swarm_reaches_zdt1_front() {
+    let problem = Zdt::new(ZdtKind::Zdt1, 5);
+    let scalarization = Scalarization::WeightedSum(vec![0.5, 0.5]);
+    let fitness = Fitness::new(
+        |x: &Array1<f64>| scalarization.apply(&problem.evaluate(x)),
+        true,
+    );
+
+    let mut particles = SwarmBuilder::new(fitness)
+        .size(20)
+        .dim(problem.dim())
+        .value_range(0.0, 1.0)
+        .confinement(Confinement::Bounded)
+        .seed(5)
+        .build()
+        .unwrap();
+
+    particles.run(Options::canonical(), 300).unwrap();
+
+    let found = vec![problem.evaluate(particles.best())];
+    assert!(generational_distance(&found, &problem.front(1000)) < 1e-2);
+}
@@ -0,0 +1,335 @@
+#![cfg(feature = "full")]
+
+//! Property tests of the boundary handling: random cases, including extreme
+//! values and tiny ranges, drawn from a seeded RNG so failures reproduce.
+
+mod common;
+
+use common::quiet;
+use ndarray::Array1;
+use pso::builder::SwarmBuilder;
+use pso::error::PsoError;
+use pso::fitness::Fitness;
+use pso::operator::Operator;
+use pso::pso::{Confinement, Options};
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_pcg::Pcg64;
+
+const CASES: usize = 2000;
+
+const CONFINED: [Confinement; 4] = [
+    Confinement::Bounded,
+    Confinement::Absorbing,
+    Confinement::Reflecting { restitution: 0.7 },
+    Confinement::Reflecting { restitution: 1.0 },
+];
+
+const EXTREMES: [f64; 9] = [
+    f64::MAX,
+    -f64::MAX,
+    f64::MIN_POSITIVE,
+    -f64::MIN_POSITIVE,
+    f64::INFINITY,
+    f64::NEG_INFINITY,
+    f64::NAN,
+    0.0,
+    -0.0,
+];
+
+/// Ordinary, huge, tiny or special values.
+fn any_f64(rng: &mut Pcg64) -> f64 {
+    match rng.gen_range(0, 4) {
+        0 => rng.gen_range(-10.0, 10.0),
+        1 => rng.gen_range(-1.0, 1.0) * 10f64.powi(rng.gen_range(-300, 300)),
+        2 => *EXTREMES.choose(rng).unwrap(),
+        _ => f64::from_bits(rng.gen()),
+    }
+}
+
+/// Valid value range, from tiny to almost the whole line.
+fn any_range(rng: &mut Pcg64) -> (f64, f64) {
+    let center = rng.gen_range(-1.0, 1.0) * 10f64.powi(rng.gen_range(-10, 10));
+    let width = 10f64.powi(rng.gen_range(-12, 300));
+    let (min, max) = (center - width, center + width);
+
+    if min < max {
+        (min, max)
+    } else {
+        (center, center.next_up())
+    }
+}
+
+fn inside(x: &Array1<f64>, (min, max): (f64, f64)) -> bool {
+    x.iter().all(|v| (min..=max).contains(v))
+}
+
+#[test]
+fn confined_positions_stay_inside_with_finite_velocities() {
+    let mut rng = Pcg64::seed_from_u64(0);
+
+    for case in 0..CASES {
+        let range = any_range(&mut rng);
+        let dim = rng.gen_range(1, 6);
+        let value = Array1::from_shape_fn(dim, |_| any_f64(&mut rng));
+        let velocity = Array1::from_shape_fn(dim, |_| any_f64(&mut rng));
+
+        for &confinement in &CONFINED {
+            let (mut x, mut v) = (value.clone(), velocity.clone());
+            confinement.apply(&mut x, &mut v, range);
+
+            assert!(
+                inside(&x, range),
+                "case {}: {:?} left {:?} at {}",
+                case,
+                confinement,
+                range,
+                x
+            );
+            assert!(
+                v.iter().all(|v| v.is_finite()),
+                "case {}: {:?} velocity {}",
+                case,
+                confinement,
+                v
+            );
+        }
+    }
+}
+
+#[test]
+fn positions_inside_are_untouched() {
+    let mut rng = Pcg64::seed_from_u64(1);
+
+    for _ in 0..CASES {
+        let range = any_range(&mut rng);
+        let value = Array1::from_shape_fn(3, |_| rng.gen_range(range.0, range.1));
+        let velocity = Array1::from_shape_fn(3, |_| rng.gen_range(-1.0, 1.0));
+
+        for &confinement in &CONFINED {
+            let (mut x, mut v) = (value.clone(), velocity.clone());
+            confinement.apply(&mut x, &mut v, range);
+            assert_eq!((x, v), (value.clone(), velocity.clone()));
+        }
+
+        // Free never confines, whatever the values
+        let (mut x, mut v) = (value.mapv(|x| x * 1e3), velocity.clone());
+        Confinement::Free.apply(&mut x, &mut v, range);
+        assert_eq!(x, value.mapv(|x| x * 1e3));
+    }
+}
+
+#[test]
+fn clamped_dimensions_follow_the_strategy() {
+    let mut rng = Pcg64::seed_from_u64(2);
+
+    for _ in 0..CASES {
+        let range = any_range(&mut rng);
+        let v = rng.gen_range(-1e6, 1e6);
+        let outside = range.1 + (range.1 - range.0).max(1.0);
+
+        let (mut x, mut bounded) = (Array1::from_elem(1, outside), Array1::from_elem(1, v));
+        Confinement::Bounded.apply(&mut x, &mut bounded, range);
+        assert_eq!(x[0], range.1);
+        assert_eq!(bounded[0], -0.5 * v);
+
+        let (mut x, mut absorbed) = (Array1::from_elem(1, f64::NAN), Array1::from_elem(1, v));
+        Confinement::Absorbing.apply(&mut x, &mut absorbed, range);
+        assert!(inside(&x, range));
+        assert_eq!(absorbed[0], 0.0);
+    }
+}
+
+#[test]
+fn reflected_dimensions_bounce_with_restitution() {
+    let range = (-1.0, 1.0);
+    let reflecting = |restitution| Confinement::Reflecting { restitution };
+
+    let (mut x, mut v) = (Array1::from(vec![1.5, -1.2]), Array1::from(vec![2.0, -1.0]));
+    reflecting(0.5).apply(&mut x, &mut v, range);
+    assert_eq!(x, Array1::from(vec![0.75, -0.9]));
+    assert_eq!(v, Array1::from(vec![-1.0, 0.5]));
+
+    // Elastic bounces keep the speed, absorbing ones stop at the bound
+    let (mut x, mut v) = (Array1::from(vec![1.5]), Array1::from(vec![2.0]));
+    reflecting(1.0).apply(&mut x, &mut v, range);
+    assert_eq!((x[0], v[0]), (0.5, -2.0));
+
+    let (mut x, mut v) = (Array1::from(vec![1.5]), Array1::from(vec![2.0]));
+    reflecting(0.0).apply(&mut x, &mut v, range);
+    assert_eq!((x[0], v[0]), (1.0, 0.0));
+
+    // Too far to bounce back inside
+    let (mut x, mut v) = (Array1::from(vec![5.0]), Array1::from(vec![2.0]));
+    reflecting(1.0).apply(&mut x, &mut v, range);
+    assert_eq!(x[0], -1.0);
+}
+
+#[test]
+fn restitution_is_validated() {
+    for &restitution in &[-0.1, 1.5, f64::NAN] {
+        let result = SwarmBuilder::new(Fitness::new(|x: &Array1<f64>| x.sum(), true))
+            .confinement(Confinement::Reflecting { restitution })
+            .build();
+        assert!(matches!(result, Err(PsoError::InvalidOptions(_))));
+    }
+}
+
+#[test]
+fn soft_walls_penalize_the_distance_outside() {
+    let range = (-1.0, 1.0);
+    let soft = Confinement::Penalized { weight: 2.0 };
+
+    let inside = Array1::from(vec![0.5, -1.0]);
+    assert_eq!(soft.penalty(&inside, range), 0.0);
+    assert_eq!(soft.penalty(&Array1::from(vec![4.0, -5.0]), range), 10.0);
+    assert_eq!(
+        soft.penalty(&Array1::from(vec![f64::NAN]), range),
+        f64::INFINITY
+    );
+    assert_eq!(
+        Confinement::Bounded.penalty(&Array1::from(vec![4.0]), range),
+        0.0
+    );
+
+    // Positions are left where they are
+    let (mut x, mut v) = (Array1::from(vec![4.0]), Array1::from(vec![3.0]));
+    soft.apply(&mut x, &mut v, range);
+    assert_eq!((x[0], v[0]), (4.0, 3.0));
+}
+
+#[test]
+fn soft_walls_let_particles_leave_with_worse_fitness() {
+    // Unconstrained optimum at 1.5, outside the box
+    let objective = |x: &Array1<f64>| x.mapv(|v| (v - 1.5).powi(2)).sum();
+    let (weight, range) = (0.5, (-1.0, 1.0));
+    let mut particles = quiet(
+        SwarmBuilder::new(Fitness::new(objective, true))
+            .size(20)
+            .dim(2)
+            .value_range(range.0, range.1)
+            .confinement(Confinement::Penalized { weight })
+            .seed(5),
+    );
+    particles.run(Options::canonical(), 100).unwrap();
+
+    // The penalized optimum is where the slope of the objective matches the
+    // weight, outside the box: 2 (x - 1.5) = -0.5 / √2 per dimension
+    let best = particles.best();
+    let expected = 1.5 - weight / (2.0 * 2f64.sqrt());
+    assert!(
+        best.iter().all(|&x| (x - expected).abs() < 1e-2),
+        "{}",
+        best
+    );
+
+    let penalty = Confinement::Penalized { weight }.penalty(best, range);
+    assert!((particles.best_fitness() - objective(best) - penalty).abs() < 1e-9);
+}
+
+#[test]
+fn soft_walls_penalize_every_entry_point() {
+    let objective = |x: &Array1<f64>| x.sum();
+    let soft = Confinement::Penalized { weight: 100.0 };
+    let range = (0.0, 1.0);
+    let mut particles = quiet(
+        SwarmBuilder::new(Fitness::new(objective, true))
+            .size(5)
+            .dim(2)
+            .value_range(range.0, range.1)
+            .confinement(soft)
+            .seed(2),
+    );
+    let best = particles.best_fitness();
+
+    let outside = Array1::from(vec![-10.0, -10.0]);
+    let expected = objective(&outside) + soft.penalty(&outside, range);
+    particles
+        .add_particle(outside.clone(), Array1::zeros(2))
+        .unwrap();
+    let added = particles.particles().last().unwrap();
+    assert_eq!(added.fitness(), expected);
+    assert_eq!(particles.best_fitness(), best);
+
+    particles.seed_around(&outside, 0.0).unwrap();
+    for particle in particles.particles() {
+        let penalty = soft.penalty(particle.value(), range);
+        assert_eq!(particle.fitness(), objective(particle.value()) + penalty);
+    }
+    assert!(particles.best_fitness() > 0.0);
+}
+
+#[test]
+fn penalty_weights_are_validated() {
+    for &weight in &[-1.0, f64::INFINITY, f64::NAN] {
+        let result = SwarmBuilder::new(Fitness::new(|x: &Array1<f64>| x.sum(), true))
+            .confinement(Confinement::Penalized { weight })
+            .build();
+        assert!(matches!(result, Err(PsoError::InvalidOptions(_))));
+    }
+}
+
+#[test]
+fn swarms_stay_inside_extreme_ranges() {
+    let mut rng = Pcg64::seed_from_u64(3);
+
+    for case in 0..50 {
+        let range = any_range(&mut rng);
+        let speed = 10f64.powi(rng.gen_range(-6, 300));
+        let confinement = *CONFINED.choose(&mut rng).unwrap();
+
+        let mut particles = quiet(
+            SwarmBuilder::new(Fitness::shared(
+                |x: &Array1<f64>| x.mapv(|v| v.abs()).sum(),
+                true,
+            ))
+            .size(6)
+            .dim(3)
+            .value_range(range.0, range.1)
+            .velocity_range(-speed, speed)
+            .confinement(confinement)
+            .operator(Operator::Mutation {
+                probability: 0.5,
+                sigma: 2.0,
+            })
+            .operator(Operator::Opposition { probability: 0.5 })
+            .seed(case),
+        );
+        particles.run(Options::canonical(), 10).unwrap();
+
+        for particle in particles.particles() {
+            assert!(inside(particle.value(), range), "case {}", case);
+            assert!(
+                particle.velocity().iter().all(|v| v.is_finite()),
+                "case {}",
+                case
+            );
+        }
+    }
+}
+
+#[test]
+fn repair_operator_brings_any_position_back() {
+    let mut rng = Pcg64::seed_from_u64(4);
+
+    for case in 0..200 {
+        let range = any_range(&mut rng);
+        let mut particles = quiet(
+            SwarmBuilder::new(Fitness::shared(|x: &Array1<f64>| x.sum(), true))
+                .size(4)
+                .dim(3)
+                .value_range(range.0, range.1)
+                .seed(case),
+        );
+
+        for i in 0..4 {
+            let value = Array1::from_shape_fn(3, |_| any_f64(&mut rng));
+            particles.mutate(i, |x| x.assign(&value));
+        }
+        particles.apply_operator(&Operator::Repair);
+
+        for particle in particles.particles() {
+            assert!(inside(particle.value(), range), "case {}", case);
+        }
+    }
+}
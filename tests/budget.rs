@@ -0,0 +1,93 @@
+#![cfg(feature = "full")]
+
+mod common;
+
+use common::{builder, quiet};
+use pso::budget::Priority;
+use pso::pso::{Algorithm, Options, Particles};
+use pso::transcript::Transcript;
+use std::io;
+
+fn swarm(budget: usize, priority: Priority) -> Particles<'static> {
+    quiet(
+        builder(20, 3, 6)
+            .evaluation_budget(budget)
+            .priority(priority),
+    )
+}
+
+#[test]
+fn runs_stop_when_the_budget_is_spent() {
+    let mut particles = swarm(50, Priority::InOrder);
+    assert_eq!(particles.evaluations_used(), 20);
+    assert_eq!(particles.evaluations_left(), Some(30));
+
+    let done = particles.run(Options::canonical(), 10).unwrap();
+
+    assert_eq!(done, 2);
+    assert_eq!(particles.evaluations_used(), 50);
+    assert!(particles.budget_exhausted());
+
+    // Further steps do nothing
+    let best = particles.best().clone();
+    particles.step(Options::canonical());
+    assert_eq!(particles.best(), &best);
+    assert_eq!(particles.steps(), 2);
+}
+
+#[test]
+fn truncated_steps_follow_the_priority() {
+    let mut particles = swarm(30, Priority::BestFirst);
+    let mut ranking: Vec<_> = (0..20).collect();
+    ranking.sort_by(|&a, &b| {
+        let fa = particles.particles()[a].fitness();
+        let fb = particles.particles()[b].fitness();
+        fa.partial_cmp(&fb).unwrap()
+    });
+    let before: Vec<_> = particles
+        .particles()
+        .iter()
+        .map(|p| p.value().clone())
+        .collect();
+
+    particles.step(Options::canonical());
+
+    for (rank, &i) in ranking.iter().enumerate() {
+        let moved = particles.particles()[i].value() != &before[i];
+        assert_eq!(moved, rank < 10);
+    }
+}
+
+#[test]
+fn every_evaluation_is_counted() {
+    let mut particles = swarm(1000, Priority::Random);
+    particles.set_transcript(Some(Transcript::new(io::sink())));
+
+    particles.run(Options::canonical(), 5).unwrap();
+    particles.restart_particle(3);
+    particles
+        .set_algorithm(Algorithm::Competitive { phi: 0.1 })
+        .unwrap();
+    particles.run(Options::canonical(), 5).unwrap();
+
+    let written = particles.transcript_mut().unwrap().written();
+    assert_eq!(particles.evaluations_used(), 20 + written);
+    assert_eq!(written, 5 * 20 + 1 + 5 * 10);
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn parallel_steps_respect_the_budget() {
+    use pso::pso::Parallelism;
+
+    let run = |parallelism| {
+        let mut particles = swarm(55, Priority::MostImproving);
+        particles.set_parallelism(parallelism).unwrap();
+        particles.run(Options::canonical(), 10).unwrap();
+
+        assert_eq!(particles.evaluations_used(), 55);
+        particles.best().clone()
+    };
+
+    assert_eq!(run(Parallelism::Serial), run(Parallelism::Deterministic));
+}
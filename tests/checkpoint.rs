@@ -0,0 +1,194 @@
+#![cfg(feature = "full")]
+
+mod common;
+
+use common::{builder, quiet};
+use ndarray::Array1;
+use pso::benchmark::sphere;
+use pso::builder::SwarmBuilder;
+use pso::checkpoint::{self, Autosave, Checkpointer};
+use pso::fitness::Fitness;
+use pso::history::Retention;
+use pso::pso::{Options, Particles};
+use pso::reconfigure::Reconfiguration;
+use pso::topology::Topology;
+use std::env;
+use std::time::Duration;
+
+fn swarm() -> Particles<'static> {
+    quiet(builder(10, 2, 17))
+}
+
+#[test]
+fn resumed_runs_match_uninterrupted_ones() {
+    let options = Options {
+        omega: None,
+        phi_1: 1.5,
+        phi_2: 1.5,
+    };
+
+    let mut uninterrupted = swarm();
+    uninterrupted.run(options, 20).unwrap();
+
+    let path = env::temp_dir().join(format!("pso-checkpoint-{}.json", std::process::id()));
+
+    let mut interrupted = swarm();
+    let mut checkpointer = Checkpointer::new(&path, options).every(Some(4)).keep(2);
+    interrupted
+        .run_with(options, 10, &mut [&mut checkpointer])
+        .unwrap();
+    checkpointer.finish().unwrap();
+
+    let mut resumed = checkpoint::resume(&path, Fitness::new(sphere, true)).unwrap();
+    assert_eq!(resumed.iter(), 10);
+    assert_eq!(resumed.run(20).unwrap(), 10);
+
+    let history = resumed.checkpointer.history();
+    assert_eq!(history.records().len(), 21);
+    assert_eq!(history.records()[20].iter, 20);
+
+    assert_eq!(resumed.particles.best(), uninterrupted.best());
+    for (a, b) in resumed
+        .particles
+        .particles()
+        .iter()
+        .zip(uninterrupted.particles())
+    {
+        assert_eq!(a.value(), b.value());
+        assert_eq!(a.velocity(), b.velocity());
+    }
+
+    let mut rotated = path.clone().into_os_string();
+    rotated.push(".1");
+    assert!(std::path::Path::new(&rotated).exists());
+
+    let _ = std::fs::remove_file(&path);
+    let _ = std::fs::remove_file(&rotated);
+}
+
+#[test]
+fn reconfiguring_a_resumed_run() {
+    let options = Options::canonical();
+    let path = env::temp_dir().join(format!("pso-reconfigure-{}.json", std::process::id()));
+
+    let mut particles = swarm();
+    let mut checkpointer = Checkpointer::new(&path, options).keep(1);
+    particles
+        .run_with(options, 5, &mut [&mut checkpointer])
+        .unwrap();
+    checkpointer.finish().unwrap();
+
+    let mut resumed = checkpoint::resume(&path, Fitness::new(sphere, true)).unwrap();
+    resumed.particles.set_verbose(false);
+
+    // Invalid changes leave the run untouched
+    let invalid = Reconfiguration::new()
+        .topology(Topology::Ring { radius: 1 })
+        .value_range(1.0, -1.0);
+    assert!(resumed.reconfigure(&invalid).is_err());
+    assert_eq!(resumed.particles.topology(), Topology::default());
+    assert_eq!(resumed.particles.value_range(), (-10.0, 10.0));
+
+    let steered = Options {
+        phi_2: 2.0,
+        ..Options::canonical()
+    };
+    let reconfiguration = Reconfiguration::new()
+        .options(steered)
+        .topology(Topology::Ring { radius: 1 })
+        .value_range(0.5, 2.0)
+        .velocity_range(-0.1, 0.1);
+    resumed.reconfigure(&reconfiguration).unwrap();
+
+    assert_eq!(resumed.options, steered);
+    assert_eq!(resumed.particles.neighborhoods().unwrap().len(), 10);
+    assert_eq!(resumed.particles.velocity_range(), (-0.1, 0.1));
+    let inside = |x: &Array1<f64>| x.iter().all(|v| (0.5..=2.0).contains(v));
+    for particle in resumed.particles.particles() {
+        assert!(inside(particle.value()) && inside(particle.best()));
+    }
+    assert!(inside(resumed.particles.best()));
+
+    resumed.run(10).unwrap();
+    assert_eq!(
+        checkpoint::Checkpoint::load(&path).unwrap().options,
+        steered
+    );
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn non_finite_fitness_round_trips() {
+    let options = Options::canonical();
+    let path = env::temp_dir().join(format!("pso-infinite-{}.json", std::process::id()));
+    let unbounded = |_: &Array1<f64>| f64::INFINITY;
+
+    let mut particles = quiet(SwarmBuilder::new(Fitness::new(unbounded, true)).seed(17));
+    let mut checkpointer = Checkpointer::new(&path, options).keep(1);
+    particles
+        .run_with(options, 3, &mut [&mut checkpointer])
+        .unwrap();
+    checkpointer.finish().unwrap();
+    assert_eq!(particles.best_fitness(), f64::INFINITY);
+
+    let resumed = checkpoint::resume(&path, Fitness::new(unbounded, true)).unwrap();
+    assert_eq!(resumed.particles.best_fitness(), f64::INFINITY);
+    assert!(resumed
+        .particles
+        .particles()
+        .iter()
+        .all(|p| p.best_fitness() == f64::INFINITY && p.fitness() == f64::INFINITY));
+
+    let history = resumed.checkpointer.history();
+    assert_eq!(history.records()[3].best_fitness, f64::INFINITY);
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn resumed_runs_keep_the_autosave_settings() {
+    let options = Options::canonical();
+    let path = env::temp_dir().join(format!("pso-autosave-{}.json", std::process::id()));
+    let autosave = Autosave {
+        every: Some(10),
+        interval: Some(Duration::from_secs(300)),
+        keep: 10,
+    };
+
+    let mut particles = swarm();
+    let mut checkpointer = Checkpointer::new(&path, options)
+        .every(autosave.every)
+        .interval(autosave.interval)
+        .keep(autosave.keep)
+        .retention(Retention::Last(3))
+        .unwrap();
+    particles
+        .run_with(options, 15, &mut [&mut checkpointer])
+        .unwrap();
+    checkpointer.finish().unwrap();
+
+    let mut resumed = checkpoint::resume(&path, Fitness::new(sphere, true)).unwrap();
+    resumed.particles.set_verbose(false);
+    assert_eq!(resumed.checkpointer.autosave(), autosave);
+    let history = resumed.checkpointer.history();
+    assert_eq!(history.retention(), Retention::Last(3));
+    assert_eq!(history.records().len(), 3);
+
+    resumed.run(25).unwrap();
+    let history = resumed.checkpointer.history();
+    assert_eq!(history.records().len(), 3);
+    assert_eq!(history.records()[2].iter, 25);
+
+    // Saved on the 10th and 20th iterations and at the end of both runs
+    let mut rotated = path.clone().into_os_string();
+    rotated.push(".3");
+    assert!(std::path::Path::new(&rotated).exists());
+
+    let _ = std::fs::remove_file(&path);
+    for i in 1..=3 {
+        let mut rotated = path.clone().into_os_string();
+        rotated.push(format!(".{}", i));
+        let _ = std::fs::remove_file(&rotated);
+    }
+}
@@ -0,0 +1,190 @@
+#![cfg(feature = "full")]
+
+mod common;
+
+use common::{builder, quiet};
+use ndarray::{arr1, Array1};
+use pso::benchmark::sphere;
+use pso::builder::SwarmBuilder;
+use pso::clustering::{kmeans, ClusterRestart};
+use pso::error::PsoError;
+use pso::fitness::Fitness;
+use pso::lineage::LineageEvent;
+use pso::pso::{Options, Particles};
+use rand::SeedableRng;
+use rand_pcg::Pcg64;
+
+fn swarm() -> Particles<'static> {
+    quiet(builder(12, 2, 5).value_range(-5.0, 5.0))
+}
+
+/// Four particles around each of the optimum, a point close to it and a
+/// far corner.
+fn three_groups(particles: &mut Particles) -> Vec<Array1<f64>> {
+    let centers = [arr1(&[0.0, 0.0]), arr1(&[0.5, 0.5]), arr1(&[-4.0, 4.0])];
+    let positions: Vec<_> = (0..12)
+        .map(|i| &centers[i / 4] + &arr1(&[0.01 * (i % 4) as f64, 0.0]))
+        .collect();
+
+    for (i, position) in positions.iter().enumerate() {
+        particles.mutate(i, |x| x.assign(position));
+    }
+    positions
+}
+
+#[test]
+fn kmeans_separates_blobs() {
+    let points: Vec<_> = (0..20)
+        .map(|i| {
+            let offset = 0.1 * (i % 5) as f64;
+            if i < 10 {
+                arr1(&[offset, -offset])
+            } else {
+                arr1(&[10.0 + offset, 10.0 - offset])
+            }
+        })
+        .collect();
+
+    let clusters = kmeans(&points, 2, &mut Pcg64::seed_from_u64(1));
+
+    assert_eq!(clusters.centroids.len(), 2);
+    assert_ne!(clusters.labels[0], clusters.labels[10]);
+    for i in 0..20 {
+        let first = if i < 10 { 0 } else { 10 };
+        assert_eq!(clusters.labels[i], clusters.labels[first]);
+    }
+    assert_eq!(
+        clusters.members(clusters.labels[0]),
+        (0..10).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn kmeans_never_has_more_clusters_than_points() {
+    let points = vec![arr1(&[1.0]), arr1(&[2.0])];
+    let clusters = kmeans(&points, 5, &mut Pcg64::seed_from_u64(1));
+
+    assert_eq!(clusters.centroids.len(), 2);
+    assert_ne!(clusters.labels[0], clusters.labels[1]);
+}
+
+#[test]
+fn kmeans_tolerates_nan_points() {
+    let points = vec![arr1(&[f64::NAN]), arr1(&[1.0]), arr1(&[5.0])];
+
+    for seed in 0..10 {
+        let clusters = kmeans(&points, 2, &mut Pcg64::seed_from_u64(seed));
+        assert_eq!(clusters.labels.len(), 3);
+        assert!(!clusters.centroids.is_empty());
+    }
+}
+
+#[test]
+fn redundant_clusters_keep_one_representative() {
+    let mut particles = swarm();
+    let positions = three_groups(&mut particles);
+    let restart = ClusterRestart {
+        every: 1,
+        clusters: 3,
+        radius: 0.1,
+    };
+
+    assert_eq!(particles.restart_clusters(&restart), 3);
+
+    let current: Vec<_> = particles.particles().iter().map(|p| p.value()).collect();
+    for i in (0..4).chain(8..12) {
+        assert_eq!(current[i], &positions[i]);
+    }
+    let kept = (4..8).filter(|&i| current[i] == &positions[i]).count();
+    assert_eq!(kept, 1);
+
+    let restarted = particles
+        .lineage()
+        .iter()
+        .filter(|l| l.event == LineageEvent::Reinitialized)
+        .count();
+    assert_eq!(restarted, 3);
+}
+
+#[test]
+fn distinct_clusters_are_kept() {
+    let mut particles = swarm();
+    three_groups(&mut particles);
+    let restart = ClusterRestart {
+        every: 1,
+        clusters: 3,
+        radius: 0.01,
+    };
+
+    assert_eq!(particles.restart_clusters(&restart), 0);
+}
+
+#[test]
+fn restarts_wait_for_their_period() {
+    let mut particles = swarm();
+    let restart = ClusterRestart {
+        every: 3,
+        clusters: 2,
+        radius: 10.0,
+    };
+
+    particles.step(Options::canonical());
+    three_groups(&mut particles);
+    assert_eq!(particles.restart_clusters(&restart), 0);
+
+    particles.step(Options::canonical());
+    particles.step(Options::canonical());
+    assert!(particles.restart_clusters(&restart) > 0);
+}
+
+#[test]
+fn cluster_restarts_run_in_the_pipeline() {
+    let mut particles = quiet(
+        SwarmBuilder::new(Fitness::new(sphere, true))
+            .size(12)
+            .dim(2)
+            .cluster_restart(ClusterRestart {
+                every: 5,
+                clusters: 3,
+                radius: 0.5,
+            })
+            .seed(5),
+    );
+
+    particles.run(Options::canonical(), 20).unwrap();
+
+    let restarts: Vec<_> = particles
+        .lineage()
+        .iter()
+        .filter(|l| l.event == LineageEvent::Reinitialized)
+        .map(|l| l.step)
+        .collect();
+    assert!(!restarts.is_empty());
+    assert!(restarts.iter().all(|step| step % 5 == 0));
+    assert_eq!(particles.best_fitness(), sphere(particles.best()));
+}
+
+#[test]
+fn invalid_cluster_restarts_are_rejected() {
+    for restart in [
+        ClusterRestart {
+            every: 0,
+            ..ClusterRestart::default()
+        },
+        ClusterRestart {
+            clusters: 1,
+            ..ClusterRestart::default()
+        },
+        ClusterRestart {
+            radius: -1.0,
+            ..ClusterRestart::default()
+        },
+    ]
+    .iter()
+    {
+        let result = SwarmBuilder::new(Fitness::new(sphere, true))
+            .cluster_restart(*restart)
+            .build();
+        assert!(matches!(result, Err(PsoError::InvalidOptions(_))));
+    }
+}
@@ -0,0 +1,24 @@
+//! Fixtures shared by the integration tests, every file pulls in the ones it
+//! needs with `mod common;`.
+#![allow(dead_code)]
+
+use pso::benchmark::sphere;
+use pso::builder::SwarmBuilder;
+use pso::fitness::Fitness;
+use pso::pso::Particles;
+
+/// Seeded swarm of `size` particles minimizing the sphere in `dim`
+/// dimensions, to be configured further.
+pub fn builder(size: usize, dim: usize, seed: u64) -> SwarmBuilder<'static> {
+    SwarmBuilder::new(Fitness::shared(sphere, true))
+        .size(size)
+        .dim(dim)
+        .seed(seed)
+}
+
+/// Builds the swarm without printing its progress.
+pub fn quiet(builder: SwarmBuilder<'_>) -> Particles<'_> {
+    let mut particles = builder.build().unwrap();
+    particles.set_verbose(false);
+    particles
+}
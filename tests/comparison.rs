@@ -0,0 +1,89 @@
+#![cfg(feature = "full")]
+
+mod common;
+
+use common::quiet;
+use ndarray::{arr1, Array1};
+use pso::builder::SwarmBuilder;
+use pso::fitness::{stable_sum, Comparison, Fitness};
+use pso::pso::{Options, Particles};
+use std::cmp::Ordering;
+
+/// Sphere that's undefined on half of the space.
+fn partial_sphere(x: &Array1<f64>) -> f64 {
+    if x[0] < 0.0 {
+        f64::NAN
+    } else {
+        x.mapv(|v| v * v).sum()
+    }
+}
+
+fn swarm(comparison: Comparison, minimization: bool) -> Particles<'static> {
+    quiet(
+        SwarmBuilder::new(Fitness::shared(partial_sphere, minimization))
+            .size(20)
+            .dim(2)
+            .seed(12)
+            .comparison(comparison),
+    )
+}
+
+#[test]
+fn nan_is_the_worst_fitness() {
+    let total = Comparison::Total;
+    assert_eq!(total.compare(f64::NAN, f64::NEG_INFINITY), Ordering::Less);
+    assert_eq!(total.compare(1.0, f64::NAN), Ordering::Greater);
+    assert_eq!(total.compare(f64::NAN, -f64::NAN), Ordering::Equal);
+    assert_eq!(total.compare(-0.0, 0.0), Ordering::Less);
+    assert!(!total.improves(f64::NAN, 1.0));
+    assert!(total.improves(1.0, f64::NAN));
+
+    assert_eq!(Comparison::Partial.compare(-0.0, 0.0), Ordering::Equal);
+    assert!(!Comparison::Partial.improves(1.0, f64::NAN));
+}
+
+#[test]
+fn total_runs_skip_nan_fitness() {
+    for &minimization in &[true, false] {
+        let mut particles = swarm(Comparison::Total, minimization);
+        assert_eq!(particles.comparison(), Comparison::Total);
+        particles.run(Options::canonical(), 30).unwrap();
+
+        assert!(!particles.best_fitness().is_nan());
+        assert!(particles.best()[0] >= 0.0);
+    }
+}
+
+#[test]
+#[should_panic(expected = "Received a NaN")]
+fn partial_runs_panic_on_nan_fitness() {
+    let mut particles = swarm(Comparison::Partial, true);
+    particles.run(Options::canonical(), 30).unwrap();
+}
+
+#[test]
+fn compares_in_the_direction_of_the_fitness() {
+    let particles = swarm(Comparison::Total, true);
+    assert_eq!(particles.compare(1.0, 2.0), Ordering::Greater);
+    assert_eq!(particles.compare(f64::NAN, 2.0), Ordering::Less);
+
+    let particles = swarm(Comparison::Total, false);
+    assert_eq!(particles.compare(1.0, 2.0), Ordering::Less);
+    assert_eq!(particles.compare(f64::NAN, 2.0), Ordering::Less);
+}
+
+#[test]
+fn stable_sum_compensates_rounding() {
+    let values = [1e16, 1.0, -1e16, 1.0];
+    assert_eq!(values.iter().sum::<f64>(), 1.0);
+    assert_eq!(stable_sum(values.iter().copied()), 2.0);
+
+    assert_eq!(stable_sum(arr1(&[0.1; 10]).iter().copied()), 1.0);
+}
+
+#[test]
+fn comparison_is_part_of_the_state() {
+    let particles = swarm(Comparison::Total, true);
+    let restored = Particles::from_state(particles.state(), Fitness::new(partial_sphere, true));
+    assert_eq!(restored.comparison(), Comparison::Total);
+}
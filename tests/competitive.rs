@@ -0,0 +1,106 @@
+#![cfg(feature = "full")]
+
+mod common;
+
+use common::{builder, quiet};
+use ndarray::Array1;
+use pso::benchmark::sphere;
+use pso::builder::SwarmBuilder;
+use pso::fitness::Fitness;
+use pso::pso::{Algorithm, Options, Particles};
+use pso::reconfigure::Reconfiguration;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+fn cso(size: usize, dim: usize, seed: u64) -> Particles<'static> {
+    quiet(
+        builder(size, dim, seed)
+            .value_range(-5.0, 5.0)
+            .velocity_range(-1.0, 1.0)
+            .algorithm(Algorithm::Competitive { phi: 0.1 }),
+    )
+}
+
+#[test]
+fn converges_in_high_dimensions() {
+    let mut particles = cso(100, 100, 3);
+    let initial = particles.best_fitness();
+
+    particles.run(Options::canonical(), 500).unwrap();
+
+    assert!(particles.best_fitness() < initial * 1e-3);
+    assert_eq!(particles.steps(), 500);
+}
+
+#[test]
+fn only_losers_move() {
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    let mut particles = quiet(
+        SwarmBuilder::new(Fitness::new(
+            |x: &Array1<f64>| {
+                CALLS.fetch_add(1, Ordering::SeqCst);
+                sphere(x)
+            },
+            true,
+        ))
+        .size(11)
+        .algorithm(Algorithm::Competitive { phi: 0.0 })
+        .seed(4),
+    );
+
+    let before: Vec<_> = particles
+        .particles()
+        .iter()
+        .map(|p| p.value().clone())
+        .collect();
+    CALLS.store(0, Ordering::SeqCst);
+    particles.step(Options::canonical());
+
+    assert_eq!(CALLS.load(Ordering::SeqCst), 5);
+    let unmoved = particles
+        .particles()
+        .iter()
+        .zip(&before)
+        .filter(|(p, before)| p.value() == *before)
+        .count();
+    assert_eq!(unmoved, 6);
+}
+
+#[test]
+fn reproducible_with_seed() {
+    let mut a = cso(20, 5, 7);
+    let mut b = cso(20, 5, 7);
+
+    a.run(Options::canonical(), 30).unwrap();
+    b.run(Options::canonical(), 30).unwrap();
+
+    assert_eq!(a.best(), b.best());
+}
+
+#[test]
+fn rejects_negative_phi() {
+    let result = SwarmBuilder::new(Fitness::new(sphere, true))
+        .algorithm(Algorithm::Competitive { phi: -1.0 })
+        .build();
+    assert!(result.is_err());
+
+    let mut particles = cso(4, 2, 0);
+    assert!(particles
+        .set_algorithm(Algorithm::Competitive { phi: f64::NAN })
+        .is_err());
+    assert_eq!(particles.algorithm(), Algorithm::Competitive { phi: 0.1 });
+}
+
+#[test]
+fn switches_algorithm_between_steps() {
+    let mut particles = cso(10, 3, 5);
+    particles.run(Options::canonical(), 10).unwrap();
+
+    let reconfiguration = Reconfiguration::new().algorithm(Algorithm::Standard);
+    let mut options = Options::canonical();
+    reconfiguration.apply(&mut particles, &mut options).unwrap();
+    assert_eq!(particles.algorithm(), Algorithm::Standard);
+
+    let restored = Particles::from_state(particles.state(), Fitness::new(sphere, true));
+    assert_eq!(restored.algorithm(), Algorithm::Standard);
+}
@@ -0,0 +1,393 @@
+#![cfg(feature = "full")]
+
+mod common;
+
+use common::quiet;
+use ndarray::{array, Array1};
+use pso::benchmark::sphere;
+use pso::builder::SwarmBuilder;
+use pso::constraints::{
+    self, AdaptivePenalty, ConstraintComparison, ConstraintHandling, Evaluation, LinearConstraints,
+};
+use pso::fitness::Fitness;
+use pso::history::History;
+use pso::problems::{Problem, Spring};
+use pso::pso::{Confinement, Options};
+use pso::report::Language;
+use rand::SeedableRng;
+use rand_pcg::Pcg64;
+use std::cmp::Ordering;
+
+#[test]
+fn projection_lands_on_the_closest_feasible_point() {
+    // x + y >= 2
+    let constraints = LinearConstraints::new(array![[-1.0, -1.0]], array![-2.0]);
+    let projected = constraints.project(&array![0.0, 0.0]);
+
+    assert!((&projected - &array![1.0, 1.0])
+        .iter()
+        .all(|v| v.abs() < 1e-6));
+
+    let constraints = constraints.with_bounds(array![1.5, -5.0], array![5.0, 5.0]);
+    let projected = constraints.project(&array![0.0, 0.0]);
+
+    assert!(constraints.is_feasible(&projected, 1e-6));
+    assert!((&projected - &array![1.5, 0.5])
+        .iter()
+        .all(|v| v.abs() < 1e-6));
+}
+
+#[test]
+fn constrained_swarm_only_visits_feasible_points() {
+    let mut particles = SwarmBuilder::new(Fitness::new(sphere, true))
+        .seed(5)
+        .build()
+        .unwrap();
+
+    let constraints = LinearConstraints::new(array![[-1.0, -1.0]], array![-2.0]);
+    particles.set_constraint_handling(Some(ConstraintHandling::Projection(constraints.clone())));
+
+    for _ in 0..50 {
+        particles.step(Options::canonical());
+        for particle in particles.particles() {
+            assert!(constraints.is_feasible(particle.value(), 1e-6));
+        }
+    }
+
+    // The constrained optimum is (1, 1)
+    assert!((sphere(particles.best()) - 2.0).abs() < 1e-2);
+}
+
+#[test]
+fn repair_hook_keeps_weights_normalized() {
+    let target = array![0.2, 0.3, 0.5];
+    let distance = move |x: &Array1<f64>| (x - &target).mapv(|v| v * v).sum();
+
+    let mut particles = quiet(
+        SwarmBuilder::new(Fitness::new(distance, true))
+            .size(10)
+            .dim(3)
+            .value_range(0.1, 1.0)
+            .seed(6),
+    );
+    particles.set_repair_hook(|x| {
+        x.mapv_inplace(|v| v.abs());
+        let sum = x.sum();
+        *x /= sum;
+    });
+
+    let normalized = |x: &Array1<f64>| (x.sum() - 1.0).abs() < 1e-9;
+    assert!(particles.particles().iter().all(|p| normalized(p.value())));
+
+    particles.run(Options::canonical(), 50).unwrap();
+    assert!(particles.particles().iter().all(|p| normalized(p.value())));
+    assert!(normalized(particles.best()));
+}
+
+#[test]
+fn simplex_projection() {
+    let projected = constraints::project_simplex(&array![0.5, 0.5, 0.5]);
+    assert!((&projected - &array![1.0 / 3.0, 1.0 / 3.0, 1.0 / 3.0])
+        .iter()
+        .all(|v| v.abs() < 1e-12));
+
+    let projected = constraints::project_simplex(&array![2.0, -1.0, 0.0]);
+    assert_eq!(projected, array![1.0, 0.0, 0.0]);
+
+    let feasible = array![0.2, 0.3, 0.5];
+    assert!((constraints::project_simplex(&feasible) - &feasible)
+        .iter()
+        .all(|v| v.abs() < 1e-12));
+}
+
+#[test]
+fn simplex_swarm_finds_the_weights() {
+    let target = array![0.1, 0.6, 0.3];
+    let objective = {
+        let target = target.clone();
+        move |x: &Array1<f64>| (x - &target).mapv(|v| v * v).sum()
+    };
+
+    let mut particles = quiet(
+        SwarmBuilder::new(Fitness::new(objective, true))
+            .size(20)
+            .dim(3)
+            .seed(7),
+    );
+    particles.set_constraint_handling(Some(ConstraintHandling::Simplex));
+    particles.run(Options::canonical(), 100).unwrap();
+
+    for particle in particles.particles() {
+        assert!(particle.value().iter().all(|&v| v >= 0.0));
+        assert!((particle.value().sum() - 1.0).abs() < 1e-9);
+    }
+    assert!(particles.best_fitness() < 1e-6);
+
+    let objective = {
+        let target = target.clone();
+        move |x: &Array1<f64>| (x - &target).mapv(|v| v * v).sum()
+    };
+    let mut particles = quiet(
+        SwarmBuilder::new(Fitness::new(constraints::on_softmax(objective), true))
+            .size(20)
+            .dim(3)
+            .seed(7),
+    );
+    particles.run(Options::canonical(), 200).unwrap();
+
+    let weights = constraints::softmax(particles.best());
+    assert!((weights.sum() - 1.0).abs() < 1e-9);
+    assert!((weights - &target).iter().all(|v| v.abs() < 1e-2));
+}
+
+fn evaluation(objective: f64, violation: f64) -> Evaluation {
+    Evaluation {
+        objective,
+        violation,
+    }
+}
+
+#[test]
+fn feasibility_rules() {
+    let mut rng = Pcg64::seed_from_u64(0);
+    let rules = ConstraintComparison::FeasibilityRules;
+    let feasible = evaluation(10.0, 0.0);
+    let better = evaluation(5.0, 0.0);
+    let infeasible = evaluation(1.0, 0.5);
+    let worse = evaluation(1.0, 2.0);
+
+    assert_eq!(
+        rules.compare(&better, &feasible, &mut rng),
+        Ordering::Greater
+    );
+    assert_eq!(
+        rules.compare(&feasible, &infeasible, &mut rng),
+        Ordering::Greater
+    );
+    assert_eq!(rules.compare(&worse, &infeasible, &mut rng), Ordering::Less);
+
+    let evaluations = [infeasible, feasible, worse, better];
+    assert_eq!(rules.rank(&evaluations, &mut rng), vec![3, 1, 0, 2]);
+
+    // A light penalty prefers the infeasible point of low objective
+    let penalty = ConstraintComparison::Penalty(1.0);
+    assert_eq!(penalty.rank(&evaluations, &mut rng), vec![0, 2, 3, 1]);
+}
+
+#[test]
+fn stochastic_ranking() {
+    let mut rng = Pcg64::seed_from_u64(1);
+    let evaluations = [
+        evaluation(1.0, 0.5),
+        evaluation(10.0, 0.0),
+        evaluation(1.0, 2.0),
+        evaluation(5.0, 0.0),
+    ];
+
+    // Never by objective unless both are feasible, same as the rules
+    let by_violation = ConstraintComparison::StochasticRanking { pf: 0.0 };
+    let rules = ConstraintComparison::FeasibilityRules;
+    assert_eq!(
+        by_violation.rank(&evaluations, &mut rng),
+        rules.rank(&evaluations, &mut rng)
+    );
+
+    // Always by objective
+    let by_objective = ConstraintComparison::StochasticRanking { pf: 1.0 };
+    let order = by_objective.rank(&evaluations, &mut rng);
+    assert_eq!(&order[2..], &[3, 1]);
+
+    // In between, infeasible points of low objective sometimes come first
+    let ranking = ConstraintComparison::stochastic_ranking();
+    let firsts: Vec<_> = (0..200)
+        .map(|_| ranking.rank(&evaluations, &mut rng)[0])
+        .collect();
+    assert!(firsts.contains(&0) && firsts.contains(&3));
+}
+
+#[test]
+fn invalid_constraint_comparisons() {
+    assert!(ConstraintComparison::Penalty(-1.0).validate().is_err());
+    assert!(ConstraintComparison::StochasticRanking { pf: 1.5 }
+        .validate()
+        .is_err());
+    assert!(ConstraintComparison::stochastic_ranking()
+        .validate()
+        .is_ok());
+}
+
+#[test]
+fn comparisons_solve_the_spring() {
+    let problem = Spring;
+    let (min, max) = problem.bounds();
+    let comparisons = [
+        ConstraintComparison::Penalty(1e6),
+        ConstraintComparison::FeasibilityRules,
+        ConstraintComparison::stochastic_ranking(),
+    ];
+
+    for &comparison in comparisons.iter() {
+        // Stochastic ranking leads a swarm moving by the feasibility rules
+        let fitness = comparison
+            .fitness(&problem)
+            .or_else(|| ConstraintComparison::FeasibilityRules.fitness(&problem))
+            .unwrap();
+
+        let mut particles = SwarmBuilder::spso_2011(fitness)
+            .dim(problem.dim())
+            .normalize(min.clone(), max.clone())
+            .confinement(Confinement::Bounded)
+            .seed(2)
+            .build()
+            .unwrap();
+
+        for _ in 0..500 {
+            particles.step(Options::spso_2011());
+            if let ConstraintComparison::StochasticRanking { .. } = comparison {
+                particles.promote_ranked_best(&problem, comparison);
+            }
+        }
+
+        let best = particles.denormalize(particles.best());
+        let relative = (problem.objective(&best) - problem.best_known()) / problem.best_known();
+        assert!(problem.is_feasible(&best, 1e-4), "{:?}", comparison);
+        assert!(relative < 0.05, "{:?}: {}", comparison, relative);
+    }
+}
+
+#[test]
+fn penalty_weights_follow_feasibility() {
+    let problem = Spring;
+    let penalty = AdaptivePenalty::new(4, 1.0);
+    let feasible = array![0.06, 0.5, 10.0];
+    let infeasible = array![0.05, 0.25, 2.0];
+
+    // Every constraint but the last is violated by most, those grow
+    let constraints = problem.constraints(&infeasible);
+    let positions = vec![feasible.clone(), infeasible.clone(), infeasible];
+    let fraction = penalty.adapt(&problem, &positions);
+    assert!((fraction - 1.0 / 3.0).abs() < 1e-12);
+
+    for (w, g) in penalty.weights().iter().zip(&constraints) {
+        assert_eq!(*w, if *g > 0.0 { 2.0 } else { 0.5 });
+    }
+
+    // All feasible, every weight shrinks
+    penalty.adapt(&problem, std::slice::from_ref(&feasible));
+    assert_eq!(
+        penalty.fitness(&problem).calculate(&feasible),
+        problem.objective(&feasible)
+    );
+
+    let clamped = AdaptivePenalty::new(1, 1.0).range(0.5, 1.5);
+    for _ in 0..3 {
+        clamped.adapt(&problem, &[]);
+    }
+    assert_eq!(clamped.weights(), vec![1.5]);
+}
+
+#[test]
+fn invalid_adaptive_penalties() {
+    assert!(AdaptivePenalty::new(1, 1.0).validate().is_ok());
+    assert!(AdaptivePenalty::new(1, 1.0).target(2.0).validate().is_err());
+    assert!(AdaptivePenalty::new(1, 1.0).factor(1.0).validate().is_err());
+    assert!(AdaptivePenalty::new(1, 1.0)
+        .range(1.0, 0.5)
+        .validate()
+        .is_err());
+}
+
+#[test]
+fn adaptive_penalty_finds_feasible_designs() {
+    let problem = Spring;
+    let (min, max) = problem.bounds();
+    let run = |adapt: bool| {
+        // Far too light to keep the swarm feasible on its own
+        let penalty = AdaptivePenalty::new(4, 1e-6);
+        let mut particles = SwarmBuilder::spso_2011(penalty.fitness(&problem))
+            .dim(problem.dim())
+            .normalize(min.clone(), max.clone())
+            .confinement(Confinement::Bounded)
+            .seed(3)
+            .build()
+            .unwrap();
+
+        for _ in 0..500 {
+            particles.step(Options::spso_2011());
+            if adapt {
+                particles.adapt_penalty(&penalty, &problem);
+            }
+        }
+
+        particles.denormalize(particles.best())
+    };
+
+    assert!(!problem.is_feasible(&run(false), 1e-4));
+
+    let best = run(true);
+    let relative = (problem.objective(&best) - problem.best_known()) / problem.best_known();
+    assert!(problem.is_feasible(&best, 1e-4));
+    assert!(relative < 0.05, "{}", relative);
+}
+
+#[test]
+fn violations_are_reported() {
+    let problem = Spring;
+    let (min, max) = problem.bounds();
+    let fitness = ConstraintComparison::Penalty(1e6)
+        .fitness(&problem)
+        .unwrap();
+
+    let mut particles = quiet(
+        SwarmBuilder::spso_2011(fitness)
+            .dim(problem.dim())
+            .normalize(min, max)
+            .seed(4),
+    );
+    particles.set_language(Language::English);
+
+    let mut history = History::new();
+    particles
+        .run_with(Options::spso_2011(), 100, &mut [&mut history])
+        .unwrap();
+
+    // Constraints see the original coordinates, like the fitness
+    let best = particles.denormalize(particles.best());
+    let expected: Vec<_> = problem
+        .constraints(&best)
+        .into_iter()
+        .map(|g| g.max(0.0))
+        .collect();
+    assert_eq!(particles.best_violations(), Some(expected.clone()));
+
+    let records = history.records();
+    assert!(records.iter().all(|r| r.feasible.unwrap() <= 40));
+    assert!(records[0].feasible < records[100].feasible);
+    assert_eq!(records[100].violations, Some(expected.clone()));
+
+    let summary = particles.summary(false).unwrap();
+    assert!(summary.contains(">>> Constraint violations: ["));
+    assert!(summary.contains(&format!(
+        ">>> Feasible particles: {} / 40",
+        records[100].feasible.unwrap()
+    )));
+
+    let json = particles.summary_json();
+    assert_eq!(json["violations"], serde_json::json!(expected));
+}
+
+#[test]
+fn unconstrained_fitnesses_report_nothing() {
+    let mut particles = quiet(
+        SwarmBuilder::new(Fitness::new(sphere, true))
+            .size(5)
+            .dim(2)
+            .seed(5),
+    );
+    particles.step(Options::canonical());
+
+    assert_eq!(particles.best_violations(), None);
+    assert_eq!(particles.feasible_particles(), None);
+    assert!(!particles.summary(false).unwrap().contains("Violación"));
+    assert!(particles.summary_json().get("violations").is_none());
+}
@@ -0,0 +1,87 @@
+#![cfg(feature = "full")]
+
+mod common;
+
+use common::quiet;
+use ndarray::{array, Array1};
+use pso::builder::SwarmBuilder;
+use pso::coordinates::{Coordinate, Coordinates};
+use pso::fitness::Fitness;
+use pso::pso::Options;
+
+#[test]
+fn coordinates_round_trip() {
+    let coordinates = Coordinates::new(vec![
+        Coordinate::Linear,
+        Coordinate::Log10,
+        Coordinate::Logit,
+        Coordinate::Affine {
+            scale: 2.0,
+            offset: -1.0,
+        },
+    ]);
+
+    let x = array![3.5, 1e-4, 0.25, 7.0];
+    let y = coordinates.encode(&x);
+    assert!((y[1] + 4.0).abs() < 1e-12);
+    assert_eq!(y[3], 4.0);
+
+    let decoded = coordinates.decode(&y);
+    assert!((decoded - &x).iter().all(|v| v.abs() < 1e-12));
+}
+
+#[test]
+fn swarm_searches_in_log_space() {
+    // Best learning rate is 1e-3, somewhere in (1e-6, 1)
+    let loss = |x: &Array1<f64>| (x[0].log10() + 3.0).powi(2);
+    let coordinates = Coordinates::uniform(1, Coordinate::Log10);
+
+    let mut particles = quiet(
+        SwarmBuilder::new(Fitness::new(coordinates.clone().wrap(loss), true))
+            .size(10)
+            .dim(1)
+            .value_range(-6.0, 0.0)
+            .seed(9),
+    );
+    particles.run(Options::canonical(), 50).unwrap();
+
+    let rate = coordinates.decode(particles.best())[0];
+    assert!((rate / 1e-3 - 1.0).abs() < 1e-3, "{}", rate);
+}
+
+#[test]
+fn normalized_search_space() {
+    // Dimensions six orders of magnitude apart
+    let (min, max) = (array![-1e-3, -1e3], array![1e-3, 1e3]);
+    let target = array![5e-4, -250.0];
+    let f = {
+        let target = target.clone();
+        move |x: &Array1<f64>| ((x - &target) / array![1e-3, 1e3]).mapv(|v| v * v).sum()
+    };
+
+    let mut particles = quiet(
+        SwarmBuilder::new(Fitness::new(f, true))
+            .size(20)
+            .seed(24)
+            .normalize(min.clone(), max.clone()),
+    );
+    particles.run(Options::canonical(), 200).unwrap();
+
+    let coordinates = particles.normalization().unwrap();
+    assert_eq!(coordinates.decode(&array![0.0, 1.0]), array![-1e-3, 1e3]);
+
+    let best = particles.denormalize(particles.best());
+    assert!(
+        ((&best - &target) / array![1e-3, 1e3])
+            .iter()
+            .all(|d| d.abs() < 1e-3),
+        "{}",
+        best
+    );
+
+    let wrong = SwarmBuilder::new(Fitness::new(|x: &Array1<f64>| x.sum(), true))
+        .dim(3)
+        .normalize(min, max)
+        .build();
+    assert!(wrong.is_err());
+}
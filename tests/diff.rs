@@ -0,0 +1,80 @@
+#![cfg(feature = "full")]
+
+mod common;
+
+use common::{builder, quiet};
+use ndarray::arr1;
+use pso::benchmark::sphere;
+use pso::diff::SwarmDiff;
+use pso::fitness::Fitness;
+use pso::pso::{Options, Particles};
+
+fn swarm() -> Particles<'static> {
+    quiet(builder(8, 2, 21))
+}
+
+#[test]
+fn identical_swarms_have_no_diff() {
+    let particles = swarm();
+    let diff = particles.diff(&swarm());
+
+    assert!(diff.is_empty());
+    assert_eq!(diff.steps, (0, 0));
+    assert_eq!(diff.to_string().lines().count(), 1);
+}
+
+#[test]
+fn reports_moves_and_improvements() {
+    let before = swarm();
+    let mut after = swarm();
+    after.step(Options::canonical());
+
+    let diff = before.diff(&after);
+    assert_eq!(diff.steps, (0, 1));
+    assert_eq!(
+        diff.best_fitness,
+        (before.best_fitness(), after.best_fitness())
+    );
+    assert_eq!(diff.moved().count(), 8);
+    assert!(diff.max_displacement() > 0.0);
+
+    for change in diff.improved() {
+        let particle = &after.particles()[change.index.1];
+        assert_eq!(particle.best(), particle.value());
+        assert_eq!(change.best_displacement, change.displacement);
+    }
+    assert_eq!(diff.to_string().lines().count(), 9);
+}
+
+#[test]
+fn resuming_from_a_state_is_exact() {
+    let mut particles = swarm();
+    particles.run(Options::canonical(), 5).unwrap();
+
+    let state = particles.state();
+    let mut resumed = Particles::from_state(state.clone(), Fitness::shared(sphere, true));
+    assert!(particles.diff_state(&state).is_empty());
+
+    particles.run(Options::canonical(), 5).unwrap();
+    resumed.run(Options::canonical(), 5).unwrap();
+    assert!(particles.diff(&resumed).is_empty());
+
+    let diff = SwarmDiff::of_states(&state, &resumed.state());
+    assert_eq!(diff.steps, (5, 10));
+}
+
+#[test]
+fn matches_particles_by_id() {
+    let before = swarm();
+    let mut after = swarm();
+    let removed = after.particles()[0].id();
+    after.remove_particle(removed).unwrap();
+    let added = after
+        .add_particle(arr1(&[0.5, 0.5]), arr1(&[0.0, 0.0]))
+        .unwrap();
+
+    let diff = before.diff(&after);
+    assert_eq!(diff.removed, vec![removed]);
+    assert_eq!(diff.added, vec![added]);
+    assert!(diff.changed.iter().all(|change| !change.moved()));
+}
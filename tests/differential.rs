@@ -0,0 +1,107 @@
+#![cfg(feature = "full")]
+
+mod common;
+
+use common::quiet;
+use pso::benchmark::rastrigin;
+use pso::builder::SwarmBuilder;
+use pso::differential::Differential;
+use pso::fitness::Fitness;
+use pso::lineage::LineageEvent;
+use pso::pso::{Options, Particles};
+
+fn swarm(differential: Option<Differential>, seed: u64) -> Particles<'static> {
+    let mut builder = SwarmBuilder::new(Fitness::new(rastrigin, true))
+        .size(20)
+        .dim(5)
+        .value_range(-5.12, 5.12)
+        .velocity_range(-1.0, 1.0)
+        .seed(seed);
+    if let Some(differential) = differential {
+        builder = builder.differential(differential);
+    }
+
+    quiet(builder)
+}
+
+#[test]
+fn only_improving_trials_are_accepted() {
+    let mut particles = swarm(None, 1);
+    let bests: Vec<_> = particles
+        .particles()
+        .iter()
+        .map(|p| p.best_fitness())
+        .collect();
+
+    let differential = Differential {
+        fraction: 1.0,
+        ..Differential::default()
+    };
+    let moved = particles.apply_differential(&differential);
+
+    let recombined: Vec<_> = particles
+        .lineage()
+        .iter()
+        .filter(|l| l.event == LineageEvent::Recombined)
+        .collect();
+    assert_eq!(recombined.len(), moved);
+
+    for (particle, before) in particles.particles().iter().zip(bests) {
+        assert!(particle.best_fitness() <= before);
+        if recombined.iter().any(|l| l.id == particle.id()) {
+            assert!(particle.best_fitness() < before);
+            assert_eq!(particle.value(), particle.best());
+        }
+    }
+}
+
+#[test]
+fn improves_on_a_rugged_landscape() {
+    let runs = 5;
+    let mut with = 0.0;
+    let mut without = 0.0;
+
+    for seed in 0..runs {
+        let mut plain = swarm(None, seed);
+        plain.run(Options::canonical(), 100).unwrap();
+        without += plain.best_fitness();
+
+        let mut hybrid = swarm(Some(Differential::default()), seed);
+        assert_eq!(hybrid.differential(), Some(Differential::default()));
+        hybrid.run(Options::canonical(), 100).unwrap();
+        with += hybrid.best_fitness();
+    }
+
+    assert!(with < without);
+}
+
+#[test]
+fn small_swarms_are_untouched() {
+    let mut particles = SwarmBuilder::new(Fitness::new(rastrigin, true))
+        .size(3)
+        .seed(2)
+        .build()
+        .unwrap();
+
+    assert_eq!(particles.apply_differential(&Differential::default()), 0);
+}
+
+#[test]
+fn rejects_invalid_parameters() {
+    let mut particles = swarm(None, 0);
+
+    let invalid = Differential {
+        crossover: 1.5,
+        ..Differential::default()
+    };
+    assert!(particles.set_differential(Some(invalid)).is_err());
+    assert!(particles.differential().is_none());
+
+    let result = SwarmBuilder::new(Fitness::new(rastrigin, true))
+        .differential(Differential {
+            fraction: -0.1,
+            ..Differential::default()
+        })
+        .build();
+    assert!(result.is_err());
+}
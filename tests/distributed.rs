@@ -0,0 +1,138 @@
+#![cfg(feature = "distributed")]
+
+mod common;
+
+use common::{builder, quiet};
+use ndarray::{arr1, Array1};
+use pso::benchmark::sphere;
+use pso::builder::SwarmBuilder;
+use pso::distributed::{Cluster, TcpTransport, Worker};
+use pso::fitness::Fitness;
+use pso::island::{ring, Island, Migration, Transport};
+use pso::pso::Options;
+use std::net::{SocketAddr, TcpListener};
+use std::thread;
+
+fn spawn_worker() -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    thread::spawn(move || Worker::new(sphere).serve(listener));
+    addr
+}
+
+#[test]
+fn remote_runs_match_local_ones() {
+    let cluster = Cluster::connect(&[spawn_worker(), spawn_worker()]).unwrap();
+    assert_eq!(cluster.workers(), 2);
+    assert_eq!(cluster.evaluate(&arr1(&[1.0, 2.0])).unwrap(), 5.0);
+
+    let run = |fitness| {
+        let mut particles = quiet(SwarmBuilder::new(fitness).size(10).seed(3));
+        particles.run(Options::canonical(), 20).unwrap();
+        particles.best().clone()
+    };
+
+    assert_eq!(run(cluster.fitness(true)), run(Fitness::new(sphere, true)));
+}
+
+#[test]
+fn failed_workers_are_dropped() {
+    // Accepts a single connection and closes it without answering
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let broken = listener.local_addr().unwrap();
+    thread::spawn(move || drop(listener.accept()));
+
+    let cluster = Cluster::connect(&[spawn_worker(), broken]).unwrap();
+    for i in 0..5 {
+        let x = arr1(&[i as f64]);
+        assert_eq!(cluster.evaluate(&x).unwrap(), sphere(&x));
+    }
+    assert_eq!(cluster.workers(), 1);
+}
+
+#[test]
+fn errors_once_every_worker_is_gone() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let broken = listener.local_addr().unwrap();
+    thread::spawn(move || drop(listener.accept()));
+
+    let cluster = Cluster::connect(&[broken]).unwrap();
+    assert!(cluster.evaluate(&arr1(&[1.0])).is_err());
+    assert_eq!(cluster.workers(), 0);
+    assert!(cluster.evaluate(&arr1(&[1.0])).is_err());
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn keeps_every_worker_busy_on_parallel_steps() {
+    use pso::pso::Parallelism;
+
+    let addrs: Vec<_> = (0..4).map(|_| spawn_worker()).collect();
+    let cluster = Cluster::connect(&addrs).unwrap();
+
+    let mut particles = quiet(
+        SwarmBuilder::new(cluster.fitness(true))
+            .size(16)
+            .seed(4)
+            .parallelism(Parallelism::Deterministic),
+    );
+    particles.run(Options::canonical(), 10).unwrap();
+
+    assert!(particles.best_fitness() < 1.0);
+    assert_eq!(cluster.workers(), 4);
+}
+
+#[test]
+fn infinite_fitness_round_trips() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    thread::spawn(move || Worker::new(|_: &Array1<f64>| f64::INFINITY).serve(listener));
+
+    let cluster = Cluster::connect(&[addr]).unwrap();
+    assert_eq!(cluster.evaluate(&arr1(&[0.0])).unwrap(), f64::INFINITY);
+}
+
+/// Runs an island on its own thread, returns its final best.
+fn spawn_island<T: Transport + Send + 'static>(
+    id: usize,
+    transport: impl FnOnce() -> T + Send + 'static,
+) -> thread::JoinHandle<Array1<f64>> {
+    thread::spawn(move || {
+        let mut particles = quiet(builder(8, 2, id as u64));
+
+        let migration = Migration { every: 4, count: 2 };
+        let mut island = Island::new(id, transport(), migration);
+        island
+            .run(&mut particles, Options::canonical(), 20)
+            .unwrap();
+        particles.best().clone()
+    })
+}
+
+#[test]
+fn tcp_islands_match_in_process_ones() {
+    let listeners: Vec<_> = (0..3)
+        .map(|_| TcpListener::bind("127.0.0.1:0").unwrap())
+        .collect();
+    let addrs: Vec<_> = listeners.iter().map(|l| l.local_addr().unwrap()).collect();
+
+    let remote: Vec<_> = listeners
+        .into_iter()
+        .enumerate()
+        .map(|(id, listener)| {
+            let next = addrs[(id + 1) % addrs.len()];
+            spawn_island(id, move || TcpTransport::connect(&listener, next).unwrap())
+        })
+        .collect();
+
+    let local: Vec<_> = ring(3)
+        .into_iter()
+        .enumerate()
+        .map(|(id, transport)| spawn_island(id, move || transport))
+        .collect();
+
+    let bests = |handles: Vec<thread::JoinHandle<_>>| -> Vec<_> {
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    };
+    assert_eq!(bests(remote), bests(local));
+}
@@ -0,0 +1,92 @@
+#![cfg(feature = "full")]
+
+mod common;
+
+use common::quiet;
+use ndarray::arr1;
+use pso::benchmark::sphere;
+use pso::builder::SwarmBuilder;
+use pso::fitness::Fitness;
+use pso::history::History;
+use pso::pso::Options;
+
+#[test]
+fn detects_and_disperses_collapsed_particles() {
+    let mut particles = quiet(
+        SwarmBuilder::new(Fitness::new(sphere, true))
+            .size(6)
+            .seed(21),
+    );
+
+    let target = arr1(&[1.0, 1.0]);
+    for i in [1, 3, 4] {
+        particles.mutate(i, |x| x.assign(&target));
+    }
+    particles.mutate(3, |x| x[0] += 1e-12);
+
+    let duplicates = particles.duplicates(1e-9);
+    assert_eq!(duplicates.len(), 2);
+    assert!(duplicates.iter().all(|i| [1, 3, 4].contains(i)));
+    assert_eq!(particles.diversity(1e-9).duplicates, 2);
+    assert!(particles.duplicates(0.0).len() < 2);
+
+    let best = particles.best_fitness();
+    assert_eq!(particles.disperse_duplicates(1e-9), 2);
+    assert!(particles.duplicates(1e-9).is_empty());
+    assert_eq!(particles.best_fitness(), best);
+
+    let diversity = particles.diversity(1e-9);
+    assert_eq!(diversity.duplicates, 0);
+    assert_eq!(diversity.dispersed, 2);
+    assert!(diversity.mean_distance > 0.0);
+}
+
+#[test]
+fn deduplication_while_running() {
+    let mut particles = quiet(
+        SwarmBuilder::new(Fitness::new(sphere, true))
+            .size(10)
+            .seed(22)
+            .deduplicate(1e-6),
+    );
+
+    particles.run(Options::canonical(), 200).unwrap();
+
+    assert!(particles.duplicates(1e-6).is_empty());
+    assert!(particles.diversity(1e-6).dispersed > 0);
+
+    let invalid = SwarmBuilder::new(Fitness::new(sphere, true))
+        .deduplicate(-1.0)
+        .build();
+    assert!(invalid.is_err());
+}
+
+#[test]
+fn per_dimension_convergence() {
+    let mut particles = quiet(
+        SwarmBuilder::new(Fitness::new(sphere, true))
+            .size(8)
+            .seed(23),
+    );
+
+    let mut history = History::new();
+    history.record(0, &particles);
+
+    // Every particle agrees on the first dimension only
+    for i in 0..8 {
+        particles.mutate(i, |x| x[0] = 0.5);
+    }
+    history.record(1, &particles);
+    history.record(2, &particles);
+
+    let stats = particles.dimension_stats();
+    assert_eq!(stats.len(), 2);
+    assert_eq!(stats[0].mean, 0.5);
+    assert_eq!(stats[0].range(), 0.0);
+    assert!(stats[1].std > 0.0);
+    assert!(stats[1].min <= stats[1].mean && stats[1].mean <= stats[1].max);
+
+    assert_eq!(particles.converged_dimensions(1e-9), [0]);
+    assert_eq!(history.converged_since(1e-9), [Some(1), None]);
+    assert_eq!(history.records()[2].dimensions, stats);
+}
@@ -0,0 +1,27 @@
+#![cfg(feature = "full")]
+
+use pso::benchmark::sphere;
+use pso::builder::SwarmBuilder;
+use pso::error::PsoError;
+use pso::fitness::Fitness;
+use std::error::Error;
+
+#[test]
+fn errors_are_std_errors() {
+    let err: Box<dyn Error> = Box::new(PsoError::Plot("no backend".to_string()));
+    assert_eq!(err.to_string(), "plotting failed: no backend");
+
+    let err = PsoError::from(std::fmt::Error);
+    assert_eq!(err, PsoError::Format);
+}
+
+#[test]
+fn summary_returns_typed_result() -> Result<(), PsoError> {
+    let particles = SwarmBuilder::new(Fitness::new(sphere, true))
+        .size(3)
+        .build()?;
+
+    let summary = particles.summary(true)?;
+    assert!(!summary.is_empty());
+    Ok(())
+}
@@ -0,0 +1,153 @@
+#![cfg(feature = "full")]
+
+use pso::benchmark::sphere;
+use pso::builder::SwarmBuilder;
+use pso::experiment::{compare, ensemble, rank_sum_test, Effect, Variant, VariantResult};
+use pso::fitness::{Fitness, Scaling};
+use pso::pso::{Options, TieBreak};
+use std::env;
+use std::fs;
+
+#[test]
+fn every_variant_runs_every_seed() {
+    let make = |seed| {
+        SwarmBuilder::new(Fitness::new(sphere, true))
+            .seed(seed)
+            .build()
+            .unwrap()
+    };
+
+    let variants = [
+        Variant::new("canonical", Options::canonical()),
+        Variant::new("social", Options::social_only(Some(0.5), 1.5))
+            .configure(|particles| particles.set_tie_break(TieBreak::MostCentral)),
+    ];
+
+    let comparison = compare(make, &variants, &[1, 2, 3], 10).unwrap();
+    assert_eq!(comparison.results.len(), 2);
+
+    for result in &comparison.results {
+        assert_eq!(result.finals.len(), 3);
+        assert_eq!(result.median_convergence().len(), 11);
+    }
+
+    let report = comparison.report();
+    assert!(report.contains("canonical") && report.contains("social"));
+}
+
+#[test]
+fn percentile_bands() {
+    // Five runs of two iterations, from 0 to 4 and from 0 to 40
+    let result = VariantResult {
+        name: "runs".to_string(),
+        finals: vec![4.0, 3.0, 2.0, 1.0, 0.0],
+        convergence: (0..5)
+            .map(|i| vec![f64::from(i), f64::from(i) * 10.0])
+            .collect(),
+    };
+
+    let bands = result.bands();
+    assert_eq!(bands.median, vec![2.0, 20.0]);
+    assert_eq!(bands.quartiles, (vec![1.0, 10.0], vec![3.0, 30.0]));
+    assert_eq!(bands.outer.0, vec![0.2, 2.0]);
+    assert_eq!(bands.outer.1, vec![3.8, 38.0]);
+    assert_eq!(
+        result.percentile_convergence(50.0),
+        result.median_convergence()
+    );
+}
+
+#[test]
+fn ensemble_plots_bands() {
+    let make = |seed| {
+        SwarmBuilder::new(Fitness::new(sphere, true))
+            .seed(seed)
+            .build()
+            .unwrap()
+    };
+
+    let variant = Variant::new("canonical", Options::canonical());
+    let result = ensemble(make, &variant, &[1, 2, 3, 4, 5], 20).unwrap();
+    assert_eq!(result.convergence.len(), 5);
+
+    // Every band contains the median
+    let bands = result.bands();
+    for i in 0..21 {
+        assert!(bands.outer.0[i] <= bands.quartiles.0[i]);
+        assert!(bands.quartiles.0[i] <= bands.median[i]);
+        assert!(bands.median[i] <= bands.quartiles.1[i]);
+        assert!(bands.quartiles.1[i] <= bands.outer.1[i]);
+    }
+
+    let comparison = compare(make, &[variant], &[1, 2, 3], 20).unwrap();
+    let path = env::temp_dir().join(format!("pso-bands-{}.svg", std::process::id()));
+    comparison.plot_bands_to_svg(&path, Scaling::Log).unwrap();
+
+    let svg = fs::read_to_string(&path).unwrap();
+    assert!(svg.contains("Convergence across seeds") && svg.contains("polygon"));
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn exact_rank_sum_test() {
+    let test = rank_sum_test(&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0]);
+    assert_eq!(test.u, 0.0);
+    assert!((test.p_value - 0.1).abs() < 1e-12);
+    assert_eq!(test.a12, 0.0);
+    assert_eq!(test.effect(), Effect::Large);
+
+    // Checked against every split of the 15 values
+    let a = [1.5, 2.1, 0.3, 4.4, 5.0, 0.9, 7.2];
+    let b = [3.3, 6.1, 8.0, 9.5, 2.8, 7.7, 10.1, 11.0];
+    let test = rank_sum_test(&a, &b);
+    assert_eq!(test.u, 7.0);
+    assert!((test.p_value - 0.013_986_013_986).abs() < 1e-9);
+    assert!(test.significant(0.05) && !test.significant(0.01));
+    assert_eq!(test.a12, 0.125);
+
+    // Symmetric
+    let swapped = rank_sum_test(&b, &a);
+    assert_eq!(swapped.u, 49.0);
+    assert!((swapped.p_value - test.p_value).abs() < 1e-12);
+}
+
+#[test]
+fn approximate_rank_sum_test_with_ties() {
+    let test = rank_sum_test(
+        &[1.0, 2.0, 2.0, 3.0, 4.0, 5.0],
+        &[3.0, 4.0, 5.0, 6.0, 6.0, 7.0],
+    );
+    assert_eq!(test.u, 4.5);
+    assert!((test.p_value - 0.035_719_65).abs() < 1e-6);
+
+    let same = rank_sum_test(&[1.0; 10], &[1.0; 10]);
+    assert_eq!(same.p_value, 1.0);
+    assert_eq!(same.a12, 0.5);
+    assert_eq!(same.effect(), Effect::Negligible);
+}
+
+#[test]
+fn variants_are_tested_by_direction() {
+    let make = |seed| {
+        SwarmBuilder::new(Fitness::new(sphere, true))
+            .seed(seed)
+            .build()
+            .unwrap()
+    };
+
+    // Barely moving swarms lose to the canonical one
+    let variants = [
+        Variant::new("canonical", Options::canonical()),
+        Variant::new("frozen", Options::cognition_only(Some(0.0), 0.0)),
+    ];
+    let seeds: Vec<_> = (0..10).collect();
+    let comparison = compare(make, &variants, &seeds, 30).unwrap();
+
+    let test = comparison.test(0, 1);
+    assert!(test.significant(0.01));
+    assert!(test.a12 > 0.9);
+    assert_eq!(comparison.test(1, 0).a12, 1.0 - test.a12);
+
+    let report = comparison.significance_report();
+    assert!(report.contains("canonical vs frozen") && report.contains("Large"));
+}
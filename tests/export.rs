@@ -0,0 +1,55 @@
+#![cfg(feature = "full")]
+
+use pso::benchmark::sphere;
+use pso::builder::SwarmBuilder;
+use pso::export::NdjsonWriter;
+use pso::fitness::Fitness;
+use pso::pso::Options;
+
+#[test]
+fn ndjson_has_one_record_per_iteration() {
+    let mut particles = SwarmBuilder::new(Fitness::new(sphere, true))
+        .size(4)
+        .dim(3)
+        .seed(1)
+        .build()
+        .unwrap();
+
+    let mut writer = NdjsonWriter::new(Vec::new());
+    particles
+        .run_with(Options::canonical(), 5, &mut [&mut writer])
+        .unwrap();
+
+    let out = String::from_utf8(writer.finish().unwrap()).unwrap();
+    let lines: Vec<_> = out.lines().collect();
+    assert_eq!(lines.len(), 6);
+
+    for (i, line) in lines.iter().enumerate() {
+        let record: serde_json::Value = serde_json::from_str(line).unwrap();
+        assert_eq!(record["iter"], i);
+        assert_eq!(record["particles"].as_array().unwrap().len(), 4);
+        assert_eq!(record["particles"][0]["x"].as_array().unwrap().len(), 3);
+    }
+}
+
+#[cfg(feature = "parquet")]
+#[test]
+fn arrow_table_has_one_row_per_particle_per_iteration() {
+    use pso::export::history_to_arrow;
+    use pso::history::History;
+
+    let mut particles = SwarmBuilder::new(Fitness::new(sphere, true))
+        .size(4)
+        .dim(3)
+        .build()
+        .unwrap();
+
+    let mut history = History::new();
+    particles
+        .run_with(Options::canonical(), 5, &mut [&mut history])
+        .unwrap();
+
+    let batch = history_to_arrow(&history).unwrap();
+    assert_eq!(batch.num_rows(), 6 * 4);
+    assert_eq!(batch.num_columns(), 6 + 3 * 3);
+}
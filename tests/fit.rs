@@ -0,0 +1,40 @@
+#![cfg(feature = "full")]
+
+use pso::error::PsoError;
+use pso::fit::{fit, Fitter};
+
+#[test]
+fn fits_an_exponential_decay() {
+    let x: Vec<f64> = (0..20).map(|i| i as f64 * 0.25).collect();
+    let y: Vec<f64> = x.iter().map(|x| 3.0 * (-0.7 * x).exp() + 0.5).collect();
+
+    let result = Fitter::new()
+        .range(-5.0, 5.0)
+        .seed(12)
+        .fit(&x, &y, 3, |x, p| p[0] * (-p[1] * x).exp() + p[2])
+        .unwrap();
+
+    assert!((result.params[0] - 3.0).abs() < 1e-3, "{}", result.params);
+    assert!((result.params[1] - 0.7).abs() < 1e-3, "{}", result.params);
+    assert!((result.params[2] - 0.5).abs() < 1e-3, "{}", result.params);
+    assert_eq!(result.residuals.len(), 20);
+    assert!(result.rmse() < 1e-3);
+}
+
+#[test]
+fn fits_a_line_with_defaults() {
+    let x = [0.0, 1.0, 2.0, 3.0];
+    let y = [1.1, 2.9, 5.1, 6.9];
+
+    let result = fit(&x, &y, 2, |x, p| p[0] * x + p[1]).unwrap();
+
+    // Ordinary least squares: slope 1.96, intercept 1.06
+    assert!((result.params[0] - 1.96).abs() < 1e-6);
+    assert!((result.params[1] - 1.06).abs() < 1e-6);
+}
+
+#[test]
+fn mismatched_data() {
+    let result = fit(&[0.0, 1.0], &[1.0], 1, |x, p| p[0] * x);
+    assert!(matches!(result, Err(PsoError::InvalidOptions(_))));
+}
@@ -0,0 +1,100 @@
+#![cfg(feature = "full")]
+
+mod common;
+
+use common::quiet;
+use ndarray::Array1;
+use pso::builder::SwarmBuilder;
+use pso::error::PsoError;
+use pso::fitness::Fitness;
+use pso::manifold::Manifold;
+use pso::pso::Options;
+
+fn shifted(x: &Array1<f64>) -> f64 {
+    (x[0] - 1.0).powi(2) + (x[1] + 2.0).powi(2) + (x[2] - 3.0).powi(2)
+}
+
+#[test]
+fn staged_optimization() {
+    let mut particles = quiet(
+        SwarmBuilder::new(Fitness::new(shifted, true))
+            .size(15)
+            .dim(3)
+            .seed(29),
+    );
+
+    // The first dimension was decided beforehand
+    particles.freeze(0, 0.5).unwrap();
+    assert_eq!(particles.frozen().get(&0), Some(&0.5));
+    assert!(particles.particles().iter().all(|p| p.value()[0] == 0.5));
+    assert_eq!(particles.best()[0], 0.5);
+
+    particles.run(Options::canonical(), 100).unwrap();
+    for particle in particles.particles() {
+        assert_eq!(particle.value()[0], 0.5);
+        assert_eq!(particle.velocity()[0], 0.0);
+    }
+    assert_eq!(particles.best()[0], 0.5);
+    assert!((particles.best_fitness() - 0.25).abs() < 1e-3);
+
+    assert_eq!(particles.unfreeze(0), Some(0.5));
+    assert_eq!(particles.unfreeze(0), None);
+    assert!(particles.frozen().is_empty());
+
+    particles.run(Options::canonical(), 200).unwrap();
+    assert!((particles.best()[0] - 1.0).abs() < 1e-2);
+    assert!(particles.best_fitness() < 1e-3);
+}
+
+#[test]
+fn invalid_freezes_are_rejected() {
+    let mut particles = SwarmBuilder::new(Fitness::new(shifted, true))
+        .dim(3)
+        .build()
+        .unwrap();
+
+    for &(d, value) in &[(3, 0.0), (0, f64::NAN), (1, f64::INFINITY)] {
+        let result = particles.freeze(d, value);
+        assert!(matches!(result, Err(PsoError::InvalidOptions(_))));
+    }
+    assert!(particles.frozen().is_empty());
+}
+
+#[test]
+fn restarts_keep_frozen_dimensions() {
+    let mut particles = SwarmBuilder::new(Fitness::new(shifted, true))
+        .size(8)
+        .dim(3)
+        .seed(4)
+        .build()
+        .unwrap();
+    particles.freeze(2, 3.0).unwrap();
+
+    particles.restart_particle(1);
+    particles.resample_worst(3);
+    particles.reinitialize();
+
+    for particle in particles.particles() {
+        assert_eq!(particle.value()[2], 3.0);
+        assert_eq!(particle.velocity()[2], 0.0);
+    }
+}
+
+#[test]
+fn restarts_stay_on_the_manifold() {
+    let mut particles = SwarmBuilder::new(Fitness::new(shifted, true))
+        .size(8)
+        .dim(3)
+        .manifold(Manifold::Sphere)
+        .seed(4)
+        .build()
+        .unwrap();
+
+    particles.restart_particle(0);
+    particles.reinitialize();
+
+    for particle in particles.particles() {
+        let norm = particle.value().dot(particle.value()).sqrt();
+        assert!((norm - 1.0).abs() < 1e-12);
+    }
+}
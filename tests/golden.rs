@@ -0,0 +1,116 @@
+#![cfg(feature = "full")]
+
+//! Outputs of a seeded run compared with the files in `tests/golden`. After
+//! an intended change of an output, run the tests with `UPDATE_GOLDEN=1` to
+//! rewrite them and review the diff.
+
+mod common;
+
+use common::{builder, quiet};
+use pso::export::NdjsonWriter;
+use pso::history::History;
+use pso::pso::{Options, Particles};
+use pso::report::{Language, NumberFormat};
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+fn swarm() -> Particles<'static> {
+    quiet(builder(4, 2, 2020))
+}
+
+fn assert_golden(name: &str, actual: &str) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("golden")
+        .join(name);
+
+    if env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, actual).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!(
+            "{} is missing, run with UPDATE_GOLDEN=1 to create it",
+            path.display()
+        )
+    });
+
+    for (i, (expected, actual)) in expected.lines().zip(actual.lines()).enumerate() {
+        assert_eq!(actual, expected, "{}:{} differs", name, i + 1);
+    }
+    assert_eq!(
+        actual.lines().count(),
+        expected.lines().count(),
+        "{} has another length",
+        name
+    );
+}
+
+#[test]
+fn summaries() {
+    let mut particles = swarm();
+    assert_golden("summary_initial.txt", &particles.summary(true).unwrap());
+
+    particles.step(Options::canonical());
+    assert_golden("summary_step.txt", &particles.summary(false).unwrap());
+
+    let summary = particles
+        .summary_with()
+        .language(Language::English)
+        .number_format(NumberFormat::new().significant(4).scientific(1e3))
+        .particles(true);
+    assert_golden("summary_english.txt", &summary.to_string());
+}
+
+#[test]
+fn json_summary() {
+    let mut particles = swarm();
+    particles.run(Options::canonical(), 3).unwrap();
+
+    let json = serde_json::to_string_pretty(&particles.summary_json()).unwrap();
+    assert_golden("summary.json", &json);
+}
+
+#[test]
+fn ndjson_export() {
+    let mut particles = swarm();
+    let mut writer = NdjsonWriter::new(Vec::new());
+    particles
+        .run_with(Options::canonical(), 3, &mut [&mut writer])
+        .unwrap();
+
+    let output = String::from_utf8(writer.finish().unwrap()).unwrap();
+    assert_golden("export.ndjson", &output);
+}
+
+#[test]
+fn plot_data() {
+    let mut particles = swarm();
+    let mut history = History::new();
+    particles
+        .run_with(Options::canonical(), 5, &mut [&mut history])
+        .unwrap();
+
+    let mut out = String::new();
+    writeln!(out, "convergence").unwrap();
+    for (iter, fitness) in history.convergence().iter().enumerate() {
+        writeln!(out, "{} {}", iter, fitness).unwrap();
+    }
+
+    writeln!(out, "best trajectory").unwrap();
+    for (iter, best) in history.best_trajectory() {
+        writeln!(out, "{} {}", iter, best).unwrap();
+    }
+
+    let id = particles.particles()[0].id();
+    writeln!(out, "trajectory of {}", id).unwrap();
+    for (iter, value) in history.trajectory(id) {
+        writeln!(out, "{} {}", iter, value).unwrap();
+    }
+
+    assert_golden("plot_data.txt", &out);
+}
@@ -0,0 +1,149 @@
+#![cfg(feature = "full")]
+
+mod common;
+
+use common::quiet;
+use pso::benchmark::sphere;
+use pso::builder::SwarmBuilder;
+use pso::error::PsoError;
+use pso::fitness::Fitness;
+use pso::history::{History, IterationRecord, Retention};
+use pso::pso::Options;
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn history_aggregates_particle_stats() {
+    let mut particles = SwarmBuilder::new(Fitness::new(sphere, true))
+        .size(8)
+        .build()
+        .unwrap();
+
+    let mut history = History::new();
+    particles
+        .run_with(Options::canonical(), 20, &mut [&mut history])
+        .unwrap();
+
+    assert_eq!(history.records().len(), 21);
+
+    let convergence = history.convergence();
+    assert!(convergence.windows(2).all(|w| w[1] <= w[0]));
+
+    let improvements = history.improvements_per_particle();
+    assert_eq!(improvements.len(), 8);
+    for particle in particles.particles() {
+        let stats = particle.stats();
+
+        assert_eq!(stats.fitness().len(), 21);
+        assert_eq!(stats.improvements(), improvements[&particle.id()]);
+        assert!((0.0..=1.0).contains(&stats.improvement_rate()));
+    }
+}
+
+fn run(history: &mut History) {
+    let mut particles = quiet(
+        SwarmBuilder::new(Fitness::new(sphere, true))
+            .size(4)
+            .seed(20),
+    );
+
+    particles
+        .run_with(Options::canonical(), 20, &mut [history])
+        .unwrap();
+}
+
+#[test]
+fn retention_policies() {
+    let mut best_only = History::with_retention(Retention::BestOnly).unwrap();
+    run(&mut best_only);
+    assert_eq!(best_only.records().len(), 21);
+    assert!(best_only.records().iter().all(|r| r.particles.is_empty()));
+
+    let mut every_nth = History::with_retention(Retention::EveryNth(5)).unwrap();
+    run(&mut every_nth);
+    let iters: Vec<_> = every_nth.records().iter().map(|r| r.iter).collect();
+    assert_eq!(iters, [0, 5, 10, 15, 20]);
+
+    let mut last = History::with_retention(Retention::Last(3)).unwrap();
+    run(&mut last);
+    let iters: Vec<_> = last.records().iter().map(|r| r.iter).collect();
+    assert_eq!(iters, [18, 19, 20]);
+}
+
+#[derive(Clone, Default)]
+struct Shared(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Shared {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn streaming_keeps_every_record_on_disk() {
+    let buffer = Shared::default();
+    let mut history = History::with_retention(Retention::Last(1))
+        .unwrap()
+        .stream_to(buffer.clone());
+    run(&mut history);
+    history.finish().unwrap();
+
+    assert_eq!(history.records().len(), 1);
+
+    let bytes = buffer.0.lock().unwrap().clone();
+    let streamed: Vec<IterationRecord> = String::from_utf8(bytes)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(streamed.len(), 21);
+    assert_eq!(streamed[20].particles.len(), 4);
+    assert_eq!(streamed[20].best_fitness, history.records()[0].best_fitness);
+}
+
+#[test]
+fn improvements_follow_particle_ids() {
+    let mut particles = quiet(
+        SwarmBuilder::new(Fitness::new(sphere, true))
+            .size(6)
+            .seed(21),
+    );
+
+    let mut history = History::new();
+    history.record(0, &particles);
+    for iter in 1..=10 {
+        if iter == 5 {
+            particles.remove_particle(0).unwrap();
+        }
+        particles.step(Options::canonical());
+        history.record(iter, &particles);
+    }
+
+    let improvements = history.improvements_per_particle();
+    assert_eq!(improvements.len(), 6);
+    assert!(improvements.contains_key(&0));
+    for particle in particles.particles() {
+        assert_eq!(
+            particle.stats().improvements(),
+            improvements[&particle.id()]
+        );
+    }
+
+    let mut best_only = History::with_retention(Retention::BestOnly).unwrap();
+    run(&mut best_only);
+    assert!(best_only.improvements_per_particle().is_empty());
+}
+
+#[test]
+fn invalid_retentions_are_rejected() {
+    for &retention in &[Retention::EveryNth(0), Retention::Last(0)] {
+        assert!(matches!(
+            History::with_retention(retention),
+            Err(PsoError::InvalidOptions(_))
+        ));
+    }
+}
@@ -0,0 +1,51 @@
+#![cfg(feature = "full")]
+
+mod common;
+
+use common::{builder, quiet};
+use pso::pso::{Options, Particles};
+
+fn swarm() -> Particles<'static> {
+    quiet(builder(6, 2, 28))
+}
+
+#[test]
+fn stepping_matches_a_regular_run() {
+    let mut regular = swarm();
+    regular.run(Options::canonical(), 15).unwrap();
+
+    let mut stepped = swarm();
+    let mut stepper = stepped.stepper(Options::canonical(), 15).unwrap();
+    let mut iters = Vec::new();
+    while let Some(iter) = stepper.step() {
+        iters.push(iter);
+        assert_eq!(stepper.particles().steps(), iter);
+    }
+
+    assert_eq!(iters, (1..=15).collect::<Vec<_>>());
+    assert_eq!(stepper.remaining(), 0);
+    assert_eq!(stepped.best(), regular.best());
+}
+
+#[test]
+fn intervening_between_steps() {
+    let mut particles = swarm();
+    let mut stepper = particles.stepper(Options::canonical(), 10).unwrap();
+
+    stepper.step();
+    let mut options = stepper.options();
+    options.phi_2 += 0.5;
+    stepper.set_options(options).unwrap();
+    assert_eq!(stepper.options().phi_2, Options::canonical().phi_2 + 0.5);
+
+    options.phi_1 = -1.0;
+    assert!(stepper.set_options(options).is_err());
+
+    stepper.particles_mut().restart_particle(0);
+    assert_eq!(stepper.particles().lineage().len(), 1);
+
+    stepper.set_iters(20);
+    assert_eq!(stepper.remaining(), 19);
+    assert_eq!(stepper.finish(), 19);
+    assert_eq!(particles.steps(), 20);
+}
@@ -0,0 +1,103 @@
+#![cfg(feature = "full")]
+
+mod common;
+
+use common::{builder, quiet};
+use ndarray::Array1;
+use pso::error::PsoError;
+use pso::island::{ring, Island, Migrant, Migration, Transport};
+use pso::lineage::LineageEvent;
+use pso::pso::{Options, Particles};
+use std::io;
+use std::thread;
+
+fn swarm(seed: u64) -> Particles<'static> {
+    quiet(builder(10, 2, seed))
+}
+
+/// Runs `n` islands on their own threads, returns their final bests.
+fn archipelago(n: usize, migration: Migration) -> Vec<(Array1<f64>, usize)> {
+    let handles: Vec<_> = ring(n)
+        .into_iter()
+        .enumerate()
+        .map(|(id, transport)| {
+            thread::spawn(move || {
+                let mut particles = swarm(id as u64);
+                let mut island = Island::new(id, transport, migration);
+                island
+                    .run(&mut particles, Options::canonical(), 30)
+                    .unwrap();
+                (particles.best().clone(), island.received())
+            })
+        })
+        .collect();
+
+    handles.into_iter().map(|h| h.join().unwrap()).collect()
+}
+
+#[test]
+fn islands_exchange_on_schedule() {
+    let migration = Migration {
+        every: 10,
+        count: 3,
+    };
+    for (_, received) in archipelago(3, migration) {
+        assert_eq!(received, 9);
+    }
+}
+
+#[test]
+fn island_runs_are_deterministic() {
+    let migration = Migration { every: 5, count: 2 };
+    assert_eq!(archipelago(4, migration), archipelago(4, migration));
+}
+
+#[test]
+fn migrants_replace_the_worst_particles() {
+    let mut transports = ring(1);
+    let mut island = Island::new(0, transports.remove(0), Migration { every: 1, count: 2 });
+    let mut particles = swarm(1);
+
+    let worst = particles
+        .particles()
+        .iter()
+        .map(|p| p.fitness())
+        .fold(f64::NEG_INFINITY, f64::max);
+    assert_eq!(island.exchange(&mut particles).unwrap(), 2);
+
+    // A single island receives its own best particles
+    assert!(particles.particles().iter().all(|p| p.fitness() < worst));
+    let migrated = particles
+        .lineage()
+        .iter()
+        .filter(|l| matches!(l.event, LineageEvent::Migrated { swarm: 0, .. }))
+        .count();
+    assert_eq!(migrated, 2);
+}
+
+struct Broken;
+
+impl Transport for Broken {
+    fn send(&mut self, _: Vec<Migrant>) -> io::Result<()> {
+        Err(io::Error::new(io::ErrorKind::BrokenPipe, "down"))
+    }
+
+    fn receive(&mut self) -> io::Result<Vec<Migrant>> {
+        Ok(Vec::new())
+    }
+}
+
+#[test]
+fn transport_failures_are_reported() {
+    let mut particles = swarm(2);
+    let mut island = Island::new(0, Broken, Migration::default());
+    let err = island
+        .run(&mut particles, Options::canonical(), 10)
+        .unwrap_err();
+    assert!(matches!(err, PsoError::Transport(_)));
+
+    let mut island = Island::new(0, Broken, Migration { every: 0, count: 1 });
+    assert!(island
+        .run(&mut particles, Options::canonical(), 10)
+        .is_err());
+}
@@ -0,0 +1,114 @@
+#![cfg(feature = "full")]
+
+mod common;
+
+use common::{builder, quiet};
+use ndarray::array;
+use pso::history::History;
+use pso::lineage::LineageEvent;
+use pso::observer::Control;
+use pso::pso::{Options, Particles};
+use pso::topology::Topology;
+
+fn swarm(seed: u64) -> Particles<'static> {
+    quiet(builder(6, 2, seed))
+}
+
+#[test]
+fn ids_survive_restarts() {
+    let mut particles = swarm(0);
+    let ids: Vec<_> = particles.particles().iter().map(|p| p.id()).collect();
+    assert_eq!(ids, (0..6).collect::<Vec<_>>());
+
+    let mut history = History::new();
+    let mut restart = |iter: usize, _: &Particles| {
+        if iter == 5 {
+            Control::RestartParticle(2)
+        } else {
+            Control::Continue
+        }
+    };
+
+    particles
+        .run_with(Options::canonical(), 10, &mut [&mut history, &mut restart])
+        .unwrap();
+
+    let after: Vec<_> = particles.particles().iter().map(|p| p.id()).collect();
+    assert_eq!(ids, after);
+
+    let lineage = history.lineage(2);
+    assert_eq!(lineage.len(), 1);
+    assert_eq!(lineage[0].0, 6);
+    assert_eq!(lineage[0].1.step, 5);
+    assert_eq!(lineage[0].1.event, LineageEvent::Reinitialized);
+
+    assert_eq!(history.trajectory(2).len(), 11);
+    assert!(history.lineage(0).is_empty());
+}
+
+#[test]
+fn migrants_get_new_ids() {
+    let mut source = swarm(1);
+    let mut target = swarm(2);
+    source.step(Options::canonical());
+
+    let migrant = source.particles()[4].clone();
+    target.migrate(0, &migrant, 1);
+    target.mutate(1, |x| x.fill(0.0));
+
+    assert_eq!(target.particles()[0].id(), 6);
+    assert_eq!(target.particles()[0].value(), migrant.value());
+    assert_eq!(target.particles()[1].best_fitness(), 0.0);
+
+    let events: Vec<_> = target.lineage().iter().map(|l| (l.id, l.event)).collect();
+    assert_eq!(
+        events,
+        vec![
+            (6, LineageEvent::Migrated { swarm: 1, id: 4 }),
+            (1, LineageEvent::Mutated),
+        ]
+    );
+}
+
+#[test]
+fn adding_and_removing_particles() {
+    let mut particles = swarm(3);
+    particles
+        .set_topology(Topology::Ring { radius: 1 })
+        .unwrap();
+
+    let best = particles.best_fitness();
+    let id = particles
+        .add_particle(array![0.0, 0.0], array![0.0, 0.0])
+        .unwrap();
+    assert_eq!(id, 6);
+    assert_eq!(particles.particles().len(), 7);
+    assert_eq!(particles.neighborhoods().unwrap().len(), 7);
+    assert_eq!(particles.best_fitness(), 0.0);
+    assert!(best > 0.0);
+
+    let removed = particles.remove_particle(6).unwrap();
+    assert_eq!(removed.id(), 6);
+    assert_eq!(particles.particles().len(), 6);
+    assert_eq!(particles.neighborhoods().unwrap().len(), 6);
+    assert_eq!(particles.best_fitness(), 0.0);
+
+    assert!(particles.remove_particle(6).is_err());
+    assert!(particles.add_particle(array![0.0], array![0.0]).is_err());
+    particles.run(Options::canonical(), 5).unwrap();
+
+    let events: Vec<_> = particles
+        .lineage()
+        .iter()
+        .map(|l| (l.id, l.event))
+        .collect();
+    assert_eq!(
+        events,
+        vec![(6, LineageEvent::Added), (6, LineageEvent::Removed)]
+    );
+
+    for id in 0..5 {
+        particles.remove_particle(id).unwrap();
+    }
+    assert!(particles.remove_particle(5).is_err());
+}
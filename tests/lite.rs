@@ -0,0 +1,149 @@
+use pso::lite::{Bf16, Clock, Fixed, Options, PackedSwarm, Position, Quantized, SmallSwarm, Swarm};
+use rand::SeedableRng;
+use rand_pcg::Pcg32;
+use std::cell::Cell;
+use std::time::Duration;
+
+fn sphere(x: &[f64]) -> f64 {
+    x.iter().map(|v| v * v).sum()
+}
+
+#[test]
+fn lite_swarm_converges() {
+    let mut swarm = Swarm::new(20, 5, (-10.0, 10.0), (-1.0, 1.0), sphere, 0);
+    let initial = swarm.best_fitness();
+
+    swarm.run(Options::canonical(), 200);
+
+    assert!(swarm.best_fitness() < initial * 1e-6);
+    assert_eq!(swarm.best_fitness(), sphere(swarm.best()));
+    assert_eq!(swarm.particles().len(), 20);
+}
+
+#[test]
+fn lite_swarm_is_reproducible() {
+    let mut a = Swarm::new(10, 3, (-5.0, 5.0), (-1.0, 1.0), sphere, 7);
+    let mut b = Swarm::new(10, 3, (-5.0, 5.0), (-1.0, 1.0), sphere, 7);
+
+    a.run(Options::canonical(), 20);
+    b.run(Options::canonical(), 20);
+
+    assert_eq!(a.best(), b.best());
+}
+
+/// Advances one millisecond every time it's read.
+struct FakeClock(Cell<u64>);
+
+impl Clock for FakeClock {
+    fn now(&self) -> Duration {
+        let now = self.0.get();
+        self.0.set(now + 1);
+        Duration::from_millis(now)
+    }
+}
+
+#[test]
+fn lite_swarm_with_injected_rng_and_clock() {
+    let rng = Pcg32::seed_from_u64(3);
+    let mut swarm = Swarm::with_rng(10, 2, (-5.0, 5.0), (-1.0, 1.0), sphere, rng);
+
+    let clock = FakeClock(Cell::new(0));
+    let iters = swarm.run_for(Options::canonical(), Duration::from_millis(10), &clock);
+
+    assert_eq!(iters, 9);
+    assert!(swarm.best_fitness() < 1.0);
+}
+
+#[test]
+fn lite_swarm_in_f32() {
+    let sphere = |x: &[f32]| x.iter().map(|v| v * v).sum::<f32>();
+    let mut swarm = Swarm::new(20, 5, (-10.0f32, 10.0), (-1.0, 1.0), sphere, 0);
+
+    swarm.run(Options::canonical(), 200);
+
+    assert!(swarm.best_fitness() < 1e-4);
+    assert_eq!(swarm.particles()[0].value.len(), 5);
+}
+
+#[test]
+fn small_swarm_matches_the_vec_one() {
+    let mut small = SmallSwarm::<_, 3>::fixed(10, (-5.0, 5.0), (-1.0, 1.0), sphere, 11);
+    let mut swarm = Swarm::new(10, 3, (-5.0, 5.0), (-1.0, 1.0), sphere, 11);
+
+    for _ in 0..50 {
+        small.step(Options::canonical());
+        swarm.step(Options::canonical());
+
+        assert_eq!(&small.best()[..], &swarm.best()[..]);
+        for (a, b) in small.particles().iter().zip(swarm.particles()) {
+            assert_eq!(&a.value[..], &b.value[..]);
+            assert_eq!(&a.velocity[..], &b.velocity[..]);
+        }
+    }
+}
+
+#[test]
+fn small_swarm_with_injected_rng_in_f32() {
+    let booth = |p: &[f32]| (p[0] + 2.0 * p[1] - 7.0).powi(2) + (2.0 * p[0] + p[1] - 5.0).powi(2);
+    let rng = Pcg32::seed_from_u64(5);
+    let mut swarm =
+        SmallSwarm::<_, 2, _, f32>::fixed_with_rng(10, (-10.0, 10.0), (-1.0, 1.0), booth, rng);
+
+    swarm.run(Options::canonical(), 100);
+
+    let best = swarm.best();
+    assert!((best[0] - 1.0).abs() < 1e-2 && (best[1] - 3.0).abs() < 1e-2);
+}
+
+#[test]
+#[should_panic(expected = "Dimension doesn't match the array")]
+fn arrays_need_their_own_dimension() {
+    <[f64; 2]>::from_fn(3, || 0.0);
+}
+
+#[test]
+fn bf16_rounds_to_nearest() {
+    let round_trip = |x: f64| Quantized::<f64>::value(<Bf16 as Quantized<f64>>::quantize(x));
+
+    for &exact in &[0.0, 1.0, -2.5, 0.15625, f64::INFINITY] {
+        assert_eq!(round_trip(exact), exact);
+    }
+
+    // 8 bits of precision
+    let pi = round_trip(std::f64::consts::PI);
+    assert!((pi - std::f64::consts::PI).abs() <= std::f64::consts::PI / 256.0);
+    assert_eq!(round_trip(1.0 + 1.0 / 256.0), 1.0);
+    assert_eq!(round_trip(1.0 + 3.0 / 256.0), 1.0 + 4.0 / 256.0);
+    assert!(round_trip(f64::NAN).is_nan());
+    assert_eq!(round_trip(1e300), f64::INFINITY);
+}
+
+#[test]
+fn fixed_point_saturates() {
+    let round_trip = |x: f64| Quantized::<f64>::value(<Fixed<16> as Quantized<f64>>::quantize(x));
+
+    assert_eq!(round_trip(1.5), 1.5);
+    assert_eq!(round_trip(-3.0), -3.0);
+    assert!((round_trip(0.1) - 0.1).abs() <= 0.5 / 65536.0);
+    assert_eq!(round_trip(1e9), f64::from(i32::MAX) / 65536.0);
+    assert_eq!(round_trip(f64::NAN), 0.0);
+}
+
+#[test]
+fn packed_swarm_converges_on_what_it_stores() {
+    let mut swarm = PackedSwarm::<_, Bf16>::packed(20, 5, (-10.0, 10.0), (-1.0, 1.0), sphere, 0);
+    swarm.run(Options::canonical(), 200);
+
+    let mut buffer = Vec::new();
+    let best = swarm.best().decode(&mut buffer);
+    assert!(swarm.best_fitness() < 1e-2);
+    assert_eq!(swarm.best_fitness(), sphere(best));
+
+    // A quarter of the memory of f64 coordinates
+    assert_eq!(std::mem::size_of_val(&swarm.particles()[0].value.0[..]), 10);
+
+    let mut fixed =
+        PackedSwarm::<_, Fixed<20>>::packed(20, 5, (-10.0, 10.0), (-1.0, 1.0), sphere, 0);
+    fixed.run(Options::canonical(), 200);
+    assert!(fixed.best_fitness() < 1e-6);
+}
@@ -0,0 +1,65 @@
+#![cfg(feature = "full")]
+
+mod common;
+
+use common::{builder, quiet};
+use pso::export::NdjsonWriter;
+use pso::manifest::Manifest;
+use pso::pso::{Options, Particles};
+use std::time::{Duration, SystemTime};
+
+fn swarm(seed: u64, size: usize) -> Particles<'static> {
+    quiet(builder(size, 2, seed))
+}
+
+#[test]
+fn records_the_run() {
+    let started = SystemTime::now();
+    let mut particles = swarm(4, 10);
+    particles.run(Options::canonical(), 10).unwrap();
+    let manifest = particles.manifest(Options::canonical(), started);
+
+    assert_eq!(manifest.crate_version, env!("CARGO_PKG_VERSION"));
+    assert_eq!(manifest.seed, Some(4));
+    assert_eq!(manifest.config_hash.len(), 16);
+    assert!(manifest.threads >= 1);
+    assert!(manifest.started <= manifest.finished);
+    assert!(!manifest.os.is_empty());
+}
+
+#[test]
+fn config_hash_ignores_progress_only() {
+    let options = Options::canonical();
+    let hash =
+        |particles: &Particles, options| particles.manifest(options, SystemTime::now()).config_hash;
+
+    let mut particles = swarm(1, 10);
+    let before = hash(&particles, options);
+    particles.run(options, 5).unwrap();
+    assert_eq!(hash(&particles, options), before);
+
+    // Same configuration with another seed
+    assert_eq!(hash(&swarm(2, 10), options), before);
+
+    assert_ne!(hash(&swarm(1, 12), options), before);
+    let mut other = options;
+    other.phi_1 += 0.1;
+    assert_ne!(hash(&particles, other), before);
+}
+
+#[test]
+fn attached_to_results_and_exports() {
+    let mut particles = swarm(3, 10);
+    let result = particles
+        .optimize_for(Options::canonical(), Duration::from_millis(20))
+        .unwrap();
+    assert_eq!(result.manifest.seed, Some(3));
+
+    let mut writer = NdjsonWriter::new(Vec::new());
+    writer.write_manifest(&result.manifest).unwrap();
+    let output = String::from_utf8(writer.finish().unwrap()).unwrap();
+
+    let line: serde_json::Value = serde_json::from_str(output.trim()).unwrap();
+    let manifest: Manifest = serde_json::from_value(line["manifest"].clone()).unwrap();
+    assert_eq!(manifest, result.manifest);
+}
@@ -0,0 +1,53 @@
+#![cfg(feature = "full")]
+
+mod common;
+
+use common::quiet;
+use ndarray::{array, Array1};
+use pso::builder::SwarmBuilder;
+use pso::fitness::Fitness;
+use pso::manifold::Manifold;
+use pso::pso::Options;
+
+#[test]
+fn tangent_and_retract() {
+    let x = array![1.0, 0.0, 0.0];
+    let mut v = array![0.5, 1.0, 0.0];
+    Manifold::Sphere.tangent(&x, &mut v);
+    assert_eq!(v, array![0.0, 1.0, 0.0]);
+
+    let mut x = &x + &v;
+    Manifold::Sphere.retract(&mut x, &mut v);
+    assert!((x.dot(&x) - 1.0).abs() < 1e-12);
+    assert!(x.dot(&v).abs() < 1e-12);
+
+    let mut y = array![3.0, 4.0];
+    let mut w = array![1.0, 1.0];
+    Manifold::Euclidean.retract(&mut y, &mut w);
+    assert_eq!((y, w), (array![3.0, 4.0], array![1.0, 1.0]));
+}
+
+#[test]
+fn sphere_swarm_finds_the_direction() {
+    let direction = array![1.0, 2.0, -2.0] / 3.0;
+    let alignment = {
+        let direction = direction.clone();
+        move |x: &Array1<f64>| x.dot(&direction)
+    };
+
+    let mut particles = quiet(
+        SwarmBuilder::new(Fitness::new(alignment, false))
+            .size(20)
+            .dim(3)
+            .seed(8)
+            .manifold(Manifold::Sphere),
+    );
+
+    let on_sphere = |x: &Array1<f64>| (x.dot(x) - 1.0).abs() < 1e-9;
+    assert!(particles.particles().iter().all(|p| on_sphere(p.value())));
+
+    particles.run(Options::canonical(), 100).unwrap();
+
+    assert!(particles.particles().iter().all(|p| on_sphere(p.value())));
+    assert!(particles.best_fitness() > 1.0 - 1e-6);
+}
@@ -0,0 +1,38 @@
+#![cfg(feature = "full")]
+
+use pso::benchmark::sphere;
+use pso::builder::SwarmBuilder;
+use pso::fitness::Fitness;
+use pso::meta::{MetaPso, MetaStrategy};
+
+#[test]
+fn tuned_configuration_is_within_the_space() {
+    let make = |size, seed| {
+        SwarmBuilder::new(Fitness::new(sphere, true))
+            .size(size)
+            .seed(seed)
+            .build()
+            .unwrap()
+    };
+
+    for &strategy in &[
+        MetaStrategy::Pso { size: 4, iters: 3 },
+        MetaStrategy::RandomSearch { samples: 10 },
+    ] {
+        let meta = MetaPso {
+            seeds: vec![1, 2, 3],
+            iters: 10,
+            size: (5, 10),
+            strategy,
+            ..MetaPso::default()
+        };
+
+        let tuned = meta.tune(make).unwrap();
+        let omega = tuned.options.omega.unwrap();
+
+        assert!(tuned.options.validate().is_ok());
+        assert!(meta.omega.0 <= omega && omega <= meta.omega.1);
+        assert!((5..=10).contains(&tuned.size));
+        assert!(tuned.score.is_finite());
+    }
+}
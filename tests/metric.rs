@@ -0,0 +1,86 @@
+#![cfg(feature = "full")]
+
+mod common;
+
+use common::quiet;
+use ndarray::{arr1, Array1};
+use pso::archive::Archive;
+use pso::benchmark::sphere;
+use pso::builder::SwarmBuilder;
+use pso::fitness::Fitness;
+use pso::metric::{Distance, Metric};
+
+#[test]
+fn built_in_metrics() {
+    let a = arr1(&[1.0, 2.0]);
+    let b = arr1(&[4.0, -2.0]);
+
+    assert_eq!(Metric::Euclidean.distance(&a, &b), 5.0);
+    assert_eq!(Metric::Manhattan.distance(&a, &b), 7.0);
+    assert_eq!(Metric::Chebyshev.distance(&a, &b), 4.0);
+
+    let cosine = Metric::Cosine;
+    assert!(cosine.distance(&a, &(&a * 3.0)).abs() < 1e-12);
+    assert!((cosine.distance(&arr1(&[1.0, 0.0]), &arr1(&[0.0, 1.0])) - 1.0).abs() < 1e-12);
+    assert!((cosine.distance(&a, &-&a) - 2.0).abs() < 1e-12);
+    assert_eq!(cosine.distance(&arr1(&[0.0, 0.0]), &arr1(&[0.0, 0.0])), 0.0);
+    assert_eq!(cosine.distance(&arr1(&[0.0, 0.0]), &a), 1.0);
+}
+
+#[test]
+fn duplicates_follow_the_swarm_metric() {
+    let mut particles = quiet(
+        SwarmBuilder::new(Fitness::new(sphere, true))
+            .size(4)
+            .seed(5)
+            .metric(Metric::Chebyshev),
+    );
+    assert_eq!(particles.metric(), Metric::Chebyshev);
+
+    particles.mutate(0, |x| x.assign(&arr1(&[1.0, 1.0])));
+    particles.mutate(1, |x| x.assign(&arr1(&[1.5, 1.5])));
+    particles.mutate(2, |x| x.assign(&arr1(&[5.0, 5.0])));
+    particles.mutate(3, |x| x.assign(&arr1(&[-5.0, -5.0])));
+
+    // Euclidean distance between the first two is ~0.71
+    assert_eq!(particles.duplicates(0.6), vec![1]);
+    particles.set_metric(Metric::Euclidean);
+    assert!(particles.duplicates(0.6).is_empty());
+
+    // Only the direction matters
+    particles.set_custom_metric(|a: &Array1<f64>, b: &Array1<f64>| Metric::Cosine.distance(a, b));
+    assert!(particles.has_custom_metric());
+    assert_eq!(particles.duplicates(1e-9), vec![1, 2]);
+
+    particles.set_metric(Metric::Manhattan);
+    assert!(!particles.has_custom_metric());
+    assert_eq!(
+        particles.distance(&arr1(&[1.0, 1.0]), &arr1(&[2.0, 3.0])),
+        3.0
+    );
+}
+
+#[test]
+fn archive_uses_its_metric() {
+    let mut archive = Archive::new(3, 1.0).with_metric(Metric::Chebyshev);
+    assert_eq!(archive.metric(), Metric::Chebyshev);
+
+    assert!(archive.offer(&arr1(&[0.0, 0.0]), 1.0, true));
+    // Manhattan distance 1.6 but Chebyshev 0.8
+    assert!(!archive.offer(&arr1(&[0.8, 0.8]), 2.0, true));
+    assert!(archive.offer(&arr1(&[1.2, 0.0]), 2.0, true));
+    assert_eq!(archive.solutions().len(), 2);
+}
+
+#[test]
+fn metric_survives_snapshots() {
+    let particles = quiet(
+        SwarmBuilder::new(Fitness::new(sphere, true))
+            .size(4)
+            .seed(5)
+            .metric(Metric::Manhattan),
+    );
+
+    let restored = pso::pso::Particles::from_state(particles.state(), Fitness::new(sphere, true));
+    assert_eq!(restored.metric(), Metric::Manhattan);
+}
@@ -0,0 +1,49 @@
+#![cfg(feature = "full")]
+
+mod common;
+
+use common::quiet;
+use ndarray::{array, Array1};
+use pso::builder::SwarmBuilder;
+use pso::mlp::Mlp;
+use pso::pso::Options;
+
+#[test]
+fn weights_layout() {
+    let mlp = Mlp::new(vec![2, 3, 1]);
+    assert_eq!(mlp.dim(), 3 * 2 + 3 + 3 + 1);
+
+    // Only the output bias is set
+    let mut weights = Array1::zeros(mlp.dim());
+    weights[12] = 0.5;
+    let outputs = mlp.forward(&weights, &array![[1.0, 2.0], [3.0, 4.0]]);
+    assert_eq!(outputs, array![[0.5], [0.5]]);
+}
+
+#[test]
+fn swarm_learns_xor() {
+    let inputs = array![[0.0, 0.0], [0.0, 1.0], [1.0, 0.0], [1.0, 1.0]];
+    let targets = array![[0.0], [1.0], [1.0], [0.0]];
+    let mlp = Mlp::new(vec![2, 4, 1]);
+
+    let mut particles = quiet(
+        SwarmBuilder::new(mlp.fitness(&inputs, &targets))
+            .size(30)
+            .dim(mlp.dim())
+            .value_range(-2.0, 2.0)
+            .velocity_range(-0.5, 0.5)
+            .seed(11),
+    );
+    particles.run(Options::canonical(), 300).unwrap();
+
+    assert!(
+        particles.best_fitness() < 1e-2,
+        "{}",
+        particles.best_fitness()
+    );
+
+    let outputs = mlp.forward(particles.best(), &inputs);
+    for (output, target) in outputs.iter().zip(targets.iter()) {
+        assert!((output - target).abs() < 0.3);
+    }
+}
@@ -0,0 +1,81 @@
+#![cfg(feature = "full")]
+
+use ndarray::Array1;
+use pso::benchmark::sphere;
+use pso::fitness::Fitness;
+use pso::pso::{Model, Options, Particles};
+
+fn swarm<'a>() -> Particles<'a> {
+    Particles::new(10, 2, (-5.0, 5.0), (-1.0, 1.0), Fitness::new(sphere, true))
+}
+
+fn distance(a: &Array1<f64>, b: &Array1<f64>) -> f64 {
+    (a - b).mapv(|v| v * v).sum().sqrt()
+}
+
+#[test]
+fn models_are_detected_from_coefficients() {
+    let full = Options {
+        omega: None,
+        phi_1: 2.0,
+        phi_2: 2.0,
+    };
+
+    assert_eq!(full.model(), Model::Full);
+    assert!(full.warnings().is_empty());
+
+    let cognition = Options::cognition_only(None, 2.0);
+    assert_eq!(cognition.model(), Model::CognitionOnly);
+    assert_eq!(cognition.warnings().len(), 1);
+
+    let social = Options::social_only(None, 2.0);
+    assert_eq!(social.model(), Model::SocialOnly);
+    assert_eq!(social.warnings().len(), 1);
+
+    let inertia = Options::social_only(None, 0.0);
+    assert_eq!(inertia.model(), Model::InertiaOnly);
+    assert_eq!(inertia.warnings().len(), 1);
+}
+
+#[test]
+fn cognition_only_without_inertia_stays_still() {
+    // Every particle starts at its own best, so with no inertia and no social
+    // term there's nothing pulling it anywhere.
+    let mut particles = swarm();
+    let before: Vec<_> = particles
+        .particles()
+        .iter()
+        .map(|p| p.value().clone())
+        .collect();
+
+    let options = Options::cognition_only(Some(0.0), 2.0);
+    for _ in 0..10 {
+        particles.step(options);
+    }
+
+    for (particle, value) in particles.particles().iter().zip(before) {
+        assert_eq!(particle.value(), &value);
+    }
+}
+
+#[test]
+fn social_only_without_inertia_moves_towards_global_best() {
+    let mut particles = swarm();
+
+    // With phi_2 <= 1 a particle can't overshoot the global best
+    let options = Options::social_only(Some(0.0), 1.0);
+    for _ in 0..10 {
+        let best = particles.best().clone();
+        let before: Vec<_> = particles
+            .particles()
+            .iter()
+            .map(|p| distance(p.value(), &best))
+            .collect();
+
+        particles.step(options);
+
+        for (particle, dist) in particles.particles().iter().zip(before) {
+            assert!(distance(particle.value(), &best) <= dist + 1e-12);
+        }
+    }
+}
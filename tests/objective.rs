@@ -0,0 +1,122 @@
+#![cfg(feature = "full")]
+
+mod common;
+
+use common::quiet;
+use ndarray::Array1;
+use pso::builder::SwarmBuilder;
+use pso::history::History;
+use pso::objective::{ObjectiveBuilder, Scalarization};
+use pso::pso::Options;
+
+fn cost(x: &Array1<f64>) -> f64 {
+    (x[0] - 3.0).powi(2)
+}
+
+fn penalty(x: &Array1<f64>) -> f64 {
+    x[1].abs()
+}
+
+#[test]
+fn weighted_sum_of_terms() {
+    let fitness = ObjectiveBuilder::new(true)
+        .term("cost", 2.0, cost)
+        .term("penalty", 1.0, penalty)
+        .scale(10.0)
+        .build();
+
+    let x = Array1::from(vec![1.0, -5.0]);
+    assert_eq!(fitness.calculate(&x), 2.0 * 4.0 + 5.0 / 10.0);
+
+    let terms = fitness.terms().unwrap();
+    assert_eq!(terms.names(), vec!["cost", "penalty"]);
+    assert_eq!(terms.values(&x), vec![8.0, 0.5]);
+}
+
+#[test]
+fn normalized_weights() {
+    let fitness = ObjectiveBuilder::new(true)
+        .term("cost", 3.0, cost)
+        .term("penalty", 1.0, penalty)
+        .normalize(true)
+        .build();
+
+    let x = Array1::from(vec![1.0, -4.0]);
+    assert_eq!(fitness.terms().unwrap().values(&x), vec![3.0, 1.0]);
+}
+
+#[test]
+fn terms_are_recorded() {
+    let fitness = ObjectiveBuilder::new(true)
+        .term("cost", 1.0, cost)
+        .term("penalty", 0.5, penalty)
+        .build();
+
+    let mut particles = SwarmBuilder::new(fitness).seed(0).build().unwrap();
+    let mut history = History::new();
+    particles
+        .run_with(Options::canonical(), 30, &mut [&mut history])
+        .unwrap();
+
+    assert_eq!(history.term_names(), ["cost", "penalty"]);
+    for record in history.records() {
+        let terms = record.terms.as_ref().unwrap();
+        assert!((terms.iter().sum::<f64>() - record.best_fitness).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn scalarizations() {
+    let sum = Scalarization::WeightedSum(vec![1.0, 2.0]);
+    assert_eq!(sum.apply(&[3.0, 4.0]), 11.0);
+
+    let chebyshev = Scalarization::Chebyshev {
+        weights: vec![1.0, 2.0],
+        ideal: vec![0.0, 1.0],
+    };
+    assert_eq!(chebyshev.apply(&[3.0, 4.0]), 6.0);
+    assert_eq!(chebyshev.apply(&[-5.0, 1.0]), 5.0);
+
+    // The first objective decides unless it's within the same step
+    let lexicographic = Scalarization::Lexicographic(vec![0.5]);
+    assert!(lexicographic.apply(&[1.0, 100.0]) < lexicographic.apply(&[1.6, -100.0]));
+    assert!(lexicographic.apply(&[1.1, -1.0]) < lexicographic.apply(&[1.2, 1.0]));
+    assert_eq!(lexicographic.objectives(), 2);
+}
+
+#[test]
+fn invalid_scalarizations() {
+    assert!(Scalarization::WeightedSum(vec![]).validate().is_err());
+    assert!(Scalarization::WeightedSum(vec![1.0, -1.0])
+        .validate()
+        .is_err());
+    assert!(Scalarization::Lexicographic(vec![0.0]).validate().is_err());
+    assert!(Scalarization::Chebyshev {
+        weights: vec![1.0, 1.0],
+        ideal: vec![0.0],
+    }
+    .validate()
+    .is_err());
+}
+
+#[test]
+fn optimizes_a_scalarized_vector_objective() {
+    // Distance to (1, 0) first, to (-1, 0) only within 0.1 steps
+    let objectives = |x: &Array1<f64>| {
+        vec![
+            ((x[0] - 1.0).powi(2) + x[1].powi(2)).sqrt(),
+            ((x[0] + 1.0).powi(2) + x[1].powi(2)).sqrt(),
+        ]
+    };
+    let fitness = Scalarization::Lexicographic(vec![0.1])
+        .fitness(objectives)
+        .unwrap();
+
+    let mut particles = quiet(SwarmBuilder::new(fitness).value_range(-2.0, 2.0).seed(9));
+    particles.run(Options::canonical(), 200).unwrap();
+
+    let best = objectives(particles.best());
+    assert!(best[0] < 0.1);
+    // Within the first step, the closest to (-1, 0)
+    assert!(particles.best()[0] < 1.0);
+}
@@ -0,0 +1,89 @@
+#![cfg(feature = "full")]
+
+mod common;
+
+use common::quiet;
+use pso::benchmark::sphere;
+use pso::builder::SwarmBuilder;
+use pso::error::PsoError;
+use pso::fitness::Fitness;
+use pso::observer::{Control, Observer};
+use pso::pso::{Options, Particles};
+
+#[test]
+fn observers_can_stop_the_run() {
+    let mut particles = SwarmBuilder::new(Fitness::new(sphere, true))
+        .build()
+        .unwrap();
+
+    let mut stop_at_3 = |iter: usize, _: &Particles| {
+        if iter == 3 {
+            Control::Stop
+        } else {
+            Control::Continue
+        }
+    };
+
+    let done = particles
+        .run_with(Options::canonical(), 100, &mut [&mut stop_at_3])
+        .unwrap();
+
+    assert_eq!(done, 3);
+}
+
+#[derive(Default)]
+struct Counter {
+    steps: usize,
+    finished: bool,
+}
+
+impl Observer for Counter {
+    fn on_step(&mut self, _iter: usize, _particles: &Particles) -> Control {
+        self.steps += 1;
+        Control::ReSampleWorst(2)
+    }
+
+    fn on_finish(&mut self, _particles: &Particles) {
+        self.finished = true;
+    }
+}
+
+#[test]
+fn interventions_keep_the_run_going() {
+    let mut particles = SwarmBuilder::new(Fitness::new(sphere, true))
+        .build()
+        .unwrap();
+
+    let mut counter = Counter::default();
+    let done = particles
+        .run_with(Options::canonical(), 5, &mut [&mut counter])
+        .unwrap();
+
+    assert_eq!(done, 5);
+    assert_eq!(counter.steps, 5);
+    assert!(counter.finished);
+}
+
+#[test]
+fn restarting_a_missing_particle_is_an_error() {
+    let mut particles = quiet(
+        SwarmBuilder::new(Fitness::new(sphere, true))
+            .size(4)
+            .seed(3),
+    );
+    particles.remove_particle(0).unwrap();
+
+    let mut counter = Counter::default();
+    let mut restart = |iter: usize, _: &Particles| {
+        if iter == 2 {
+            Control::RestartParticle(3)
+        } else {
+            Control::Continue
+        }
+    };
+
+    let result = particles.run_with(Options::canonical(), 10, &mut [&mut counter, &mut restart]);
+    assert!(matches!(result, Err(PsoError::InvalidOptions(_))));
+    assert_eq!(particles.steps(), 2);
+    assert!(counter.finished);
+}
@@ -0,0 +1,243 @@
+#![cfg(feature = "full")]
+
+mod common;
+
+use common::{builder, quiet};
+use ndarray::{arr1, Array1};
+use pso::benchmark::sphere;
+use pso::builder::SwarmBuilder;
+use pso::differential::Differential;
+use pso::fitness::Fitness;
+use pso::lineage::LineageEvent;
+use pso::manifold::Manifold;
+use pso::operator::Operator;
+use pso::pso::{Options, Particles};
+use pso::zones::Zone;
+
+fn swarm(seed: u64) -> Particles<'static> {
+    quiet(
+        builder(10, 3, seed)
+            .value_range(-5.0, 5.0)
+            .velocity_range(-1.0, 1.0),
+    )
+}
+
+#[test]
+fn builder_keeps_the_pipeline_order() {
+    let particles = SwarmBuilder::new(Fitness::new(sphere, true))
+        .deduplicate(1e-6)
+        .operator(Operator::Repair)
+        .differential(Differential::default())
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        particles.operators(),
+        &[
+            Operator::Deduplication { tolerance: 1e-6 },
+            Operator::Repair,
+            Operator::Differential(Differential::default()),
+        ]
+    );
+    assert_eq!(particles.deduplication(), Some(1e-6));
+}
+
+#[test]
+fn shortcuts_edit_the_pipeline_in_place() {
+    let mut particles = swarm(0);
+    particles
+        .set_operators(vec![
+            Operator::Deduplication { tolerance: 0.1 },
+            Operator::Repair,
+        ])
+        .unwrap();
+
+    particles.set_deduplication(Some(0.5));
+    assert_eq!(
+        particles.operators()[0],
+        Operator::Deduplication { tolerance: 0.5 }
+    );
+
+    particles
+        .set_differential(Some(Differential::default()))
+        .unwrap();
+    assert_eq!(particles.operators().len(), 3);
+
+    particles.set_deduplication(None);
+    assert_eq!(
+        particles.operators(),
+        &[
+            Operator::Repair,
+            Operator::Differential(Differential::default())
+        ]
+    );
+}
+
+#[test]
+fn invalid_pipelines_are_rejected() {
+    let mut particles = swarm(0);
+    particles.add_operator(Operator::Repair).unwrap();
+
+    let invalid = vec![
+        Operator::Opposition { probability: 0.5 },
+        Operator::Mutation {
+            probability: 2.0,
+            sigma: 0.1,
+        },
+    ];
+    assert!(particles.set_operators(invalid).is_err());
+    assert!(particles
+        .add_operator(Operator::Restart {
+            stagnation: 0,
+            fraction: 0.5
+        })
+        .is_err());
+    assert_eq!(particles.operators(), &[Operator::Repair]);
+}
+
+#[test]
+fn mutation_moves_particles() {
+    let mut particles = swarm(1);
+    let before: Vec<_> = particles
+        .particles()
+        .iter()
+        .map(|p| p.value().clone())
+        .collect();
+
+    let mutation = Operator::Mutation {
+        probability: 1.0,
+        sigma: 0.1,
+    };
+    assert_eq!(particles.apply_operator(&mutation), 10);
+
+    for (particle, before) in particles.particles().iter().zip(before) {
+        assert_ne!(particle.value(), &before);
+    }
+    let mutated = particles
+        .lineage()
+        .iter()
+        .filter(|l| l.event == LineageEvent::Mutated)
+        .count();
+    assert_eq!(mutated, 10);
+}
+
+#[test]
+fn opposition_and_local_search_only_improve() {
+    let mut particles = swarm(2);
+    particles.mutate(0, |x| x.assign(&arr1(&[4.0, 4.0, 4.0])));
+    let bests: Vec<_> = particles
+        .particles()
+        .iter()
+        .map(|p| p.best_fitness())
+        .collect();
+
+    particles.apply_operator(&Operator::Opposition { probability: 1.0 });
+    particles.apply_operator(&Operator::LocalSearch {
+        samples: 20,
+        radius: 0.01,
+    });
+
+    for (particle, before) in particles.particles().iter().zip(bests) {
+        assert!(particle.best_fitness() <= before);
+    }
+}
+
+#[test]
+fn repair_clamps_into_the_value_range() {
+    let mut particles = swarm(3);
+    particles.mutate(2, |x| x[1] = 9.0);
+
+    assert_eq!(particles.apply_operator(&Operator::Repair), 1);
+    assert_eq!(particles.particles()[2].value()[1], 5.0);
+    assert_eq!(particles.apply_operator(&Operator::Repair), 0);
+}
+
+#[test]
+fn restarts_after_stagnating() {
+    let mut particles = swarm(4);
+    particles
+        .set_operators(vec![Operator::Restart {
+            stagnation: 3,
+            fraction: 0.5,
+        }])
+        .unwrap();
+
+    particles.run(Options::canonical(), 200).unwrap();
+
+    let restarts = particles
+        .lineage()
+        .iter()
+        .filter(|l| l.event == LineageEvent::Reinitialized)
+        .count();
+    assert!(restarts > 0);
+    assert_eq!(restarts % 5, 0);
+    assert!(particles.stagnant_steps() < 200);
+}
+
+#[test]
+fn pipeline_survives_snapshots() {
+    let mut particles = swarm(5);
+    particles
+        .set_operators(vec![Operator::Opposition { probability: 0.1 }])
+        .unwrap();
+
+    let restored = Particles::from_state(particles.state(), Fitness::new(sphere, true));
+    assert_eq!(restored.operators(), particles.operators());
+}
+
+/// The mutation comes first, it moves every particle.
+const MOVING: [Operator; 4] = [
+    Operator::Mutation {
+        probability: 1.0,
+        sigma: 0.5,
+    },
+    Operator::Opposition { probability: 1.0 },
+    Operator::LocalSearch {
+        samples: 10,
+        radius: 0.3,
+    },
+    Operator::Differential(Differential {
+        fraction: 1.0,
+        scale: 0.8,
+        crossover: 0.9,
+    }),
+];
+
+#[test]
+fn operators_stay_on_the_manifold() {
+    let mut particles = quiet(
+        SwarmBuilder::new(Fitness::new(|x: &Array1<f64>| x[0], true))
+            .size(10)
+            .dim(3)
+            .manifold(Manifold::Sphere)
+            .seed(6),
+    );
+
+    for _ in 0..5 {
+        for operator in MOVING.iter() {
+            particles.apply_operator(operator);
+
+            for particle in particles.particles() {
+                let norm = particle.value().dot(particle.value()).sqrt();
+                assert!((norm - 1.0).abs() < 1e-9, "{:?}: {}", operator, norm);
+            }
+        }
+    }
+}
+
+#[test]
+fn operators_avoid_zones() {
+    let mut particles = swarm(7);
+    let zone = Zone::sphere(arr1(&[0.0, 0.0, 0.0]), 2.0);
+    particles.add_zone(zone.clone()).unwrap();
+
+    for _ in 0..5 {
+        for operator in MOVING.iter() {
+            particles.apply_operator(operator);
+
+            for particle in particles.particles() {
+                assert!(!zone.contains(particle.value()), "{:?}", operator);
+            }
+        }
+    }
+}
@@ -0,0 +1,92 @@
+#![cfg(feature = "full")]
+
+use ndarray::Array1;
+use pso::benchmark::sphere;
+use pso::builder::SwarmBuilder;
+use pso::fitness::Fitness;
+use pso::pso::{Options, Parallelism, Particles};
+
+#[cfg_attr(not(feature = "parallel"), allow(dead_code))]
+fn run(parallelism: Parallelism) -> Vec<(Array1<f64>, f64)> {
+    let mut particles = SwarmBuilder::new(Fitness::shared(sphere, true))
+        .size(16)
+        .seed(7)
+        .parallelism(parallelism)
+        .build()
+        .unwrap();
+
+    (0..20)
+        .map(|_| {
+            particles.step(Options::canonical());
+            (particles.best().clone(), particles.best_fitness())
+        })
+        .collect()
+}
+
+#[test]
+fn serial_with_shared_fitness_is_allowed() {
+    let particles = SwarmBuilder::new(Fitness::shared(sphere, true))
+        .parallelism(Parallelism::Serial)
+        .build();
+
+    assert!(particles.is_ok());
+}
+
+#[test]
+fn parallel_needs_shared_fitness() {
+    let mut particles = Particles::with_seed(
+        8,
+        2,
+        (-10.0, 10.0),
+        (-1.0, 1.0),
+        Fitness::new(sphere, true),
+        0,
+    );
+
+    assert!(particles
+        .set_parallelism(Parallelism::Deterministic)
+        .is_err());
+    assert_eq!(particles.parallelism(), Parallelism::Serial);
+}
+
+#[cfg(not(feature = "parallel"))]
+#[test]
+fn parallel_needs_feature() {
+    let particles = SwarmBuilder::new(Fitness::shared(sphere, true))
+        .parallelism(Parallelism::Parallel)
+        .build();
+
+    assert!(particles.is_err());
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn deterministic_matches_serial() {
+    assert_eq!(run(Parallelism::Serial), run(Parallelism::Deterministic));
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn parallel_converges() {
+    let bests = run(Parallelism::Parallel);
+    assert!(bests.last().unwrap().1 <= bests[0].1);
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn parallel_snapshots_restore_serially_without_shared_fitness() {
+    let particles = SwarmBuilder::new(Fitness::shared(sphere, true))
+        .parallelism(Parallelism::Parallel)
+        .seed(7)
+        .build()
+        .unwrap();
+
+    let state = particles.state();
+    let mut restored = Particles::from_state(state.clone(), Fitness::new(sphere, true));
+    assert_eq!(restored.parallelism(), Parallelism::Serial);
+    restored.set_verbose(false);
+    restored.step(Options::canonical());
+
+    let shared = Particles::from_state(state, Fitness::shared(sphere, true));
+    assert_eq!(shared.parallelism(), Parallelism::Parallel);
+}
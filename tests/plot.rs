@@ -0,0 +1,118 @@
+#![cfg(feature = "full")]
+
+mod common;
+
+use common::quiet;
+use ndarray::s;
+use pso::benchmark::sphere;
+use pso::builder::SwarmBuilder;
+use pso::density::VisitDensity;
+use pso::fitness::{Fitness, Scaling};
+use pso::history::History;
+use pso::pso::Options;
+use std::env;
+use std::fs;
+
+#[test]
+fn svg_frames_and_convergence() {
+    let mut particles = quiet(
+        SwarmBuilder::new(Fitness::new(sphere, true))
+            .size(5)
+            .seed(25),
+    );
+
+    let mut history = History::new();
+    particles
+        .run_with(Options::canonical(), 10, &mut [&mut history])
+        .unwrap();
+
+    let dir = env::temp_dir();
+    let frame = dir.join(format!("pso-frame-{}.svg", std::process::id()));
+    let convergence = dir.join(format!("pso-convergence-{}.svg", std::process::id()));
+
+    particles.plot_to_svg(&frame, 10).unwrap();
+    history
+        .plot_convergence_to_svg(&convergence, Scaling::Log)
+        .unwrap();
+
+    let svg = fs::read_to_string(&frame).unwrap();
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.contains("PSO (iter = 10)"));
+    assert!(svg.matches("<circle").count() >= 5);
+    for label in &["best: ", "mean: ", "diversity: ", "omega: "] {
+        assert!(svg.contains(label), "{}", label);
+    }
+
+    let svg = fs::read_to_string(&convergence).unwrap();
+    assert!(svg.contains("Convergence"));
+    assert!(svg.contains("<polyline"));
+
+    fs::remove_file(frame).unwrap();
+    fs::remove_file(convergence).unwrap();
+}
+
+#[test]
+fn visit_density_heatmap() {
+    let mut particles = quiet(
+        SwarmBuilder::new(Fitness::new(sphere, true))
+            .size(8)
+            .value_range(-5.0, 5.0)
+            .seed(26),
+    );
+
+    let mut density = VisitDensity::new((-5.0, 5.0), (-5.0, 5.0), 10);
+    particles
+        .run_with(Options::canonical(), 30, &mut [&mut density])
+        .unwrap();
+
+    assert_eq!(density.total(), 8 * 31);
+    assert!(density.coverage() > 0.0 && density.coverage() < 1.0);
+
+    // The swarm spends most of its time around the optimum
+    let center: usize = density.counts().slice(s![4..6, 4..6]).sum();
+    assert!(center > density.total() / 4, "{}", density.counts());
+
+    let path = env::temp_dir().join(format!("pso-density-{}.svg", std::process::id()));
+    density.plot_to_svg(&path).unwrap();
+    let svg = fs::read_to_string(&path).unwrap();
+    assert!(svg.contains("<rect"));
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn best_trajectory_over_contours() {
+    let mut particles = quiet(
+        SwarmBuilder::new(Fitness::new(sphere, true))
+            .size(6)
+            .dim(3)
+            .seed(27),
+    );
+
+    let mut history = History::new();
+    particles
+        .run_with(Options::canonical(), 20, &mut [&mut history])
+        .unwrap();
+
+    let trajectory = history.best_trajectory();
+    assert_eq!(trajectory.len(), 21);
+    assert_eq!(trajectory[20].1, *particles.best());
+
+    let path = env::temp_dir().join(format!("pso-trajectory-{}.svg", std::process::id()));
+    history
+        .plot_best_trajectory_to_svg(&path, particles.fitness(), (-10.0, 10.0), (-10.0, 10.0))
+        .unwrap();
+    let svg = fs::read_to_string(&path).unwrap();
+    assert!(svg.contains("Global best"));
+    assert!(svg.matches("<polyline").count() >= 1);
+    fs::remove_file(path).unwrap();
+
+    let path = env::temp_dir().join(format!("pso-empty-{}.svg", std::process::id()));
+    let empty = History::new().plot_best_trajectory_to_svg(
+        &path,
+        particles.fitness(),
+        (-1.0, 1.0),
+        (-1.0, 1.0),
+    );
+    assert!(empty.is_err());
+    let _ = fs::remove_file(path);
+}
@@ -0,0 +1,53 @@
+#![cfg(feature = "full")]
+
+mod common;
+
+use common::quiet;
+use ndarray::{array, Array1};
+use pso::builder::SwarmBuilder;
+use pso::constraints::ConstraintHandling;
+use pso::objective::ObjectiveBuilder;
+use pso::pso::Options;
+
+fn assets(w: &Array1<f64>) -> usize {
+    w.iter().filter(|&&v| v > 1e-3).count()
+}
+
+#[test]
+fn mean_variance_with_cardinality_penalty() {
+    let returns = array![0.08, 0.12, 0.10, 0.15, 0.06];
+    let covariance = array![
+        [0.040, 0.006, 0.004, 0.010, 0.002],
+        [0.006, 0.090, 0.012, 0.030, 0.003],
+        [0.004, 0.012, 0.060, 0.015, 0.002],
+        [0.010, 0.030, 0.015, 0.160, 0.004],
+        [0.002, 0.003, 0.002, 0.004, 0.010],
+    ];
+
+    let fitness = {
+        let returns = returns.clone();
+        ObjectiveBuilder::new(true)
+            .term("risk", 3.0, move |w: &Array1<f64>| {
+                w.dot(&covariance.dot(w))
+            })
+            .term("return", -1.0, move |w| w.dot(&returns))
+            .term("cardinality", 1.0, |w| assets(w).saturating_sub(2) as f64)
+            .build()
+    };
+
+    let mut particles = quiet(
+        SwarmBuilder::new(fitness)
+            .size(30)
+            .dim(5)
+            .value_range(0.0, 1.0)
+            .velocity_range(-0.1, 0.1)
+            .seed(13),
+    );
+    particles.set_constraint_handling(Some(ConstraintHandling::Simplex));
+    particles.run(Options::canonical(), 200).unwrap();
+
+    let weights = particles.best();
+    assert!(weights.iter().all(|&w| w >= 0.0));
+    assert!((weights.sum() - 1.0).abs() < 1e-9);
+    assert!(assets(weights) <= 2, "{}", weights);
+}
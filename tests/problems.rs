@@ -0,0 +1,192 @@
+#![cfg(feature = "full")]
+
+mod common;
+
+use common::quiet;
+use ndarray::{array, Array1};
+use pso::builder::SwarmBuilder;
+use pso::constraints::{ConstraintHandling, LinearConstraints};
+use pso::fitness::Fitness;
+use pso::problems::{
+    decode, BinaryProblem, FeatureSelection, Knapsack, Overweight, PressureVessel, Problem, Spring,
+    WeldedBeam,
+};
+use pso::pso::{Confinement, Options};
+use rand::SeedableRng;
+use rand_pcg::Pcg64;
+
+#[test]
+fn best_known_solutions() {
+    let cases: [(&dyn Problem, Array1<f64>); 3] = [
+        (
+            &PressureVessel,
+            array![0.778168641, 0.384649163, 40.3196187, 200.0],
+        ),
+        (&WeldedBeam, array![0.20573, 3.470489, 9.036624, 0.20573]),
+        (&Spring, array![0.051690, 0.356750, 11.287126]),
+    ];
+
+    for (problem, x) in &cases {
+        let relative = (problem.objective(x) - problem.best_known()).abs() / problem.best_known();
+        assert!(
+            relative < 1e-4,
+            "{}: {}",
+            problem.name(),
+            problem.objective(x)
+        );
+
+        assert!(problem.is_feasible(x, 1e-2), "{}", problem.name());
+    }
+}
+
+#[test]
+fn welded_beam_shear_is_active_at_the_optimum() {
+    let x = array![0.20573, 3.470489, 9.036624, 0.20573];
+    let shear = WeldedBeam.constraints(&x)[0];
+
+    assert!(shear.abs() < 1.0, "{}", shear);
+}
+
+#[test]
+fn swarm_solves_the_spring() {
+    let problem = Spring;
+    let (min, max) = problem.bounds();
+
+    let fitness = Fitness::new(|x: &Array1<f64>| problem.penalized(x, 1e6), true);
+    let mut particles = quiet(
+        SwarmBuilder::spso_2011(fitness)
+            .dim(problem.dim())
+            .value_range(0.0, 15.0)
+            .seed(14),
+    );
+    particles.set_constraint_handling(Some(ConstraintHandling::Projection(
+        LinearConstraints::bounds(min, max),
+    )));
+    particles.run(Options::spso_2011(), 500).unwrap();
+
+    let best = particles.best();
+    assert!(problem.is_feasible(best, 1e-3));
+    assert!(problem.objective(best) < problem.best_known() * 1.05);
+}
+
+fn knapsack() -> Knapsack {
+    Knapsack::new(
+        vec![23.0, 31.0, 29.0, 44.0, 53.0, 38.0, 63.0, 85.0, 89.0, 82.0],
+        vec![92.0, 57.0, 49.0, 68.0, 60.0, 43.0, 67.0, 84.0, 87.0, 72.0],
+        165.0,
+    )
+}
+
+#[test]
+fn knapsack_optimum() {
+    // Classic instance, items 0, 1, 2, 3 and 5 are optimal
+    let problem = knapsack();
+    assert_eq!(problem.optimum(), Some(309.0));
+
+    let mut bits = vec![false; 10];
+    for &i in &[0, 1, 2, 3, 5] {
+        bits[i] = true;
+    }
+    assert_eq!(problem.weight(&bits), 165.0);
+    assert_eq!(problem.score(&bits), 309.0);
+
+    let fractional = Knapsack::new(vec![1.5], vec![1.0], 2.0);
+    assert_eq!(fractional.optimum(), None);
+}
+
+#[test]
+fn knapsack_overweight() {
+    let all = vec![true; 10];
+    let repaired = knapsack().repair(&all);
+    let problem = knapsack();
+    assert!(problem.weight(&repaired) <= problem.capacity());
+    assert_eq!(problem.score(&all), problem.value(&repaired));
+
+    // Items of best value per weight are kept
+    assert!(repaired[0] && !repaired[9]);
+
+    let penalized = knapsack().overweight(Overweight::Penalty(10.0));
+    let excess = problem.weight(&all) - problem.capacity();
+    assert_eq!(penalized.score(&all), problem.value(&all) - 10.0 * excess);
+}
+
+#[test]
+fn decoding() {
+    let bits = decode(&array![0.2, 0.5, 0.51, 1.0]);
+    assert_eq!(bits, vec![false, false, true, true]);
+}
+
+#[test]
+fn swarm_solves_knapsack() {
+    let mut rng = Pcg64::seed_from_u64(0);
+    let problem = Knapsack::random(20, &mut rng);
+    let fitness = Fitness::new(|x: &Array1<f64>| problem.evaluate(x), false);
+
+    let mut particles = SwarmBuilder::new(fitness)
+        .size(30)
+        .dim(problem.dim())
+        .value_range(0.0, 1.0)
+        .confinement(Confinement::Bounded)
+        .seed(1)
+        .build()
+        .unwrap();
+    particles.run(Options::canonical(), 300).unwrap();
+
+    let best = problem.repair(&decode(particles.best()));
+    assert_eq!(problem.value(&best), particles.best_fitness());
+    assert!(particles.best_fitness() >= 0.95 * problem.best_known().unwrap());
+}
+
+#[test]
+fn synthetic_feature_selection() {
+    let mut rng = Pcg64::seed_from_u64(2);
+    let problem = FeatureSelection::synthetic(30, 5, &mut rng);
+    let relevant = problem.relevant().unwrap().to_vec();
+    assert_eq!(relevant.len(), 5);
+
+    let mut bits = vec![false; 30];
+    for &i in &relevant {
+        bits[i] = true;
+    }
+    assert_eq!(problem.score(&bits), problem.best_known().unwrap());
+
+    // An extra feature only costs, a missing one loses score
+    let extra = (0..30).find(|i| !relevant.contains(i)).unwrap();
+    let mut more = bits.clone();
+    more[extra] = true;
+    assert!(problem.score(&more) < problem.score(&bits));
+
+    let mut fewer = bits.clone();
+    fewer[relevant[0]] = false;
+    assert!(problem.score(&fewer) < problem.score(&bits));
+
+    let fitness = Fitness::new(|x: &Array1<f64>| problem.evaluate(x), false);
+    let mut particles = SwarmBuilder::new(fitness)
+        .size(30)
+        .dim(problem.dim())
+        .value_range(0.0, 1.0)
+        .confinement(Confinement::Bounded)
+        .seed(3)
+        .build()
+        .unwrap();
+    particles.run(Options::canonical(), 300).unwrap();
+
+    // Every relevant feature is found, an extra one barely costs
+    let selected = FeatureSelection::selected(&decode(particles.best()));
+    assert!(relevant.iter().all(|i| selected.contains(i)));
+    assert!(selected.len() <= relevant.len() + 1);
+}
+
+#[test]
+fn custom_feature_score() {
+    // Pairs of features only score together
+    let problem = FeatureSelection::new(4, |selected: &[usize]| {
+        let pair = |a, b| selected.contains(&a) && selected.contains(&b);
+        f64::from(u8::from(pair(0, 1))) + f64::from(u8::from(pair(2, 3)))
+    })
+    .cost(0.1);
+
+    assert_eq!(problem.best_known(), None);
+    assert_eq!(problem.score(&[true, true, false, false]), 0.8);
+    assert_eq!(problem.score(&[true, false, true, false]), -0.2);
+}
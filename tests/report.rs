@@ -0,0 +1,133 @@
+#![cfg(feature = "full")]
+
+mod common;
+
+use common::quiet;
+use ndarray::Array1;
+use pso::benchmark::sphere;
+use pso::builder::SwarmBuilder;
+use pso::fitness::Fitness;
+use pso::pso::Options;
+use pso::report::{Language, NumberFormat, SwarmSummary};
+
+#[test]
+fn summary_languages() {
+    let particles = SwarmBuilder::new(Fitness::new(sphere, true))
+        .size(3)
+        .seed(0)
+        .build()
+        .unwrap();
+
+    let spanish = particles.summary(true).unwrap();
+    assert!(spanish.contains(">>> Cúmulo de partículas <<<"));
+    assert!(spanish.contains(">>> Mejor global"));
+
+    let english = particles
+        .summary_with()
+        .language(Language::English)
+        .particles(true)
+        .to_string();
+    assert!(english.contains(">>> Swarm <<<"));
+    assert!(english.contains(">>> Personal bests <<<"));
+    assert!(english.contains(">>> Global best"));
+}
+
+#[test]
+fn summary_precision_and_alignment() {
+    let particles = SwarmBuilder::new(Fitness::new(sphere, true))
+        .size(12)
+        .seed(1)
+        .language(Language::English)
+        .build()
+        .unwrap();
+
+    let summary = particles.summary_with().precision(2).to_string();
+    let fitness: Vec<_> = summary
+        .lines()
+        .skip_while(|line| !line.starts_with(">>> Fitness"))
+        .skip(1)
+        .take(12)
+        .collect();
+
+    assert!(fitness[0].starts_with(" 1) "));
+    assert!(fitness[11].starts_with("12) "));
+    assert!(fitness.iter().all(|line| line.len() == fitness[0].len()));
+    assert!(fitness
+        .iter()
+        .all(|line| line.split('.').nth(1).unwrap().len() == 2));
+}
+
+#[test]
+fn particle_display() {
+    let particles = SwarmBuilder::new(Fitness::new(sphere, true))
+        .size(1)
+        .seed(2)
+        .build()
+        .unwrap();
+
+    let particle = &particles.particles()[0];
+    let text = format!("{:.1}", particle);
+    assert!(text.starts_with("x: ["));
+    assert!(text.ends_with(&format!("fitness: {:.1}", particle.fitness())));
+}
+
+#[test]
+fn summary_json_round_trips() {
+    let mut particles = quiet(
+        SwarmBuilder::new(Fitness::new(sphere, true))
+            .size(4)
+            .seed(3),
+    );
+    particles.step(Options::canonical());
+
+    let json = particles.summary_json();
+    assert_eq!(json["steps"], 1);
+    assert_eq!(json["particles"].as_array().unwrap().len(), 4);
+    assert_eq!(json["best_fitness"], particles.best_fitness());
+
+    let summary: SwarmSummary = serde_json::from_value(json).unwrap();
+    assert_eq!(summary.best, particles.best().to_vec());
+    assert_eq!(
+        summary.particles[2].fitness,
+        particles.particles()[2].fitness()
+    );
+}
+
+#[test]
+fn number_format() {
+    let full = NumberFormat::new();
+    assert_eq!(full.format(0.1 + 0.2), "0.30000000000000004");
+
+    let short = NumberFormat::new().significant(3);
+    assert_eq!(short.format(0.1 + 0.2), "0.300");
+    assert_eq!(short.format(-123.456), "-123");
+    assert_eq!(short.format(0.00123456), "0.00123");
+
+    let scientific = NumberFormat::new().significant(3).scientific(1e4);
+    assert_eq!(scientific.format(123456.0), "1.23e5");
+    assert_eq!(scientific.format(0.0000123), "1.23e-5");
+    assert_eq!(scientific.format(12.0), "12.0");
+    assert_eq!(scientific.format(0.0), "0");
+
+    let array = Array1::from(vec![1.0, 2.5]);
+    assert_eq!(short.array(&array), "[1.00, 2.50]");
+}
+
+#[test]
+fn summary_uses_number_format() {
+    let particles = SwarmBuilder::new(Fitness::new(sphere, true))
+        .size(3)
+        .seed(5)
+        .number_format(NumberFormat::new().significant(2).scientific(1e3))
+        .build()
+        .unwrap();
+
+    let summary = particles.summary(false).unwrap();
+    let global = summary.lines().last().unwrap();
+    let fitness = global.rsplit("fitness: ").next().unwrap();
+    assert_eq!(
+        fitness,
+        particles.number_format().format(particles.best_fitness())
+    );
+    assert!(fitness.len() <= 7, "{}", fitness);
+}
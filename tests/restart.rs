@@ -0,0 +1,29 @@
+#![cfg(feature = "full")]
+
+use pso::benchmark::sphere;
+use pso::builder::SwarmBuilder;
+use pso::fitness::Fitness;
+use pso::pso::Options;
+use pso::restart::RestartOptions;
+
+#[test]
+fn restarts_archive_every_optimum() {
+    let mut particles = SwarmBuilder::new(Fitness::new(sphere, true))
+        .seed(3)
+        .build()
+        .unwrap();
+
+    let restart = RestartOptions {
+        budget: 200,
+        stagnation: 5,
+        tabu_radius: Some(1.0),
+    };
+
+    let archive = particles
+        .run_with_restarts(Options::canonical(), restart)
+        .unwrap();
+
+    assert!(archive.len() > 1);
+    assert!(archive.windows(2).all(|w| w[0].fitness <= w[1].fitness));
+    assert!(archive.iter().all(|o| o.iter <= restart.budget));
+}
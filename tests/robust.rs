@@ -0,0 +1,132 @@
+#![cfg(feature = "full")]
+
+mod common;
+
+use common::quiet;
+use ndarray::{array, Array1};
+use pso::benchmark::sphere;
+use pso::builder::SwarmBuilder;
+use pso::error::PsoError;
+use pso::fitness::Fitness;
+use pso::pso::Options;
+use rand::{Rng, SeedableRng};
+use rand_pcg::Pcg64;
+use std::cell::RefCell;
+use std::time::Duration;
+
+#[test]
+fn deterministic_objective_keeps_the_best() {
+    let mut particles = quiet(
+        SwarmBuilder::new(Fitness::new(sphere, true))
+            .size(10)
+            .seed(16),
+    );
+    particles.run(Options::canonical(), 20).unwrap();
+
+    let candidates = particles.robust_best(3, 5).unwrap();
+    assert_eq!(candidates.len(), 3);
+    assert_eq!(candidates[0].value, *particles.best());
+    assert_eq!(candidates[0].mean, particles.best_fitness());
+    assert_eq!(candidates[0].std_error, 0.0);
+    assert!(candidates.windows(2).all(|w| w[0].mean <= w[1].mean));
+}
+
+#[test]
+fn noisy_objective_is_averaged() {
+    let rng = RefCell::new(Pcg64::seed_from_u64(0));
+    let noisy = |x: &Array1<f64>| sphere(x) + rng.borrow_mut().gen_range(-1.0, 1.0);
+
+    let mut particles = quiet(
+        SwarmBuilder::new(Fitness::new(noisy, true))
+            .size(10)
+            .seed(17),
+    );
+    particles.run(Options::canonical(), 20).unwrap();
+
+    let candidates = particles.robust_best(5, 100).unwrap();
+    assert_eq!(candidates.len(), 5);
+    for candidate in &candidates {
+        assert_eq!(candidate.evaluations, 100);
+        assert!(candidate.std_error > 0.0 && candidate.std_error < 0.1);
+        assert!((candidate.mean - sphere(&candidate.value)).abs() < 0.3);
+    }
+
+    // A single lucky evaluation looks better than the mean
+    assert!(particles.best_fitness() < candidates[0].mean);
+}
+
+#[test]
+fn shared_bests_are_evaluated_once() {
+    let rng = RefCell::new(Pcg64::seed_from_u64(1));
+    let noisy = |x: &Array1<f64>| sphere(x) + rng.borrow_mut().gen_range(-1.0, 1.0);
+
+    let mut particles = quiet(SwarmBuilder::new(Fitness::new(noisy, true)).size(6).seed(3));
+
+    // Same best, different noisy fitnesses
+    for _ in 0..3 {
+        particles
+            .add_particle(array![0.5, 0.5], Array1::zeros(2))
+            .unwrap();
+    }
+
+    let candidates = particles.robust_best(9, 10).unwrap();
+    assert_eq!(candidates.len(), 7);
+    for (i, candidate) in candidates.iter().enumerate() {
+        assert!(candidates[..i].iter().all(|c| c.value != candidate.value));
+    }
+}
+
+#[test]
+fn candidates_are_in_the_original_coordinates() {
+    let mut particles = quiet(
+        SwarmBuilder::new(Fitness::new(sphere, true))
+            .size(10)
+            .dim(2)
+            .normalize(array![-4.0, 10.0], array![4.0, 20.0])
+            .robust(3, 2)
+            .seed(4),
+    );
+
+    let result = particles
+        .optimize_for(Options::canonical(), Duration::from_millis(10))
+        .unwrap();
+    let robust = result.robust_best.unwrap();
+
+    assert_eq!(robust.value, result.best);
+    assert_eq!(robust.mean, result.best_fitness);
+    assert_eq!(robust.evaluations, 2);
+}
+
+#[test]
+fn robust_selection_is_opt_in() {
+    let mut particles = SwarmBuilder::new(Fitness::new(sphere, true))
+        .seed(5)
+        .build()
+        .unwrap();
+
+    let result = particles
+        .optimize_for(Options::canonical(), Duration::from_secs(0))
+        .unwrap();
+    assert!(result.robust_best.is_none());
+}
+
+#[test]
+fn invalid_robust_selections_are_rejected() {
+    let particles = SwarmBuilder::new(Fitness::new(sphere, true))
+        .seed(6)
+        .build()
+        .unwrap();
+    assert!(matches!(
+        particles.robust_best(0, 5),
+        Err(PsoError::InvalidOptions(_))
+    ));
+    assert!(matches!(
+        particles.robust_best(3, 1),
+        Err(PsoError::InvalidOptions(_))
+    ));
+
+    let result = SwarmBuilder::new(Fitness::new(sphere, true))
+        .robust(3, 1)
+        .build();
+    assert!(matches!(result, Err(PsoError::InvalidOptions(_))));
+}
@@ -0,0 +1,31 @@
+#![cfg(feature = "full")]
+
+use pso::fitness::Scaling;
+
+#[test]
+fn scalings_preserve_the_order() {
+    let values = [1e6, 3.0, -2.0, 0.0, 1e-3];
+
+    for &scaling in &[
+        Scaling::Identity,
+        Scaling::Log,
+        Scaling::Rank,
+        Scaling::Sigma,
+    ] {
+        let scaled = scaling.apply(&values);
+
+        for i in 0..values.len() {
+            for j in 0..values.len() {
+                assert_eq!(values[i] < values[j], scaled[i] < scaled[j]);
+            }
+        }
+    }
+}
+
+#[test]
+fn scalings_values() {
+    assert_eq!(Scaling::Log.apply(&[99.0, -9.0]), vec![2.0, -1.0]);
+    assert_eq!(Scaling::Rank.apply(&[5.0, 1.0, 3.0]), vec![1.0, 0.0, 0.5]);
+    assert_eq!(Scaling::Sigma.apply(&[1.0, 3.0]), vec![-1.0, 1.0]);
+    assert_eq!(Scaling::Sigma.apply(&[2.0, 2.0]), vec![0.0, 0.0]);
+}
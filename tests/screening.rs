@@ -0,0 +1,97 @@
+#![cfg(feature = "full")]
+
+mod common;
+
+use common::quiet;
+use pso::benchmark::rastrigin;
+use pso::builder::SwarmBuilder;
+use pso::error::PsoError;
+use pso::fitness::Fitness;
+use pso::pso::{Options, Particles};
+use pso::screening::Screening;
+
+fn swarm(screening: Screening) -> Particles<'static> {
+    quiet(
+        SwarmBuilder::new(Fitness::new(rastrigin, true))
+            .size(20)
+            .dim(4)
+            .value_range(-5.12, 5.12)
+            .screening(screening)
+            .seed(3),
+    )
+}
+
+#[test]
+fn only_the_most_promising_are_evaluated() {
+    let mut particles = swarm(Screening::new(5));
+
+    for step in 1..=20 {
+        particles.step(Options::canonical());
+
+        let estimated = particles
+            .particles()
+            .iter()
+            .filter(|p| p.is_estimated())
+            .count();
+        assert_eq!(estimated, 15);
+        assert_eq!(particles.evaluations_used(), 20 + 5 * step);
+    }
+}
+
+#[test]
+fn estimates_never_become_bests() {
+    let mut particles = swarm(Screening::new(3));
+
+    for _ in 0..50 {
+        particles.step(Options::canonical());
+
+        for particle in particles.particles() {
+            assert_eq!(particle.best_fitness(), rastrigin(particle.best()));
+            if !particle.is_estimated() {
+                assert_eq!(particle.fitness(), rastrigin(particle.value()));
+            }
+        }
+        assert_eq!(particles.best_fitness(), rastrigin(particles.best()));
+    }
+}
+
+#[test]
+fn surrogate_estimates_guide_the_evaluations() {
+    let mut particles = swarm(Screening::new(4).surrogate(40, 0.3));
+    let initial = particles.best_fitness();
+
+    particles.run(Options::canonical(), 50).unwrap();
+
+    assert_eq!(particles.evaluations_used(), 20 + 4 * 50);
+    assert_eq!(particles.evaluations().len(), 40);
+    assert!(particles.best_fitness() < initial);
+    assert_eq!(particles.best_fitness(), rastrigin(particles.best()));
+}
+
+#[test]
+fn screening_respects_the_budget() {
+    let mut particles = swarm(Screening::new(5));
+    particles.set_evaluation_budget(Some(27));
+
+    let steps = particles.run(Options::canonical(), 10).unwrap();
+
+    assert_eq!(steps, 2);
+    assert_eq!(particles.evaluations_used(), 27);
+    assert!(particles.budget_exhausted());
+}
+
+#[test]
+fn invalid_screenings_are_rejected() {
+    for screening in [
+        Screening::new(0),
+        Screening::new(3).surrogate(1, 0.2),
+        Screening::new(3).surrogate(10, 0.0),
+    ]
+    .iter()
+    {
+        let result = SwarmBuilder::new(Fitness::new(rastrigin, true))
+            .screening(*screening)
+            .build();
+        assert!(matches!(result, Err(PsoError::InvalidOptions(_))));
+    }
+}
@@ -0,0 +1,103 @@
+#![cfg(feature = "full")]
+
+mod common;
+
+use common::quiet;
+use ndarray::{array, Array1};
+use pso::benchmark::sphere;
+use pso::builder::SwarmBuilder;
+use pso::error::PsoError;
+use pso::fitness::Fitness;
+use pso::pso::Options;
+use pso::sensitivity::latin_hypercube;
+use rand::SeedableRng;
+use rand_pcg::Pcg64;
+use std::time::Duration;
+
+#[test]
+fn latin_hypercube_strata() {
+    let mut rng = Pcg64::seed_from_u64(0);
+    let points = latin_hypercube(10, 3, &mut rng);
+
+    for column in points.gencolumns() {
+        let mut strata: Vec<_> = column.iter().map(|v| (v * 10.0) as usize).collect();
+        strata.sort_unstable();
+        assert_eq!(strata, (0..10).collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn sensitivity_ranks_dimensions() {
+    let f = |x: &Array1<f64>| 100.0 * x[0] * x[0] + x[1] * x[1];
+
+    let mut particles = quiet(
+        SwarmBuilder::new(Fitness::new(f, true))
+            .size(20)
+            .dim(3)
+            .sensitivity(0.05, 200)
+            .seed(15),
+    );
+
+    let result = particles
+        .optimize_for(Options::canonical(), Duration::from_secs(0))
+        .unwrap();
+    let sensitivity = result.sensitivity.unwrap();
+
+    assert_eq!(sensitivity.samples, 200);
+    assert_eq!(sensitivity.radius, Array1::from_elem(3, 0.05 * 20.0));
+    let indices = &sensitivity.indices;
+    assert!(indices[0] > 0.8, "{}", indices);
+    assert!(indices[0] > 5.0 * indices[1].max(indices[2]), "{}", indices);
+    assert!(sensitivity.max_change >= sensitivity.mean_change);
+}
+
+#[test]
+fn sensitivity_is_opt_in() {
+    let mut particles = SwarmBuilder::new(Fitness::new(sphere, true))
+        .seed(15)
+        .build()
+        .unwrap();
+
+    let result = particles
+        .optimize_for(Options::canonical(), Duration::from_secs(0))
+        .unwrap();
+    assert!(result.sensitivity.is_none());
+}
+
+#[test]
+fn normalized_radius_is_in_the_original_coordinates() {
+    let mut particles = quiet(
+        SwarmBuilder::new(Fitness::new(sphere, true))
+            .size(10)
+            .normalize(array![-1.0, 0.0], array![1.0, 100.0])
+            .sensitivity(0.1, 20)
+            .seed(16),
+    );
+
+    let result = particles
+        .optimize_for(Options::canonical(), Duration::from_secs(0))
+        .unwrap();
+    let radius = result.sensitivity.unwrap().radius;
+
+    assert!((radius[0] - 0.2).abs() < 1e-12, "{}", radius);
+    assert!((radius[1] - 10.0).abs() < 1e-9, "{}", radius);
+}
+
+#[test]
+fn invalid_sensitivity_analyses_are_rejected() {
+    let mut particles = SwarmBuilder::new(Fitness::new(sphere, true))
+        .seed(17)
+        .build()
+        .unwrap();
+    assert!(matches!(
+        particles.sensitivity(0.05, 3),
+        Err(PsoError::InvalidOptions(_))
+    ));
+
+    for &(radius, samples) in &[(0.05, 2), (-0.1, 10), (f64::NAN, 10)] {
+        let result = SwarmBuilder::new(Fitness::new(sphere, true))
+            .sensitivity(radius, samples)
+            .build();
+        assert!(matches!(result, Err(PsoError::InvalidOptions(_))));
+    }
+}
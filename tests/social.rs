@@ -0,0 +1,69 @@
+#![cfg(feature = "full")]
+
+mod common;
+
+use common::{builder, quiet};
+use pso::benchmark::sphere;
+use pso::builder::SwarmBuilder;
+use pso::fitness::Fitness;
+use pso::pso::{Algorithm, Options, Particles};
+
+fn sl_pso(size: usize, dim: usize, seed: u64) -> Particles<'static> {
+    quiet(
+        builder(size, dim, seed)
+            .value_range(-5.0, 5.0)
+            .velocity_range(-1.0, 1.0)
+            .algorithm(Algorithm::social_learning()),
+    )
+}
+
+#[test]
+fn converges_in_high_dimensions() {
+    let mut particles = sl_pso(60, 200, 3);
+    let initial = particles.best_fitness();
+
+    particles.run(Options::canonical(), 300).unwrap();
+
+    assert!(particles.best_fitness() < initial * 1e-2);
+}
+
+#[test]
+fn best_particle_never_learns() {
+    let mut particles = sl_pso(10, 5, 4);
+
+    for _ in 0..20 {
+        let best = (0..10)
+            .min_by(|&a, &b| {
+                let fa = particles.particles()[a].fitness();
+                let fb = particles.particles()[b].fitness();
+                fa.partial_cmp(&fb).unwrap()
+            })
+            .unwrap();
+        let value = particles.particles()[best].value().clone();
+
+        particles.step(Options::canonical());
+        assert_eq!(particles.particles()[best].value(), &value);
+    }
+}
+
+#[test]
+fn reproducible_with_seed() {
+    let mut a = sl_pso(20, 5, 7);
+    let mut b = sl_pso(20, 5, 7);
+
+    a.run(Options::canonical(), 30).unwrap();
+    b.run(Options::canonical(), 30).unwrap();
+
+    assert_eq!(a.best(), b.best());
+}
+
+#[test]
+fn rejects_negative_parameters() {
+    let result = SwarmBuilder::new(Fitness::new(sphere, true))
+        .algorithm(Algorithm::SocialLearning {
+            alpha: -0.5,
+            beta: 0.01,
+        })
+        .build();
+    assert!(result.is_err());
+}
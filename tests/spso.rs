@@ -0,0 +1,95 @@
+#![cfg(feature = "full")]
+
+mod common;
+
+use common::quiet;
+use pso::benchmark::sphere;
+use pso::builder::SwarmBuilder;
+use pso::fitness::Fitness;
+use pso::pso::{Confinement, Options, Particles};
+use pso::topology::Topology;
+use pso::velocity::VelocityPolicy;
+
+fn spso(seed: u64) -> Particles<'static> {
+    SwarmBuilder::spso_2011(Fitness::new(sphere, true))
+        .dim(10)
+        .value_range(-100.0, 100.0)
+        .seed(seed)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn preset_configuration() {
+    let particles = spso(0);
+
+    assert_eq!(particles.particles().len(), 40);
+    assert_eq!(particles.topology(), Topology::Random { k: 3 });
+    assert_eq!(particles.velocity_policy(), VelocityPolicy::Hypersphere);
+    assert_eq!(particles.confinement(), Confinement::Bounded);
+
+    for particle in particles.particles() {
+        let reached = particle.value() + particle.velocity();
+        assert!(reached.iter().all(|x| (-100.0..=100.0).contains(x)));
+    }
+}
+
+#[test]
+fn stays_confined_and_converges() {
+    let mut particles = spso(1);
+
+    for _ in 0..300 {
+        particles.step(Options::spso_2011());
+
+        for particle in particles.particles() {
+            assert!(particle
+                .value()
+                .iter()
+                .all(|x| (-100.0..=100.0).contains(x)));
+        }
+    }
+
+    assert!(particles.best_fitness() < 1e-6);
+}
+
+#[test]
+fn reproducible_with_seed() {
+    let mut a = spso(2);
+    let mut b = spso(2);
+
+    a.run(Options::spso_2011(), 20).unwrap();
+    b.run(Options::spso_2011(), 20).unwrap();
+
+    assert_eq!(a.best(), b.best());
+}
+
+#[test]
+fn absorbing_confinement_stops_at_the_bounds() {
+    let mut particles = quiet(
+        SwarmBuilder::new(Fitness::new(sphere, true))
+            .size(10)
+            .dim(3)
+            .value_range(-1.0, 1.0)
+            .velocity_range(-5.0, 5.0)
+            .confinement(Confinement::Absorbing)
+            .seed(3),
+    );
+
+    let mut hits = 0;
+    for _ in 0..20 {
+        particles.step(Options::spso_2011());
+
+        for particle in particles.particles() {
+            let dims = particle.value().iter().zip(particle.velocity().iter());
+            for (x, v) in dims {
+                assert!((-1.0..=1.0).contains(x));
+                if x.abs() == 1.0 {
+                    assert_eq!(*v, 0.0);
+                    hits += 1;
+                }
+            }
+        }
+    }
+
+    assert!(hits > 0);
+}
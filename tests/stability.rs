@@ -0,0 +1,38 @@
+#![cfg(feature = "full")]
+
+use pso::pso::Options;
+use pso::stability::{self, Condition};
+
+#[test]
+fn ranges_are_consistent_with_each_other() {
+    for &condition in &[Condition::Order1, Condition::Order2] {
+        for &omega in &[0.0, 0.3, 0.5, 0.72984, 0.9] {
+            let (_, max) = stability::phi_range(omega, condition).unwrap();
+            let phi = max * 0.99;
+
+            let (min_omega, max_omega) = stability::omega_range(phi, condition).unwrap();
+            assert!(min_omega <= omega && omega <= max_omega);
+        }
+    }
+}
+
+#[test]
+fn order2_is_stricter_than_order1() {
+    assert!(stability::is_convergent(0.5, 5.0, Condition::Order1));
+    assert!(!stability::is_convergent(0.5, 5.0, Condition::Order2));
+    assert_eq!(stability::omega_range(5.0, Condition::Order2), None);
+    assert_eq!(stability::phi_range(1.0, Condition::Order1), None);
+}
+
+#[test]
+fn canonical_options_converge() {
+    let options = Options::canonical();
+    let phi = options.phi_1 + options.phi_2;
+
+    assert!(options.validate().is_ok());
+    assert!(stability::is_convergent(
+        options.omega.unwrap(),
+        phi,
+        Condition::Order2
+    ));
+}
@@ -0,0 +1,128 @@
+#![cfg(feature = "full")]
+
+mod common;
+
+use common::{builder, quiet};
+use ndarray::arr1;
+use pso::benchmark::sphere;
+use pso::builder::SwarmBuilder;
+use pso::fitness::Fitness;
+use pso::lineage::LineageEvent;
+use pso::operator::Operator;
+use pso::pso::{Options, Particles};
+use pso::surrogate::{expected_improvement, GaussianProcess, Surrogate};
+
+fn swarm(surrogate: Option<Surrogate>, seed: u64) -> Particles<'static> {
+    let mut builder = builder(8, 2, seed)
+        .value_range(-5.0, 5.0)
+        .velocity_range(-1.0, 1.0);
+    if let Some(surrogate) = surrogate {
+        builder = builder.surrogate(surrogate);
+    }
+
+    quiet(builder)
+}
+
+#[test]
+fn process_interpolates_its_samples() {
+    let inputs: Vec<_> = [-2.0, -1.0, 0.0, 1.0, 2.0, 1.0]
+        .iter()
+        .map(|&x| arr1(&[x]))
+        .collect();
+    let outputs: Vec<_> = inputs.iter().map(sphere).collect();
+
+    let process = GaussianProcess::fit(&inputs, &outputs, 1.0).unwrap();
+    for (x, y) in inputs.iter().zip(&outputs) {
+        let (mean, std) = process.predict(x);
+        assert!((mean - y).abs() < 1e-3);
+        assert!(std < 1e-2);
+    }
+
+    let (_, far) = process.predict(&arr1(&[10.0]));
+    assert!(far > 1.0);
+
+    assert!(GaussianProcess::fit(&inputs[..1], &outputs[..1], 1.0).is_none());
+}
+
+#[test]
+fn expected_improvement_rewards_uncertainty() {
+    assert_eq!(expected_improvement(2.0, 0.0, 1.0), 1.0);
+    assert_eq!(expected_improvement(0.0, 0.0, 1.0), 0.0);
+    assert!(expected_improvement(0.0, 2.0, 1.0) > expected_improvement(0.0, 1.0, 1.0));
+    assert!(expected_improvement(0.0, 1.0, 1.0) > 0.0);
+}
+
+#[test]
+fn scouts_replace_the_worst_particle() {
+    let surrogate = Surrogate {
+        every: 1,
+        memory: 20,
+        ..Surrogate::default()
+    };
+    let mut particles = swarm(Some(surrogate), 1);
+    assert_eq!(particles.evaluations().len(), 8);
+
+    particles.run(Options::canonical(), 10).unwrap();
+
+    assert_eq!(particles.evaluations().len(), 20);
+    let scouts = particles
+        .lineage()
+        .iter()
+        .filter(|l| l.event == LineageEvent::Scouted)
+        .count();
+    assert_eq!(scouts, 10);
+}
+
+#[test]
+fn memory_follows_the_pipeline() {
+    let mut particles = swarm(None, 2);
+    assert!(particles.evaluations().is_empty());
+
+    particles.run(Options::canonical(), 3).unwrap();
+    assert!(particles.evaluations().is_empty());
+
+    particles
+        .add_operator(Operator::Surrogate(Surrogate::default()))
+        .unwrap();
+    particles.run(Options::canonical(), 3).unwrap();
+    // Plus the scout of the 5th step
+    assert_eq!(particles.evaluations().len(), 8 + 3 * 8 + 1);
+
+    particles.set_operators(Vec::new()).unwrap();
+    assert!(particles.evaluations().is_empty());
+}
+
+#[test]
+fn accelerates_convergence_on_a_small_budget() {
+    let surrogate = Surrogate {
+        every: 1,
+        memory: 40,
+        candidates: 200,
+        ..Surrogate::default()
+    };
+
+    let (mut with, mut without) = (0.0, 0.0);
+    for seed in 0..5 {
+        let mut plain = swarm(None, seed);
+        plain.run(Options::canonical(), 15).unwrap();
+        without += plain.best_fitness();
+
+        let mut hybrid = swarm(Some(surrogate), seed);
+        hybrid.run(Options::canonical(), 15).unwrap();
+        with += hybrid.best_fitness();
+    }
+
+    assert!(with < without);
+}
+
+#[test]
+fn rejects_invalid_settings() {
+    let invalid = Surrogate {
+        memory: 1,
+        ..Surrogate::default()
+    };
+    assert!(SwarmBuilder::new(Fitness::new(sphere, true))
+        .surrogate(invalid)
+        .build()
+        .is_err());
+}
@@ -0,0 +1,76 @@
+#![cfg(feature = "full")]
+
+mod common;
+
+use common::{builder, quiet};
+use pso::pso::{Options, Particles};
+use pso::telemetry::Metrics;
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+
+fn swarm() -> Particles<'static> {
+    quiet(builder(10, 2, 8))
+}
+
+fn get(addr: &str, path: &str) -> String {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    write!(stream, "GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path).unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    response
+}
+
+#[test]
+fn records_the_run() {
+    let mut particles = swarm();
+    let mut metrics = Metrics::new();
+    particles
+        .run_with(Options::canonical(), 15, &mut [&mut metrics])
+        .unwrap();
+
+    let snapshot = metrics.snapshot();
+    assert_eq!(snapshot.iterations, 15);
+    assert_eq!(snapshot.evaluations, particles.evaluations_used());
+    assert_eq!(snapshot.best_fitness, particles.best_fitness());
+    assert!(snapshot.diversity > 0.0);
+    assert!(snapshot.iterations_per_second > 0.0);
+    assert!(!snapshot.running);
+}
+
+#[test]
+fn renders_the_prometheus_text_format() {
+    let metrics = Metrics::new();
+    let text = metrics.render();
+
+    assert!(text.contains("# TYPE pso_iterations_total counter\npso_iterations_total 0\n"));
+    assert!(text.contains("# TYPE pso_best_fitness gauge\n"));
+    assert!(text.contains("pso_running 0\n"));
+    assert_eq!(text.lines().filter(|l| !l.starts_with('#')).count(), 6);
+}
+
+#[test]
+fn serves_the_metrics_while_running() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+
+    let mut metrics = Metrics::new();
+    let server = metrics.clone();
+    thread::spawn(move || server.serve(listener));
+
+    let mut particles = swarm();
+    particles
+        .run_with(Options::canonical(), 5, &mut [&mut metrics])
+        .unwrap();
+
+    let response = get(&addr, "/metrics");
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(response.contains("pso_iterations_total 5\n"));
+    assert!(response.contains(&format!(
+        "pso_evaluations_total {}\n",
+        particles.evaluations_used()
+    )));
+
+    assert!(get(&addr, "/").starts_with("HTTP/1.1 404 Not Found\r\n"));
+}
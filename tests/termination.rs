@@ -0,0 +1,38 @@
+#![cfg(feature = "full")]
+
+use pso::benchmark::sphere;
+use pso::builder::SwarmBuilder;
+use pso::fitness::Fitness;
+use pso::pso::Options;
+use pso::termination::Plateau;
+
+#[test]
+fn plateau_uses_the_whole_window() {
+    let mut plateau = Plateau::relative(3, 0.01);
+
+    assert!(!plateau.update(100.0));
+    assert!(!plateau.update(99.99));
+    assert!(!plateau.update(99.98));
+    assert!(plateau.update(99.97));
+    assert!(!plateau.update(50.0));
+
+    let mut plateau = Plateau::absolute(1, 0.5);
+    assert!(!plateau.update(10.0));
+    assert!(!plateau.update(9.0));
+    assert!(plateau.update(8.9));
+}
+
+#[test]
+fn plateau_stops_a_converged_run() {
+    let mut particles = SwarmBuilder::new(Fitness::new(sphere, true))
+        .seed(9)
+        .build()
+        .unwrap();
+
+    let mut plateau = Plateau::absolute(10, 1e-12);
+    let done = particles
+        .run_with(Options::canonical(), 10_000, &mut [&mut plateau])
+        .unwrap();
+
+    assert!(done < 10_000);
+}
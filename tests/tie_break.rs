@@ -0,0 +1,68 @@
+#![cfg(feature = "full")]
+
+mod common;
+
+use common::quiet;
+use ndarray::Array1;
+use pso::builder::SwarmBuilder;
+use pso::fitness::{Comparison, Fitness};
+use pso::pso::{Confinement, Options, TieBreak};
+
+fn plateau(x: &Array1<f64>) -> f64 {
+    // Flat everywhere except for a step, plenty of ties
+    if x[0] > 0.0 {
+        0.0
+    } else {
+        1.0
+    }
+}
+
+fn run(seed: u64, tie_break: TieBreak) -> Vec<Array1<f64>> {
+    let mut particles = SwarmBuilder::new(Fitness::new(plateau, true))
+        .seed(seed)
+        .tie_break(tie_break)
+        .build()
+        .unwrap();
+
+    (0..10)
+        .map(|_| {
+            particles.step(Options::canonical());
+            particles.best().clone()
+        })
+        .collect()
+}
+
+#[test]
+fn runs_are_deterministic_given_a_seed() {
+    for &tie_break in &[
+        TieBreak::First,
+        TieBreak::Random,
+        TieBreak::ClosestToPrevious,
+        TieBreak::MostCentral,
+    ] {
+        assert_eq!(run(42, tie_break), run(42, tie_break));
+    }
+}
+
+#[test]
+fn ties_never_replace_the_global_best() {
+    let bests = run(7, TieBreak::Random);
+    assert!(bests.windows(2).all(|w| plateau(&w[1]) <= plateau(&w[0])));
+}
+
+#[test]
+fn ties_between_nan_positions_dont_panic() {
+    let mut particles = quiet(
+        SwarmBuilder::new(Fitness::new(|_: &Array1<f64>| 1.0, true))
+            .size(6)
+            .dim(2)
+            .seed(3)
+            .confinement(Confinement::Free)
+            .comparison(Comparison::Total)
+            .tie_break(TieBreak::MostCentral),
+    );
+    particles.mutate(0, |x| x.fill(f64::NAN));
+
+    particles.step(Options::canonical());
+    assert_eq!(particles.best_fitness(), 1.0);
+}
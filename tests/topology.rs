@@ -0,0 +1,155 @@
+#![cfg(feature = "full")]
+
+use pso::benchmark::sphere;
+use pso::builder::SwarmBuilder;
+use pso::fitness::Fitness;
+use pso::pso::Options;
+use pso::topology::Topology;
+use rand::{thread_rng, SeedableRng};
+use rand_pcg::Pcg64;
+
+#[test]
+fn von_neumann_grid_neighbors() {
+    let mut rng = thread_rng();
+    assert_eq!(
+        Topology::von_neumann(20),
+        Topology::VonNeumann {
+            rows: 4,
+            cols: 5,
+            wrap: true
+        }
+    );
+
+    let wrapping = Topology::von_neumann(12)
+        .neighborhoods(12, &mut rng)
+        .unwrap();
+    assert_eq!(wrapping[0], vec![0, 1, 3, 4, 8]);
+    assert!(wrapping.iter().all(|n| n.len() == 5));
+
+    let bounded = Topology::VonNeumann {
+        rows: 3,
+        cols: 4,
+        wrap: false,
+    };
+    let bounded = bounded.neighborhoods(12, &mut rng).unwrap();
+    assert_eq!(bounded[0], vec![0, 1, 4]);
+    assert_eq!(bounded[5], vec![1, 4, 5, 6, 9]);
+}
+
+#[test]
+fn ring_wraps_around() {
+    let mut rng = thread_rng();
+    let ring = Topology::Ring { radius: 1 }
+        .neighborhoods(5, &mut rng)
+        .unwrap();
+    assert_eq!(ring[0], vec![0, 1, 4]);
+    assert_eq!(ring[4], vec![0, 3, 4]);
+}
+
+#[test]
+fn grid_must_fit_swarm() {
+    let grid = Topology::VonNeumann {
+        rows: 3,
+        cols: 3,
+        wrap: true,
+    };
+
+    let particles = SwarmBuilder::new(Fitness::new(sphere, true))
+        .size(10)
+        .topology(grid)
+        .build();
+
+    assert!(particles.is_err());
+}
+
+#[test]
+fn von_neumann_converges() {
+    let mut particles = SwarmBuilder::new(Fitness::new(sphere, true))
+        .size(20)
+        .seed(3)
+        .topology(Topology::von_neumann(20))
+        .build()
+        .unwrap();
+
+    let initial = particles.best_fitness();
+    particles.run(Options::canonical(), 100).unwrap();
+
+    assert!(particles.best_fitness() < initial);
+    assert!(particles.best_fitness() < 1e-2);
+}
+
+#[test]
+fn random_informants() {
+    let mut rng = Pcg64::seed_from_u64(0);
+    let neighborhoods = Topology::spso_2011().neighborhoods(10, &mut rng).unwrap();
+
+    let links: usize = neighborhoods.iter().map(|n| n.len()).sum();
+    assert!(links <= 10 * 4);
+    for (i, neighbors) in neighborhoods.iter().enumerate() {
+        assert!(neighbors.contains(&i));
+    }
+}
+
+#[test]
+fn random_topology_is_resampled_on_stagnation() {
+    let mut particles = SwarmBuilder::new(Fitness::new(sphere, true))
+        .size(10)
+        .seed(5)
+        .topology(Topology::spso_2011())
+        .build()
+        .unwrap();
+
+    let mut resampled = false;
+    for _ in 0..30 {
+        let before = particles.neighborhoods().unwrap().to_vec();
+        let best = particles.best_fitness();
+        particles.step(Options::canonical());
+
+        let after = particles.neighborhoods().unwrap();
+        if particles.best_fitness() < best {
+            assert_eq!(before, after);
+        } else {
+            resampled |= before != after;
+        }
+    }
+
+    assert!(resampled);
+}
+
+#[test]
+fn hierarchy_tree() {
+    let mut rng = thread_rng();
+    let tree = Topology::Hierarchy { branching: 3 }
+        .neighborhoods(7, &mut rng)
+        .unwrap();
+
+    assert_eq!(tree[0], vec![0]);
+    assert_eq!(tree[3], vec![0, 3]);
+    assert_eq!(tree[4], vec![1, 4]);
+    assert_eq!(tree[6], vec![1, 6]);
+}
+
+#[test]
+fn hierarchy_keeps_better_particles_above() {
+    let mut particles = SwarmBuilder::new(Fitness::new(sphere, true))
+        .size(13)
+        .seed(8)
+        .topology(Topology::Hierarchy { branching: 3 })
+        .build()
+        .unwrap();
+
+    let mut ids: Vec<_> = particles.particles().iter().map(|p| p.id()).collect();
+    particles.run(Options::canonical(), 50).unwrap();
+
+    // Particles only change places, and the root is better than its children
+    let mut after: Vec<_> = particles.particles().iter().map(|p| p.id()).collect();
+    ids.sort_unstable();
+    after.sort_unstable();
+    assert_eq!(ids, after);
+
+    let root = particles.particles()[0].best_fitness();
+    for child in &particles.particles()[1..4] {
+        assert!(root <= child.best_fitness());
+    }
+    assert!(particles.best_fitness() < 1e-2);
+}
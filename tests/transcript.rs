@@ -0,0 +1,65 @@
+#![cfg(feature = "full")]
+
+mod common;
+
+use common::quiet;
+use ndarray::Array1;
+use pso::benchmark::sphere;
+use pso::builder::SwarmBuilder;
+use pso::fitness::Fitness;
+use pso::pso::Options;
+use pso::transcript::Transcript;
+use std::env;
+use std::fs;
+
+#[test]
+fn every_evaluation_is_logged() {
+    let path = env::temp_dir().join(format!("pso-transcript-{}.jsonl", std::process::id()));
+    let _ = fs::remove_file(&path);
+
+    let mut particles = quiet(
+        SwarmBuilder::new(Fitness::new(sphere, true))
+            .size(4)
+            .seed(32)
+            .transcript(Transcript::append(&path).unwrap()),
+    );
+
+    particles.run(Options::canonical(), 5).unwrap();
+    particles.restart_particle(2);
+    particles.mutate(0, |x| x.fill(0.0));
+
+    let mut transcript = particles.set_transcript(None).unwrap();
+    assert_eq!(transcript.written(), 4 * 6 + 2);
+    transcript.finish().unwrap();
+
+    let evaluations = Transcript::read(&path).unwrap();
+    assert_eq!(evaluations.len(), 26);
+    assert!(evaluations[..4].iter().all(|e| e.iter == 0));
+    assert!(evaluations.windows(2).all(|w| w[0].iter <= w[1].iter));
+    assert!(evaluations
+        .windows(2)
+        .all(|w| w[0].timestamp <= w[1].timestamp));
+
+    // Positions and fitness are enough to reconstruct the run
+    for evaluation in &evaluations {
+        assert_eq!(evaluation.fitness, sphere(&evaluation.value));
+    }
+    let last = &evaluations[25];
+    assert_eq!((last.iter, last.id, last.fitness), (5, 0, 0.0));
+    assert_eq!(
+        evaluations[4 * 6..]
+            .iter()
+            .map(|e| e.id)
+            .collect::<Vec<_>>(),
+        [2, 0]
+    );
+
+    // Appending keeps the previous entries
+    let mut transcript = Transcript::append(&path).unwrap();
+    transcript.write(6, 1, &Array1::zeros(2), 0.0);
+    transcript.finish().unwrap();
+    drop(transcript);
+    assert_eq!(Transcript::read(&path).unwrap().len(), 27);
+
+    fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,52 @@
+#![cfg(feature = "full")]
+
+use pso::coordinates::Coordinate;
+use pso::error::PsoError;
+use pso::tuner::Tuner;
+
+#[test]
+fn tuner_finds_the_best_configuration() {
+    let tuner = Tuner::new()
+        .float("learning_rate", 1e-6, 1.0)
+        .transform(Coordinate::Log10)
+        .integer("layers", 1.0, 8.0)
+        .float("dropout", 0.0, 0.9)
+        .seed(10)
+        .iters(60);
+
+    let report = tuner
+        .tune(
+            |config| {
+                (config["learning_rate"].log10() + 3.0).powi(2)
+                    + (config["layers"] - 4.0).powi(2)
+                    + (config["dropout"] - 0.2).powi(2)
+            },
+            true,
+        )
+        .unwrap();
+
+    assert!((report.best["learning_rate"].log10() + 3.0).abs() < 1e-2);
+    assert_eq!(report.best["layers"], 4.0);
+    assert!((report.best["dropout"] - 0.2).abs() < 1e-2);
+    assert_eq!(report.evaluations, 20 * (60 + 1));
+
+    let text = report.to_string();
+    assert!(text.contains("layers        = 4"));
+}
+
+#[test]
+fn invalid_parameters() {
+    let objective = |_: &pso::tuner::Config| 0.0;
+
+    let log = Tuner::new()
+        .float("rate", 0.0, 1.0)
+        .transform(Coordinate::Log10);
+    assert!(matches!(
+        log.tune(objective, true),
+        Err(PsoError::InvalidOptions(_))
+    ));
+
+    let twice = Tuner::new().float("a", 0.0, 1.0).integer("a", 0.0, 2.0);
+    assert!(twice.validate().is_err());
+    assert!(Tuner::new().validate().is_err());
+}
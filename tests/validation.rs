@@ -0,0 +1,97 @@
+#![cfg(feature = "full")]
+
+use pso::benchmark::sphere;
+use pso::builder::SwarmBuilder;
+use pso::error::PsoError;
+use pso::fitness::Fitness;
+use pso::pso::{Options, Particles};
+
+fn rule(result: Result<(), PsoError>) -> String {
+    match result {
+        Err(PsoError::InvalidOptions(rule)) => rule,
+        Err(err) => panic!("unexpected error: {}", err),
+        Ok(()) => panic!("expected an error"),
+    }
+}
+
+#[test]
+fn omega_out_of_range_is_rejected() {
+    let options = Options {
+        omega: Some(1.5),
+        phi_1: 1.0,
+        phi_2: 1.0,
+    };
+
+    assert!(rule(options.validate()).starts_with("omega must be in [0, 1.2]"));
+}
+
+#[test]
+fn unstable_coefficients_are_rejected() {
+    let options = Options {
+        omega: Some(0.0),
+        phi_1: 2.0,
+        phi_2: 2.0,
+    };
+
+    assert!(rule(options.validate()).starts_with("phi_1 + phi_2 must be less than"));
+
+    let options = Options {
+        omega: Some(0.5),
+        ..options
+    };
+
+    assert!(options.validate().is_ok());
+}
+
+#[test]
+fn negative_phi_is_rejected() {
+    let options = Options::social_only(None, -1.0);
+    assert!(rule(options.validate()).starts_with("phi_2 must be"));
+}
+
+#[test]
+fn builder_rejects_degenerate_swarms() {
+    let fitness = || Fitness::new(sphere, true);
+
+    let size = SwarmBuilder::new(fitness()).size(0);
+    assert_eq!(rule(size.validate()), "swarm size must be positive");
+
+    let dim = SwarmBuilder::new(fitness()).dim(0);
+    assert_eq!(rule(dim.validate()), "dim must be at least 1");
+
+    let bounds = SwarmBuilder::new(fitness()).value_range(1.0, -1.0);
+    assert!(rule(bounds.validate()).starts_with("value range must satisfy min < max"));
+
+    assert!(SwarmBuilder::new(fitness()).build().is_ok());
+}
+
+#[test]
+fn fallible_constructors() {
+    let fitness = || Fitness::new(sphere, true);
+    let ranges = ((-1.0, 1.0), (-0.1, 0.1));
+
+    let cases = [
+        (0, 2, ranges, "swarm size must be positive"),
+        (3, 0, ranges, "dim must be at least 1"),
+        (3, 2, ((1.0, 1.0), ranges.1), "value range"),
+        (3, 2, (ranges.0, (0.0, f64::NAN)), "velocity range"),
+    ];
+
+    for &(size, dim, (value_range, velocity_range), expected) in &cases {
+        let new = Particles::try_new(size, dim, value_range, velocity_range, fitness());
+        let seeded = Particles::try_with_seed(size, dim, value_range, velocity_range, fitness(), 0);
+
+        for result in [new.map(|_| ()), seeded.map(|_| ())] {
+            assert!(rule(result).starts_with(expected), "{}", expected);
+        }
+    }
+
+    let particles = Particles::try_with_seed(1, 1, ranges.0, ranges.1, fitness(), 0).unwrap();
+    assert_eq!(particles.particles().len(), 1);
+}
+
+#[test]
+#[should_panic(expected = "swarm size must be positive")]
+fn empty_swarms_panic_with_the_rule() {
+    Particles::new(0, 2, (-1.0, 1.0), (-1.0, 1.0), Fitness::new(sphere, true));
+}
@@ -0,0 +1,100 @@
+#![cfg(feature = "full")]
+
+mod common;
+
+use common::{builder, quiet};
+use ndarray::Array1;
+use pso::benchmark::sphere;
+use pso::builder::SwarmBuilder;
+use pso::fitness::Fitness;
+use pso::pso::{Options, Particles};
+use pso::velocity::{Coefficients, VelocityPolicy};
+
+fn swarm(policy: VelocityPolicy, seed: u64) -> Particles<'static> {
+    quiet(builder(20, 5, seed).velocity_policy(policy))
+}
+
+#[test]
+fn every_policy_converges() {
+    let policies = [
+        VelocityPolicy::Classic,
+        VelocityPolicy::Componentwise,
+        VelocityPolicy::RandomRotation,
+        VelocityPolicy::Hypersphere,
+    ];
+
+    for &policy in &policies {
+        let mut particles = swarm(policy, 0);
+        let initial = particles.best_fitness();
+        particles.run(Options::canonical(), 150).unwrap();
+
+        assert!(
+            particles.best_fitness() < initial * 1e-3,
+            "{:?} didn't converge",
+            policy
+        );
+    }
+}
+
+#[test]
+fn random_rotation_is_reproducible() {
+    let mut a = swarm(VelocityPolicy::RandomRotation, 4);
+    let mut b = swarm(VelocityPolicy::RandomRotation, 4);
+
+    a.run(Options::canonical(), 10).unwrap();
+    b.run(Options::canonical(), 10).unwrap();
+
+    assert_eq!(a.best(), b.best());
+}
+
+#[test]
+fn per_dimension_coefficients() {
+    let options = Options::canonical();
+
+    for &policy in &[
+        VelocityPolicy::Classic,
+        VelocityPolicy::Componentwise,
+        VelocityPolicy::RandomRotation,
+        VelocityPolicy::Hypersphere,
+    ] {
+        let mut scalar = swarm(policy, 30);
+        scalar.run(options, 10).unwrap();
+
+        let mut uniform = swarm(policy, 30);
+        uniform
+            .set_coefficients(Some(Coefficients::uniform(5, &options)))
+            .unwrap();
+        uniform.run(options, 10).unwrap();
+
+        assert_eq!(scalar.best(), uniform.best(), "{:?}", policy);
+    }
+
+    // The last dimension doesn't move at all
+    let mut coefficients = Coefficients::uniform(5, &options);
+    for c in [&mut coefficients.phi_1, &mut coefficients.phi_2] {
+        c[4] = 0.0;
+    }
+    let mut omega = Array1::from_elem(5, 0.7);
+    omega[4] = 0.0;
+    coefficients.omega = Some(omega);
+
+    let mut particles = SwarmBuilder::new(Fitness::new(sphere, true))
+        .dim(5)
+        .seed(31)
+        .coefficients(coefficients.clone())
+        .build()
+        .unwrap();
+    let initial: Vec<_> = particles.particles().iter().map(|p| p.value()[4]).collect();
+    particles.run(Options::canonical(), 10).unwrap();
+    let after: Vec<_> = particles.particles().iter().map(|p| p.value()[4]).collect();
+    assert_eq!(initial, after);
+
+    let wrong = SwarmBuilder::new(Fitness::new(sphere, true))
+        .dim(3)
+        .coefficients(coefficients.clone())
+        .build();
+    assert!(wrong.is_err());
+
+    coefficients.phi_2[0] = -1.0;
+    assert!(particles.set_coefficients(Some(coefficients)).is_err());
+}
@@ -0,0 +1,128 @@
+#![cfg(feature = "full")]
+
+mod common;
+
+use common::{builder, quiet};
+use pso::benchmark::sphere;
+use pso::builder::SwarmBuilder;
+use pso::error::PsoError;
+use pso::fitness::Fitness;
+use pso::lineage::LineageEvent;
+use pso::pso::{Options, Particles};
+use pso::warmup::Warmup;
+
+fn swarm(warmup: Option<Warmup>) -> Particles<'static> {
+    let mut builder = builder(10, 3, 7);
+    if let Some(warmup) = warmup {
+        builder = builder.warmup(warmup);
+    }
+
+    quiet(builder)
+}
+
+fn options() -> Options {
+    Options {
+        omega: Some(0.4),
+        phi_1: 1.5,
+        phi_2: 1.5,
+    }
+}
+
+fn resampled(particles: &Particles, step: usize) -> usize {
+    particles
+        .lineage()
+        .iter()
+        .filter(|l| l.step == step && l.event == LineageEvent::Reinitialized)
+        .count()
+}
+
+#[test]
+fn warmup_overrides_omega_then_switches() {
+    let warmup = Warmup {
+        iters: 3,
+        omega: 0.95,
+        resample: 0.2,
+    };
+    let mut particles = swarm(Some(warmup));
+
+    for step in 1..=5 {
+        assert_eq!(particles.warming_up(), step <= 3);
+        particles.step(options());
+
+        let expected = if step <= 3 { 0.95 } else { 0.4 };
+        assert_eq!(particles.omega(), Some(expected));
+        assert_eq!(resampled(&particles, step), if step <= 3 { 2 } else { 0 });
+    }
+}
+
+#[test]
+fn resampling_never_takes_the_whole_swarm() {
+    let warmup = Warmup {
+        iters: 1,
+        omega: 0.9,
+        resample: 1.0,
+    };
+    let mut particles = swarm(Some(warmup));
+    particles.step(options());
+
+    assert_eq!(resampled(&particles, 1), 9);
+}
+
+#[test]
+fn resampling_respects_the_budget() {
+    let mut particles = swarm(Some(Warmup::default()));
+    // 10 initial evaluations, 10 for the movement and only 1 to resample
+    particles.set_evaluation_budget(Some(21));
+    particles.step(options());
+
+    assert_eq!(resampled(&particles, 1), 1);
+    assert!(particles.budget_exhausted());
+}
+
+#[test]
+fn no_warmup_changes_nothing() {
+    let mut plain = swarm(None);
+    let mut idle = swarm(Some(Warmup {
+        iters: 0,
+        ..Warmup::default()
+    }));
+
+    for _ in 0..10 {
+        plain.step(options());
+        idle.step(options());
+    }
+
+    assert_eq!(plain.best(), idle.best());
+    assert!(!idle.warming_up());
+}
+
+#[test]
+fn invalid_warmups_are_rejected() {
+    for warmup in [
+        Warmup {
+            omega: 2.0,
+            ..Warmup::default()
+        },
+        Warmup {
+            resample: 1.5,
+            ..Warmup::default()
+        },
+    ]
+    .iter()
+    {
+        let result = SwarmBuilder::new(Fitness::new(sphere, true))
+            .warmup(*warmup)
+            .build();
+        assert!(matches!(result, Err(PsoError::InvalidOptions(_))));
+    }
+}
+
+#[test]
+fn warmup_survives_snapshots() {
+    let mut particles = swarm(Some(Warmup::default()));
+    particles.step(options());
+
+    let restored = Particles::from_state(particles.state(), Fitness::new(sphere, true));
+    assert_eq!(restored.warmup(), Some(Warmup::default()));
+    assert!(restored.warming_up());
+}
@@ -0,0 +1,73 @@
+#![cfg(feature = "full")]
+
+use ndarray::array;
+use pso::benchmark::sphere;
+use pso::builder::SwarmBuilder;
+use pso::error::PsoError;
+use pso::fitness::Fitness;
+use pso::pso::Options;
+use pso::zones::{Zone, ZoneHandling};
+
+#[test]
+fn positions_are_pushed_to_the_boundary() {
+    let zone = Zone::sphere(array![0.0, 0.0], 2.0);
+    let mut value = array![1.0, 0.0];
+    let mut velocity = array![-1.0, 0.5];
+
+    zone.push_out(&mut value, &mut velocity, ZoneHandling::Repel);
+    assert!((value[0] - 2.0).abs() < 1e-9 && value[1] == 0.0);
+    assert_eq!(velocity, array![1.0, 0.5]);
+
+    let zone = Zone::cuboid(array![-1.0, -1.0], array![1.0, 1.0]);
+    let mut value = array![0.2, 0.9];
+    let mut velocity = array![0.0, -1.0];
+
+    zone.push_out(&mut value, &mut velocity, ZoneHandling::Repair);
+    assert_eq!(value, array![0.2, 1.0]);
+    assert_eq!(velocity, array![0.0, -1.0]);
+}
+
+#[test]
+fn particles_never_stay_inside_zones() {
+    let mut particles = SwarmBuilder::new(Fitness::new(sphere, true))
+        .seed(11)
+        .build()
+        .unwrap();
+
+    // The optimum itself is excluded
+    let zone = Zone::sphere(array![0.0, 0.0], 1.0);
+    particles.add_zone(zone.clone()).unwrap();
+    particles.set_zone_handling(ZoneHandling::Repel);
+
+    for _ in 0..30 {
+        particles.step(Options::canonical());
+        assert!(particles
+            .particles()
+            .iter()
+            .all(|p| !zone.contains(p.value())));
+    }
+
+    assert!(!zone.contains(particles.best()));
+}
+
+#[test]
+fn zones_of_the_wrong_dimension_are_rejected() {
+    let mut particles = SwarmBuilder::new(Fitness::new(sphere, true))
+        .dim(2)
+        .seed(12)
+        .build()
+        .unwrap();
+
+    for zone in [
+        Zone::sphere(array![0.0, 0.0, 0.0], 1.0),
+        Zone::cuboid(array![0.0, 0.0], array![1.0]),
+    ]
+    .iter()
+    {
+        assert!(matches!(
+            particles.add_zone(zone.clone()),
+            Err(PsoError::InvalidOptions(_))
+        ));
+    }
+    assert!(particles.zones().is_empty());
+}